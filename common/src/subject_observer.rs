@@ -1,14 +1,41 @@
-use std::rc::Rc;
+use std::sync::{Arc, Mutex, PoisonError};
 
 pub trait Observer<S: Subject<E>, E: Clone> {
     fn update(&self, source: &S, event: E);
+
+    /// Identifies the observer in diagnostics, its type name by default.
+    fn name(&self) -> &'static str {
+        std::any::type_name::<Self>()
+    }
+}
+
+/// Lets single-threaded observers, e.g. keeping their state in a `Cell` or a `RefCell`, be
+/// shared with subjects as `Arc::new(Mutex::new(observer))`.
+impl<S, E, O> Observer<S, E> for Mutex<O>
+where
+    S: Subject<E>,
+    E: Clone,
+    O: Observer<S, E>,
+{
+    fn update(&self, source: &S, event: E) {
+        self.lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .update(source, event);
+    }
+
+    fn name(&self) -> &'static str {
+        std::any::type_name::<O>()
+    }
 }
 
 pub trait Subject<E: Clone> {
-    fn register_observer(&mut self, observer: Rc<dyn Observer<Self, E>>);
-    fn unregister_observer(&mut self, observer: Rc<dyn Observer<Self, E>>);
+    fn register_observer(&mut self, observer: SharedObserver<Self, E>);
+    fn unregister_observer(&mut self, observer: SharedObserver<Self, E>);
     fn notify_observers(&self, event: E);
 }
 
+/// Observer shared with a subject, which may be moved to or notified from other threads.
+pub type SharedObserver<S, E> = Arc<dyn Observer<S, E> + Send + Sync>;
+
 pub type Observers<S, E> = Vec<dyn Observer<S, E>>;
-pub type SharedObservers<S, E> = Vec<Rc<dyn Observer<S, E>>>;
+pub type SharedObservers<S, E> = Vec<SharedObserver<S, E>>;
@@ -1,4 +1,9 @@
-use utoipa::OpenApi;
+use log::warn;
+use strategies::info::shipped_strategies;
+use utoipa::{
+    openapi::{self, RefOr, Schema},
+    Modify, OpenApi,
+};
 
 pub mod v1;
 
@@ -6,10 +11,71 @@ pub mod v1;
 #[openapi(
     paths(
         v1::run,
+        v1::start_run,
+        v1::get_run_diff,
+        v1::get_run,
+        v1::get_run_events,
+        v1::nim::play_nim_move,
+        v1::get_run_evaluations,
+        v1::get_run_timeseries,
+        v1::annotate_run,
+        v1::get_strategies,
     ),
-    components(schemas(v1::Parameters)),
+    components(schemas(
+        v1::Parameters,
+        v1::AnnotationRequest,
+        v1::nim::NimMove,
+        v1::pagination::Fields
+    )),
+    modifiers(&StrategySchemas),
     tags(
             (name = "run", description = "Run management endpoints.")
         ),
     )]
 pub(super) struct ApiDoc;
+
+/// Adds the parameter schemas of the shipped strategies to the components, as
+/// `<strategy>_parameters`.
+struct StrategySchemas;
+
+impl Modify for StrategySchemas {
+    fn modify(&self, openapi: &mut openapi::OpenApi) {
+        let components = openapi.components.get_or_insert_with(Default::default);
+        for strategy in shipped_strategies() {
+            match serde_json::from_value::<RefOr<Schema>>(strategy.parameters) {
+                Ok(schema) => {
+                    components
+                        .schemas
+                        .insert(format!("{}_parameters", strategy.name), schema);
+                }
+                Err(e) => warn!("Invalid parameters schema of {}: {e}", strategy.name),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use utoipa::OpenApi;
+
+    use super::ApiDoc;
+
+    #[test]
+    fn test_api_doc_strategy_schemas() {
+        // When
+        let result = ApiDoc::openapi().components.unwrap();
+
+        // Then
+        assert!(
+            [
+                "my_strategy_parameters",
+                "nim_parameters",
+                "one_max_parameters"
+            ]
+            .iter()
+            .all(|name| result.schemas.contains_key(*name)),
+            "Should document the parameters of the shipped strategies"
+        );
+        assert!(result.schemas.contains_key("Parameters"));
+    }
+}
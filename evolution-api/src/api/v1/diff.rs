@@ -0,0 +1,275 @@
+use genetic::{
+    crossover::CrossoverType, evolution::DEFAULT_MUTATION_RATE, mutation::MutationType,
+    selection::SelectionType, Evaluation,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use strategies::{info::StrategyInfo, my_strategy::MyStrategy};
+use utoipa::IntoParams;
+
+use super::{runs::RunRecord, Parameters};
+
+/// Population size of runs not setting one.
+pub const DEFAULT_POPULATION_SIZE: usize = 128;
+/// Word searched by runs not setting one.
+pub const DEFAULT_TARGET: &str = "florent";
+/// Strategy evolved by runs not setting one.
+pub const DEFAULT_STRATEGY: &str = MyStrategy::NAME;
+/// Crossover ratio of runs not setting one.
+pub const DEFAULT_CROSSOVER_RATE: f32 = 1.0;
+
+/// Settings a run actually used, the parameters completed with their defaults.
+#[derive(Serialize, Clone, Debug, PartialEq)]
+pub struct EffectiveConfig {
+    pub strategy: String,
+    pub strategy_parameters: Option<Value>,
+    pub population_size: usize,
+    pub target: String,
+    pub crossover_rate: f32,
+    pub crossover_mutation_rate: f32,
+    pub crossover_mutation_type: MutationType,
+    pub crossover_selection_type: SelectionType,
+    pub crossover_type: CrossoverType,
+    pub seed: Option<u64>,
+    pub seed_from_run: Option<u64>,
+}
+
+impl From<&Parameters> for EffectiveConfig {
+    fn from(parameters: &Parameters) -> Self {
+        EffectiveConfig {
+            strategy: parameters
+                .strategy
+                .clone()
+                .unwrap_or(DEFAULT_STRATEGY.to_string()),
+            strategy_parameters: parameters.strategy_parameters.clone(),
+            population_size: parameters
+                .population_size
+                .unwrap_or(DEFAULT_POPULATION_SIZE),
+            target: parameters
+                .target
+                .clone()
+                .unwrap_or(DEFAULT_TARGET.to_string()),
+            crossover_rate: parameters.crossover_rate.unwrap_or(DEFAULT_CROSSOVER_RATE),
+            crossover_mutation_rate: parameters
+                .crossover_mutation_rate
+                .unwrap_or(DEFAULT_MUTATION_RATE),
+            crossover_mutation_type: parameters
+                .crossover_mutation_type
+                .clone()
+                .unwrap_or_default(),
+            crossover_selection_type: parameters
+                .crossover_selection_type
+                .clone()
+                .unwrap_or(SelectionType::Weight),
+            crossover_type: parameters.crossover_type.clone().unwrap_or_default(),
+            seed: parameters.seed,
+            seed_from_run: parameters.seed_from_run,
+        }
+    }
+}
+
+#[derive(Deserialize, IntoParams, Clone, Debug)]
+#[into_params(parameter_in = Query)]
+pub struct DiffQuery {
+    /// First run to compare.
+    pub a: u64,
+    /// Second run to compare.
+    pub b: u64,
+}
+
+/// Setting whose value differs between the compared runs.
+#[derive(Serialize, Clone, Debug, PartialEq)]
+pub struct FieldDiff {
+    pub field: String,
+    pub a: Value,
+    pub b: Value,
+}
+
+/// Outcome of a run, with fitness statistics of its last generation once completed.
+#[derive(Serialize, Clone, Debug, PartialEq)]
+pub struct RunOutcome {
+    pub id: u64,
+    pub status: String,
+    pub generation: u64,
+    pub best_fitness: Option<f32>,
+    pub mean_fitness: Option<f32>,
+    pub config: EffectiveConfig,
+}
+
+impl RunOutcome {
+    pub fn new(record: &RunRecord, population: Option<&[Evaluation]>) -> Self {
+        let fitnesses = population
+            .map(|evaluations| {
+                evaluations
+                    .iter()
+                    .filter_map(Evaluation::evaluated_fitness)
+                    .collect::<Vec<_>>()
+            })
+            .filter(|fitnesses| !fitnesses.is_empty())
+            .map(|fitnesses| {
+                let best = fitnesses.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+                let mean = fitnesses.iter().sum::<f32>() / fitnesses.len() as f32;
+                (best, mean)
+            });
+        RunOutcome {
+            id: record.id,
+            status: record.status.to_string(),
+            generation: record.generation,
+            best_fitness: fitnesses.map(|(best, _)| best),
+            mean_fitness: fitnesses.map(|(_, mean)| mean),
+            config: EffectiveConfig::from(&record.parameters),
+        }
+    }
+}
+
+/// Two runs side by side, with the settings telling them apart.
+#[derive(Serialize, Clone, Debug, PartialEq)]
+pub struct RunDiff {
+    pub a: RunOutcome,
+    pub b: RunOutcome,
+    /// Settings changed from `a` to `b`, by name.
+    pub differences: Vec<FieldDiff>,
+}
+
+impl RunDiff {
+    pub fn new(a: RunOutcome, b: RunOutcome) -> Self {
+        let differences = diff_fields(&to_value(&a.config), &to_value(&b.config));
+        RunDiff { a, b, differences }
+    }
+}
+
+fn to_value(config: &EffectiveConfig) -> Value {
+    // Only custom operators, unavailable through the API, fail to serialize
+    serde_json::to_value(config).unwrap_or(Value::Null)
+}
+
+fn diff_fields(a: &Value, b: &Value) -> Vec<FieldDiff> {
+    match (a, b) {
+        (Value::Object(a), Value::Object(b)) => a
+            .iter()
+            .filter_map(|(field, value)| {
+                let other = b.get(field).unwrap_or(&Value::Null);
+                (value != other).then(|| FieldDiff {
+                    field: field.clone(),
+                    a: value.clone(),
+                    b: other.clone(),
+                })
+            })
+            .collect(),
+        _ => vec![],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use genetic::{
+        evolution::{EvolutionStatus, DEFAULT_MUTATION_RATE},
+        selection::SelectionType,
+        Evaluation,
+    };
+    use serde_json::json;
+
+    use crate::api::v1::{runs::RunRecord, Parameters};
+
+    use super::{EffectiveConfig, FieldDiff, RunDiff, RunOutcome, DEFAULT_POPULATION_SIZE};
+
+    fn record(id: u64, parameters: Parameters) -> RunRecord {
+        RunRecord {
+            id,
+            parameters,
+            status: EvolutionStatus::Completed,
+            generation: 12,
+            annotations: vec![],
+            hall_of_fame: vec![],
+            population_size: 0,
+            progress: None,
+            metadata: None,
+        }
+    }
+
+    #[test]
+    fn test_effective_config_from() {
+        // When
+        let result = EffectiveConfig::from(&Parameters::default());
+
+        // Then
+        assert_eq!(DEFAULT_POPULATION_SIZE, result.population_size);
+        assert_eq!(
+            DEFAULT_MUTATION_RATE, result.crossover_mutation_rate,
+            "Should resolve defaults of the engine"
+        );
+        assert_eq!(SelectionType::Weight, result.crossover_selection_type);
+    }
+
+    #[test]
+    fn test_run_outcome_new() {
+        // Given
+        let population = [0.2, 0.6]
+            .map(|fitness| Evaluation {
+                fitness,
+                evaluated: true,
+                ..Default::default()
+            })
+            .to_vec();
+
+        // When
+        let result = RunOutcome::new(&record(1, Parameters::default()), Some(&population));
+
+        // Then
+        assert_eq!(Some(0.6), result.best_fitness);
+        assert_eq!(Some(0.4), result.mean_fitness);
+        assert_eq!("Completed", result.status);
+        assert_eq!(
+            None,
+            RunOutcome::new(&record(1, Parameters::default()), None).best_fitness,
+            "Should not compute statistics without population"
+        );
+    }
+
+    #[test]
+    fn test_run_diff_new() {
+        // Given
+        let a = record(1, Parameters::default());
+        let b = record(
+            2,
+            Parameters {
+                population_size: Some(64),
+                crossover_selection_type: Some(SelectionType::Tournament(3)),
+                seed: Some(7),
+                strategy_parameters: Some(json!({ "stick_count": 5 })),
+                ..Default::default()
+            },
+        );
+
+        // When
+        let result = RunDiff::new(RunOutcome::new(&a, None), RunOutcome::new(&b, None));
+
+        // Then
+        assert_eq!(
+            vec![
+                FieldDiff {
+                    field: "crossover_selection_type".to_string(),
+                    a: json!("Weight"),
+                    b: json!({ "Tournament": 3 }),
+                },
+                FieldDiff {
+                    field: "population_size".to_string(),
+                    a: json!(128),
+                    b: json!(64),
+                },
+                FieldDiff {
+                    field: "seed".to_string(),
+                    a: json!(null),
+                    b: json!(7),
+                },
+                FieldDiff {
+                    field: "strategy_parameters".to_string(),
+                    a: json!(null),
+                    b: json!({ "stick_count": 5 }),
+                },
+            ],
+            result.differences,
+            "Should only report changed settings"
+        );
+    }
+}
@@ -1,22 +1,41 @@
-use std::rc::Rc;
+pub mod diff;
+pub mod nim;
+pub mod pagination;
+pub mod registry;
+pub mod runs;
+
+use std::{collections::BTreeMap, convert::Infallible, sync::Arc, time::Duration};
 
 use actix_web::{
-    post,
-    web::{Data, Json, ServiceConfig},
+    get,
+    http::header::{CACHE_CONTROL, LOCATION},
+    post, rt,
+    web::{self, Bytes, Data, Json, Path, Query, ServiceConfig},
     HttpResponse, Responder,
 };
 use common::subject_observer::Subject;
-use futures::executor::block_on;
+use diff::{DiffQuery, EffectiveConfig, RunDiff, RunOutcome};
+use futures::{executor::block_on, StreamExt};
 use genetic::{
-    evolution::{EvolutionConfig, EvolutionEngine, GenerationRenewalConfig, GeneticRenewalParam},
+    crossover::CrossoverType,
+    evolution::{
+        EvolutionConfig, EvolutionEngine, GenerationRenewalConfig, GeneticRenewalParam,
+        InitialPopulation, Snapshot, TargetFitness,
+    },
+    mutation::MutationType,
     selection::SelectionType,
+    Genome,
 };
-use genetic_ext::gateways::StatsdGateway;
-use log::debug;
-use rand::thread_rng;
+use genetic_ext::gateways::{ObserverTimingGateway, StatsdGateway};
+use log::{debug, warn};
+use pagination::{EvaluationItem, Fields, PageQuery, Pagination};
+use rand::{thread_rng, Rng};
+use registry::{build_strategy, TARGET_FITNESS};
+use runs::{RunRegistry, RunResult, RunTracker};
 use serde::{Deserialize, Serialize};
-use strategies::my_strategy::MyStrategy;
-use utoipa::ToSchema;
+use serde_json::Value;
+use strategies::info::shipped_strategies;
+use utoipa::{IntoParams, ToSchema};
 
 use crate::config::app::AppConfig;
 
@@ -24,94 +43,374 @@ use crate::config::app::AppConfig;
 // #[openapi(paths(run), components(schemas(Parameters)))]
 // pub struct RunApi;
 
+/// Generations between two emissions of the observer timings.
+const OBSERVER_TIMING_PERIOD: u64 = 10;
+
 pub fn configure() -> impl FnOnce(&mut ServiceConfig) {
     |config: &mut ServiceConfig| {
-        config.service(run);
+        config
+            .service(run)
+            .service(start_run)
+            .service(get_run_diff)
+            .service(get_run)
+            .service(get_run_events)
+            .service(nim::play_nim_move)
+            .service(get_run_evaluations)
+            .service(get_run_timeseries)
+            .service(annotate_run)
+            .service(get_strategies);
     }
 }
 
-#[derive(Serialize, Deserialize, ToSchema, Clone, Debug)]
+#[derive(Serialize, Deserialize, ToSchema, Clone, Debug, Default)]
 pub struct Parameters {
     crossover_rate: Option<f32>,
     crossover_mutation_rate: Option<f32>,
+    crossover_mutation_type: Option<MutationType>,
     crossover_selection_type: Option<SelectionType>,
+    crossover_type: Option<CrossoverType>,
+    /// Labels of the run in its metadata, next to the `run` label holding its id.
+    labels: Option<BTreeMap<String, String>>,
     population_size: Option<usize>,
+    /// Seed of the random generator of the run, drawn when missing and kept in the run record
+    /// so that the run can be reproduced.
+    seed: Option<u64>,
+    /// Run whose hall of fame seeds the initial population.
+    seed_from_run: Option<u64>,
+    /// Name of the evolved strategy, see `GET /strategies`, `my_strategy` when missing.
+    strategy: Option<String>,
+    /// Parameters of the strategy following its schema, e.g. `{ "stick_count": 21 }` for `nim`.
+    #[schema(value_type = Option<Object>)]
+    strategy_parameters: Option<Value>,
+    /// Word evolved by `my_strategy`.
     target: Option<String>,
 }
 
 #[utoipa::path(
     request_body = Parameters,
-//    responses(
-//        (status = 201, description = "Todo created successfully", body = Todo),
-//        (status = 409, description = "Todo with id already exists", body = ErrorResponse, example = json!(ErrorResponse::Conflict(String::from("id = 1"))))
-//    )
+    responses(
+        (status = 200, description = "Last generation and solutions of the completed run"),
+        (status = 400, description = "Unknown strategy or invalid strategy parameters"),
+        (status = 404, description = "Unknown run to seed from")
+    )
 )]
 #[post("/run")]
-pub async fn run(config: Data<AppConfig>, parameters: Json<Parameters>) -> impl Responder {
-    let parameters = parameters.into_inner();
-    debug!("Starting evolution with parameters: {:?}", parameters);
+pub async fn run(
+    config: Data<AppConfig>,
+    registry: Data<RunRegistry>,
+    parameters: Json<Parameters>,
+) -> impl Responder {
+    let mut parameters = parameters.into_inner();
+    let (run_id, seeds) = match register_run(&registry, &mut parameters) {
+        Ok(registered) => registered,
+        Err(response) => return response,
+    };
+    match execute(config, registry, run_id, parameters, seeds).await {
+        Ok(infos) => HttpResponse::Ok()
+            .insert_header((LOCATION, format!("/runs/{run_id}")))
+            .json(RunResult::new(run_id, &infos, TARGET_FITNESS)),
+        Err(err) => HttpResponse::InternalServerError().body(err),
+    }
+}
 
-    let population_size = parameters.population_size.unwrap_or(128);
-    let target = parameters.target.clone().unwrap_or("florent".to_string());
+#[utoipa::path(
+    request_body = Parameters,
+    responses(
+        (status = 202, description = "Record of the started run, followed by `GET /runs/{id}/events`"),
+        (status = 400, description = "Unknown strategy or invalid strategy parameters"),
+        (status = 404, description = "Unknown run to seed from")
+    )
+)]
+#[post("/runs")]
+pub async fn start_run(
+    config: Data<AppConfig>,
+    registry: Data<RunRegistry>,
+    parameters: Json<Parameters>,
+) -> impl Responder {
+    let mut parameters = parameters.into_inner();
+    let (run_id, seeds) = match register_run(&registry, &mut parameters) {
+        Ok(registered) => registered,
+        Err(response) => return response,
+    };
+    let record = registry.get(run_id);
+    rt::spawn(execute(config, registry, run_id, parameters, seeds));
+    HttpResponse::Accepted()
+        .insert_header((LOCATION, format!("/runs/{run_id}")))
+        .json(record)
+}
 
-    let bytes = target.as_bytes();
-    let threshold = bytes.len() as f32;
+#[utoipa::path(
+    params(("id" = u64, Path, description = "Run identifier")),
+    responses(
+        (status = 200, description = "Server-sent `status` and `progress` events of the run, ending with a `done` event holding its record"),
+        (status = 404, description = "Unknown run")
+    )
+)]
+#[get("/runs/{id}/events")]
+pub async fn get_run_events(registry: Data<RunRegistry>, id: Path<u64>) -> impl Responder {
+    match registry.subscribe(id.into_inner()) {
+        Some(events) => HttpResponse::Ok()
+            .content_type("text/event-stream")
+            .insert_header((CACHE_CONTROL, "no-cache"))
+            .streaming(events.map(|event| Ok::<_, Infallible>(Bytes::from(event.to_sse())))),
+        None => HttpResponse::NotFound().finish(),
+    }
+}
 
+/// Registers a run of valid parameters, drawing its seed when missing, returning its id with
+/// the genomes seeding it.
+fn register_run(
+    registry: &RunRegistry,
+    parameters: &mut Parameters,
+) -> Result<(u64, Vec<Genome>), HttpResponse> {
+    debug!("Starting evolution with parameters: {:?}", parameters);
+    if let Err(err) = build_strategy(&EffectiveConfig::from(&*parameters)) {
+        return Err(HttpResponse::BadRequest().body(err));
+    }
+    let seeds = match parameters.seed_from_run {
+        Some(seed_run_id) => match registry.get(seed_run_id) {
+            Some(record) => record.hall_of_fame,
+            None => {
+                return Err(HttpResponse::NotFound().body(format!("Unknown run {seed_run_id}")));
+            }
+        },
+        None => vec![],
+    };
+    parameters.seed.get_or_insert_with(|| thread_rng().gen());
+    Ok((registry.create(parameters.clone()), seeds))
+}
+
+/// Evolves the registered run up to its completion, then ends the streams of its events.
+async fn execute(
+    config: Data<AppConfig>,
+    registry: Data<RunRegistry>,
+    run_id: u64,
+    parameters: Parameters,
+    seeds: Vec<Genome>,
+) -> Result<Snapshot, String> {
+    let effective_config = EffectiveConfig::from(&parameters);
     let settings = EvolutionConfig {
         generation_renewal_config: Some(GenerationRenewalConfig {
             cloning: None,
             crossover: Some(GeneticRenewalParam {
-                mutation_rate: parameters.crossover_mutation_rate,
-                ratio: parameters.crossover_rate.unwrap_or(1.0),
-                selection_type: parameters
-                    .crossover_selection_type
-                    .unwrap_or(SelectionType::Weight),
+                mutation_rate: Some(effective_config.crossover_mutation_rate),
+                mutation_type: effective_config.crossover_mutation_type,
+                ratio: effective_config.crossover_rate,
+                selection_type: effective_config.crossover_selection_type,
+                crossover_type: effective_config.crossover_type,
+                ..Default::default()
             }),
+            random_ratio: None,
         }),
-        population_size,
+        population_size: effective_config.population_size,
+        max_duration: Some(Duration::from_secs(config.max_run_duration_secs)),
     };
     debug!("Running evolution with configuration: {:?}", settings);
 
-    let gateway = Rc::new(
+    let gateway = Arc::new(
         StatsdGateway::new(
             (config.statsd_host.clone(), config.statsd_port),
             config.statsd_factor,
         )
-        .unwrap(),
+        .unwrap()
+        .per_run(),
     );
 
-    let mut engine = EvolutionEngine::default();
-    engine.register_observer(gateway.clone());
+    let timing_gateway = Arc::new(ObserverTimingGateway::new(OBSERVER_TIMING_PERIOD));
+    let tracker = RunTracker::new(run_id, registry.clone());
+
+    let is_complete = TargetFitness(TARGET_FITNESS);
+    // Drawn when the run was registered
+    let seed = parameters.seed.unwrap_or_else(|| thread_rng().gen());
+    let mut builder = EvolutionEngine::builder().seed(seed).config(settings);
+    for (key, value) in parameters.labels.iter().flatten() {
+        builder = builder.label(key, value);
+    }
+    builder = builder
+        .label("run", run_id.to_string())
+        .observer(gateway.clone())
+        .observer(timing_gateway.clone())
+        .observer(tracker.clone());
+    if !seeds.is_empty() {
+        builder = builder.initial_population(InitialPopulation::Seeded(seeds));
+    }
+    let mut evolution_run = match builder.build() {
+        Ok(evolution_run) => evolution_run,
+        Err(err) => {
+            registry.finish(run_id);
+            return Err(err.to_string());
+        }
+    };
 
-    let result = block_on(engine.start(
-        &MyStrategy::new(bytes),
-        &settings,
-        |_, fitnesses| fitnesses.iter().any(|&fitness| fitness >= threshold),
-        &mut thread_rng(),
-    ));
+    // Runs on the blocking threads so that the workers keep serving the other requests, the
+    // strategy being built there as it may not be sent across threads
+    let evolution = web::block(move || {
+        let result = build_strategy(&EffectiveConfig::from(&parameters)).and_then(|strategy| {
+            block_on(evolution_run.start(&strategy, is_complete)).map_err(|err| err.to_string())
+        });
+        (evolution_run.into_engine(), result)
+    })
+    .await;
+    let (mut engine, result) = match evolution {
+        Ok(evolution) => evolution,
+        Err(err) => {
+            registry.finish(run_id);
+            return Err(err.to_string());
+        }
+    };
 
     engine.unregister_observer(gateway);
+    engine.unregister_observer(timing_gateway);
+    engine.unregister_observer(tracker);
 
-    match result {
+    match engine.annotations() {
+        Ok(annotations) => {
+            registry.update(run_id, |record| record.annotations.extend(annotations));
+        }
+        Err(err) => warn!("Unable to retrieve annotations of run {run_id}: {err}"),
+    }
+
+    match &result {
         Ok(infos) => {
             debug!("Evolution done");
-            HttpResponse::Ok().body(format!(
-                "{}-{:?}",
-                infos.generation,
-                infos
-                    .evaluations
-                    .iter()
-                    .enumerate()
-                    .filter(|e| e.1.fitness >= threshold)
-                    .map(|e| (e.0, unsafe {
-                        String::from_utf8_unchecked(e.1.genome.clone())
-                    }))
-                    .collect::<Vec<_>>()
-            ))
+            registry.record_hall_of_fame(run_id, infos);
+            registry.record_population(run_id, infos);
         }
-        Err(err) => {
-            debug!("Evolution failed");
-            HttpResponse::InternalServerError().body(err.to_string())
+        Err(_) => debug!("Evolution failed"),
+    }
+    registry.finish(run_id);
+    result
+}
+
+#[derive(Deserialize, ToSchema, Clone, Debug)]
+pub struct AnnotationRequest {
+    text: String,
+}
+
+#[derive(Deserialize, IntoParams, Clone, Debug, Default)]
+#[into_params(parameter_in = Query)]
+pub struct RunQuery {
+    /// Whether to include the genomes of the hall of fame (the default) or only the run state.
+    fields: Option<Fields>,
+}
+
+#[utoipa::path(
+    params(("id" = u64, Path, description = "Run identifier"), RunQuery),
+    responses(
+        (status = 200, description = "Run record, including its annotations"),
+        (status = 404, description = "Unknown run")
+    )
+)]
+#[get("/runs/{id}")]
+pub async fn get_run(
+    registry: Data<RunRegistry>,
+    id: Path<u64>,
+    query: Query<RunQuery>,
+) -> impl Responder {
+    match registry.get(id.into_inner()) {
+        Some(mut record) => {
+            if query.fields == Some(Fields::Fitness) {
+                record.hall_of_fame.clear();
+            }
+            HttpResponse::Ok().json(record)
         }
+        None => HttpResponse::NotFound().finish(),
+    }
+}
+
+#[utoipa::path(
+    params(DiffQuery),
+    responses(
+        (status = 200, description = "Differences between the effective settings of the runs, with their outcomes"),
+        (status = 404, description = "Unknown run")
+    )
+)]
+#[get("/runs/diff")]
+pub async fn get_run_diff(registry: Data<RunRegistry>, query: Query<DiffQuery>) -> impl Responder {
+    let outcome = |id| {
+        registry.get(id).map(|record| {
+            registry
+                .read_population(id, |evaluations| {
+                    RunOutcome::new(&record, Some(evaluations))
+                })
+                .unwrap_or_else(|| RunOutcome::new(&record, None))
+        })
+    };
+    match (outcome(query.a), outcome(query.b)) {
+        (Some(a), Some(b)) => HttpResponse::Ok().json(RunDiff::new(a, b)),
+        (None, _) => HttpResponse::NotFound().body(format!("Unknown run {}", query.a)),
+        (_, None) => HttpResponse::NotFound().body(format!("Unknown run {}", query.b)),
+    }
+}
+
+#[utoipa::path(
+    params(("id" = u64, Path, description = "Run identifier"), PageQuery),
+    responses(
+        (status = 200, description = "Page of the evaluations of the last generation of the run"),
+        (status = 404, description = "Unknown or uncompleted run")
+    )
+)]
+#[get("/runs/{id}/evaluations")]
+pub async fn get_run_evaluations(
+    registry: Data<RunRegistry>,
+    id: Path<u64>,
+    query: Query<PageQuery>,
+) -> impl Responder {
+    let id = id.into_inner();
+    let path = format!("/runs/{id}/evaluations");
+    let page = registry.read_population(id, |evaluations| {
+        let pagination = Pagination::new(&query, evaluations.len());
+        let items = pagination
+            .range()
+            .map(|index| EvaluationItem::new(index, &evaluations[index], pagination.fields))
+            .collect();
+        pagination.page(items, &path)
+    });
+    match page {
+        Some(page) => HttpResponse::Ok().json(page),
+        None => HttpResponse::NotFound().finish(),
+    }
+}
+
+#[utoipa::path(
+    params(("id" = u64, Path, description = "Run identifier")),
+    responses(
+        (status = 200, description = "Fitness of the last generations of the run, overlaid with its annotations"),
+        (status = 404, description = "Unknown run")
+    )
+)]
+#[get("/runs/{id}/timeseries")]
+pub async fn get_run_timeseries(registry: Data<RunRegistry>, id: Path<u64>) -> impl Responder {
+    match registry.timeseries(id.into_inner()) {
+        Some(timeseries) => HttpResponse::Ok().json(timeseries),
+        None => HttpResponse::NotFound().finish(),
+    }
+}
+
+#[utoipa::path(
+    request_body = AnnotationRequest,
+    params(("id" = u64, Path, description = "Run identifier")),
+    responses(
+        (status = 201, description = "Annotation attached to the run"),
+        (status = 404, description = "Unknown run")
+    )
+)]
+#[post("/runs/{id}/annotations")]
+pub async fn annotate_run(
+    registry: Data<RunRegistry>,
+    id: Path<u64>,
+    annotation: Json<AnnotationRequest>,
+) -> impl Responder {
+    match registry.annotate(id.into_inner(), annotation.into_inner().text) {
+        Some(annotation) => HttpResponse::Created().json(annotation),
+        None => HttpResponse::NotFound().finish(),
     }
 }
+
+#[utoipa::path(responses(
+    (status = 200, description = "Name, version, description, genome and parameter schemas of the available strategies")
+))]
+#[get("/strategies")]
+pub async fn get_strategies() -> impl Responder {
+    HttpResponse::Ok().json(shipped_strategies())
+}
@@ -0,0 +1,142 @@
+use actix_web::{
+    post,
+    web::{Data, Json, Path},
+    HttpResponse, Responder,
+};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use super::{diff::EffectiveConfig, registry::NimParameters, runs::RunRegistry};
+
+/// Move of a game of Nim, players taking 1 to 3 sticks in turn, whoever takes the last one
+/// losing.
+#[derive(Serialize, Deserialize, ToSchema, Clone, Copy, Debug, PartialEq)]
+pub struct NimMove {
+    /// Sticks taken by the player.
+    pub taken: u8,
+    /// Sticks left after the move.
+    pub remaining: u8,
+}
+
+/// Sticks left in the game the agent plays.
+#[derive(Deserialize, ToSchema, Clone, Copy, Debug)]
+pub struct NimTurn {
+    remaining: u8,
+}
+
+#[utoipa::path(
+    request_body = NimTurn,
+    params(("id" = u64, Path, description = "Run identifier")),
+    responses(
+        (status = 200, description = "Move of the best agent of the run", body = NimMove),
+        (status = 400, description = "No sticks left or more sticks than the game starts with"),
+        (status = 404, description = "Unknown run"),
+        (status = 409, description = "Run not evolving a Nim agent or without agent yet")
+    )
+)]
+#[post("/runs/{id}/nim/moves")]
+pub async fn play_nim_move(
+    registry: Data<RunRegistry>,
+    id: Path<u64>,
+    turn: Json<NimTurn>,
+) -> impl Responder {
+    let Some(record) = registry.get(id.into_inner()) else {
+        return HttpResponse::NotFound().finish();
+    };
+    let parameters = match NimParameters::of(&EffectiveConfig::from(&record.parameters)) {
+        Ok(parameters) => parameters,
+        Err(err) => return HttpResponse::Conflict().body(err),
+    };
+    let Some(agent) = record.hall_of_fame.first() else {
+        return HttpResponse::Conflict().body("No agent evolved yet");
+    };
+    if turn.remaining == 0 || turn.remaining > parameters.stick_count {
+        return HttpResponse::BadRequest().body(format!(
+            "Remaining sticks must be between 1 and {}",
+            parameters.stick_count
+        ));
+    }
+    match parameters.strategy() {
+        Ok(strategy) => {
+            let taken = strategy.play(agent, turn.remaining);
+            HttpResponse::Ok().json(NimMove {
+                taken,
+                remaining: turn.remaining - taken,
+            })
+        }
+        Err(err) => HttpResponse::Conflict().body(err),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use actix_web::{
+        http::{header::LOCATION, StatusCode},
+        test::{self, TestRequest},
+        web::Data,
+        App,
+    };
+    use serde_json::{json, Value};
+
+    use crate::{
+        api::v1::{configure, runs::RunRegistry},
+        config::app::AppConfig,
+    };
+
+    use super::NimMove;
+
+    #[actix_web::test]
+    async fn test_nim_game_over_api() {
+        // Given
+        let app = test::init_service(
+            App::new()
+                .app_data(Data::new(AppConfig::new().unwrap()))
+                .app_data(Data::new(RunRegistry::default()))
+                .configure(configure()),
+        )
+        .await;
+        let started = test::call_service(
+            &app,
+            TestRequest::post()
+                .uri("/runs")
+                .set_json(json!({ "strategy": "nim", "strategy_parameters": { "stick_count": 5 } }))
+                .to_request(),
+        )
+        .await;
+        assert_eq!(StatusCode::ACCEPTED, started.status());
+        let location = started
+            .headers()
+            .get(LOCATION)
+            .unwrap()
+            .to_str()
+            .unwrap()
+            .to_string();
+        let record: Value = test::read_body_json(started).await;
+        assert!(
+            record["parameters"]["seed"].is_u64(),
+            "Should record the seed drawn for the run"
+        );
+        let events = test::call_service(
+            &app,
+            TestRequest::get()
+                .uri(&format!("{location}/events"))
+                .to_request(),
+        )
+        .await;
+        let events = String::from_utf8(test::read_body(events).await.to_vec()).unwrap();
+        assert!(events.ends_with("\n\n") && events.contains("event: done\n"));
+
+        // When
+        let result: NimMove = test::call_and_read_body_json(
+            &app,
+            TestRequest::post()
+                .uri(&format!("{location}/nim/moves"))
+                .set_json(json!({ "remaining": 5 }))
+                .to_request(),
+        )
+        .await;
+
+        // Then
+        assert_eq!(5, result.taken + result.remaining);
+    }
+}
@@ -0,0 +1,245 @@
+use genetic::{Evaluation, Genome};
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+/// Evaluations per page when not requested.
+pub const DEFAULT_PER_PAGE: usize = 100;
+/// Largest page served, keeping responses of big populations reasonable.
+pub const MAX_PER_PAGE: usize = 1000;
+
+/// Parts of the evaluations included in responses.
+#[derive(Deserialize, ToSchema, Clone, Copy, Debug, Default, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum Fields {
+    /// Fitness and age only.
+    #[default]
+    Fitness,
+    /// Genomes and their described phenotypes included.
+    Genomes,
+}
+
+#[derive(Deserialize, IntoParams, Clone, Debug, Default)]
+#[into_params(parameter_in = Query)]
+pub struct PageQuery {
+    /// Page number, starting from 1.
+    pub page: Option<usize>,
+    /// Evaluations per page, up to 1000.
+    pub per_page: Option<usize>,
+    pub fields: Option<Fields>,
+}
+
+#[derive(Serialize, Clone, Debug, PartialEq)]
+pub struct PageLinks {
+    #[serde(rename = "self")]
+    pub current: String,
+    pub first: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prev: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next: Option<String>,
+    pub last: String,
+}
+
+#[derive(Serialize, Clone, Debug, PartialEq)]
+pub struct EvaluationItem {
+    /// Position of the evaluation in the population.
+    pub index: usize,
+    pub fitness: f32,
+    pub age: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub genome: Option<Genome>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub phenotype: Option<String>,
+}
+
+impl EvaluationItem {
+    pub fn new(index: usize, evaluation: &Evaluation, fields: Fields) -> Self {
+        EvaluationItem {
+            index,
+            fitness: evaluation.fitness,
+            age: evaluation.age,
+            genome: (fields == Fields::Genomes).then(|| evaluation.genome.clone()),
+            phenotype: evaluation
+                .phenotype
+                .clone()
+                .filter(|_| fields == Fields::Genomes),
+        }
+    }
+}
+
+#[derive(Serialize, Clone, Debug, PartialEq)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    pub page: usize,
+    pub per_page: usize,
+    pub total_items: usize,
+    pub total_pages: usize,
+    pub links: PageLinks,
+}
+
+/// Page requested from a collection of `total_items`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Pagination {
+    pub page: usize,
+    pub per_page: usize,
+    pub total_items: usize,
+    pub fields: Fields,
+}
+
+impl Pagination {
+    /// Clamps the query within the collection, an out of range page being served empty.
+    pub fn new(query: &PageQuery, total_items: usize) -> Self {
+        Pagination {
+            page: query.page.unwrap_or(1).max(1),
+            per_page: query
+                .per_page
+                .unwrap_or(DEFAULT_PER_PAGE)
+                .clamp(1, MAX_PER_PAGE),
+            total_items,
+            fields: query.fields.unwrap_or_default(),
+        }
+    }
+
+    /// Number of pages, at least one so that an empty collection has a page to link to.
+    pub fn total_pages(&self) -> usize {
+        self.total_items.div_ceil(self.per_page).max(1)
+    }
+
+    /// Positions of the items of the page in the collection.
+    pub fn range(&self) -> std::ops::Range<usize> {
+        let start = (self.page - 1)
+            .saturating_mul(self.per_page)
+            .min(self.total_items);
+        start..(start + self.per_page).min(self.total_items)
+    }
+
+    pub fn links(&self, path: &str) -> PageLinks {
+        let link = |page: usize| {
+            let fields = match self.fields {
+                Fields::Fitness => "fitness",
+                Fields::Genomes => "genomes",
+            };
+            format!(
+                "{path}?page={page}&per_page={}&fields={fields}",
+                self.per_page
+            )
+        };
+        let total_pages = self.total_pages();
+        PageLinks {
+            current: link(self.page),
+            first: link(1),
+            prev: (self.page > 1).then(|| link((self.page - 1).min(total_pages))),
+            next: (self.page < total_pages).then(|| link(self.page + 1)),
+            last: link(total_pages),
+        }
+    }
+
+    pub fn page<T>(&self, items: Vec<T>, path: &str) -> Page<T> {
+        Page {
+            items,
+            page: self.page,
+            per_page: self.per_page,
+            total_items: self.total_items,
+            total_pages: self.total_pages(),
+            links: self.links(path),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Fields, PageQuery, Pagination, MAX_PER_PAGE};
+
+    #[test]
+    fn test_pagination_new() {
+        // When
+        let result = Pagination::new(
+            &PageQuery {
+                page: Some(0),
+                per_page: Some(1_000_000),
+                fields: None,
+            },
+            10,
+        );
+
+        // Then
+        assert_eq!(1, result.page);
+        assert_eq!(MAX_PER_PAGE, result.per_page, "Should cap the page size");
+        assert_eq!(Fields::Fitness, result.fields);
+    }
+
+    #[test]
+    fn test_pagination_range() {
+        // Given
+        let query = PageQuery {
+            page: Some(3),
+            per_page: Some(4),
+            fields: None,
+        };
+
+        // Then
+        assert_eq!(8..10, Pagination::new(&query, 10).range());
+        assert_eq!(
+            8..8,
+            Pagination::new(&query, 8).range(),
+            "Should serve out of range pages empty"
+        );
+        assert_eq!(
+            10..10,
+            Pagination::new(
+                &PageQuery {
+                    page: Some(usize::MAX),
+                    ..query
+                },
+                10
+            )
+            .range(),
+            "Should serve the last possible page empty"
+        );
+    }
+
+    #[test]
+    fn test_pagination_links() {
+        // Given
+        let pagination = Pagination::new(
+            &PageQuery {
+                page: Some(2),
+                per_page: Some(4),
+                fields: Some(Fields::Genomes),
+            },
+            10,
+        );
+
+        // When
+        let result = pagination.links("/runs/1/evaluations");
+
+        // Then
+        assert_eq!(3, pagination.total_pages());
+        assert_eq!(
+            "/runs/1/evaluations?page=2&per_page=4&fields=genomes",
+            result.current
+        );
+        assert_eq!(
+            Some("/runs/1/evaluations?page=1&per_page=4&fields=genomes".to_string()),
+            result.prev
+        );
+        assert_eq!(
+            Some("/runs/1/evaluations?page=3&per_page=4&fields=genomes".to_string()),
+            result.next
+        );
+        assert_eq!(
+            "/runs/1/evaluations?page=3&per_page=4&fields=genomes",
+            result.last
+        );
+
+        // When
+        let result = Pagination::new(&PageQuery::default(), 0).links("/runs/1/evaluations");
+
+        // Then
+        assert_eq!(
+            (None, None),
+            (result.prev, result.next),
+            "Should not link beyond a single page"
+        );
+    }
+}
@@ -0,0 +1,96 @@
+use genetic::adaptation::{DynStrategy, Erased};
+use serde::Deserialize;
+use serde_json::Value;
+use strategies::{info::StrategyInfo, my_strategy::MyStrategy, nim_strategy::NimStrategy};
+
+use super::diff::EffectiveConfig;
+
+/// Fitness of the perfect individuals of the strategies runs can evolve, ending their runs.
+pub const TARGET_FITNESS: f32 = 1.0;
+
+/// Parameters of [`NimStrategy`], see its schema in `GET /strategies`.
+#[derive(Deserialize, Clone, Copy, Debug, PartialEq)]
+pub struct NimParameters {
+    pub stick_count: u8,
+}
+
+impl NimParameters {
+    /// Parameters of a run evolving a [`NimStrategy`].
+    pub fn of(config: &EffectiveConfig) -> Result<Self, String> {
+        if config.strategy != NimStrategy::NAME {
+            return Err(format!(
+                "Run evolves {}, not {}",
+                config.strategy,
+                NimStrategy::NAME
+            ));
+        }
+        serde_json::from_value(config.strategy_parameters.clone().unwrap_or(Value::Null))
+            .map_err(|e| format!("Invalid parameters of {}: {e}", NimStrategy::NAME))
+    }
+
+    pub fn strategy(&self) -> Result<NimStrategy, String> {
+        NimStrategy::new(self.stick_count).map_err(|e| e.to_string())
+    }
+}
+
+/// Strategy a run evolves, picked by name among the ones the API serves.
+pub fn build_strategy(config: &EffectiveConfig) -> Result<Box<DynStrategy>, String> {
+    match config.strategy.as_str() {
+        MyStrategy::NAME => Ok(Erased::boxed(MyStrategy::new(config.target.as_bytes()))),
+        NimStrategy::NAME => Ok(Erased::boxed(NimParameters::of(config)?.strategy()?)),
+        name => Err(format!("Unknown strategy {name}")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use genetic::adaptation::Strategy;
+    use serde_json::json;
+
+    use crate::api::v1::{diff::EffectiveConfig, Parameters};
+
+    use super::{build_strategy, NimParameters};
+
+    fn config(strategy: &str, strategy_parameters: serde_json::Value) -> EffectiveConfig {
+        EffectiveConfig::from(&Parameters {
+            strategy: Some(strategy.to_string()),
+            strategy_parameters: Some(strategy_parameters),
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn test_build_strategy() {
+        // When
+        let result = build_strategy(&config("nim", json!({ "stick_count": 5 })));
+
+        // Then
+        assert_eq!(
+            4,
+            result.unwrap().genome_size(),
+            "Should build the strategy from its parameters"
+        );
+        assert_eq!(
+            7,
+            build_strategy(&EffectiveConfig::from(&Parameters::default()))
+                .unwrap()
+                .genome_size(),
+            "Should evolve the default target by default"
+        );
+        assert!(build_strategy(&config("nim", json!({}))).is_err());
+        assert!(build_strategy(&config("nim", json!({ "stick_count": 1 }))).is_err());
+        assert!(build_strategy(&config("unknown", json!({}))).is_err());
+    }
+
+    #[test]
+    fn test_nim_parameters_of() {
+        assert_eq!(
+            Ok(NimParameters { stick_count: 9 }),
+            NimParameters::of(&config("nim", json!({ "stick_count": 9 })))
+        );
+        assert!(
+            NimParameters::of(&config("my_strategy", json!({ "stick_count": 9 }))).is_err(),
+            "Should only read the parameters of Nim runs"
+        );
+    }
+}
@@ -0,0 +1,694 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex, PoisonError, RwLock,
+    },
+    time::SystemTime,
+};
+
+use actix_web::web::Data;
+use common::subject_observer::Observer;
+use futures::channel::mpsc::{self, UnboundedReceiver, UnboundedSender};
+use genetic::{
+    analysis::GenerationStats,
+    evolution::{
+        Annotation, EventType, EvolutionEngine, EvolutionStatus, RunMetadata, RunProgress, Snapshot,
+    },
+    Evaluation, Genome,
+};
+use serde::Serialize;
+
+use super::Parameters;
+
+/// Number of best genomes kept from a completed run to seed later ones.
+pub const HALL_OF_FAME_SIZE: usize = 10;
+/// Number of runs kept by default, the oldest being evicted beyond.
+pub const DEFAULT_MAX_RUNS: usize = 100;
+/// Number of generations kept in the timeseries of a run, the oldest being dropped once full.
+pub const TIMESERIES_CAPACITY: usize = 1000;
+
+#[derive(Serialize, Clone, Debug)]
+pub struct RunRecord {
+    pub id: u64,
+    pub parameters: Parameters,
+    pub status: EvolutionStatus,
+    pub generation: u64,
+    pub annotations: Vec<Annotation>,
+    /// Best distinct genomes of the last generation, fittest first.
+    pub hall_of_fame: Vec<Genome>,
+    /// Number of individuals of the last generation, whose evaluations are served by pages.
+    pub population_size: usize,
+    /// Throughput and estimated end of the run, once a generation is completed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub progress: Option<RunProgress>,
+    /// Identity of the run in the engine and the metrics, once started.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<RunMetadata>,
+}
+
+/// Outcome of a run returned once it completes.
+#[derive(Serialize, Clone, Debug, PartialEq)]
+pub struct RunResult {
+    pub id: u64,
+    pub generation: u64,
+    pub evaluation_count: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<RunMetadata>,
+    /// Evaluations of the last generation reaching the target fitness.
+    pub solutions: Vec<Solution>,
+}
+
+#[derive(Serialize, Clone, Debug, PartialEq)]
+pub struct Solution {
+    /// Position of the evaluation in the last generation.
+    pub index: usize,
+    #[serde(flatten)]
+    pub evaluation: Evaluation,
+}
+
+impl RunResult {
+    pub fn new(id: u64, snapshot: &Snapshot, threshold: f32) -> Self {
+        RunResult {
+            id,
+            generation: snapshot.generation,
+            evaluation_count: snapshot.evaluation_count,
+            metadata: snapshot.metadata.clone(),
+            solutions: snapshot
+                .evaluations
+                .iter()
+                .enumerate()
+                .filter(|(_, evaluation)| evaluation.fitness >= threshold)
+                .map(|(index, evaluation)| Solution {
+                    index,
+                    evaluation: evaluation.clone(),
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Fitness statistics of an evaluated generation.
+#[derive(Serialize, Clone, Copy, Debug, PartialEq)]
+pub struct FitnessPoint {
+    pub generation: u64,
+    pub min: f32,
+    pub max: f32,
+    pub mean: f32,
+}
+
+/// Fitness of the last generations of a run, with the annotations of the run laid over.
+#[derive(Serialize, Clone, Debug, PartialEq)]
+pub struct Timeseries {
+    pub points: Vec<FitnessPoint>,
+    pub annotations: Vec<Annotation>,
+}
+
+/// Change of a run streamed to its subscribers, see [`RunRegistry::subscribe`].
+#[derive(Clone, Debug)]
+pub enum RunEvent {
+    Status(EvolutionStatus),
+    Progress(RunProgress),
+    /// Last event of the run, with its final record.
+    Done(Box<RunRecord>),
+}
+
+impl RunEvent {
+    /// Server-sent event named after the variant, its data being the JSON of its content.
+    pub fn to_sse(&self) -> String {
+        let (name, data) = match self {
+            RunEvent::Status(status) => ("status", serde_json::to_string(status)),
+            RunEvent::Progress(progress) => ("progress", serde_json::to_string(progress)),
+            RunEvent::Done(record) => ("done", serde_json::to_string(record)),
+        };
+        format!("event: {name}\ndata: {}\n\n", data.unwrap_or_default())
+    }
+}
+
+/// In-memory bookkeeping of the runs handled by the service, the oldest runs being evicted
+/// once `max_runs` are recorded.
+pub struct RunRegistry {
+    max_runs: usize,
+    last_id: AtomicU64,
+    runs: RwLock<HashMap<u64, RunRecord>>,
+    /// Last generation of the completed runs, kept apart from the records so that reading a
+    /// record does not copy the population.
+    populations: RwLock<HashMap<u64, Vec<Evaluation>>>,
+    timeseries: RwLock<HashMap<u64, VecDeque<FitnessPoint>>>,
+    /// Subscribers to the events of the runs not finished yet.
+    subscribers: Mutex<HashMap<u64, Vec<UnboundedSender<RunEvent>>>>,
+}
+
+impl Default for RunRegistry {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_RUNS)
+    }
+}
+
+impl RunRegistry {
+    pub fn new(max_runs: usize) -> Self {
+        RunRegistry {
+            max_runs: max_runs.max(1),
+            last_id: AtomicU64::default(),
+            runs: RwLock::default(),
+            populations: RwLock::default(),
+            timeseries: RwLock::default(),
+            subscribers: Mutex::default(),
+        }
+    }
+
+    pub fn create(&self, parameters: Parameters) -> u64 {
+        let id = self.last_id.fetch_add(1, Ordering::Relaxed) + 1;
+        let record = RunRecord {
+            id,
+            parameters,
+            status: EvolutionStatus::default(),
+            generation: 0,
+            annotations: vec![],
+            hall_of_fame: vec![],
+            population_size: 0,
+            progress: None,
+            metadata: None,
+        };
+        self.subscribers
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(id, vec![]);
+        let evicted = {
+            let mut runs = self.runs.write().unwrap_or_else(PoisonError::into_inner);
+            runs.insert(id, record);
+            let mut ids = runs.keys().copied().collect::<Vec<_>>();
+            ids.sort_unstable();
+            let evicted = ids[..ids.len().saturating_sub(self.max_runs)].to_vec();
+            for id in &evicted {
+                runs.remove(id);
+            }
+            evicted
+        };
+        if !evicted.is_empty() {
+            let mut populations = self
+                .populations
+                .write()
+                .unwrap_or_else(PoisonError::into_inner);
+            let mut timeseries = self
+                .timeseries
+                .write()
+                .unwrap_or_else(PoisonError::into_inner);
+            let mut subscribers = self
+                .subscribers
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner);
+            for id in &evicted {
+                populations.remove(id);
+                timeseries.remove(id);
+                subscribers.remove(id);
+            }
+        }
+        id
+    }
+
+    pub fn get(&self, id: u64) -> Option<RunRecord> {
+        self.runs
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(&id)
+            .cloned()
+    }
+
+    /// Applies `update` to the run, returning its result or `None` when the run is unknown.
+    pub fn update<T>(&self, id: u64, update: impl FnOnce(&mut RunRecord) -> T) -> Option<T> {
+        self.runs
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get_mut(&id)
+            .map(update)
+    }
+
+    /// Keeps the best genomes of the final snapshot of a run.
+    pub fn record_hall_of_fame(&self, id: u64, snapshot: &Snapshot) {
+        self.update(id, |record| {
+            record.hall_of_fame = best_genomes(snapshot, HALL_OF_FAME_SIZE)
+        });
+    }
+
+    /// Keeps the last generation of a run to serve it by pages.
+    pub fn record_population(&self, id: u64, snapshot: &Snapshot) {
+        let recorded = self
+            .update(id, |record| {
+                record.population_size = snapshot.evaluations.len()
+            })
+            .is_some();
+        if recorded {
+            self.populations
+                .write()
+                .unwrap_or_else(PoisonError::into_inner)
+                .insert(id, snapshot.evaluations.clone());
+        }
+    }
+
+    /// Applies `read` to the last generation of the run, `None` when the run is unknown or not
+    /// completed.
+    pub fn read_population<T>(&self, id: u64, read: impl FnOnce(&[Evaluation]) -> T) -> Option<T> {
+        self.populations
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(&id)
+            .map(|evaluations| read(evaluations))
+    }
+
+    /// Appends the fitness of an evaluated generation to the timeseries of the run.
+    pub fn record_fitness(&self, id: u64, generation: u64, stats: &GenerationStats) {
+        let known = self
+            .runs
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .contains_key(&id);
+        if !known {
+            return;
+        }
+        let mut timeseries = self
+            .timeseries
+            .write()
+            .unwrap_or_else(PoisonError::into_inner);
+        let points = timeseries.entry(id).or_default();
+        if points.len() == TIMESERIES_CAPACITY {
+            points.pop_front();
+        }
+        points.push_back(FitnessPoint {
+            generation,
+            min: stats.min,
+            max: stats.max,
+            mean: stats.mean,
+        });
+    }
+
+    /// Fitness of the last generations of the run with its annotations, `None` when the run
+    /// is unknown.
+    pub fn timeseries(&self, id: u64) -> Option<Timeseries> {
+        let annotations = self
+            .runs
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(&id)?
+            .annotations
+            .clone();
+        let points = self
+            .timeseries
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(&id)
+            .map(|points| points.iter().copied().collect())
+            .unwrap_or_default();
+        Some(Timeseries {
+            points,
+            annotations,
+        })
+    }
+
+    /// Events of the run from now on, ending with [`RunEvent::Done`], `None` when the run is
+    /// unknown. Subscribing to a finished run only yields its final record.
+    pub fn subscribe(&self, id: u64) -> Option<UnboundedReceiver<RunEvent>> {
+        let (sender, receiver) = mpsc::unbounded();
+        if let Some(senders) = self
+            .subscribers
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get_mut(&id)
+        {
+            senders.push(sender);
+            return Some(receiver);
+        }
+        // The receiver is kept, sending cannot fail
+        let _ = sender.unbounded_send(RunEvent::Done(Box::new(self.get(id)?)));
+        Some(receiver)
+    }
+
+    /// Sends the event to the subscribers of the run, forgetting the ones gone.
+    pub fn publish(&self, id: u64, event: RunEvent) {
+        if let Some(senders) = self
+            .subscribers
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get_mut(&id)
+        {
+            senders.retain(|sender| sender.unbounded_send(event.clone()).is_ok());
+        }
+    }
+
+    /// Ends the streams of events of the run with its final record.
+    pub fn finish(&self, id: u64) {
+        let senders = self
+            .subscribers
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .remove(&id);
+        if let (Some(senders), Some(record)) = (senders, self.get(id)) {
+            for sender in senders {
+                let _ = sender.unbounded_send(RunEvent::Done(Box::new(record.clone())));
+            }
+        }
+    }
+
+    pub fn annotate(&self, id: u64, text: String) -> Option<Annotation> {
+        self.update(id, |record| {
+            let annotation = Annotation::new(record.generation, text, SystemTime::now());
+            record.annotations.push(annotation.clone());
+            annotation
+        })
+    }
+}
+
+fn best_genomes(snapshot: &Snapshot, count: usize) -> Vec<Genome> {
+    let mut evaluations = snapshot
+        .evaluations
+        .iter()
+        .filter(|e| e.evaluated)
+        .collect::<Vec<_>>();
+    evaluations.sort_by(|a, b| b.fitness.total_cmp(&a.fitness));
+    let mut genomes: Vec<Genome> = Vec::with_capacity(count);
+    for evaluation in evaluations {
+        if genomes.len() == count {
+            break;
+        }
+        if !genomes.contains(&evaluation.genome) {
+            genomes.push(evaluation.genome.clone());
+        }
+    }
+    genomes
+}
+
+/// Keeps the record of a run up to date with the engine executing it.
+pub struct RunTracker {
+    id: u64,
+    registry: Data<RunRegistry>,
+}
+
+impl RunTracker {
+    pub fn new(id: u64, registry: Data<RunRegistry>) -> Arc<Self> {
+        Arc::new(RunTracker { id, registry })
+    }
+}
+
+impl Observer<EvolutionEngine, EventType> for RunTracker {
+    fn update(&self, source: &EvolutionEngine, event: EventType) {
+        match event {
+            EventType::GenerationCreated => {
+                let generation = source.snapshot().generation;
+                self.registry
+                    .update(self.id, |record| record.generation = generation);
+            }
+            EventType::GenerationCompleted { .. } => {
+                let snapshot = source.snapshot();
+                if let Some(stats) = &snapshot.stats {
+                    self.registry
+                        .record_fitness(self.id, snapshot.generation, stats);
+                }
+            }
+            EventType::Progress(progress) => {
+                self.registry
+                    .update(self.id, |record| record.progress = Some(progress));
+                self.registry.publish(self.id, RunEvent::Progress(progress));
+            }
+            EventType::StatusChanged(status) => {
+                let metadata = source.metadata().cloned();
+                self.registry.update(self.id, |record| {
+                    record.status = status;
+                    record.metadata = metadata;
+                });
+                self.registry.publish(self.id, RunEvent::Status(status));
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use genetic::{
+        analysis::GenerationStats,
+        evolution::{EvolutionStatus, Snapshot},
+        Evaluation,
+    };
+
+    use crate::api::v1::Parameters;
+
+    use super::{
+        best_genomes, FitnessPoint, RunEvent, RunRegistry, RunResult, TIMESERIES_CAPACITY,
+    };
+
+    #[test]
+    fn test_run_registry_create() {
+        // Given
+        let registry = RunRegistry::default();
+
+        // When
+        let first = registry.create(Parameters::default());
+        let second = registry.create(Parameters::default());
+
+        // Then
+        assert_ne!(first, second, "Should give each run its own id");
+        assert!(
+            matches!(registry.get(first), Some(record) if record.status == EvolutionStatus::New),
+            "Should register new runs"
+        );
+    }
+
+    #[test]
+    fn test_run_registry_annotate() {
+        // Given
+        let registry = RunRegistry::default();
+        let id = registry.create(Parameters::default());
+        registry.update(id, |record| record.generation = 4);
+
+        // When
+        let result = registry.annotate(id, "manual tweak".to_string());
+
+        // Then
+        assert!(
+            matches!(&result, Some(annotation) if annotation.generation == 4),
+            "Should annotate the current generation of the run"
+        );
+        assert_eq!(
+            vec![result.unwrap()],
+            registry.get(id).unwrap().annotations,
+            "Should store the annotation with the run"
+        );
+        assert!(
+            registry.annotate(id + 1, "unknown".to_string()).is_none(),
+            "Should not annotate unknown runs"
+        );
+    }
+
+    #[test]
+    fn test_run_registry_subscribe() {
+        // Given
+        let registry = RunRegistry::default();
+        let id = registry.create(Parameters::default());
+        let mut events = registry.subscribe(id).unwrap();
+
+        // When
+        registry.publish(id, RunEvent::Status(EvolutionStatus::Running));
+        registry.finish(id);
+
+        // Then
+        assert!(matches!(
+            events.try_next(),
+            Ok(Some(RunEvent::Status(EvolutionStatus::Running)))
+        ));
+        assert!(matches!(events.try_next(), Ok(Some(RunEvent::Done(record))) if record.id == id));
+        assert!(
+            matches!(events.try_next(), Ok(None)),
+            "Should end the events with the run"
+        );
+        let mut late_events = registry.subscribe(id).unwrap();
+        assert!(
+            matches!(late_events.try_next(), Ok(Some(RunEvent::Done(_)))),
+            "Should only yield the final record of a finished run"
+        );
+        assert!(registry.subscribe(id + 1).is_none());
+    }
+
+    #[test]
+    fn test_run_registry_create_should_evict_oldest_runs() {
+        // Given
+        let registry = RunRegistry::new(2);
+        let first = registry.create(Parameters::default());
+        registry.record_population(first, &Snapshot::default());
+
+        // When
+        let second = registry.create(Parameters::default());
+        let third = registry.create(Parameters::default());
+
+        // Then
+        assert!(
+            registry.get(first).is_none(),
+            "Should evict the oldest run beyond the max"
+        );
+        assert!(registry.read_population(first, |_| ()).is_none());
+        assert!(registry.get(second).is_some() && registry.get(third).is_some());
+    }
+
+    #[test]
+    fn test_run_registry_timeseries() {
+        // Given
+        let registry = RunRegistry::default();
+        let id = registry.create(Parameters::default());
+        let stats = GenerationStats {
+            min: 0.1,
+            max: 0.9,
+            mean: 0.5,
+            std_dev: 0.2,
+            best: 0,
+            count: 4,
+        };
+        for generation in 0..TIMESERIES_CAPACITY as u64 + 2 {
+            registry.record_fitness(id, generation, &stats);
+        }
+        let annotation = registry.annotate(id, "manual tweak".to_string()).unwrap();
+
+        // When
+        let result = registry.timeseries(id).unwrap();
+
+        // Then
+        assert_eq!(TIMESERIES_CAPACITY, result.points.len());
+        assert_eq!(
+            FitnessPoint {
+                generation: 2,
+                min: 0.1,
+                max: 0.9,
+                mean: 0.5,
+            },
+            result.points[0],
+            "Should drop the oldest generations once full"
+        );
+        assert_eq!(
+            vec![annotation],
+            result.annotations,
+            "Should overlay the annotations of the run"
+        );
+        registry.record_fitness(id + 1, 0, &stats);
+        assert!(
+            registry.timeseries(id + 1).is_none(),
+            "Should not record the fitness of unknown runs"
+        );
+    }
+
+    #[test]
+    fn test_run_registry_record_population() {
+        // Given
+        let registry = RunRegistry::default();
+        let id = registry.create(Parameters::default());
+        let snapshot = Snapshot {
+            generation: 2,
+            evaluations: vec![Evaluation::default(); 3],
+            evaluation_count: 0,
+            hall_of_fame: vec![],
+            stats: None,
+            diversity: None,
+            parameters: None,
+            metadata: None,
+            lineage: None,
+        };
+
+        // When
+        registry.record_population(id, &snapshot);
+        registry.record_population(id + 1, &snapshot);
+
+        // Then
+        assert_eq!(3, registry.get(id).unwrap().population_size);
+        assert_eq!(
+            Some(3),
+            registry.read_population(id, |evaluations| evaluations.len()),
+            "Should keep the population of the run"
+        );
+        assert!(
+            registry.read_population(id + 1, |_| ()).is_none(),
+            "Should not keep populations of unknown runs"
+        );
+    }
+
+    #[test]
+    fn test_run_result() {
+        // Given
+        let snapshot = Snapshot {
+            generation: 5,
+            evaluations: [0.4, 3.0, 2.0, 3.0]
+                .into_iter()
+                .map(|fitness| Evaluation {
+                    fitness,
+                    ..Default::default()
+                })
+                .collect(),
+            evaluation_count: 24,
+            hall_of_fame: vec![],
+            stats: None,
+            diversity: None,
+            parameters: None,
+            metadata: None,
+            lineage: None,
+        };
+
+        // When
+        let result = RunResult::new(7, &snapshot, 3.0);
+
+        // Then
+        assert_eq!(
+            (7, 5, 24),
+            (result.id, result.generation, result.evaluation_count)
+        );
+        assert_eq!(
+            vec![1, 3],
+            result
+                .solutions
+                .iter()
+                .map(|solution| solution.index)
+                .collect::<Vec<_>>(),
+            "Should keep the evaluations reaching the target"
+        );
+        let json = serde_json::to_value(&result).unwrap();
+        assert_eq!(
+            Some(3.0),
+            json["solutions"][0]["fitness"].as_f64(),
+            "Should flatten the evaluations of the solutions"
+        );
+    }
+
+    #[test]
+    fn test_best_genomes() {
+        // Given
+        let snapshot = Snapshot {
+            generation: 3,
+            evaluations: [
+                (vec![1], 0.2),
+                (vec![2], 0.9),
+                (vec![3], 0.5),
+                (vec![2], 0.9),
+            ]
+            .into_iter()
+            .map(|(genome, fitness)| Evaluation {
+                genome,
+                fitness,
+                evaluated: true,
+                ..Default::default()
+            })
+            .collect(),
+            evaluation_count: 0,
+            hall_of_fame: vec![],
+            stats: None,
+            diversity: None,
+            parameters: None,
+            metadata: None,
+            lineage: None,
+        };
+
+        // When
+        let result = best_genomes(&snapshot, 2);
+
+        // Then
+        assert_eq!(
+            vec![vec![2], vec![3]],
+            result,
+            "Should keep the fittest distinct genomes first"
+        );
+    }
+}
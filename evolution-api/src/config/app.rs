@@ -10,6 +10,8 @@ const DEFAULT_CONFIG_PREFIX: &str = "APP";
 pub struct AppConfig {
     pub service_host: String,
     pub service_port: u16,
+    pub max_run_duration_secs: u64,
+    pub max_runs: usize,
     pub statsd_host: String,
     pub statsd_port: u16,
     pub statsd_factor: f32,
@@ -5,7 +5,7 @@ use std::io;
 
 use ::config::ConfigError;
 use actix_web::{middleware::Logger, web::Data, App, HttpServer};
-use api::ApiDoc;
+use api::{v1::runs::RunRegistry, ApiDoc};
 use config::{app::AppConfig, log};
 use thiserror::Error;
 use utoipa::OpenApi;
@@ -37,10 +37,12 @@ async fn main() -> io::Result<()> {
 
     let openapi = ApiDoc::openapi();
 
+    let runs = Data::new(RunRegistry::new(app_config.max_runs));
     let data = Data::new(app_config);
     HttpServer::new(move || {
         App::new()
             .app_data(data.clone())
+            .app_data(runs.clone())
             .wrap(Logger::default())
             .configure(api::v1::configure())
             .service(RapiDoc::with_openapi(API_MANIFEST_PATH, openapi.clone()).path(API_DOC_PATH))
@@ -0,0 +1,111 @@
+//! `#[derive(GenomeDecode)]`, re-exported by `genetic::schema` with the `derive` feature.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{
+    parse_macro_input, spanned::Spanned, Data, DeriveInput, Error, Expr, ExprRange, Fields, Index,
+    Member, RangeLimits, Type,
+};
+
+/// Implements `genetic::schema::GenomeDecode`, each field of the struct taking a segment of the
+/// genome in order of declaration.
+///
+/// Fields require a `#[gene(range = start..end)]` attribute. `f32` and `f64` fields are real
+/// fields, other types being integer fields converted with `as`.
+#[proc_macro_derive(GenomeDecode, attributes(gene))]
+pub fn derive_genome_decode(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(&input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+fn expand(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let Data::Struct(data) = &input.data else {
+        return Err(Error::new(
+            input.span(),
+            "GenomeDecode can only be derived for structs",
+        ));
+    };
+
+    let mut fields = Vec::new();
+    let mut values = Vec::new();
+    for (index, field) in data.fields.iter().enumerate() {
+        let member = match &field.ident {
+            Some(ident) => Member::Named(ident.clone()),
+            None => Member::Unnamed(Index::from(index)),
+        };
+        let name = match &field.ident {
+            Some(ident) => ident.to_string(),
+            None => index.to_string(),
+        };
+        let (start, end) = parse_range(field)?;
+        let ty = &field.ty;
+        if is_float(ty) {
+            fields.push(quote! { .float(#name, (#start) as f64..(#end) as f64) });
+            values.push(quote! {
+                #member: genes[#index].as_float().unwrap_or_default() as #ty
+            });
+        } else {
+            fields.push(quote! { .int(#name, (#start) as i64..(#end) as i64) });
+            values.push(quote! {
+                #member: genes[#index].as_int().unwrap_or_default() as #ty
+            });
+        }
+    }
+    let construct = match data.fields {
+        Fields::Unit => quote! { Self },
+        _ => quote! { Self { #(#values),* } },
+    };
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::genetic::schema::GenomeDecode for #ident #ty_generics #where_clause {
+            fn schema() -> ::genetic::schema::GeneSchema {
+                ::genetic::schema::GeneSchema::new() #(#fields)*
+            }
+
+            #[allow(unused_variables)]
+            fn from_genes(genes: &[::genetic::schema::GeneValue]) -> Self {
+                #construct
+            }
+        }
+    })
+}
+
+/// Bounds of the `#[gene(range = start..end)]` attribute of the field.
+fn parse_range(field: &syn::Field) -> syn::Result<(Box<Expr>, Box<Expr>)> {
+    let attribute = field
+        .attrs
+        .iter()
+        .find(|attribute| attribute.path().is_ident("gene"))
+        .ok_or_else(|| Error::new(field.span(), "Missing #[gene(range = start..end)]"))?;
+    let mut range = None;
+    attribute.parse_nested_meta(|meta| {
+        if meta.path.is_ident("range") {
+            range = Some(meta.value()?.parse::<ExprRange>()?);
+            Ok(())
+        } else {
+            Err(meta.error("Unknown gene attribute, expected `range`"))
+        }
+    })?;
+    match range {
+        Some(ExprRange {
+            start: Some(start),
+            limits: RangeLimits::HalfOpen(_),
+            end: Some(end),
+            ..
+        }) => Ok((start, end)),
+        Some(range) => Err(Error::new(
+            range.span(),
+            "Expected a bounded half-open range start..end",
+        )),
+        None => Err(Error::new(attribute.span(), "Missing range = start..end")),
+    }
+}
+
+fn is_float(ty: &Type) -> bool {
+    matches!(ty, Type::Path(path) if path.path.is_ident("f32") || path.path.is_ident("f64"))
+}
@@ -0,0 +1,58 @@
+use genetic::{
+    codec::GenomeCodec,
+    schema::{DecodeGenome, GeneValue, GenomeDecode},
+};
+
+#[derive(Debug, PartialEq, GenomeDecode)]
+struct Params {
+    #[gene(range = 1..10)]
+    depth: u8,
+    #[gene(range = -1.0..1.0)]
+    rate: f32,
+    #[gene(range = -1000..1000)]
+    offset: i32,
+}
+
+#[derive(Debug, PartialEq, GenomeDecode)]
+struct Point(#[gene(range = 0..100)] usize, #[gene(range = 0.0..1.0)] f64);
+
+#[test]
+fn test_schema() {
+    assert_eq!(
+        genetic::schema::GeneSchema::new()
+            .int("depth", 1..10)
+            .float("rate", -1.0..1.0)
+            .int("offset", -1000..1000),
+        Params::schema()
+    );
+    assert_eq!(5, Point::schema().genome_size());
+}
+
+#[test]
+fn test_decode() {
+    // Given
+    let genome = Params::schema().encode(&[
+        GeneValue::Int(7),
+        GeneValue::Float(0.5),
+        GeneValue::Int(-999),
+    ]);
+
+    // When
+    let result = genome.decode::<Params>();
+
+    // Then
+    assert_eq!(
+        Ok(Params {
+            depth: 7,
+            rate: 0.5,
+            offset: -999
+        }),
+        result
+    );
+    assert_eq!(
+        Ok(Point(99, 0.0)),
+        vec![u8::MAX, 0, 0, 0, 0].decode::<Point>(),
+        "Should decode tuple structs"
+    );
+    assert!(vec![0; 4].decode::<Point>().is_err());
+}
@@ -0,0 +1,114 @@
+//! Evaluation of the generations on remote worker processes.
+//!
+//! The master, a [`WorkerPool`] run by [`genetic::evolution::EvolutionEngine::start_batch`],
+//! splits each generation into chunks sent to the workers served by [`serve`]. Messages are
+//! JSON documents, one per line, over TCP: an [`EvaluationRequest`] answered by an
+//! [`EvaluationResponse`] on the same connection.
+mod worker;
+mod worker_pool;
+
+use std::io::{self, BufRead, Write};
+
+use genetic::Genome;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use thiserror::Error;
+pub use worker::{serve, serve_connection};
+pub use worker_pool::WorkerPool;
+
+#[derive(Error, Debug)]
+pub enum DistributedError {
+    #[error("Unable to communicate with worker: {0}")]
+    Io(#[from] io::Error),
+    #[error("Invalid message: {0}")]
+    Protocol(#[from] serde_json::Error),
+    #[error("Connection closed before the response")]
+    Closed,
+    #[error("Worker failed to evaluate: {0}")]
+    Evaluation(String),
+    #[error("No worker to evaluate the genomes")]
+    NoWorker,
+    #[error("Chunk {chunk} failed after {attempts} attempt(s), last with: {cause}")]
+    Exhausted {
+        chunk: usize,
+        attempts: usize,
+        cause: Box<DistributedError>,
+    },
+}
+
+/// Genomes of a chunk of a generation to evaluate.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct EvaluationRequest {
+    pub genomes: Vec<Genome>,
+}
+
+/// Outcome of an [`EvaluationRequest`].
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum EvaluationResponse {
+    /// Fitnesses of the genomes, in the order of the request.
+    Fitnesses(Vec<f32>),
+    Error(String),
+}
+
+/// Writes a message on its own line.
+fn send<T: Serialize>(writer: &mut impl Write, message: &T) -> Result<(), DistributedError> {
+    let mut line = serde_json::to_vec(message)?;
+    line.push(b'\n');
+    writer.write_all(&line)?;
+    writer.flush()?;
+    Ok(())
+}
+
+/// Reads the message of the next line, `None` once the connection is closed.
+fn receive<T: DeserializeOwned>(reader: &mut impl BufRead) -> Result<Option<T>, DistributedError> {
+    let mut line = String::new();
+    if reader.read_line(&mut line)? == 0 {
+        return Ok(None);
+    }
+    Ok(Some(serde_json::from_str(&line)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{BufReader, Cursor};
+
+    use super::{receive, send, EvaluationRequest, EvaluationResponse};
+
+    #[test]
+    fn test_send_receive() {
+        // Given
+        let mut buffer = vec![];
+
+        // When
+        send(
+            &mut buffer,
+            &EvaluationRequest {
+                genomes: vec![vec![1, 2], vec![3, 4]],
+            },
+        )
+        .unwrap();
+        send(&mut buffer, &EvaluationResponse::Fitnesses(vec![0.5])).unwrap();
+
+        // Then
+        assert_eq!(
+            "{\"genomes\":[[1,2],[3,4]]}\n{\"fitnesses\":[0.5]}\n",
+            String::from_utf8(buffer.clone()).unwrap()
+        );
+        let mut reader = BufReader::new(Cursor::new(buffer));
+        assert_eq!(
+            Some(EvaluationRequest {
+                genomes: vec![vec![1, 2], vec![3, 4]],
+            }),
+            receive(&mut reader).unwrap()
+        );
+        assert_eq!(
+            Some(EvaluationResponse::Fitnesses(vec![0.5])),
+            receive(&mut reader).unwrap()
+        );
+        assert_eq!(
+            None,
+            receive::<EvaluationRequest>(&mut reader).unwrap(),
+            "Should tell the end of the connection"
+        );
+    }
+}
@@ -0,0 +1,134 @@
+use std::{
+    io::{self, BufReader},
+    net::{TcpListener, TcpStream},
+};
+
+use genetic::{adaptation::Strategy, Genome};
+use log::{debug, warn};
+
+use super::{receive, send, DistributedError, EvaluationRequest, EvaluationResponse};
+
+/// Evaluates the requests of the masters connecting to the listener with the strategy, one
+/// connection at a time so that each worker process runs a single evaluation.
+///
+/// Only returns when the listener fails, the failures of a connection being logged.
+pub fn serve<S: Strategy>(listener: TcpListener, strategy: &S) -> io::Result<()> {
+    for stream in listener.incoming() {
+        let stream = stream?;
+        let peer = stream.peer_addr().map_or_else(
+            |_| "unknown peer".to_string(),
+            |address| address.to_string(),
+        );
+        debug!("Serving evaluations to {peer}");
+        if let Err(err) = serve_connection(stream, strategy) {
+            warn!("Connection with {peer} failed: {err}");
+        }
+    }
+    Ok(())
+}
+
+/// Evaluates the requests received on the connection until the master closes it.
+pub fn serve_connection<S: Strategy>(
+    stream: TcpStream,
+    strategy: &S,
+) -> Result<(), DistributedError> {
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let mut writer = stream;
+    while let Some(EvaluationRequest { genomes }) = receive(&mut reader)? {
+        send(&mut writer, &evaluate(strategy, &genomes))?;
+    }
+    Ok(())
+}
+
+fn evaluate<S: Strategy>(strategy: &S, genomes: &[Genome]) -> EvaluationResponse {
+    let genome_size = strategy.genome_size();
+    if let Some(genome) = genomes.iter().find(|genome| genome.len() != genome_size) {
+        return EvaluationResponse::Error(format!(
+            "Genome of {} genes while the strategy expects {}",
+            genome.len(),
+            genome_size
+        ));
+    }
+    EvaluationResponse::Fitnesses(
+        genomes
+            .iter()
+            .map(|genome| strategy.evaluate(&strategy.decode(genome)))
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        io::BufReader,
+        net::{TcpListener, TcpStream},
+        thread,
+    };
+
+    use genetic::{adaptation::Strategy, Genome};
+
+    use super::serve;
+    use crate::distributed::{receive, send, EvaluationRequest, EvaluationResponse};
+
+    struct Sum;
+
+    impl Strategy for Sum {
+        type Phenotype = u32;
+
+        fn genome_size(&self) -> usize {
+            2
+        }
+
+        fn decode(&self, genome: &Genome) -> u32 {
+            genome.iter().map(|&gene| gene as u32).sum()
+        }
+
+        fn evaluate(&self, sum: &u32) -> f32 {
+            *sum as f32 / 510.0
+        }
+    }
+
+    #[test]
+    fn test_serve() {
+        // Given
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        thread::spawn(move || serve(listener, &Sum));
+        let stream = TcpStream::connect(address).unwrap();
+        let mut reader = BufReader::new(stream.try_clone().unwrap());
+        let mut writer = stream;
+
+        // When
+        send(
+            &mut writer,
+            &EvaluationRequest {
+                genomes: vec![vec![0, 0], vec![255, 0]],
+            },
+        )
+        .unwrap();
+
+        // Then
+        assert_eq!(
+            Some(EvaluationResponse::Fitnesses(vec![0.0, 0.5])),
+            receive(&mut reader).unwrap()
+        );
+
+        // When
+        send(
+            &mut writer,
+            &EvaluationRequest {
+                genomes: vec![vec![1, 2, 3]],
+            },
+        )
+        .unwrap();
+
+        // Then
+        assert!(
+            matches!(
+                receive(&mut reader).unwrap(),
+                Some(EvaluationResponse::Error(_))
+            ),
+            "Should reject genomes not matching the strategy"
+        );
+    }
+}
@@ -0,0 +1,404 @@
+use std::{
+    error::Error,
+    io::BufReader,
+    net::{SocketAddr, TcpStream},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc::{self, RecvTimeoutError, Sender},
+        Arc,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+use genetic::{adaptation::BatchStrategy, Genome};
+use log::{debug, warn};
+
+use super::{receive, send, DistributedError, EvaluationRequest, EvaluationResponse};
+
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(60);
+const DEFAULT_MAX_ATTEMPTS: usize = 3;
+
+/// Master dispatching the genomes of each generation to remote workers, see [`super::serve`].
+///
+/// The generation is split into chunks, by default one per worker, sent to the workers in turn.
+/// A chunk failing, e.g. an unreachable worker or one exceeding the timeout, is sent again to
+/// the next worker, up to the maximum attempts. With a straggler delay, chunks still running
+/// after it are also sent to another worker, the first response being kept.
+///
+/// Workers cannot repair genomes, strategies relying on [`genetic::adaptation::Strategy::repair`]
+/// are not suited.
+pub struct WorkerPool {
+    workers: Vec<SocketAddr>,
+    genome_size: usize,
+    chunk_size: Option<usize>,
+    timeout: Duration,
+    straggler_delay: Option<Duration>,
+    max_attempts: usize,
+    next_worker: AtomicUsize,
+}
+
+/// Chunk of the generation being evaluated.
+struct Chunk {
+    request: Arc<EvaluationRequest>,
+    attempts: usize,
+    running: usize,
+    dispatched_at: Instant,
+    fitnesses: Option<Vec<f32>>,
+}
+
+impl WorkerPool {
+    /// Dispatches genomes of `genome_size` genes to the workers listening at the addresses.
+    pub fn new(workers: Vec<SocketAddr>, genome_size: usize) -> Self {
+        WorkerPool {
+            workers,
+            genome_size,
+            chunk_size: None,
+            timeout: DEFAULT_TIMEOUT,
+            straggler_delay: None,
+            max_attempts: DEFAULT_MAX_ATTEMPTS,
+            next_worker: AtomicUsize::new(0),
+        }
+    }
+
+    /// Number of genomes sent at once to a worker.
+    pub fn chunk_size(mut self, chunk_size: usize) -> Self {
+        self.chunk_size = Some(chunk_size.max(1));
+        self
+    }
+
+    /// Time allowed to connect to a worker and to get its response.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Time after which a chunk still running is also sent to another worker.
+    pub fn straggler_delay(mut self, delay: Duration) -> Self {
+        self.straggler_delay = Some(delay);
+        self
+    }
+
+    /// Number of times a chunk is sent before giving up, backups of stragglers included.
+    pub fn max_attempts(mut self, max_attempts: usize) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self
+    }
+
+    /// Fitnesses of the genomes, in their order.
+    pub fn evaluate(&self, genomes: &[&Genome]) -> Result<Vec<f32>, DistributedError> {
+        if genomes.is_empty() {
+            return Ok(vec![]);
+        }
+        if self.workers.is_empty() {
+            return Err(DistributedError::NoWorker);
+        }
+        let chunk_size = self
+            .chunk_size
+            .unwrap_or_else(|| genomes.len().div_ceil(self.workers.len()));
+        let now = Instant::now();
+        let mut chunks = genomes
+            .chunks(chunk_size)
+            .map(|genomes| Chunk {
+                request: Arc::new(EvaluationRequest {
+                    genomes: genomes.iter().map(|&genome| genome.clone()).collect(),
+                }),
+                attempts: 0,
+                running: 0,
+                dispatched_at: now,
+                fitnesses: None,
+            })
+            .collect::<Vec<_>>();
+
+        let (sender, receiver) = mpsc::channel();
+        for (index, chunk) in chunks.iter_mut().enumerate() {
+            self.dispatch(index, chunk, &sender);
+        }
+        let mut remaining = chunks.len();
+        while remaining > 0 {
+            let wait = self
+                .next_straggler_deadline(&chunks)
+                .map_or(Duration::MAX, |deadline| {
+                    deadline.saturating_duration_since(Instant::now())
+                });
+            let (index, result) = match receiver.recv_timeout(wait) {
+                Ok(received) => received,
+                Err(RecvTimeoutError::Timeout) => {
+                    self.dispatch_stragglers(&mut chunks, &sender);
+                    continue;
+                }
+                Err(RecvTimeoutError::Disconnected) => unreachable!("The sender is kept"),
+            };
+            let chunk = &mut chunks[index];
+            chunk.running -= 1;
+            if chunk.fitnesses.is_some() {
+                continue;
+            }
+            match result {
+                Ok(fitnesses) => {
+                    chunk.fitnesses = Some(fitnesses);
+                    remaining -= 1;
+                }
+                Err(err) if chunk.attempts < self.max_attempts => {
+                    warn!("Chunk {index} failed, sending it again: {err}");
+                    self.dispatch(index, chunk, &sender);
+                }
+                Err(err) if chunk.running == 0 => {
+                    return Err(DistributedError::Exhausted {
+                        chunk: index,
+                        attempts: chunk.attempts,
+                        cause: Box::new(err),
+                    });
+                }
+                Err(err) => warn!("Chunk {index} failed, waiting for its other attempts: {err}"),
+            }
+        }
+        Ok(chunks
+            .into_iter()
+            .flat_map(|chunk| chunk.fitnesses.unwrap_or_default())
+            .collect())
+    }
+
+    /// Sends the chunk to the next worker from a thread of its own, left behind once the
+    /// chunk is evaluated by another attempt.
+    fn dispatch(
+        &self,
+        index: usize,
+        chunk: &mut Chunk,
+        sender: &Sender<(usize, Result<Vec<f32>, DistributedError>)>,
+    ) {
+        let worker =
+            self.workers[self.next_worker.fetch_add(1, Ordering::Relaxed) % self.workers.len()];
+        chunk.attempts += 1;
+        chunk.running += 1;
+        chunk.dispatched_at = Instant::now();
+        let request = chunk.request.clone();
+        let sender = sender.clone();
+        let timeout = self.timeout;
+        thread::spawn(move || {
+            // The master may not wait for stragglers
+            let _ = sender.send((index, request_worker(worker, &request, timeout)));
+        });
+    }
+
+    fn next_straggler_deadline(&self, chunks: &[Chunk]) -> Option<Instant> {
+        let delay = self.straggler_delay?;
+        chunks
+            .iter()
+            .filter(|chunk| self.is_straggler_candidate(chunk))
+            .map(|chunk| chunk.dispatched_at + delay)
+            .min()
+    }
+
+    fn dispatch_stragglers(
+        &self,
+        chunks: &mut [Chunk],
+        sender: &Sender<(usize, Result<Vec<f32>, DistributedError>)>,
+    ) {
+        let Some(delay) = self.straggler_delay else {
+            return;
+        };
+        for (index, chunk) in chunks.iter_mut().enumerate() {
+            if self.is_straggler_candidate(chunk) && chunk.dispatched_at.elapsed() >= delay {
+                debug!("Chunk {index} is straggling, sending a backup");
+                self.dispatch(index, chunk, sender);
+            }
+        }
+    }
+
+    fn is_straggler_candidate(&self, chunk: &Chunk) -> bool {
+        chunk.fitnesses.is_none() && chunk.running > 0 && chunk.attempts < self.max_attempts
+    }
+}
+
+impl BatchStrategy for WorkerPool {
+    fn genome_size(&self) -> usize {
+        self.genome_size
+    }
+
+    fn evaluate_batch(
+        &self,
+        genomes: &[&Genome],
+    ) -> Result<Vec<f32>, Box<dyn Error + Send + Sync>> {
+        Ok(self.evaluate(genomes)?)
+    }
+}
+
+/// Sends the request to the worker on a new connection and waits for its fitnesses.
+fn request_worker(
+    worker: SocketAddr,
+    request: &EvaluationRequest,
+    timeout: Duration,
+) -> Result<Vec<f32>, DistributedError> {
+    let stream = TcpStream::connect_timeout(&worker, timeout)?;
+    stream.set_read_timeout(Some(timeout))?;
+    stream.set_write_timeout(Some(timeout))?;
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let mut writer = stream;
+    send(&mut writer, request)?;
+    match receive(&mut reader)? {
+        Some(EvaluationResponse::Fitnesses(fitnesses))
+            if fitnesses.len() == request.genomes.len() =>
+        {
+            Ok(fitnesses)
+        }
+        Some(EvaluationResponse::Fitnesses(fitnesses)) => {
+            Err(DistributedError::Evaluation(format!(
+                "{} fitnesses for {} genomes",
+                fitnesses.len(),
+                request.genomes.len()
+            )))
+        }
+        Some(EvaluationResponse::Error(message)) => Err(DistributedError::Evaluation(message)),
+        None => Err(DistributedError::Closed),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        net::{SocketAddr, TcpListener},
+        thread,
+        time::{Duration, Instant},
+    };
+
+    use genetic::{
+        adaptation::{BatchStrategy, Strategy},
+        Genome,
+    };
+
+    use super::WorkerPool;
+    use crate::distributed::{serve, DistributedError};
+
+    /// Reads genomes as the sum of their genes, after a delay.
+    struct Sum(Duration);
+
+    impl Strategy for Sum {
+        type Phenotype = u32;
+
+        fn genome_size(&self) -> usize {
+            2
+        }
+
+        fn decode(&self, genome: &Genome) -> u32 {
+            genome.iter().map(|&gene| gene as u32).sum()
+        }
+
+        fn evaluate(&self, sum: &u32) -> f32 {
+            thread::sleep(self.0);
+            *sum as f32 / 510.0
+        }
+    }
+
+    fn spawn_worker(delay: Duration) -> SocketAddr {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        thread::spawn(move || serve(listener, &Sum(delay)));
+        address
+    }
+
+    /// Address of a worker closing the connections without responding.
+    fn closing_worker() -> SocketAddr {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        thread::spawn(move || listener.incoming().for_each(drop));
+        address
+    }
+
+    fn genomes() -> Vec<Genome> {
+        (0..10u8).map(|gene| vec![gene, 2 * gene]).collect()
+    }
+
+    fn expected() -> Vec<f32> {
+        (0..10).map(|gene| (3 * gene) as f32 / 510.0).collect()
+    }
+
+    #[test]
+    fn test_worker_pool_evaluate_batch() {
+        // Given
+        let pool = WorkerPool::new(
+            vec![spawn_worker(Duration::ZERO), spawn_worker(Duration::ZERO)],
+            2,
+        )
+        .chunk_size(3);
+        let genomes = genomes();
+
+        // When
+        let result = pool.evaluate_batch(&genomes.iter().collect::<Vec<_>>());
+
+        // Then
+        assert_eq!(expected(), result.unwrap());
+        assert_eq!(2, pool.genome_size());
+    }
+
+    #[test]
+    fn test_worker_pool_should_retry_failed_chunks() {
+        // Given
+        let pool = WorkerPool::new(vec![closing_worker(), spawn_worker(Duration::ZERO)], 2);
+        let genomes = genomes();
+
+        // When
+        let result = pool.evaluate(&genomes.iter().collect::<Vec<_>>());
+
+        // Then
+        assert_eq!(expected(), result.unwrap());
+
+        // Given
+        let pool = WorkerPool::new(vec![closing_worker()], 2).max_attempts(2);
+
+        // When
+        let result = pool.evaluate(&genomes.iter().collect::<Vec<_>>());
+
+        // Then
+        assert!(
+            matches!(result, Err(DistributedError::Exhausted { attempts: 2, .. })),
+            "Should give up after the maximum attempts, got {result:?}"
+        );
+    }
+
+    #[test]
+    fn test_worker_pool_should_fail_without_workers() {
+        // Given
+        let pool = WorkerPool::new(vec![], 2);
+        let genomes = genomes();
+
+        // When
+        let result = pool.evaluate(&genomes.iter().collect::<Vec<_>>());
+
+        // Then
+        assert!(
+            matches!(result, Err(DistributedError::NoWorker)),
+            "Should not evaluate without workers, got {result:?}"
+        );
+        assert!(
+            matches!(pool.evaluate(&[]), Ok(fitnesses) if fitnesses.is_empty()),
+            "Should not need workers to evaluate no genome"
+        );
+    }
+
+    #[test]
+    fn test_worker_pool_should_back_up_stragglers() {
+        // Given
+        let pool = WorkerPool::new(
+            vec![
+                spawn_worker(Duration::from_secs(5)),
+                spawn_worker(Duration::ZERO),
+            ],
+            2,
+        )
+        .chunk_size(10)
+        .straggler_delay(Duration::from_millis(50));
+        let genomes = genomes();
+
+        // When
+        let started_at = Instant::now();
+        let result = pool.evaluate(&genomes.iter().collect::<Vec<_>>());
+
+        // Then
+        assert_eq!(expected(), result.unwrap());
+        assert!(
+            started_at.elapsed() < Duration::from_secs(5),
+            "Should keep the response of the backup"
+        );
+    }
+}
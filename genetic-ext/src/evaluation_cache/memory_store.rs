@@ -0,0 +1,181 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::{Mutex, PoisonError},
+};
+
+use genetic::Genome;
+
+use super::{EvaluationCacheError, EvaluationStore, StoredEvaluation};
+
+type Key = (String, Genome);
+
+/// Keeps evaluations in memory, for the lifetime of the store or up to a capacity beyond which
+/// the least recently used ones are dropped.
+#[derive(Default)]
+pub struct MemoryStore {
+    capacity: Option<usize>,
+    entries: Mutex<Entries>,
+}
+
+#[derive(Default)]
+struct Entries {
+    /// Evaluation and last use of each genome.
+    evaluations: HashMap<Key, (StoredEvaluation, u64)>,
+    /// Evaluations by last use, only maintained when the store is bounded.
+    usage: BTreeMap<u64, Key>,
+    tick: u64,
+}
+
+impl MemoryStore {
+    /// Store keeping at most `capacity` evaluations, none if zero.
+    pub fn with_capacity(capacity: usize) -> Self {
+        MemoryStore {
+            capacity: Some(capacity),
+            entries: Mutex::default(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .evaluations
+            .len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl Entries {
+    /// Marks the evaluation stored under `key` as the most recently used one.
+    fn touch(&mut self, key: &Key, last_use: u64) -> u64 {
+        self.usage.remove(&last_use);
+        self.tick += 1;
+        self.usage.insert(self.tick, key.clone());
+        self.tick
+    }
+}
+
+impl EvaluationStore for MemoryStore {
+    fn get(
+        &self,
+        strategy_id: &str,
+        genome: &Genome,
+    ) -> Result<Option<StoredEvaluation>, EvaluationCacheError> {
+        let key = (strategy_id.to_string(), genome.clone());
+        let mut entries = self.entries.lock().unwrap_or_else(PoisonError::into_inner);
+        let Some(&(_, last_use)) = entries.evaluations.get(&key) else {
+            return Ok(None);
+        };
+        let tick = match self.capacity {
+            Some(_) => entries.touch(&key, last_use),
+            None => last_use,
+        };
+        Ok(entries.evaluations.get_mut(&key).map(|entry| {
+            entry.1 = tick;
+            entry.0.clone()
+        }))
+    }
+
+    fn put(
+        &self,
+        strategy_id: &str,
+        genome: &Genome,
+        evaluation: &StoredEvaluation,
+    ) -> Result<(), EvaluationCacheError> {
+        let key = (strategy_id.to_string(), genome.clone());
+        let evaluation = evaluation.clone();
+        let mut entries = self.entries.lock().unwrap_or_else(PoisonError::into_inner);
+        let Some(capacity) = self.capacity else {
+            entries.evaluations.insert(key, (evaluation, 0));
+            return Ok(());
+        };
+        if capacity == 0 {
+            return Ok(());
+        }
+
+        let last_use = entries.evaluations.get(&key).map(|&(_, last_use)| last_use);
+        match last_use {
+            Some(last_use) => {
+                let tick = entries.touch(&key, last_use);
+                entries.evaluations.insert(key, (evaluation, tick));
+            }
+            None => {
+                if entries.evaluations.len() >= capacity {
+                    if let Some((_, oldest)) = entries.usage.pop_first() {
+                        entries.evaluations.remove(&oldest);
+                    }
+                }
+                entries.tick += 1;
+                let tick = entries.tick;
+                entries.usage.insert(tick, key.clone());
+                entries.evaluations.insert(key, (evaluation, tick));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use crate::evaluation_cache::{EvaluationStore, StoredEvaluation};
+
+    use super::MemoryStore;
+
+    fn evaluation(fitness: f32) -> StoredEvaluation {
+        (fitness, vec![], BTreeMap::new())
+    }
+
+    fn fitness(store: &MemoryStore, genome: u8) -> Option<f32> {
+        store
+            .get("sum", &vec![genome])
+            .unwrap()
+            .map(|(fitness, _, _)| fitness)
+    }
+
+    #[test]
+    fn test_memory_store_should_drop_least_recently_used() {
+        // Given
+        let store = MemoryStore::with_capacity(2);
+        store.put("sum", &vec![1], &evaluation(0.1)).unwrap();
+        store.put("sum", &vec![2], &evaluation(0.2)).unwrap();
+
+        // When
+        fitness(&store, 1);
+        store.put("sum", &vec![3], &evaluation(0.3)).unwrap();
+
+        // Then
+        assert_eq!(2, store.len());
+        assert_eq!(Some(0.1), fitness(&store, 1));
+        assert_eq!(
+            None,
+            fitness(&store, 2),
+            "Should drop the least recently used evaluation"
+        );
+        assert_eq!(Some(0.3), fitness(&store, 3));
+
+        // When
+        store.put("sum", &vec![3], &evaluation(0.4)).unwrap();
+        store.put("sum", &vec![4], &evaluation(0.5)).unwrap();
+
+        // Then
+        assert_eq!(Some(0.4), fitness(&store, 3));
+        assert_eq!(None, fitness(&store, 1));
+    }
+
+    #[test]
+    fn test_memory_store_without_capacity() {
+        // Given
+        let store = MemoryStore::with_capacity(0);
+
+        // When
+        store.put("sum", &vec![1], &evaluation(0.1)).unwrap();
+
+        // Then
+        assert!(store.is_empty(), "Should not keep evaluations");
+    }
+}
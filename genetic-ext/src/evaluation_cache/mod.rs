@@ -0,0 +1,319 @@
+mod memory_store;
+#[cfg(feature = "persistent-cache")]
+mod redb_store;
+
+use std::{
+    collections::BTreeMap,
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+use genetic::{
+    adaptation::{EvalContext, Strategy},
+    Genome,
+};
+use log::warn;
+pub use memory_store::MemoryStore;
+#[cfg(feature = "persistent-cache")]
+pub use redb_store::RedbStore;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum EvaluationCacheError {
+    #[error("Unable to access evaluation store: {0}")]
+    Store(String),
+}
+
+/// Fitness, objectives and metadata of an evaluated genome, see
+/// [`Strategy::evaluate_with_metadata`].
+pub type StoredEvaluation = (f32, Vec<f32>, BTreeMap<String, String>);
+
+/// Storage of the evaluations computed for genomes, keyed by strategy and genome so that a store
+/// can be shared by runs of several strategies.
+pub trait EvaluationStore: Send + Sync {
+    fn get(
+        &self,
+        strategy_id: &str,
+        genome: &Genome,
+    ) -> Result<Option<StoredEvaluation>, EvaluationCacheError>;
+    fn put(
+        &self,
+        strategy_id: &str,
+        genome: &Genome,
+        evaluation: &StoredEvaluation,
+    ) -> Result<(), EvaluationCacheError>;
+}
+
+/// Strategy reusing the evaluations already recorded in a store, e.g. a bounded [`MemoryStore`]
+/// to skip re-evaluating clones and duplicates, or a [`RedbStore`] to share them across the runs
+/// of a parameter sweep.
+///
+/// Only suits deterministic strategies, the context of the evaluations being ignored: genomes
+/// resampled by the engine are always evaluated. Store failures are logged and fall back to
+/// evaluating the genome.
+pub struct CachedStrategy<S, C> {
+    strategy: S,
+    strategy_id: String,
+    store: C,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+impl<S: Strategy, C: EvaluationStore> CachedStrategy<S, C> {
+    /// Wraps `strategy`, identified in the store by `strategy_id` which must change whenever its
+    /// evaluation does.
+    pub fn new(strategy: S, strategy_id: impl Into<String>, store: C) -> Self {
+        CachedStrategy {
+            strategy,
+            strategy_id: strategy_id.into(),
+            store,
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        }
+    }
+
+    /// Number of evaluations read from the store.
+    pub fn hits(&self) -> u64 {
+        self.hits.load(Ordering::Relaxed)
+    }
+
+    /// Number of evaluations delegated to the wrapped strategy.
+    pub fn misses(&self) -> u64 {
+        self.misses.load(Ordering::Relaxed)
+    }
+
+    /// Evaluation of the genome read from the store, or computed by `evaluate` then stored.
+    fn cached(
+        &self,
+        genome: &Genome,
+        evaluate: impl FnOnce(&S::Phenotype) -> StoredEvaluation,
+    ) -> StoredEvaluation {
+        match self.store.get(&self.strategy_id, genome) {
+            Ok(Some(evaluation)) => {
+                self.hits.fetch_add(1, Ordering::Relaxed);
+                return evaluation;
+            }
+            Ok(None) => {}
+            Err(err) => warn!("Unable to read cached evaluation: {err}"),
+        }
+
+        self.misses.fetch_add(1, Ordering::Relaxed);
+        let evaluation = evaluate(&self.strategy.decode(genome));
+        if let Err(err) = self.store.put(&self.strategy_id, genome, &evaluation) {
+            warn!("Unable to cache evaluation: {err}");
+        }
+        evaluation
+    }
+}
+
+impl<S: Strategy, C: EvaluationStore> Strategy for CachedStrategy<S, C> {
+    type Phenotype = Genome;
+
+    fn genome_size(&self) -> usize {
+        self.strategy.genome_size()
+    }
+
+    fn decode(&self, genome: &Genome) -> Genome {
+        genome.clone()
+    }
+
+    fn evaluate(&self, genome: &Genome) -> f32 {
+        self.evaluate_with_metadata(genome).0
+    }
+
+    fn evaluate_objectives(&self, genome: &Genome) -> (f32, Vec<f32>) {
+        let (fitness, objectives, _) = self.evaluate_with_metadata(genome);
+        (fitness, objectives)
+    }
+
+    fn evaluate_with_metadata(&self, genome: &Genome) -> StoredEvaluation {
+        self.cached(genome, |phenotype| {
+            self.strategy.evaluate_with_metadata(phenotype)
+        })
+    }
+
+    fn evaluate_in_context(&self, genome: &Genome, context: &EvalContext) -> StoredEvaluation {
+        if context.samples > 1 {
+            return self
+                .strategy
+                .evaluate_in_context(&self.strategy.decode(genome), context);
+        }
+        self.cached(genome, |phenotype| {
+            self.strategy.evaluate_in_context(phenotype, context)
+        })
+    }
+
+    fn describe(&self, genome: &Genome) -> Option<String> {
+        self.strategy.describe(&self.strategy.decode(genome))
+    }
+
+    fn repair(&self, genome: &mut Genome) {
+        self.strategy.repair(genome);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        collections::BTreeMap,
+        sync::atomic::{AtomicU64, Ordering},
+    };
+
+    use genetic::{
+        adaptation::{EvalContext, Strategy},
+        Genome,
+    };
+
+    use super::{CachedStrategy, EvaluationStore, MemoryStore};
+
+    struct Sum;
+
+    impl Strategy for Sum {
+        type Phenotype = Genome;
+
+        fn genome_size(&self) -> usize {
+            2
+        }
+
+        fn decode(&self, genome: &Genome) -> Genome {
+            genome.clone()
+        }
+
+        fn evaluate(&self, genome: &Genome) -> f32 {
+            genome.iter().map(|&g| g as f32).sum::<f32>() / 510.0
+        }
+    }
+
+    #[test]
+    fn test_cached_strategy_evaluate() {
+        // Given
+        let strategy = CachedStrategy::new(Sum, "sum", MemoryStore::default());
+
+        // When
+        let first = strategy.evaluate(&vec![255, 0]);
+        let second = strategy.evaluate(&vec![255, 0]);
+
+        // Then
+        assert_eq!(0.5, first);
+        assert_eq!(first, second);
+        assert_eq!(
+            (1, 1),
+            (strategy.hits(), strategy.misses()),
+            "Should only evaluate a genome once"
+        );
+    }
+
+    #[test]
+    fn test_cached_strategy_should_use_existing_evaluations() {
+        // Given
+        let store = MemoryStore::default();
+        store
+            .put("sum", &vec![1, 1], &(0.9, vec![], BTreeMap::new()))
+            .unwrap();
+        store
+            .put("other", &vec![2, 2], &(0.9, vec![], BTreeMap::new()))
+            .unwrap();
+        let strategy = CachedStrategy::new(Sum, "sum", store);
+
+        // Then
+        assert_eq!(0.9, strategy.evaluate(&vec![1, 1]));
+        assert_ne!(
+            0.9,
+            strategy.evaluate(&vec![2, 2]),
+            "Should not use evaluations of other strategies"
+        );
+    }
+
+    /// Sum with its seeded noise as objective and the number of evaluations as metadata.
+    #[derive(Default)]
+    struct NoisySum {
+        evaluations: AtomicU64,
+    }
+
+    impl Strategy for NoisySum {
+        type Phenotype = Genome;
+
+        fn genome_size(&self) -> usize {
+            2
+        }
+
+        fn decode(&self, genome: &Genome) -> Genome {
+            genome.clone()
+        }
+
+        fn evaluate(&self, genome: &Genome) -> f32 {
+            Sum.evaluate(genome)
+        }
+
+        fn evaluate_in_context(
+            &self,
+            genome: &Genome,
+            context: &EvalContext,
+        ) -> (f32, Vec<f32>, BTreeMap<String, String>) {
+            let count = self.evaluations.fetch_add(1, Ordering::Relaxed) + 1;
+            (
+                self.evaluate(genome),
+                vec![context.rng_seed as f32],
+                BTreeMap::from([("count".to_string(), count.to_string())]),
+            )
+        }
+    }
+
+    #[test]
+    fn test_cached_strategy_evaluate_in_context() {
+        // Given
+        let strategy = CachedStrategy::new(NoisySum::default(), "noisy", MemoryStore::default());
+        let context = EvalContext {
+            rng_seed: 3,
+            samples: 1,
+            ..Default::default()
+        };
+
+        // When
+        let first = strategy.evaluate_in_context(&vec![255, 0], &context);
+        let second = strategy.evaluate_in_context(
+            &vec![255, 0],
+            &EvalContext {
+                rng_seed: 4,
+                ..context
+            },
+        );
+
+        // Then
+        assert_eq!(
+            (
+                0.5,
+                vec![3.0],
+                BTreeMap::from([("count".to_string(), "1".to_string())])
+            ),
+            first,
+            "Should evaluate in context"
+        );
+        assert_eq!(
+            first, second,
+            "Should reuse the objectives and metadata of the evaluation"
+        );
+        assert_eq!(first, strategy.evaluate_with_metadata(&vec![255, 0]));
+    }
+
+    #[test]
+    fn test_cached_strategy_should_not_cache_resampled_evaluations() {
+        // Given
+        let strategy = CachedStrategy::new(NoisySum::default(), "noisy", MemoryStore::default());
+        let context = EvalContext {
+            samples: 2,
+            ..Default::default()
+        };
+
+        // When
+        strategy.evaluate_in_context(&vec![255, 0], &context);
+        let result = strategy.evaluate_in_context(&vec![255, 0], &context);
+
+        // Then
+        assert_eq!(
+            Some(&"2".to_string()),
+            result.2.get("count"),
+            "Should evaluate each sample"
+        );
+        assert_eq!((0, 0), (strategy.hits(), strategy.misses()));
+    }
+}
@@ -0,0 +1,102 @@
+use std::path::Path;
+
+use genetic::Genome;
+use redb::{Database, Durability, TableDefinition};
+
+use super::{EvaluationCacheError, EvaluationStore, StoredEvaluation};
+
+/// Evaluations encoded as JSON, by strategy and genome.
+const EVALUATIONS: TableDefinition<(&str, &[u8]), &[u8]> = TableDefinition::new("evaluations");
+
+fn store_error(err: impl ToString) -> EvaluationCacheError {
+    EvaluationCacheError::Store(err.to_string())
+}
+
+/// Persists evaluations to a redb database file, so that they outlive the runs.
+///
+/// Writes are not synced to disk one by one: evaluations of a crashed process may be lost, which
+/// only costs their computation.
+pub struct RedbStore {
+    database: Database,
+}
+
+impl RedbStore {
+    /// Opens the database at `path`, creating it if needed.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, EvaluationCacheError> {
+        let database = Database::create(path).map_err(store_error)?;
+        let transaction = database.begin_write().map_err(store_error)?;
+        transaction.open_table(EVALUATIONS).map_err(store_error)?;
+        transaction.commit().map_err(store_error)?;
+        Ok(RedbStore { database })
+    }
+}
+
+impl EvaluationStore for RedbStore {
+    fn get(
+        &self,
+        strategy_id: &str,
+        genome: &Genome,
+    ) -> Result<Option<StoredEvaluation>, EvaluationCacheError> {
+        let transaction = self.database.begin_read().map_err(store_error)?;
+        let table = transaction.open_table(EVALUATIONS).map_err(store_error)?;
+        let evaluation = table
+            .get((strategy_id, genome.as_slice()))
+            .map_err(store_error)?;
+        evaluation
+            .map(|evaluation| serde_json::from_slice(evaluation.value()).map_err(store_error))
+            .transpose()
+    }
+
+    fn put(
+        &self,
+        strategy_id: &str,
+        genome: &Genome,
+        evaluation: &StoredEvaluation,
+    ) -> Result<(), EvaluationCacheError> {
+        let evaluation = serde_json::to_vec(evaluation).map_err(store_error)?;
+        let mut transaction = self.database.begin_write().map_err(store_error)?;
+        transaction.set_durability(Durability::Eventual);
+        {
+            let mut table = transaction.open_table(EVALUATIONS).map_err(store_error)?;
+            table
+                .insert((strategy_id, genome.as_slice()), evaluation.as_slice())
+                .map_err(store_error)?;
+        }
+        transaction.commit().map_err(store_error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::BTreeMap, env, fs, process};
+
+    use crate::evaluation_cache::EvaluationStore;
+
+    use super::RedbStore;
+
+    #[test]
+    fn test_redb_store() {
+        // Given
+        let path = env::temp_dir().join(format!("evolution-lab-cache-{}.redb", process::id()));
+        let store = RedbStore::open(&path).unwrap();
+
+        // When
+        let evaluation = (
+            0.25,
+            vec![1.0],
+            BTreeMap::from([("steps".to_string(), "3".to_string())]),
+        );
+        store.put("sum", &vec![1, 2], &evaluation).unwrap();
+        drop(store);
+        let store = RedbStore::open(&path).unwrap();
+
+        // Then
+        assert_eq!(
+            Some(evaluation),
+            store.get("sum", &vec![1, 2]).unwrap(),
+            "Should keep evaluations across openings"
+        );
+        assert_eq!(None, store.get("other", &vec![1, 2]).unwrap());
+        fs::remove_file(path).unwrap();
+    }
+}
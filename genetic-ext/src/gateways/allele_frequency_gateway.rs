@@ -0,0 +1,84 @@
+use common::subject_observer::Observer;
+use dipstick::InputScope;
+use genetic::evolution::{EventType, EvolutionEngine};
+use log::trace;
+
+use super::{CONVERGED_LOCI, METRICS_LOCUS_PREFIX, MY_PROXY};
+
+/// Emits, every `period` generations, the frequency of the dominant allele of each locus and
+/// the count of loci having converged.
+///
+/// Metrics go through the shared proxy, so a [`super::StatsdGateway`] or a
+/// [`super::GraphiteGateway`] must be created to target a backend.
+pub struct AlleleFrequencyGateway {
+    period: u64,
+    convergence_threshold: f32,
+}
+
+impl AlleleFrequencyGateway {
+    pub fn new(period: u64, convergence_threshold: f32) -> Self {
+        AlleleFrequencyGateway {
+            period: period.max(1),
+            convergence_threshold,
+        }
+    }
+
+    fn is_due(&self, generation: u64) -> bool {
+        generation.rem_euclid(self.period) == 0
+    }
+}
+
+impl Observer<EvolutionEngine, EventType> for AlleleFrequencyGateway {
+    fn update(&self, source: &EvolutionEngine, event: EventType) {
+        if event == EventType::Evaluated {
+            let snapshot = source.snapshot();
+            if !self.is_due(snapshot.generation) {
+                return;
+            }
+
+            let frequencies = snapshot.allele_frequencies();
+            for (locus, locus_frequencies) in frequencies.iter().enumerate() {
+                if let Some((_, frequency)) = locus_frequencies.dominant() {
+                    MY_PROXY
+                        .gauge(&format!("{METRICS_LOCUS_PREFIX}{locus}"))
+                        .value(frequency);
+                }
+            }
+            let converged_count = frequencies
+                .iter()
+                .filter(|f| f.is_converged(self.convergence_threshold))
+                .count();
+            trace!(
+                "Sending allele frequencies for generation {}: {converged_count}/{} converged loci",
+                snapshot.generation,
+                frequencies.len()
+            );
+            CONVERGED_LOCI.value(converged_count);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::AlleleFrequencyGateway;
+
+    #[test]
+    fn test_allele_frequency_gateway_is_due() {
+        // Given
+        let gateway = AlleleFrequencyGateway::new(3, 0.9);
+
+        // Then
+        assert!(gateway.is_due(0));
+        assert!(!gateway.is_due(2));
+        assert!(gateway.is_due(6), "Should emit every period generations");
+    }
+
+    #[test]
+    fn test_allele_frequency_gateway_new_should_reject_zero_period() {
+        // Given
+        let gateway = AlleleFrequencyGateway::new(0, 0.9);
+
+        // Then
+        assert!(gateway.is_due(1), "Should emit every generation");
+    }
+}
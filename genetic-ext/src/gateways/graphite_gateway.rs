@@ -4,9 +4,11 @@ use common::subject_observer::Observer;
 use dipstick::{Graphite, Input};
 use genetic::evolution::{EventType, EvolutionEngine};
 
-use super::{MAX, MY_PROXY};
+use super::{run_gauge, MAX, MY_PROXY};
 
-pub struct GraphiteGateway {}
+pub struct GraphiteGateway {
+    per_run: bool,
+}
 
 impl GraphiteGateway {
     pub fn new<A>(address: A) -> Result<Self, Error>
@@ -15,21 +17,21 @@ impl GraphiteGateway {
     {
         let graphite_scope = Graphite::send_to(address)?.metrics();
         MY_PROXY.target(graphite_scope);
-        Ok(GraphiteGateway {})
+        Ok(GraphiteGateway { per_run: false })
+    }
+
+    /// See [`super::StatsdGateway::per_run`].
+    pub fn per_run(mut self) -> Self {
+        self.per_run = true;
+        self
     }
 }
 
 impl Observer<EvolutionEngine, EventType> for GraphiteGateway {
     fn update(&self, source: &EvolutionEngine, event: EventType) {
         if event == EventType::Evaluated {
-            let snapshot = source.snapshot();
-            if let Some(max_fitness) = snapshot
-                .evaluations
-                .iter()
-                .map(|e| e.fitness)
-                .reduce(f32::max)
-            {
-                MAX.value(max_fitness);
+            if let Some(stats) = source.stats() {
+                run_gauge(source.metadata().filter(|_| self.per_run), &MAX, "max").value(stats.max);
             }
         }
     }
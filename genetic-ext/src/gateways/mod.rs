@@ -1,23 +1,62 @@
+mod allele_frequency_gateway;
 mod graphite_gateway;
+mod multi_gateway;
+mod observer_timing_gateway;
 mod statsd_gateway;
 
+pub use allele_frequency_gateway::AlleleFrequencyGateway;
 use const_format::concatcp;
 pub use graphite_gateway::GraphiteGateway;
+pub use multi_gateway::{
+    FileSink, FitnessStats, MultiGateway, MultiGatewayConfig, ScopeSink, SinkConfig, SinkKind,
+    StatsSink,
+};
+pub use observer_timing_gateway::ObserverTimingGateway;
 pub use statsd_gateway::StatsdGateway;
 
 use dipstick::*;
+use genetic::evolution::RunMetadata;
 
 const METRICS_PREFIX: &str = "evolution-lab.";
+const METRICS_BEST: &str = concatcp!(METRICS_PREFIX, "best");
+const METRICS_CONVERGED_LOCI: &str = concatcp!(METRICS_PREFIX, "converged-loci");
+const METRICS_GENERATION_DURATION: &str = concatcp!(METRICS_PREFIX, "generation-duration");
+const METRICS_LOCUS_PREFIX: &str = concatcp!(METRICS_PREFIX, "locus.");
 const METRICS_MAX: &str = concatcp!(METRICS_PREFIX, "max");
 const METRICS_MEAN: &str = concatcp!(METRICS_PREFIX, "mean");
 const METRICS_MIN: &str = concatcp!(METRICS_PREFIX, "min");
+const METRICS_OBSERVER_PREFIX: &str = concatcp!(METRICS_PREFIX, "observer.");
+const METRICS_RUN_PREFIX: &str = concatcp!(METRICS_PREFIX, "run.");
 const METRICS_STD_DEV: &str = concatcp!(METRICS_PREFIX, "std-dev");
 
 metrics! {
     MY_PROXY: Proxy = "Graphite_Proxy" => {
+        BEST: Gauge = METRICS_BEST;
+        CONVERGED_LOCI: Gauge = METRICS_CONVERGED_LOCI;
+        GENERATION_DURATION: Timer = METRICS_GENERATION_DURATION;
         MAX: Gauge = METRICS_MAX;
         MEAN: Gauge = METRICS_MEAN;
         MIN: Gauge = METRICS_MIN;
         STD_DEV: Gauge = METRICS_STD_DEV;
     }
 }
+
+/// Gauge of the given run, e.g. `evolution-lab.run.<id>.max`, the shared one otherwise.
+fn run_gauge(run: Option<&RunMetadata>, shared: &Gauge, name: &str) -> Gauge {
+    match run {
+        Some(run) => MY_PROXY.gauge(&run_metric(run, name)),
+        None => shared.clone(),
+    }
+}
+
+/// Timer of the given run, the shared one otherwise, see [`run_gauge`].
+fn run_timer(run: Option<&RunMetadata>, shared: &Timer, name: &str) -> Timer {
+    match run {
+        Some(run) => MY_PROXY.timer(&run_metric(run, name)),
+        None => shared.clone(),
+    }
+}
+
+fn run_metric(run: &RunMetadata, name: &str) -> String {
+    format!("{METRICS_RUN_PREFIX}{}.{name}", run.id)
+}
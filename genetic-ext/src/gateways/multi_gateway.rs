@@ -0,0 +1,412 @@
+use std::{
+    fs::{File, OpenOptions},
+    io::{Error, Write},
+    path::{Path, PathBuf},
+    sync::{Mutex, PoisonError},
+};
+
+use common::subject_observer::Observer;
+use dipstick::{Graphite, Input, InputScope, Statsd};
+use genetic::{
+    analysis::GenerationStats,
+    evolution::{EventType, EvolutionEngine},
+};
+use log::{trace, warn};
+use serde::Deserialize;
+
+use crate::gateways::{METRICS_MAX, METRICS_MEAN, METRICS_MIN, METRICS_STD_DEV};
+
+/// Fitness statistics of one generation, computed once and shared by all the sinks.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FitnessStats {
+    pub generation: u64,
+    pub min: f32,
+    pub max: f32,
+    pub mean: f32,
+    pub std_dev: f32,
+}
+
+impl FitnessStats {
+    /// Scales the statistics computed by the engine for a generation by `factor`.
+    pub fn new(generation: u64, stats: &GenerationStats, factor: f32) -> Self {
+        FitnessStats {
+            generation,
+            min: stats.min * factor,
+            max: stats.max * factor,
+            mean: stats.mean * factor,
+            std_dev: stats.std_dev * factor.abs(),
+        }
+    }
+}
+
+/// Destination of the statistics forwarded by a [`MultiGateway`].
+pub trait StatsSink: Send + Sync {
+    fn send(&self, stats: &FitnessStats) -> Result<(), Error>;
+}
+
+/// Sink sending the statistics as gauges of a dipstick scope, e.g. a statsd or graphite one.
+pub struct ScopeSink<S> {
+    scope: S,
+}
+
+impl<S: InputScope + Send + Sync> ScopeSink<S> {
+    pub fn new(scope: S) -> Self {
+        ScopeSink { scope }
+    }
+}
+
+impl<S: InputScope + Send + Sync> StatsSink for ScopeSink<S> {
+    fn send(&self, stats: &FitnessStats) -> Result<(), Error> {
+        self.scope.gauge(METRICS_MIN).value(stats.min);
+        self.scope.gauge(METRICS_MAX).value(stats.max);
+        self.scope.gauge(METRICS_MEAN).value(stats.mean);
+        self.scope.gauge(METRICS_STD_DEV).value(stats.std_dev);
+        Ok(())
+    }
+}
+
+/// Sink appending the statistics as CSV lines `generation,min,max,mean,std-dev` to a file.
+pub struct FileSink {
+    file: Mutex<File>,
+}
+
+impl FileSink {
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, Error> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        Ok(FileSink {
+            file: Mutex::new(file),
+        })
+    }
+}
+
+impl StatsSink for FileSink {
+    fn send(&self, stats: &FitnessStats) -> Result<(), Error> {
+        writeln!(
+            self.file.lock().unwrap_or_else(PoisonError::into_inner),
+            "{},{},{},{},{}",
+            stats.generation,
+            stats.min,
+            stats.max,
+            stats.mean,
+            stats.std_dev
+        )
+    }
+}
+
+/// Settings of a [`MultiGateway`], e.g. in JSON
+/// `{ "factor": 1000, "sinks": [{ "type": "statsd", "address": "127.0.0.1:8125" },
+/// { "type": "file", "path": "stats.csv", "samplingRate": 0.1 }] }`.
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct MultiGatewayConfig {
+    /// Factor applied to the fitness before computing statistics, 1 when missing.
+    #[serde(default = "default_one")]
+    pub factor: f32,
+    pub sinks: Vec<SinkConfig>,
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SinkConfig {
+    #[serde(flatten)]
+    pub kind: SinkKind,
+    /// Fraction of the generations sent to the sink, all of them when missing.
+    #[serde(default = "default_one")]
+    pub sampling_rate: f32,
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum SinkKind {
+    Statsd { address: String },
+    Graphite { address: String },
+    File { path: PathBuf },
+}
+
+fn default_one() -> f32 {
+    1.0
+}
+
+struct SampledSink {
+    sink: Box<dyn StatsSink>,
+    sampling_rate: f32,
+}
+
+/// Forwards the fitness statistics of each evaluated generation to several sinks, computing them
+/// only once from the snapshot.
+///
+/// Sampling is deterministic: a sink with a rate of `0.25` receives every fourth generation.
+pub struct MultiGateway {
+    factor: f32,
+    sinks: Vec<SampledSink>,
+}
+
+impl MultiGateway {
+    pub fn new(factor: f32) -> Self {
+        MultiGateway {
+            factor,
+            sinks: Vec::new(),
+        }
+    }
+
+    /// Opens all the sinks of `config`.
+    pub fn from_config(config: &MultiGatewayConfig) -> Result<Self, Error> {
+        config
+            .sinks
+            .iter()
+            .try_fold(MultiGateway::new(config.factor), |gateway, sink| {
+                let opened: Box<dyn StatsSink> = match &sink.kind {
+                    SinkKind::Statsd { address } => {
+                        Box::new(ScopeSink::new(Statsd::send_to(address.as_str())?.metrics()))
+                    }
+                    SinkKind::Graphite { address } => Box::new(ScopeSink::new(
+                        Graphite::send_to(address.as_str())?.metrics(),
+                    )),
+                    SinkKind::File { path } => Box::new(FileSink::open(path)?),
+                };
+                Ok(gateway.with_sink(opened, sink.sampling_rate))
+            })
+    }
+
+    /// Adds `sink`, receiving the given fraction of the generations.
+    pub fn with_sink(mut self, sink: Box<dyn StatsSink>, sampling_rate: f32) -> Self {
+        self.sinks.push(SampledSink {
+            sink,
+            sampling_rate,
+        });
+        self
+    }
+
+    fn publish(&self, stats: &FitnessStats) {
+        for sampled in self
+            .sinks
+            .iter()
+            .filter(|sampled| is_sampled(stats.generation, sampled.sampling_rate))
+        {
+            if let Err(err) = sampled.sink.send(stats) {
+                warn!(
+                    "Unable to send metrics of generation {}: {err}",
+                    stats.generation
+                );
+            }
+        }
+    }
+}
+
+/// Whether `generation` crosses a new unit of `rate` generations, spreading the samples evenly.
+fn is_sampled(generation: u64, rate: f32) -> bool {
+    let rate = f64::from(rate.clamp(0.0, 1.0));
+    ((generation + 1) as f64 * rate).floor() > (generation as f64 * rate).floor()
+}
+
+impl Observer<EvolutionEngine, EventType> for MultiGateway {
+    fn update(&self, source: &EvolutionEngine, event: EventType) {
+        if event == EventType::Evaluated {
+            if let Some(stats) = source.stats() {
+                let stats = FitnessStats::new(source.generation(), &stats, self.factor);
+                trace!("Sending metrics {stats:?}");
+                self.publish(&stats);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        io::Error,
+        sync::{Arc, Mutex},
+    };
+
+    use common::subject_observer::Subject;
+    use common_test::get_seeded_rng;
+    use futures::executor::block_on;
+    use genetic::{
+        adaptation::Strategy,
+        analysis::GenerationStats,
+        evolution::{EvolutionConfig, EvolutionEngine, MaxGenerations},
+        Evaluation, Genome,
+    };
+
+    use super::{
+        is_sampled, FitnessStats, MultiGateway, MultiGatewayConfig, SinkConfig, SinkKind, StatsSink,
+    };
+
+    struct MemorySink(Arc<Mutex<Vec<u64>>>);
+
+    impl StatsSink for MemorySink {
+        fn send(&self, stats: &FitnessStats) -> Result<(), Error> {
+            self.0.lock().unwrap().push(stats.generation);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_fitness_stats_new() {
+        // Given
+        let mut evaluations = [1.0, 2.0, 3.0]
+            .map(|fitness| Evaluation {
+                fitness,
+                evaluated: true,
+                ..Default::default()
+            })
+            .to_vec();
+        evaluations.push(Evaluation::from(vec![0]));
+
+        // When
+        let stats = GenerationStats::compute(&evaluations).unwrap();
+        let result = FitnessStats::new(4, &stats, 10.0);
+
+        // Then
+        assert_eq!(4, result.generation);
+        assert_eq!(10.0, result.min, "Should ignore unevaluated genomes");
+        assert_eq!(30.0, result.max);
+        assert_eq!(20.0, result.mean);
+        assert_eq!(8.164967, result.std_dev);
+    }
+
+    #[test]
+    fn test_is_sampled() {
+        // Then
+        assert!((0..10).all(|generation| is_sampled(generation, 1.0)));
+        assert!((0..10).all(|generation| !is_sampled(generation, 0.0)));
+        assert_eq!(
+            vec![3, 7, 11],
+            (0..12)
+                .filter(|&generation| is_sampled(generation, 0.25))
+                .collect::<Vec<_>>(),
+            "Should spread samples evenly"
+        );
+    }
+
+    #[test]
+    fn test_multi_gateway_publish() {
+        // Given
+        let every = Arc::new(Mutex::new(Vec::new()));
+        let half = Arc::new(Mutex::new(Vec::new()));
+        let gateway = MultiGateway::new(1.0)
+            .with_sink(Box::new(MemorySink(every.clone())), 1.0)
+            .with_sink(Box::new(MemorySink(half.clone())), 0.5);
+
+        // When
+        for generation in 0..4 {
+            gateway.publish(&FitnessStats {
+                generation,
+                min: 0.0,
+                max: 0.0,
+                mean: 0.0,
+                std_dev: 0.0,
+            });
+        }
+
+        // Then
+        assert_eq!(vec![0, 1, 2, 3], *every.lock().unwrap());
+        assert_eq!(
+            vec![1, 3],
+            *half.lock().unwrap(),
+            "Should apply sampling rates per sink"
+        );
+    }
+
+    struct Sum;
+
+    impl Strategy for Sum {
+        type Phenotype = Genome;
+
+        fn genome_size(&self) -> usize {
+            2
+        }
+
+        fn decode(&self, genome: &Genome) -> Genome {
+            genome.clone()
+        }
+
+        fn evaluate(&self, genome: &Genome) -> f32 {
+            genome.iter().map(|&g| g as f32).sum::<f32>() / 510.0
+        }
+    }
+
+    #[test]
+    fn test_multi_gateway_registered() {
+        // Given
+        let sent = Arc::new(Mutex::new(Vec::new()));
+        let gateway = MultiGateway::new(1.0).with_sink(Box::new(MemorySink(sent.clone())), 1.0);
+        let mut engine = EvolutionEngine::default();
+        engine.register_observer(Arc::new(gateway));
+        let config = EvolutionConfig {
+            population_size: 4,
+            generation_renewal_config: None,
+            max_duration: None,
+        };
+
+        // When
+        block_on(engine.start(
+            &Sum,
+            &config,
+            MaxGenerations(2),
+            &mut get_seeded_rng().unwrap(),
+        ))
+        .unwrap();
+
+        // Then
+        assert_eq!(
+            vec![0, 1, 2],
+            *sent.lock().unwrap(),
+            "Should publish the statistics of the engine it observes"
+        );
+    }
+
+    #[test]
+    fn test_multi_gateway_config_deserialize() {
+        // Given
+        let json = r#"{
+            "sinks": [
+                { "type": "statsd", "address": "127.0.0.1:8125" },
+                { "type": "file", "path": "stats.csv", "samplingRate": 0.1 }
+            ]
+        }"#;
+
+        // When
+        let result: MultiGatewayConfig = serde_json::from_str(json).unwrap();
+
+        // Then
+        assert_eq!(1.0, result.factor);
+        assert_eq!(
+            vec![
+                SinkConfig {
+                    kind: SinkKind::Statsd {
+                        address: "127.0.0.1:8125".to_string()
+                    },
+                    sampling_rate: 1.0
+                },
+                SinkConfig {
+                    kind: SinkKind::File {
+                        path: "stats.csv".into()
+                    },
+                    sampling_rate: 0.1
+                }
+            ],
+            result.sinks
+        );
+    }
+
+    #[test]
+    fn test_multi_gateway_from_config() {
+        // Given
+        let config = MultiGatewayConfig {
+            factor: 1.0,
+            sinks: vec![SinkConfig {
+                kind: SinkKind::Graphite {
+                    address: String::new(),
+                },
+                sampling_rate: 1.0,
+            }],
+        };
+
+        // Then
+        assert!(
+            MultiGateway::from_config(&config).is_err(),
+            "Should fail when a sink cannot be opened"
+        );
+    }
+}
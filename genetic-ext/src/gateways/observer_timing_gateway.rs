@@ -0,0 +1,80 @@
+use common::subject_observer::Observer;
+use dipstick::InputScope;
+use genetic::evolution::{EventType, EvolutionEngine};
+use log::{trace, warn};
+
+use super::{METRICS_OBSERVER_PREFIX, MY_PROXY};
+
+/// Emits, every `period` generations, the mean and max update latency of each observer of the
+/// engine, in microseconds, to find which one slows the generation loop.
+///
+/// Metrics go through the shared proxy, so a [`super::StatsdGateway`] or a
+/// [`super::GraphiteGateway`] must be created to target a backend.
+pub struct ObserverTimingGateway {
+    period: u64,
+}
+
+impl ObserverTimingGateway {
+    pub fn new(period: u64) -> Self {
+        ObserverTimingGateway {
+            period: period.max(1),
+        }
+    }
+
+    fn is_due(&self, generation: u64) -> bool {
+        generation.rem_euclid(self.period) == 0
+    }
+}
+
+/// Last segment of an observer type name, e.g. `StatsdGateway`.
+fn metric_name(observer_name: &str) -> &str {
+    let name = observer_name.split('<').next().unwrap_or(observer_name);
+    name.rsplit("::").next().unwrap_or(name)
+}
+
+impl Observer<EvolutionEngine, EventType> for ObserverTimingGateway {
+    fn update(&self, source: &EvolutionEngine, event: EventType) {
+        if event == EventType::Evaluated {
+            let generation = source.snapshot().generation;
+            if !self.is_due(generation) {
+                return;
+            }
+
+            let timings = match source.observer_timings() {
+                Ok(timings) => timings,
+                Err(err) => {
+                    warn!("Unable to read observer timings: {err}");
+                    return;
+                }
+            };
+            trace!(
+                "Sending timings of {} observer(s) for generation {generation}",
+                timings.len()
+            );
+            for timing in timings {
+                let prefix = format!("{METRICS_OBSERVER_PREFIX}{}", metric_name(timing.name));
+                MY_PROXY
+                    .gauge(&format!("{prefix}.mean-us"))
+                    .value(timing.mean().as_micros() as u64);
+                MY_PROXY
+                    .gauge(&format!("{prefix}.max-us"))
+                    .value(timing.max.as_micros() as u64);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::metric_name;
+
+    #[test]
+    fn test_metric_name() {
+        assert_eq!(
+            "StatsdGateway",
+            metric_name("genetic_ext::gateways::statsd_gateway::StatsdGateway"),
+            "Should keep the type name without its path"
+        );
+        assert_eq!("Wrapper", metric_name("my_crate::Wrapper<other::Inner>"));
+    }
+}
@@ -3,15 +3,19 @@ use std::{fmt::Debug, io::Error, net::ToSocketAddrs};
 use common::subject_observer::Observer;
 use dipstick::{Input, Statsd};
 use genetic::{
+    analysis::GenerationStats,
     evolution::{EventType, EvolutionEngine},
-    Evaluation,
 };
 use log::trace;
 
-use crate::gateways::{MAX, MEAN, MIN, MY_PROXY, STD_DEV};
+use crate::gateways::{
+    run_gauge, run_timer, FitnessStats, BEST, GENERATION_DURATION, MAX, MEAN, MIN, MY_PROXY,
+    STD_DEV,
+};
 
 pub struct StatsdGateway {
     factor: f32,
+    per_run: bool,
 }
 
 impl StatsdGateway {
@@ -22,45 +26,56 @@ impl StatsdGateway {
         let statsd_scope = Statsd::send_to(address)?.metrics();
         MY_PROXY.target(statsd_scope);
 
-        Ok(StatsdGateway { factor })
+        Ok(StatsdGateway {
+            factor,
+            per_run: false,
+        })
     }
 
-    fn compute_stats(&self, evaluations: &[Evaluation]) -> (f32, f32, f32, f32) {
-        let fitness_iter = evaluations.iter().map(|e| e.fitness * self.factor);
-        let (min, max, sum, count) = fitness_iter.clone().fold(
-            (f32::INFINITY, f32::NEG_INFINITY, 0.0, 0),
-            |(min, max, sum, count), value| {
-                (min.min(value), max.max(value), sum + value, count + 1)
-            },
-        );
-        let mean = sum / count as f32;
-        let variance: f32 = fitness_iter
-            .map(|value| (value - mean).powi(2))
-            .sum::<f32>()
-            / count as f32;
-        let std_dev = variance.sqrt();
-        (min, max, mean, std_dev)
+    /// Sends the metrics of each run apart, under `evolution-lab.run.<id>.`, so that concurrent
+    /// runs can be told apart, see [`genetic::evolution::RunMetadata::id`].
+    pub fn per_run(mut self) -> Self {
+        self.per_run = true;
+        self
+    }
+
+    fn compute_stats(&self, stats: &GenerationStats) -> (f32, f32, f32, f32) {
+        let stats = FitnessStats::new(0, stats, self.factor);
+        (stats.min, stats.max, stats.mean, stats.std_dev)
     }
 }
 
 impl Observer<EvolutionEngine, EventType> for StatsdGateway {
     fn update(&self, source: &EvolutionEngine, event: EventType) {
-        if event == EventType::Evaluated {
-            let snapshot = source.snapshot();
-            let (min, max, mean, std_dev) = self.compute_stats(&snapshot.evaluations);
-
-            trace!("Sending metrics for generation {}: min={min}, max={max}, mean={mean}, std-dev={std_dev}", snapshot.generation);
-            MIN.value(min);
-            MAX.value(max);
-            MEAN.value(mean);
-            STD_DEV.value(std_dev);
+        let run = source.metadata().filter(|_| self.per_run);
+        match event {
+            EventType::Evaluated => {
+                let Some(stats) = source.stats() else {
+                    return;
+                };
+                let (min, max, mean, std_dev) = self.compute_stats(&stats);
+
+                trace!("Sending metrics for generation {}: min={min}, max={max}, mean={mean}, std-dev={std_dev}", source.generation());
+                run_gauge(run, &MIN, "min").value(min);
+                run_gauge(run, &MAX, "max").value(max);
+                run_gauge(run, &MEAN, "mean").value(mean);
+                run_gauge(run, &STD_DEV, "std-dev").value(std_dev);
+            }
+            EventType::BestImproved { fitness, .. } => {
+                run_gauge(run, &BEST, "best").value(fitness * self.factor);
+            }
+            EventType::GenerationCompleted { duration } => {
+                run_timer(run, &GENERATION_DURATION, "generation-duration")
+                    .interval_us(duration.as_micros() as u64);
+            }
+            _ => {}
         }
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use genetic::Evaluation;
+    use genetic::{analysis::GenerationStats, Evaluation};
 
     use super::StatsdGateway;
 
@@ -85,6 +100,8 @@ mod tests {
         );
         let result = result.unwrap();
         assert_eq!(factor, result.factor);
+        assert!(!result.per_run);
+        assert!(result.per_run().per_run);
     }
 
     #[test]
@@ -96,19 +113,25 @@ mod tests {
             Evaluation {
                 fitness: 1.0,
                 genome: vec![],
+                evaluated: true,
+                ..Default::default()
             },
             Evaluation {
                 fitness: 2.0,
                 genome: vec![],
+                evaluated: true,
+                ..Default::default()
             },
             Evaluation {
                 fitness: 3.0,
                 genome: vec![],
+                evaluated: true,
+                ..Default::default()
             },
         ];
 
         // When
-        let result = gateway.compute_stats(&evaluations);
+        let result = gateway.compute_stats(&GenerationStats::compute(&evaluations).unwrap());
 
         // Then
         assert_eq!(1.0, result.0);
@@ -1 +1,5 @@
+#[cfg(feature = "distributed")]
+pub mod distributed;
+pub mod evaluation_cache;
 pub mod gateways;
+pub mod snapshot_dump;
@@ -0,0 +1,52 @@
+use std::{fs, path::PathBuf};
+
+use super::{snapshot_name, SnapshotDumpError, SnapshotSink};
+
+/// Writes each snapshot to its own file of a local directory, creating it if needed.
+pub struct DirectorySink {
+    directory: PathBuf,
+}
+
+impl DirectorySink {
+    pub fn new(directory: impl Into<PathBuf>) -> Self {
+        DirectorySink {
+            directory: directory.into(),
+        }
+    }
+}
+
+impl SnapshotSink for DirectorySink {
+    fn store(&self, generation: u64, content: &[u8]) -> Result<(), SnapshotDumpError> {
+        fs::create_dir_all(&self.directory)?;
+        fs::write(self.directory.join(snapshot_name(generation)), content)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, fs, process};
+
+    use crate::snapshot_dump::{snapshot_name, SnapshotSink};
+
+    use super::DirectorySink;
+
+    #[test]
+    fn test_directory_sink_store() {
+        // Given
+        let directory = env::temp_dir().join(format!("evolution-lab-dump-{}", process::id()));
+        let sink = DirectorySink::new(&directory);
+
+        // When
+        let result = sink.store(3, b"{}");
+
+        // Then
+        assert!(result.is_ok(), "Should create the directory when missing");
+        assert_eq!(
+            b"{}".to_vec(),
+            fs::read(directory.join(snapshot_name(3))).unwrap(),
+            "Should write the content to the generation file"
+        );
+        fs::remove_dir_all(directory).unwrap();
+    }
+}
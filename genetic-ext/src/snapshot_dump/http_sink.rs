@@ -0,0 +1,59 @@
+use super::{snapshot_name, SnapshotDumpError, SnapshotSink};
+
+const GENERATION_PLACEHOLDER: &str = "{generation}";
+
+/// Uploads each snapshot with an HTTP `PUT`.
+///
+/// The `{generation}` placeholder of the URL is replaced by the generation, otherwise the
+/// snapshot name is appended to it, e.g. `http://host/dumps/` + `snapshot-00000010.json`.
+pub struct HttpSink {
+    url: String,
+}
+
+impl HttpSink {
+    pub fn new(url: impl Into<String>) -> Self {
+        HttpSink { url: url.into() }
+    }
+
+    fn url_for(&self, generation: u64) -> String {
+        if self.url.contains(GENERATION_PLACEHOLDER) {
+            self.url
+                .replace(GENERATION_PLACEHOLDER, &generation.to_string())
+        } else {
+            format!("{}{}", self.url, snapshot_name(generation))
+        }
+    }
+}
+
+impl SnapshotSink for HttpSink {
+    fn store(&self, generation: u64, content: &[u8]) -> Result<(), SnapshotDumpError> {
+        put(&self.url_for(generation), content)
+    }
+}
+
+pub(super) fn put(url: &str, content: &[u8]) -> Result<(), SnapshotDumpError> {
+    ureq::put(url)
+        .set("Content-Type", "application/json")
+        .send_bytes(content)
+        .map(|_| ())
+        .map_err(|e| SnapshotDumpError::Upload(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::HttpSink;
+
+    #[test]
+    fn test_http_sink_url_for() {
+        assert_eq!(
+            "http://host/runs/1/snapshots/7",
+            HttpSink::new("http://host/runs/1/snapshots/{generation}").url_for(7),
+            "Should replace the generation placeholder"
+        );
+        assert_eq!(
+            "http://host/dumps/snapshot-00000007.json",
+            HttpSink::new("http://host/dumps/").url_for(7),
+            "Should append the snapshot name without placeholder"
+        );
+    }
+}
@@ -0,0 +1,226 @@
+mod directory_sink;
+#[cfg(feature = "http")]
+mod http_sink;
+#[cfg(feature = "s3")]
+mod s3_sink;
+
+use std::io;
+
+use common::subject_observer::Observer;
+pub use directory_sink::DirectorySink;
+use genetic::evolution::{EventType, EvolutionEngine, Snapshot};
+#[cfg(feature = "http")]
+pub use http_sink::HttpSink;
+use log::{error, trace};
+#[cfg(feature = "s3")]
+pub use s3_sink::S3Sink;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum SnapshotDumpError {
+    #[error("Unable to serialize snapshot: {0}")]
+    Serialization(#[from] serde_json::Error),
+    #[error("Unable to write snapshot: {0}")]
+    Io(#[from] io::Error),
+    #[error("Unable to upload snapshot: {0}")]
+    Upload(String),
+}
+
+/// Destination of the snapshots dumped by a [`SnapshotDumpObserver`].
+pub trait SnapshotSink: Send + Sync {
+    /// Stores the serialized snapshot of the given generation.
+    fn store(&self, generation: u64, content: &[u8]) -> Result<(), SnapshotDumpError>;
+}
+
+/// Name under which sinks store the snapshot of a generation.
+pub fn snapshot_name(generation: u64) -> String {
+    format!("snapshot-{generation:08}.json")
+}
+
+/// Serializes the whole snapshot as JSON to a sink every `period` generations.
+///
+/// Gives long runs a coarse-grained history of their full state without paying for a dump at
+/// each generation. Failures are logged and do not interrupt the evolution.
+pub struct SnapshotDumpObserver {
+    period: u64,
+    sink: Box<dyn SnapshotSink>,
+}
+
+impl SnapshotDumpObserver {
+    pub fn new(period: u64, sink: impl SnapshotSink + 'static) -> Self {
+        SnapshotDumpObserver {
+            period: period.max(1),
+            sink: Box::new(sink),
+        }
+    }
+
+    fn is_due(&self, generation: u64) -> bool {
+        generation.rem_euclid(self.period) == 0
+    }
+
+    pub fn dump(&self, snapshot: &Snapshot) -> Result<(), SnapshotDumpError> {
+        let content = serde_json::to_vec(snapshot)?;
+        self.sink.store(snapshot.generation, &content)
+    }
+}
+
+impl Observer<EvolutionEngine, EventType> for SnapshotDumpObserver {
+    fn update(&self, source: &EvolutionEngine, event: EventType) {
+        if event == EventType::Evaluated {
+            let snapshot = source.snapshot();
+            if !self.is_due(snapshot.generation) {
+                return;
+            }
+
+            trace!("Dumping snapshot of generation {}", snapshot.generation);
+            if let Err(e) = self.dump(snapshot) {
+                error!(
+                    "Unable to dump snapshot of generation {}: {e}",
+                    snapshot.generation
+                );
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use common::subject_observer::Subject;
+    use common_test::get_seeded_rng;
+    use futures::executor::block_on;
+    use genetic::{
+        adaptation::Strategy,
+        evolution::{EvolutionConfig, EvolutionEngine, MaxGenerations, Snapshot},
+        Evaluation, Genome,
+    };
+
+    use super::{snapshot_name, SnapshotDumpError, SnapshotDumpObserver, SnapshotSink};
+
+    type Stored = Arc<Mutex<Vec<(u64, Vec<u8>)>>>;
+
+    #[derive(Default)]
+    struct MemorySink {
+        stored: Stored,
+    }
+
+    impl SnapshotSink for MemorySink {
+        fn store(&self, generation: u64, content: &[u8]) -> Result<(), SnapshotDumpError> {
+            self.stored
+                .lock()
+                .unwrap()
+                .push((generation, content.to_vec()));
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_snapshot_dump_observer_is_due() {
+        // Given
+        let observer = SnapshotDumpObserver::new(5, MemorySink::default());
+
+        // Then
+        assert!(observer.is_due(0));
+        assert!(!observer.is_due(3));
+        assert!(observer.is_due(10), "Should dump every period generations");
+        assert!(
+            SnapshotDumpObserver::new(0, MemorySink::default()).is_due(1),
+            "Should dump every generation when period is 0"
+        );
+    }
+
+    #[test]
+    fn test_snapshot_dump_observer_dump() {
+        // Given
+        let sink = MemorySink::default();
+        let stored = sink.stored.clone();
+        let observer = SnapshotDumpObserver::new(5, sink);
+        let snapshot = Snapshot {
+            generation: 10,
+            evaluations: vec![Evaluation {
+                genome: vec![1, 2],
+                fitness: 0.5,
+                ..Default::default()
+            }],
+            evaluation_count: 30,
+            hall_of_fame: vec![],
+            stats: None,
+            diversity: None,
+            parameters: None,
+            metadata: None,
+            lineage: None,
+        };
+
+        // When
+        observer.dump(&snapshot).unwrap();
+
+        // Then
+        let stored = stored.lock().unwrap();
+        assert_eq!(1, stored.len());
+        assert_eq!(10, stored[0].0, "Should store the snapshot generation");
+        assert_eq!(
+            snapshot,
+            serde_json::from_slice::<Snapshot>(&stored[0].1).unwrap(),
+            "Should store the whole snapshot as JSON"
+        );
+    }
+
+    struct Sum;
+
+    impl Strategy for Sum {
+        type Phenotype = Genome;
+
+        fn genome_size(&self) -> usize {
+            2
+        }
+
+        fn decode(&self, genome: &Genome) -> Genome {
+            genome.clone()
+        }
+
+        fn evaluate(&self, genome: &Genome) -> f32 {
+            genome.iter().map(|&g| g as f32).sum::<f32>() / 510.0
+        }
+    }
+
+    #[test]
+    fn test_snapshot_dump_observer_registered() {
+        // Given
+        let sink = MemorySink::default();
+        let stored = sink.stored.clone();
+        let mut engine = EvolutionEngine::default();
+        engine.register_observer(Arc::new(SnapshotDumpObserver::new(2, sink)));
+        let config = EvolutionConfig {
+            population_size: 4,
+            generation_renewal_config: None,
+            max_duration: None,
+        };
+
+        // When
+        block_on(engine.start(
+            &Sum,
+            &config,
+            MaxGenerations(3),
+            &mut get_seeded_rng().unwrap(),
+        ))
+        .unwrap();
+
+        // Then
+        assert_eq!(
+            vec![0, 2],
+            stored
+                .lock()
+                .unwrap()
+                .iter()
+                .map(|(generation, _)| *generation)
+                .collect::<Vec<_>>(),
+            "Should dump the snapshots of the engine it observes"
+        );
+    }
+
+    #[test]
+    fn test_snapshot_name() {
+        assert_eq!("snapshot-00000042.json", snapshot_name(42));
+    }
+}
@@ -0,0 +1,46 @@
+use super::{http_sink::put, snapshot_name, SnapshotDumpError, SnapshotSink};
+
+/// Uploads each snapshot to S3 through presigned `PUT` URLs.
+///
+/// `presign` receives the object key (`prefix` followed by the snapshot name) and returns the
+/// URL to upload to, so credentials stay with whatever service signs the requests.
+pub struct S3Sink {
+    prefix: String,
+    presign: Box<dyn Fn(&str) -> String + Send + Sync>,
+}
+
+impl S3Sink {
+    pub fn new(
+        prefix: impl Into<String>,
+        presign: impl Fn(&str) -> String + Send + Sync + 'static,
+    ) -> Self {
+        S3Sink {
+            prefix: prefix.into(),
+            presign: Box::new(presign),
+        }
+    }
+
+    fn key_for(&self, generation: u64) -> String {
+        format!("{}{}", self.prefix, snapshot_name(generation))
+    }
+}
+
+impl SnapshotSink for S3Sink {
+    fn store(&self, generation: u64, content: &[u8]) -> Result<(), SnapshotDumpError> {
+        put(&(self.presign)(&self.key_for(generation)), content)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::S3Sink;
+
+    #[test]
+    fn test_s3_sink_key_for() {
+        // Given
+        let sink = S3Sink::new("runs/1/", |key| format!("https://bucket/{key}"));
+
+        // Then
+        assert_eq!("runs/1/snapshot-00000002.json", sink.key_for(2));
+    }
+}
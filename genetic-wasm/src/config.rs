@@ -0,0 +1,118 @@
+use genetic::{
+    crossover::CrossoverType,
+    evolution::{EvolutionConfig, GenerationRenewalConfig, GeneticRenewalParam},
+    mutation::MutationType,
+    selection::SelectionType,
+};
+use serde::Deserialize;
+
+/// Settings of a browser evolution, given as a plain JS object, e.g.
+/// `{ populationSize: 64, genomeSize: 8, crossover: { ratio: 0.8, selectionType: { Tournament: 3 } } }`.
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Config {
+    pub population_size: usize,
+    pub genome_size: usize,
+    pub cloning: Option<RenewalConfig>,
+    pub crossover: Option<RenewalConfig>,
+    /// See [`GenerationRenewalConfig::random_ratio`].
+    pub random_ratio: Option<f32>,
+    /// Seed of the random generator, drawn from `Math.random()` when missing.
+    pub seed: Option<u64>,
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq, Default)]
+#[serde(rename_all = "camelCase", default)]
+pub struct RenewalConfig {
+    pub mutation_rate: Option<f32>,
+    pub mutation_type: MutationType,
+    pub ratio: f32,
+    pub selection_type: SelectionType,
+    pub crossover_type: CrossoverType,
+    pub distinct_parents: Option<bool>,
+}
+
+impl From<RenewalConfig> for GeneticRenewalParam {
+    fn from(config: RenewalConfig) -> Self {
+        GeneticRenewalParam {
+            mutation_rate: config.mutation_rate,
+            mutation_type: config.mutation_type,
+            ratio: config.ratio,
+            selection_type: config.selection_type,
+            crossover_type: config.crossover_type,
+            distinct_parents: config.distinct_parents,
+        }
+    }
+}
+
+impl Config {
+    pub fn evolution_config(&self) -> EvolutionConfig {
+        let generation_renewal_config =
+            (self.cloning.is_some() || self.crossover.is_some() || self.random_ratio.is_some())
+                .then(|| GenerationRenewalConfig {
+                    cloning: self.cloning.clone().map(Into::into),
+                    crossover: self.crossover.clone().map(Into::into),
+                    random_ratio: self.random_ratio,
+                });
+        EvolutionConfig {
+            population_size: self.population_size,
+            generation_renewal_config,
+            max_duration: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use genetic::{crossover::CrossoverType, selection::SelectionType};
+
+    use super::{Config, RenewalConfig};
+
+    #[test]
+    fn test_config_evolution_config() {
+        // Given
+        let config = Config {
+            population_size: 64,
+            genome_size: 8,
+            cloning: None,
+            crossover: Some(RenewalConfig {
+                ratio: 0.8,
+                selection_type: SelectionType::Tournament(3),
+                crossover_type: CrossoverType::Uniform(0.5),
+                ..Default::default()
+            }),
+            random_ratio: None,
+            seed: None,
+        };
+
+        // When
+        let result = config.evolution_config();
+
+        // Then
+        assert_eq!(64, result.population_size);
+        let renewal = result.generation_renewal_config.unwrap();
+        assert!(renewal.cloning.is_none());
+        let crossover = renewal.crossover.unwrap();
+        assert_eq!(0.8, crossover.ratio);
+        assert_eq!(CrossoverType::Uniform(0.5), crossover.crossover_type);
+    }
+
+    #[test]
+    fn test_config_evolution_config_should_skip_renewal_when_unset() {
+        // Given
+        let config = Config {
+            population_size: 16,
+            genome_size: 4,
+            cloning: None,
+            crossover: None,
+            random_ratio: None,
+            seed: Some(1),
+        };
+
+        // Then
+        assert!(config
+            .evolution_config()
+            .generation_renewal_config
+            .is_none());
+    }
+}
@@ -0,0 +1,165 @@
+//! Browser bindings of the evolution engine, driven one generation at a time from JS against a
+//! JS fitness function.
+//!
+//! ```js
+//! const evolution = new Evolution({ populationSize: 64, genomeSize: 8 }, (genome) => score(genome));
+//! evolution.onEvent(({ event, generation }) => console.log(event, generation));
+//! evolution.start();
+//! while (evolution.generation < 100) evolution.step();
+//! console.log(evolution.snapshot().evaluations);
+//! ```
+
+mod config;
+mod strategy;
+
+use std::sync::{Arc, Mutex, PoisonError};
+
+use common::subject_observer::{Observer, Subject};
+use futures::executor::block_on;
+use genetic::evolution::{EventType, EvolutionConfig, EvolutionEngine, EvolutionError, Snapshot};
+use js_sys::{Function, Uint8Array};
+use rand::{rngs::SmallRng, SeedableRng};
+use serde::Serialize;
+use wasm_bindgen::prelude::*;
+
+pub use config::{Config, RenewalConfig};
+use strategy::FitnessStrategy;
+
+#[derive(Serialize)]
+struct JsEvent {
+    event: String,
+    generation: u64,
+}
+
+/// Queues the events of the engine, the JS listeners being called once the engine returns
+/// since JS values cannot be shared with the engine observers.
+#[derive(Default)]
+struct EventQueue(Mutex<Vec<JsEvent>>);
+
+impl EventQueue {
+    fn take(&self) -> Vec<JsEvent> {
+        std::mem::take(&mut *self.0.lock().unwrap_or_else(PoisonError::into_inner))
+    }
+}
+
+impl Observer<EvolutionEngine, EventType> for EventQueue {
+    fn update(&self, source: &EvolutionEngine, event: EventType) {
+        self.0
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push(JsEvent {
+                event: format!("{:?}", event),
+                generation: source.snapshot().generation,
+            });
+    }
+}
+
+fn evaluate(fitness: &Function, genome: &[u8]) -> f32 {
+    // Anything but a number is reported as an invalid evaluation by the engine
+    fitness
+        .call1(&JsValue::NULL, &Uint8Array::from(genome))
+        .ok()
+        .and_then(|value| value.as_f64())
+        .map_or(f32::NAN, |value| value as f32)
+}
+
+fn to_js_error(error: EvolutionError) -> JsError {
+    JsError::new(&error.to_string())
+}
+
+type JsFitness = Box<dyn Fn(&Vec<u8>) -> f32>;
+
+/// Engine of a started evolution, prepared to be stepped one generation at a time.
+struct Session {
+    engine: EvolutionEngine,
+    events: Arc<EventQueue>,
+}
+
+#[wasm_bindgen]
+pub struct Evolution {
+    config: EvolutionConfig,
+    listeners: Vec<Function>,
+    rng: SmallRng,
+    session: Option<Session>,
+    snapshot: Option<Snapshot>,
+    strategy: FitnessStrategy<JsFitness>,
+}
+
+#[wasm_bindgen]
+impl Evolution {
+    /// Creates an evolution from a config object (see [`Config`]) and a fitness function
+    /// receiving a genome as `Uint8Array` and returning a number between 0 and 1.
+    #[wasm_bindgen(constructor)]
+    pub fn new(config: JsValue, fitness: Function) -> Result<Evolution, JsError> {
+        let config: Config = serde_wasm_bindgen::from_value(config)?;
+        let seed = config
+            .seed
+            .unwrap_or_else(|| (js_sys::Math::random() * u64::MAX as f64) as u64);
+        Ok(Evolution {
+            config: config.evolution_config(),
+            listeners: Vec::new(),
+            rng: SmallRng::seed_from_u64(seed),
+            session: None,
+            snapshot: None,
+            strategy: FitnessStrategy::new(
+                config.genome_size,
+                Box::new(move |genome| evaluate(&fitness, genome)),
+            ),
+        })
+    }
+
+    /// Registers a listener called with `{ event, generation }` for each engine event.
+    #[wasm_bindgen(js_name = onEvent)]
+    pub fn on_event(&mut self, listener: Function) {
+        self.listeners.push(listener);
+    }
+
+    /// Evaluates a random initial population, restarting the evolution if already started.
+    pub fn start(&mut self) -> Result<(), JsError> {
+        self.snapshot = None;
+        let events = Arc::new(EventQueue::default());
+        let mut engine = EvolutionEngine::default();
+        engine.register_observer(events.clone());
+        let prepared = engine.prepare(&self.strategy, &self.config, &mut self.rng, None);
+        self.session = Some(Session { engine, events });
+        self.dispatch();
+        prepared.map_err(to_js_error)?;
+        self.step()
+    }
+
+    /// Renews the population and evaluates the new generation.
+    pub fn step(&mut self) -> Result<(), JsError> {
+        let Some(session) = self.session.as_mut() else {
+            return Err(JsError::new("Evolution must be started before stepping"));
+        };
+        let result = block_on(session.engine.step(&self.strategy, &mut self.rng));
+        self.dispatch();
+        self.snapshot = Some(result.map_err(to_js_error)?);
+        Ok(())
+    }
+
+    /// Current generation with its evaluations, `undefined` before the start.
+    pub fn snapshot(&self) -> Result<JsValue, JsError> {
+        Ok(serde_wasm_bindgen::to_value(&self.snapshot)?)
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn generation(&self) -> Option<u64> {
+        self.snapshot.as_ref().map(|s| s.generation)
+    }
+
+    /// Calls the listeners with the events queued while the engine ran.
+    fn dispatch(&self) {
+        let Some(session) = self.session.as_ref() else {
+            return;
+        };
+        for event in session.events.take() {
+            if let Ok(value) = serde_wasm_bindgen::to_value(&event) {
+                for listener in &self.listeners {
+                    // A failing listener must not stop the evolution
+                    let _ = listener.call1(&JsValue::NULL, &value);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,73 @@
+use genetic::{adaptation::Strategy, Genome};
+
+/// Strategy delegating the evaluation of the raw genomes to a fitness function.
+pub struct FitnessStrategy<F> {
+    genome_size: usize,
+    fitness: F,
+}
+
+impl<F: Fn(&Genome) -> f32> FitnessStrategy<F> {
+    pub fn new(genome_size: usize, fitness: F) -> Self {
+        FitnessStrategy {
+            genome_size,
+            fitness,
+        }
+    }
+}
+
+impl<F: Fn(&Genome) -> f32> Strategy for FitnessStrategy<F> {
+    type Phenotype = Genome;
+
+    fn genome_size(&self) -> usize {
+        self.genome_size
+    }
+
+    fn decode(&self, genome: &Genome) -> Genome {
+        genome.clone()
+    }
+
+    fn evaluate(&self, genome: &Genome) -> f32 {
+        (self.fitness)(genome)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use common_test::get_seeded_rng;
+    use futures::executor::block_on;
+    use genetic::evolution::{EvolutionConfig, EvolutionEngine};
+
+    use super::FitnessStrategy;
+
+    #[test]
+    fn test_fitness_strategy_step() {
+        // Given
+        let calls = AtomicUsize::new(0);
+        let strategy = FitnessStrategy::new(2, |genome: &Vec<u8>| {
+            calls.fetch_add(1, Ordering::Relaxed);
+            genome[0] as f32 / 255.0
+        });
+        let config = EvolutionConfig {
+            population_size: 10,
+            generation_renewal_config: None,
+            max_duration: None,
+        };
+        let mut rng = get_seeded_rng().unwrap();
+        let mut engine = EvolutionEngine::default();
+        engine.prepare(&strategy, &config, &mut rng, None).unwrap();
+
+        // When
+        let first = block_on(engine.step(&strategy, &mut rng)).unwrap();
+        let second = block_on(engine.step(&strategy, &mut rng)).unwrap();
+
+        // Then
+        assert_eq!((0, 1), (first.generation, second.generation));
+        assert!(
+            calls.load(Ordering::Relaxed) <= 20,
+            "Should not evaluate the generations again"
+        );
+        assert!(second.evaluations.iter().all(|e| e.evaluated));
+    }
+}
@@ -0,0 +1,78 @@
+//! Compares the pressure of the selection schemes over a population given by its fitnesses.
+//!
+//! ```sh
+//! cargo run -p genetic --example selection_pressure -- 0.1 0.2 0.5 0.9
+//! ```
+
+use std::{env, process::exit};
+
+use genetic::{
+    evolution::Snapshot,
+    selection::{SelectionPressure, SelectionType},
+    Evaluation,
+};
+use rand::thread_rng;
+
+const SAMPLES: usize = 10_000;
+
+fn main() {
+    let fitnesses = env::args()
+        .skip(1)
+        .map(|arg| arg.parse::<f32>())
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap_or_else(|e| {
+            eprintln!("Fitnesses must be numbers: {e}");
+            exit(1)
+        });
+    if fitnesses.is_empty() {
+        eprintln!("Usage: selection_pressure <fitness>...");
+        exit(1);
+    }
+
+    let snapshot = Snapshot {
+        evaluations: fitnesses
+            .iter()
+            .map(|&fitness| Evaluation {
+                fitness,
+                ..Default::default()
+            })
+            .collect(),
+        ..Default::default()
+    };
+    let population_size = fitnesses.len();
+    let selection_types = [
+        SelectionType::Chance,
+        SelectionType::Weight,
+        SelectionType::Age,
+        SelectionType::Ranking(population_size),
+        SelectionType::Tournament(2.min(population_size)),
+        SelectionType::Tournament(population_size.div_ceil(2)),
+    ];
+
+    let mut rng = thread_rng();
+    for selection_type in &selection_types {
+        match snapshot.selection_pressure(selection_type, SAMPLES, &mut rng) {
+            Ok(pressure) => print_report(selection_type, &fitnesses, &pressure),
+            Err(e) => println!("{selection_type:?}: {e}\n"),
+        }
+    }
+}
+
+fn print_report(selection_type: &SelectionType, fitnesses: &[f32], pressure: &SelectionPressure) {
+    let summary = pressure.summary();
+    println!(
+        "{selection_type:?}: max expected offspring {:.2}, loss of diversity {:.2}, gini {:.2}",
+        summary.max_expected_offspring, summary.loss_of_diversity, summary.gini
+    );
+    for (i, (fitness, offspring)) in fitnesses
+        .iter()
+        .zip(pressure.expected_offspring())
+        .enumerate()
+    {
+        println!(
+            "  #{i:<3} fitness {fitness:<8.3} probability {:.3}  expected offspring {offspring:.2}",
+            pressure.probability(i)
+        );
+    }
+    println!();
+}
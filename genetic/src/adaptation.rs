@@ -1,7 +1,307 @@
+#[cfg(feature = "async")]
+use std::future::Future;
+use std::{any::Any, collections::BTreeMap, error::Error};
+
 use crate::Genome;
 
+/// Problem solved by the evolution: genomes are decoded to phenotypes, e.g. a tour or a program,
+/// which are then evaluated.
 pub trait Strategy {
+    /// Solution encoded by a genome, the genome itself for strategies reading raw genes.
+    type Phenotype;
+
+    fn genome_size(&self) -> usize;
+
+    fn decode(&self, genome: &Genome) -> Self::Phenotype;
+
+    fn evaluate(&self, phenotype: &Self::Phenotype) -> f32;
+
+    /// Fitness of a phenotype along with the values of its objectives, kept in
+    /// [`crate::Evaluation::objectives`]. Only [`Strategy::evaluate`] without objectives by
+    /// default.
+    fn evaluate_objectives(&self, phenotype: &Self::Phenotype) -> (f32, Vec<f32>) {
+        (self.evaluate(phenotype), vec![])
+    }
+
+    /// Fitness and objectives of a phenotype along with auxiliary data, e.g. statistics of the
+    /// simulation run to evaluate it, kept in [`crate::Evaluation::metadata`]. Only
+    /// [`Strategy::evaluate_objectives`] without metadata by default.
+    fn evaluate_with_metadata(
+        &self,
+        phenotype: &Self::Phenotype,
+    ) -> (f32, Vec<f32>, BTreeMap<String, String>) {
+        let (fitness, objectives) = self.evaluate_objectives(phenotype);
+        (fitness, objectives, BTreeMap::new())
+    }
+
+    /// Same as [`Strategy::evaluate_with_metadata`] knowing the circumstances of the evaluation,
+    /// e.g. to anneal the difficulty over the generations or to seed a random generator per
+    /// individual. Ignores the context by default.
+    fn evaluate_in_context(
+        &self,
+        phenotype: &Self::Phenotype,
+        _context: &EvalContext,
+    ) -> (f32, Vec<f32>, BTreeMap<String, String>) {
+        self.evaluate_with_metadata(phenotype)
+    }
+
+    /// Readable form of a phenotype, exposed in [`crate::Evaluation::phenotype`] so that
+    /// observers do not need to decode genomes. Nothing by default.
+    fn describe(&self, _phenotype: &Self::Phenotype) -> Option<String> {
+        None
+    }
+
+    /// Normalizes a genome created by the engine, e.g. to turn it back into a permutation or fit
+    /// it within a budget, instead of penalizing it in [`Strategy::evaluate`].
+    ///
+    /// Called on random genomes, offsprings and mutated clones before their evaluation. Does
+    /// nothing by default.
+    fn repair(&self, _genome: &mut Genome) {}
+}
+
+/// Problem evaluating the genomes of a generation at once, e.g. on remote workers, run by
+/// [`crate::evolution::EvolutionEngine::start_batch`].
+pub trait BatchStrategy {
+    fn genome_size(&self) -> usize;
+
+    /// Fitnesses of the genomes, in their order, a failure interrupting the run.
+    fn evaluate_batch(&self, genomes: &[&Genome])
+        -> Result<Vec<f32>, Box<dyn Error + Send + Sync>>;
+
+    /// See [`Strategy::repair`].
+    fn repair(&self, _genome: &mut Genome) {}
+}
+
+/// Problem evaluating a generation laid out in a contiguous buffer, e.g. to upload it to a GPU
+/// kernel, run by [`crate::evolution::EvolutionEngine::start_batch`] through [`Buffered`].
+pub trait BufferStrategy {
     fn genome_size(&self) -> usize;
 
-    fn evaluate(&self, genome: &Genome) -> f32;
+    /// Writes the fitness of each genome of `genomes`, made of `genome_size` bytes per genome
+    /// one after another, at its position in `fitnesses`, a failure interrupting the run.
+    fn evaluate_buffer(
+        &self,
+        genomes: &[u8],
+        fitnesses: &mut [f32],
+    ) -> Result<(), Box<dyn Error + Send + Sync>>;
+
+    /// See [`Strategy::repair`].
+    fn repair(&self, _genome: &mut Genome) {}
+}
+
+/// Adapts a [`BufferStrategy`] to a [`BatchStrategy`], copying the genomes of the batch into a
+/// buffer.
+pub struct Buffered<S>(pub S);
+
+impl<S: BufferStrategy> BatchStrategy for Buffered<S> {
+    fn genome_size(&self) -> usize {
+        self.0.genome_size()
+    }
+
+    fn evaluate_batch(
+        &self,
+        genomes: &[&Genome],
+    ) -> Result<Vec<f32>, Box<dyn Error + Send + Sync>> {
+        let buffer = genomes
+            .iter()
+            .flat_map(|genome| genome.iter().copied())
+            .collect::<Vec<_>>();
+        let mut fitnesses = vec![0.0; genomes.len()];
+        self.0.evaluate_buffer(&buffer, &mut fitnesses)?;
+        Ok(fitnesses)
+    }
+
+    fn repair(&self, genome: &mut Genome) {
+        self.0.repair(genome);
+    }
+}
+
+/// Circumstances of the evaluation of a genome, see [`Strategy::evaluate_in_context`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct EvalContext {
+    /// Generation of the genome.
+    pub generation: u64,
+    /// Position of the genome in the population, or among the injected genomes.
+    pub index: usize,
+    /// Seed drawn by the engine for this evaluation, distinct for each sample when resampling.
+    pub rng_seed: u64,
+    /// Times the genome is evaluated, more than once when resampling, see
+    /// [`crate::evolution::EvolutionEngineBuilder::resampling`].
+    pub samples: usize,
+}
+
+/// Strategy picked at runtime, e.g. by name, whatever its phenotype, see [`Erased`].
+pub type DynStrategy = dyn Strategy<Phenotype = Box<dyn Any>>;
+
+/// Adapts a strategy to [`DynStrategy`], boxing its phenotypes.
+pub struct Erased<S>(pub S);
+
+impl<S> Erased<S>
+where
+    S: Strategy + 'static,
+    S::Phenotype: 'static,
+{
+    /// Boxes the strategy as a [`DynStrategy`].
+    pub fn boxed(strategy: S) -> Box<DynStrategy> {
+        Box::new(Erased(strategy))
+    }
+
+    fn phenotype<'a>(&self, phenotype: &'a Box<dyn Any>) -> &'a S::Phenotype {
+        phenotype
+            .downcast_ref()
+            .expect("Phenotype should be decoded by the erased strategy")
+    }
+}
+
+impl<S> Strategy for Erased<S>
+where
+    S: Strategy + 'static,
+    S::Phenotype: 'static,
+{
+    type Phenotype = Box<dyn Any>;
+
+    fn genome_size(&self) -> usize {
+        self.0.genome_size()
+    }
+
+    fn decode(&self, genome: &Genome) -> Self::Phenotype {
+        Box::new(self.0.decode(genome))
+    }
+
+    fn evaluate(&self, phenotype: &Self::Phenotype) -> f32 {
+        self.0.evaluate(self.phenotype(phenotype))
+    }
+
+    fn evaluate_objectives(&self, phenotype: &Self::Phenotype) -> (f32, Vec<f32>) {
+        self.0.evaluate_objectives(self.phenotype(phenotype))
+    }
+
+    fn evaluate_with_metadata(
+        &self,
+        phenotype: &Self::Phenotype,
+    ) -> (f32, Vec<f32>, BTreeMap<String, String>) {
+        self.0.evaluate_with_metadata(self.phenotype(phenotype))
+    }
+
+    fn evaluate_in_context(
+        &self,
+        phenotype: &Self::Phenotype,
+        context: &EvalContext,
+    ) -> (f32, Vec<f32>, BTreeMap<String, String>) {
+        self.0
+            .evaluate_in_context(self.phenotype(phenotype), context)
+    }
+
+    fn describe(&self, phenotype: &Self::Phenotype) -> Option<String> {
+        self.0.describe(self.phenotype(phenotype))
+    }
+
+    fn repair(&self, genome: &mut Genome) {
+        self.0.repair(genome);
+    }
+}
+
+/// Strategies behind pointers, e.g. a `Box<DynStrategy>`, run as the strategies they point to.
+macro_rules! impl_strategy_for_pointer {
+    ($($pointer:ty),*) => {
+        $(
+            impl<S: Strategy + ?Sized> Strategy for $pointer {
+                type Phenotype = S::Phenotype;
+
+                fn genome_size(&self) -> usize {
+                    (**self).genome_size()
+                }
+
+                fn decode(&self, genome: &Genome) -> Self::Phenotype {
+                    (**self).decode(genome)
+                }
+
+                fn evaluate(&self, phenotype: &Self::Phenotype) -> f32 {
+                    (**self).evaluate(phenotype)
+                }
+
+                fn evaluate_objectives(&self, phenotype: &Self::Phenotype) -> (f32, Vec<f32>) {
+                    (**self).evaluate_objectives(phenotype)
+                }
+
+                fn evaluate_with_metadata(
+                    &self,
+                    phenotype: &Self::Phenotype,
+                ) -> (f32, Vec<f32>, BTreeMap<String, String>) {
+                    (**self).evaluate_with_metadata(phenotype)
+                }
+
+                fn evaluate_in_context(
+                    &self,
+                    phenotype: &Self::Phenotype,
+                    context: &EvalContext,
+                ) -> (f32, Vec<f32>, BTreeMap<String, String>) {
+                    (**self).evaluate_in_context(phenotype, context)
+                }
+
+                fn describe(&self, phenotype: &Self::Phenotype) -> Option<String> {
+                    (**self).describe(phenotype)
+                }
+
+                fn repair(&self, genome: &mut Genome) {
+                    (**self).repair(genome);
+                }
+            }
+        )*
+    };
+}
+
+impl_strategy_for_pointer!(&S, Box<S>);
+
+/// Problem whose evaluation awaits, e.g. an external service, run by
+/// [`crate::evolution::EvolutionEngine::start_async`] without blocking the other evaluations of
+/// the generation.
+#[cfg(feature = "async")]
+pub trait AsyncStrategy: Send + Sync + 'static {
+    fn genome_size(&self) -> usize;
+
+    fn evaluate(&self, genome: &Genome) -> impl Future<Output = f32> + Send;
+
+    /// See [`Strategy::repair`].
+    fn repair(&self, _genome: &mut Genome) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use std::error::Error;
+
+    use super::{BatchStrategy, BufferStrategy, Buffered};
+
+    /// Sums the genes of the genomes of the buffer.
+    struct Sum;
+
+    impl BufferStrategy for Sum {
+        fn genome_size(&self) -> usize {
+            2
+        }
+
+        fn evaluate_buffer(
+            &self,
+            genomes: &[u8],
+            fitnesses: &mut [f32],
+        ) -> Result<(), Box<dyn Error + Send + Sync>> {
+            for (genome, fitness) in genomes.chunks_exact(2).zip(fitnesses.iter_mut()) {
+                *fitness = genome.iter().map(|&gene| gene as f32).sum::<f32>() / 510.0;
+            }
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_buffered() {
+        // Given
+        let genomes = [vec![0, 0], vec![255, 0], vec![255, 255]];
+
+        // When
+        let result = Buffered(Sum).evaluate_batch(&genomes.iter().collect::<Vec<_>>());
+
+        // Then
+        assert_eq!(vec![0.0, 0.5, 1.0], result.unwrap());
+        assert_eq!(2, Buffered(Sum).genome_size());
+    }
 }
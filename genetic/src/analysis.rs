@@ -0,0 +1,265 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::Evaluation;
+
+const ALLELES_COUNT: usize = u8::MAX as usize + 1;
+
+/// Distribution of the byte values found at one position of the genomes.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AlleleFrequencies {
+    counts: [u32; ALLELES_COUNT],
+    total: u32,
+}
+
+impl Default for AlleleFrequencies {
+    fn default() -> Self {
+        Self {
+            counts: [0; ALLELES_COUNT],
+            total: 0,
+        }
+    }
+}
+
+impl AlleleFrequencies {
+    pub fn add(&mut self, allele: u8) {
+        self.counts[allele as usize] += 1;
+        self.total += 1;
+    }
+
+    pub fn count(&self, allele: u8) -> u32 {
+        self.counts[allele as usize]
+    }
+
+    pub fn total(&self) -> u32 {
+        self.total
+    }
+
+    pub fn frequency(&self, allele: u8) -> f32 {
+        if self.total > 0 {
+            self.count(allele) as f32 / self.total as f32
+        } else {
+            0.0
+        }
+    }
+
+    /// Returns the most frequent allele with its frequency, the lowest value winning ties.
+    pub fn dominant(&self) -> Option<(u8, f32)> {
+        (self.total > 0).then(|| {
+            let allele = (0..=u8::MAX)
+                .rev()
+                .max_by_key(|&allele| self.count(allele))
+                .unwrap_or_default();
+            (allele, self.frequency(allele))
+        })
+    }
+
+    /// Shannon entropy of the distribution in bits, 0 when a single allele is found, up to 8.
+    pub fn entropy(&self) -> f32 {
+        if self.total == 0 {
+            return 0.0;
+        }
+        self.counts
+            .iter()
+            .filter(|&&count| count > 0)
+            .map(|&count| {
+                let frequency = count as f32 / self.total as f32;
+                -frequency * frequency.log2()
+            })
+            .sum()
+    }
+
+    /// A locus is converged once its dominant allele reaches the given frequency.
+    pub fn is_converged(&self, threshold: f32) -> bool {
+        self.dominant()
+            .is_some_and(|(_, frequency)| frequency >= threshold)
+    }
+}
+
+/// Computes the allele distribution of each locus across the population.
+///
+/// Genomes shorter than the longest one only contribute to the loci they cover.
+pub fn allele_frequencies(evaluations: &[Evaluation]) -> Vec<AlleleFrequencies> {
+    let loci_count = evaluations
+        .iter()
+        .map(|e| e.genome.len())
+        .max()
+        .unwrap_or_default();
+    let mut frequencies = vec![AlleleFrequencies::default(); loci_count];
+    for evaluation in evaluations {
+        for (locus, &allele) in evaluation.genome.iter().enumerate() {
+            frequencies[locus].add(allele);
+        }
+    }
+    frequencies
+}
+
+/// Fitness statistics of the evaluated individuals of a generation, see
+/// [`crate::evolution::Snapshot::stats`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct GenerationStats {
+    pub min: f32,
+    pub max: f32,
+    pub mean: f32,
+    /// Population standard deviation.
+    pub std_dev: f32,
+    /// Position of the fittest individual in the generation, the first one winning ties.
+    pub best: usize,
+    /// Number of evaluated individuals the statistics are computed from.
+    pub count: usize,
+}
+
+impl GenerationStats {
+    /// Computes the statistics of the evaluated `evaluations`, `None` if none is evaluated.
+    pub fn compute(evaluations: &[Evaluation]) -> Option<Self> {
+        let mut fitnesses = evaluations
+            .iter()
+            .enumerate()
+            .filter_map(|(index, e)| e.evaluated_fitness().map(|fitness| (index, fitness)));
+        let (best, first) = fitnesses.next()?;
+        let mut stats = GenerationStats {
+            min: first,
+            max: first,
+            mean: first,
+            std_dev: 0.0,
+            best,
+            count: 1,
+        };
+        // Welford's online algorithm, `std_dev` summing the squared deviations until the end
+        for (index, fitness) in fitnesses {
+            stats.count += 1;
+            if fitness > stats.max {
+                stats.max = fitness;
+                stats.best = index;
+            }
+            stats.min = stats.min.min(fitness);
+            let delta = fitness - stats.mean;
+            stats.mean += delta / stats.count as f32;
+            stats.std_dev += delta * (fitness - stats.mean);
+        }
+        stats.std_dev = (stats.std_dev / stats.count as f32).sqrt();
+        Some(stats)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Evaluation;
+
+    use super::{allele_frequencies, AlleleFrequencies, GenerationStats};
+
+    #[test]
+    fn test_generation_stats_compute() {
+        // Given
+        let mut evaluations = [1.0, 3.0, 2.0, 3.0]
+            .map(|fitness| Evaluation {
+                fitness,
+                evaluated: true,
+                ..Default::default()
+            })
+            .to_vec();
+        evaluations.insert(0, Evaluation::from(vec![0]));
+
+        // When
+        let result = GenerationStats::compute(&evaluations).unwrap();
+
+        // Then
+        assert_eq!(4, result.count, "Should ignore unevaluated genomes");
+        assert_eq!((1.0, 3.0, 2.25), (result.min, result.max, result.mean));
+        assert!((result.std_dev - 0.8291562).abs() < 1e-6);
+        assert_eq!(
+            2, result.best,
+            "Should point at the first fittest individual"
+        );
+        assert_eq!(None, GenerationStats::compute(&[Evaluation::from(vec![0])]));
+    }
+
+    #[test]
+    fn test_allele_frequencies() {
+        // Given
+        let evaluations = vec![
+            Evaluation {
+                genome: vec![1, 2, 3],
+                ..Default::default()
+            },
+            Evaluation {
+                genome: vec![1, 4, 3],
+                ..Default::default()
+            },
+            Evaluation {
+                genome: vec![1, 5],
+                ..Default::default()
+            },
+        ];
+
+        // When
+        let result = allele_frequencies(&evaluations);
+
+        // Then
+        assert_eq!(3, result.len(), "Should compute one distribution per locus");
+        assert_eq!(Some((1, 1.0)), result[0].dominant());
+        assert_eq!(1.0 / 3.0, result[1].frequency(4));
+        assert_eq!(
+            2,
+            result[2].total(),
+            "Should only count genomes covering the locus"
+        );
+    }
+
+    #[test]
+    fn test_allele_frequencies_should_be_empty_without_genomes() {
+        assert!(allele_frequencies(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_allele_frequencies_dominant() {
+        // Given
+        let mut frequencies = AlleleFrequencies::default();
+
+        // Then
+        assert_eq!(
+            None,
+            frequencies.dominant(),
+            "Should not have dominant when empty"
+        );
+        assert!(!frequencies.is_converged(0.0));
+
+        // When
+        [7, 3, 7, 3, 9].into_iter().for_each(|a| frequencies.add(a));
+
+        // Then
+        assert_eq!(
+            Some((3, 0.4)),
+            frequencies.dominant(),
+            "Should pick the lowest allele on ties"
+        );
+        assert!(frequencies.is_converged(0.4));
+        assert!(!frequencies.is_converged(0.5));
+    }
+
+    #[test]
+    fn test_allele_frequencies_entropy() {
+        // Given
+        let mut frequencies = AlleleFrequencies::default();
+
+        // Then
+        assert_eq!(0.0, frequencies.entropy());
+
+        // When
+        [7, 7].into_iter().for_each(|a| frequencies.add(a));
+
+        // Then
+        assert_eq!(
+            0.0,
+            frequencies.entropy(),
+            "Should be 0 for a single allele"
+        );
+
+        // When
+        [3, 3, 5, 9].into_iter().for_each(|a| frequencies.add(a));
+
+        // Then
+        assert!((frequencies.entropy() - 1.9182958).abs() < 1e-6);
+    }
+}
@@ -0,0 +1,93 @@
+//! Bitstring view of genomes for boolean problems, bit `i` being the bit of weight `i % 8` of the
+//! gene `i / 8`.
+//!
+//! Pairs with [`crate::mutation::MutationType::BitFlip`] and
+//! [`crate::crossover::CrossoverType::UniformBit`] to search at bit rather than byte granularity.
+
+use crate::Genome;
+
+/// Number of bits of a genome of the given size.
+pub fn bit_len(genome_size: usize) -> usize {
+    genome_size * u8::BITS as usize
+}
+
+/// Size of the smallest genome holding the given number of bits.
+pub fn genome_size_for_bits(bits: usize) -> usize {
+    bits.div_ceil(u8::BITS as usize)
+}
+
+pub fn get_bit(genome: &Genome, index: usize) -> bool {
+    genome[index / u8::BITS as usize] & (1 << (index % u8::BITS as usize)) != 0
+}
+
+pub fn set_bit(genome: &mut Genome, index: usize, value: bool) {
+    let mask = 1 << (index % u8::BITS as usize);
+    let gene = &mut genome[index / u8::BITS as usize];
+    if value {
+        *gene |= mask;
+    } else {
+        *gene &= !mask;
+    }
+}
+
+/// Iterates over the bits of the genome.
+pub fn bits(genome: &Genome) -> impl Iterator<Item = bool> + '_ {
+    (0..bit_len(genome.len())).map(|index| get_bit(genome, index))
+}
+
+/// Number of set bits of the genome.
+pub fn popcount(genome: &Genome) -> u32 {
+    genome.iter().map(|gene| gene.count_ones()).sum()
+}
+
+/// Number of bits differing between two genomes of the same size.
+pub fn hamming_distance(a: &Genome, b: &Genome) -> u32 {
+    a.iter().zip(b).map(|(a, b)| (a ^ b).count_ones()).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        bit_len, bits, genome_size_for_bits, get_bit, hamming_distance, popcount, set_bit,
+    };
+
+    #[test]
+    fn test_genome_size_for_bits() {
+        assert_eq!(0, genome_size_for_bits(0));
+        assert_eq!(1, genome_size_for_bits(8));
+        assert_eq!(2, genome_size_for_bits(9), "Should round up to whole genes");
+        assert_eq!(16, bit_len(2));
+    }
+
+    #[test]
+    fn test_set_bit() {
+        // Given
+        let mut genome = vec![0, 0];
+
+        // When
+        set_bit(&mut genome, 1, true);
+        set_bit(&mut genome, 9, true);
+        set_bit(&mut genome, 10, true);
+        set_bit(&mut genome, 10, false);
+
+        // Then
+        assert_eq!(vec![0b10, 0b10], genome);
+        assert!(get_bit(&genome, 9));
+        assert!(!get_bit(&genome, 10));
+        assert_eq!(
+            vec![false, true, false, false],
+            bits(&genome).take(4).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_popcount() {
+        assert_eq!(0, popcount(&vec![0, 0]));
+        assert_eq!(9, popcount(&vec![0xff, 0b100]));
+        assert_eq!(
+            3,
+            hamming_distance(&vec![0b111, 0], &vec![0b110, 0b11]),
+            "Should count differing bits"
+        );
+    }
+}
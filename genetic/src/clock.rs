@@ -0,0 +1,94 @@
+use std::{
+    fmt::Debug,
+    sync::Mutex,
+    time::{Duration, SystemTime},
+};
+
+/// Source of the current time for the time dependent behaviors of the engine, so that they can be
+/// tested deterministically and replayed with a [`MockClock`].
+pub trait Clock: Debug + Send + Sync {
+    fn now(&self) -> SystemTime;
+
+    /// Time spent since `earlier`, zero when `earlier` is in the future.
+    fn since(&self, earlier: SystemTime) -> Duration {
+        self.now().duration_since(earlier).unwrap_or_default()
+    }
+}
+
+/// Wall clock of the system.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> SystemTime {
+        SystemTime::now()
+    }
+}
+
+/// Clock only moving when told to.
+#[derive(Debug)]
+pub struct MockClock {
+    now: Mutex<SystemTime>,
+}
+
+impl Default for MockClock {
+    fn default() -> Self {
+        Self::new(SystemTime::UNIX_EPOCH)
+    }
+}
+
+impl MockClock {
+    pub fn new(now: SystemTime) -> Self {
+        Self {
+            now: Mutex::new(now),
+        }
+    }
+
+    pub fn set(&self, now: SystemTime) {
+        *self.now.lock().unwrap_or_else(|e| e.into_inner()) = now;
+    }
+
+    pub fn advance(&self, duration: Duration) {
+        *self.now.lock().unwrap_or_else(|e| e.into_inner()) += duration;
+    }
+}
+
+impl Clock for MockClock {
+    fn now(&self) -> SystemTime {
+        *self.now.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, SystemTime};
+
+    use super::{Clock, MockClock};
+
+    #[test]
+    fn test_mock_clock() {
+        // Given
+        let clock = MockClock::default();
+        let start = clock.now();
+
+        // When
+        clock.advance(Duration::from_secs(3));
+
+        // Then
+        assert_eq!(
+            Duration::from_secs(3),
+            clock.since(start),
+            "Should only move when advanced"
+        );
+
+        // When
+        clock.set(SystemTime::UNIX_EPOCH);
+
+        // Then
+        assert_eq!(
+            Duration::ZERO,
+            clock.since(start + Duration::from_secs(1)),
+            "Should not measure negative durations"
+        );
+    }
+}
@@ -0,0 +1,384 @@
+//! Typed genomes: codecs translate gene vectors to the byte genomes evolved by the engine, so that
+//! strategies evaluate their own gene type instead of hand-rolling the decoding.
+//!
+//! Selection and operators keep working on bytes, every codec thus decodes any genome of its
+//! size, e.g. a permutation from any random keys.
+
+use thiserror::Error;
+
+use crate::{
+    adaptation::Strategy,
+    bits::{bits, genome_size_for_bits, set_bit},
+    Genome,
+};
+
+#[derive(Error, Debug, PartialEq)]
+pub enum CodecError {
+    #[error("A choice codec needs between 1 and 256 choices, got: {0}")]
+    InvalidChoiceCount(usize),
+    #[error("Invalid real bounds: [{0}, {1}]")]
+    InvalidBounds(f64, f64),
+    #[error("A permutation codec supports up to 65536 elements, got: {0}")]
+    PermutationTooLarge(usize),
+}
+
+/// Translation between typed genes and byte genomes.
+pub trait GenomeCodec {
+    type Gene;
+
+    /// Number of bytes of the encoded genomes.
+    fn genome_size(&self) -> usize;
+
+    fn encode(&self, genes: &[Self::Gene]) -> Genome;
+
+    /// Reads the genes of a genome of [`GenomeCodec::genome_size`] bytes.
+    fn decode(&self, genome: &Genome) -> Vec<Self::Gene>;
+}
+
+/// Raw bytes, one gene each.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ByteCodec {
+    pub count: usize,
+}
+
+impl GenomeCodec for ByteCodec {
+    type Gene = u8;
+
+    fn genome_size(&self) -> usize {
+        self.count
+    }
+
+    fn encode(&self, genes: &[u8]) -> Genome {
+        genes.to_vec()
+    }
+
+    fn decode(&self, genome: &Genome) -> Vec<u8> {
+        genome.clone()
+    }
+}
+
+/// Bitstrings, see [`crate::bits`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct BitCodec {
+    pub count: usize,
+}
+
+impl GenomeCodec for BitCodec {
+    type Gene = bool;
+
+    fn genome_size(&self) -> usize {
+        genome_size_for_bits(self.count)
+    }
+
+    fn encode(&self, genes: &[bool]) -> Genome {
+        let mut genome = vec![0; self.genome_size()];
+        for (index, &bit) in genes.iter().enumerate().take(self.count) {
+            set_bit(&mut genome, index, bit);
+        }
+        genome
+    }
+
+    fn decode(&self, genome: &Genome) -> Vec<bool> {
+        bits(genome).take(self.count).collect()
+    }
+}
+
+/// Reals within bounds, each gene spreading a little-endian `u32` over the bounds so that any
+/// bytes decode to a finite value.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RealCodec {
+    count: usize,
+    min: f64,
+    max: f64,
+}
+
+/// Number of bytes of a gene of a [`RealCodec`].
+const BOUNDED_REAL_SIZE: usize = std::mem::size_of::<u32>();
+
+impl RealCodec {
+    pub fn new(count: usize, min: f64, max: f64) -> Result<Self, CodecError> {
+        if !(min.is_finite() && max.is_finite() && min <= max) {
+            return Err(CodecError::InvalidBounds(min, max));
+        }
+        Ok(RealCodec { count, min, max })
+    }
+}
+
+impl GenomeCodec for RealCodec {
+    type Gene = f64;
+
+    fn genome_size(&self) -> usize {
+        self.count * BOUNDED_REAL_SIZE
+    }
+
+    fn encode(&self, genes: &[f64]) -> Genome {
+        let range = self.max - self.min;
+        genes
+            .iter()
+            .flat_map(|&gene| {
+                let ratio = if range > 0.0 {
+                    ((gene - self.min) / range).clamp(0.0, 1.0)
+                } else {
+                    0.0
+                };
+                ((ratio * u32::MAX as f64).round() as u32).to_le_bytes()
+            })
+            .collect()
+    }
+
+    fn decode(&self, genome: &Genome) -> Vec<f64> {
+        genome
+            .chunks_exact(BOUNDED_REAL_SIZE)
+            .map(|bytes| {
+                let value =
+                    u32::from_le_bytes(bytes.try_into().expect("Chunks should be gene sized"));
+                self.min + (self.max - self.min) * value as f64 / u32::MAX as f64
+            })
+            .collect()
+    }
+}
+
+/// Values of a finite set, e.g. the variants of an enum, each gene being one byte spread evenly
+/// over the choices.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChoiceCodec<T> {
+    count: usize,
+    choices: Vec<T>,
+}
+
+impl<T: Clone + PartialEq> ChoiceCodec<T> {
+    pub fn new(count: usize, choices: Vec<T>) -> Result<Self, CodecError> {
+        if choices.is_empty() || choices.len() > 256 {
+            return Err(CodecError::InvalidChoiceCount(choices.len()));
+        }
+        Ok(ChoiceCodec { count, choices })
+    }
+}
+
+impl<T: Clone + PartialEq> GenomeCodec for ChoiceCodec<T> {
+    type Gene = T;
+
+    fn genome_size(&self) -> usize {
+        self.count
+    }
+
+    /// Genes not among the choices are encoded as the first choice.
+    fn encode(&self, genes: &[T]) -> Genome {
+        let choice_count = self.choices.len();
+        genes
+            .iter()
+            .map(|gene| {
+                let index = self.choices.iter().position(|c| c == gene).unwrap_or(0);
+                // Middle of the range of bytes decoded as the choice
+                ((2 * index + 1) * 256 / (2 * choice_count)) as u8
+            })
+            .collect()
+    }
+
+    fn decode(&self, genome: &Genome) -> Vec<T> {
+        genome
+            .iter()
+            .map(|&byte| self.choices[byte as usize * self.choices.len() / 256].clone())
+            .collect()
+    }
+}
+
+/// Permutations of `0..size` encoded as random keys: each element has a little-endian `u16` key
+/// and the permutation lists the elements by increasing key, ties broken by element.
+///
+/// Any genome decodes to a valid permutation, thus every crossover and mutation applies.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PermutationCodec {
+    size: usize,
+}
+
+/// Number of bytes of the key of an element of a [`PermutationCodec`].
+const PERMUTATION_KEY_SIZE: usize = std::mem::size_of::<u16>();
+
+impl PermutationCodec {
+    pub fn new(size: usize) -> Result<Self, CodecError> {
+        if size > u16::MAX as usize + 1 {
+            return Err(CodecError::PermutationTooLarge(size));
+        }
+        Ok(PermutationCodec { size })
+    }
+}
+
+impl GenomeCodec for PermutationCodec {
+    type Gene = usize;
+
+    fn genome_size(&self) -> usize {
+        self.size * PERMUTATION_KEY_SIZE
+    }
+
+    fn encode(&self, genes: &[usize]) -> Genome {
+        let mut genome = vec![0; self.genome_size()];
+        for (rank, &element) in genes.iter().enumerate().take(self.size) {
+            let key = (rank * (u16::MAX as usize + 1) / self.size.max(1)) as u16;
+            let start = element * PERMUTATION_KEY_SIZE;
+            genome[start..start + PERMUTATION_KEY_SIZE].copy_from_slice(&key.to_le_bytes());
+        }
+        genome
+    }
+
+    fn decode(&self, genome: &Genome) -> Vec<usize> {
+        let keys = genome
+            .chunks_exact(PERMUTATION_KEY_SIZE)
+            .map(|bytes| u16::from_le_bytes(bytes.try_into().expect("Chunks should be key sized")))
+            .collect::<Vec<_>>();
+        let mut elements = (0..keys.len()).collect::<Vec<_>>();
+        elements.sort_by_key(|&element| keys[element]);
+        elements
+    }
+}
+
+/// Strategy evaluating typed genes, run by the engine through [`Decoded`].
+pub trait TypedStrategy {
+    type Codec: GenomeCodec;
+
+    fn codec(&self) -> &Self::Codec;
+
+    fn evaluate(&self, genes: &[<Self::Codec as GenomeCodec>::Gene]) -> f32;
+}
+
+/// Adapts a [`TypedStrategy`] to the engine, decoding genomes before their evaluation.
+pub struct Decoded<S>(pub S);
+
+impl<S: TypedStrategy> Strategy for Decoded<S> {
+    type Phenotype = Vec<<S::Codec as GenomeCodec>::Gene>;
+
+    fn genome_size(&self) -> usize {
+        self.0.codec().genome_size()
+    }
+
+    fn decode(&self, genome: &Genome) -> Self::Phenotype {
+        self.0.codec().decode(genome)
+    }
+
+    fn evaluate(&self, genes: &Self::Phenotype) -> f32 {
+        self.0.evaluate(genes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use common_test::get_seeded_rng;
+    use rand::{distributions::Standard, Rng};
+
+    use crate::adaptation::Strategy;
+
+    use super::{
+        BitCodec, ByteCodec, ChoiceCodec, CodecError, Decoded, GenomeCodec, PermutationCodec,
+        RealCodec, TypedStrategy,
+    };
+
+    #[test]
+    fn test_byte_and_bit_codecs() {
+        // Given
+        let bit_codec = BitCodec { count: 10 };
+        let bits = [
+            true, false, true, true, false, false, false, false, false, true,
+        ];
+
+        // Then
+        assert_eq!(vec![1, 2], ByteCodec { count: 2 }.decode(&vec![1, 2]));
+        assert_eq!(2, bit_codec.genome_size());
+        assert_eq!(bits.to_vec(), bit_codec.decode(&bit_codec.encode(&bits)));
+    }
+
+    #[test]
+    fn test_real_codec() {
+        // Given
+        let codec = RealCodec::new(3, -5.0, 5.0).unwrap();
+
+        // When
+        let result = codec.decode(&codec.encode(&[-5.0, 0.0, 12.0]));
+
+        // Then
+        assert_eq!(12, codec.genome_size());
+        assert!(
+            result
+                .iter()
+                .zip([-5.0, 0.0, 5.0])
+                .all(|(real, expected)| (real - expected).abs() < 1e-6),
+            "Should decode encoded reals within bounds, got {result:?}"
+        );
+        assert_eq!(
+            Err(CodecError::InvalidBounds(1.0, 0.0)),
+            RealCodec::new(1, 1.0, 0.0)
+        );
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    enum Move {
+        Left,
+        Right,
+        Jump,
+    }
+
+    #[test]
+    fn test_choice_codec() {
+        // Given
+        let codec = ChoiceCodec::new(4, vec![Move::Left, Move::Right, Move::Jump]).unwrap();
+        let moves = [Move::Jump, Move::Left, Move::Right, Move::Jump];
+
+        // Then
+        assert_eq!(moves.to_vec(), codec.decode(&codec.encode(&moves)));
+        assert_eq!(
+            vec![Move::Left, Move::Jump],
+            codec.decode(&vec![0, 255]),
+            "Should spread the bytes over the choices"
+        );
+        assert!(ChoiceCodec::<u8>::new(1, vec![]).is_err());
+    }
+
+    #[test]
+    fn test_permutation_codec() {
+        // Given
+        let mut rng = get_seeded_rng().unwrap();
+        let codec = PermutationCodec::new(6).unwrap();
+        let permutation = vec![3, 0, 5, 1, 4, 2];
+
+        // Then
+        assert_eq!(permutation, codec.decode(&codec.encode(&permutation)));
+        let mut result = codec.decode(&(&mut rng).sample_iter(Standard).take(12).collect());
+        result.sort();
+        assert_eq!(
+            (0..6).collect::<Vec<_>>(),
+            result,
+            "Should decode any genome to a permutation"
+        );
+    }
+
+    /// Counts the elements already in place.
+    struct Sorting(PermutationCodec);
+
+    impl TypedStrategy for Sorting {
+        type Codec = PermutationCodec;
+
+        fn codec(&self) -> &PermutationCodec {
+            &self.0
+        }
+
+        fn evaluate(&self, genes: &[usize]) -> f32 {
+            let in_place = genes.iter().enumerate().filter(|(i, e)| i == *e).count();
+            in_place as f32 / genes.len() as f32
+        }
+    }
+
+    #[test]
+    fn test_decoded_evaluate() {
+        // Given
+        let codec = PermutationCodec::new(4).unwrap();
+        let strategy = Decoded(Sorting(codec.clone()));
+
+        // Then
+        assert_eq!(8, strategy.genome_size());
+        assert_eq!(
+            vec![0, 2, 1, 3],
+            strategy.decode(&codec.encode(&[0, 2, 1, 3]))
+        );
+        assert_eq!(1.0, strategy.evaluate(&vec![0, 1, 2, 3]));
+        assert_eq!(0.5, strategy.evaluate(&vec![0, 2, 1, 3]));
+    }
+}
@@ -0,0 +1,47 @@
+use rand::Rng;
+
+use crate::{
+    real::{decode_reals, encode_reals},
+    Genome,
+};
+
+/// Arithmetic crossover of real-valued genomes: weighted average of the parents, with a weight
+/// drawn once per offspring.
+pub fn arithmetic_crossover(parents: (&Genome, &Genome), rng: &mut impl Rng) -> Genome {
+    let weight = rng.gen::<f32>();
+    let reals = decode_reals(parents.0)
+        .into_iter()
+        .zip(decode_reals(parents.1))
+        .map(|(first, second)| weight * first + (1.0 - weight) * second)
+        .collect::<Vec<_>>();
+    encode_reals(&reals)
+}
+
+#[cfg(test)]
+mod tests {
+    use common_test::get_seeded_rng;
+
+    use crate::real::{decode_reals, encode_reals};
+
+    use super::arithmetic_crossover;
+
+    #[test]
+    fn test_arithmetic_crossover() {
+        // Given
+        let mut rng = get_seeded_rng().unwrap();
+        let first = encode_reals(&[0.0, 10.0, -4.0]);
+        let second = encode_reals(&[2.0, 20.0, 4.0]);
+
+        // When
+        let result = decode_reals(&arithmetic_crossover((&first, &second), &mut rng));
+
+        // Then
+        let weight = (2.0 - result[0]) / 2.0;
+        assert!((0.0..=1.0).contains(&weight));
+        assert!(
+            (result[1] - (20.0 - 10.0 * weight)).abs() < 1e-4
+                && (result[2] - (4.0 - 8.0 * weight)).abs() < 1e-4,
+            "Should interpolate all genes with the same weight"
+        );
+    }
+}
@@ -0,0 +1,76 @@
+use rand::Rng;
+
+use crate::{
+    real::{decode_reals, encode_reals},
+    Genome,
+};
+
+/// Blend crossover (BLX-α) of real-valued genomes: draws each gene uniformly in the interval
+/// spanned by the parent genes, extended on both sides by `alpha` times its length.
+///
+/// Genes that are not finite in either parent are taken from the first one.
+pub fn blend_crossover(parents: (&Genome, &Genome), alpha: f32, rng: &mut impl Rng) -> Genome {
+    let reals = decode_reals(parents.0)
+        .into_iter()
+        .zip(decode_reals(parents.1))
+        .map(|(first, second)| {
+            if !first.is_finite() || !second.is_finite() {
+                return first;
+            }
+            let extent = alpha * (first - second).abs();
+            let low = first.min(second) - extent;
+            let high = first.max(second) + extent;
+            if low < high {
+                rng.gen_range(low..=high)
+            } else {
+                first
+            }
+        })
+        .collect::<Vec<_>>();
+    encode_reals(&reals)
+}
+
+#[cfg(test)]
+mod tests {
+    use common_test::get_seeded_rng;
+
+    use crate::real::{decode_reals, encode_reals};
+
+    use super::blend_crossover;
+
+    #[test]
+    fn test_blend_crossover() {
+        // Given
+        let mut rng = get_seeded_rng().unwrap();
+        let first = encode_reals(&[0.0; 100]);
+        let second = encode_reals(&[1.0; 100]);
+
+        // When
+        let result = decode_reals(&blend_crossover((&first, &second), 0.5, &mut rng));
+
+        // Then
+        assert_eq!(100, result.len());
+        assert!(
+            result.iter().all(|gene| (-0.5..=1.5).contains(gene)),
+            "Should draw genes within the extended parent interval"
+        );
+        assert!(
+            result.iter().any(|gene| !(0.0..=1.0).contains(gene)),
+            "Should explore outside the parent interval"
+        );
+    }
+
+    #[test]
+    fn test_blend_crossover_with_same_genes() {
+        // Given
+        let mut rng = get_seeded_rng().unwrap();
+        let parent = encode_reals(&[3.0, f32::NAN]);
+
+        // When
+        let result = decode_reals(&blend_crossover((&parent, &parent), 0.5, &mut rng));
+
+        // Then
+        assert_eq!(3.0, result[0], "Should keep genes shared by the parents");
+        assert!(result[1].is_nan());
+    }
+}
@@ -0,0 +1,62 @@
+use rand::{seq::index::sample, Rng};
+
+use crate::Genome;
+
+/// Cuts the parents at `k` distinct points and alternates them between cuts, starting with the
+/// first parent.
+///
+/// `k` must be lower than `genome_size`, see [`super::CrossoverType::validate`].
+pub fn k_point_crossover(
+    parents: (&Genome, &Genome),
+    genome_size: usize,
+    k: usize,
+    rng: &mut impl Rng,
+) -> Genome {
+    let mut points = sample(rng, genome_size - 1, k)
+        .into_iter()
+        .map(|point| point + 1)
+        .collect::<Vec<_>>();
+    points.sort_unstable();
+    points.push(genome_size);
+
+    let mut offspring = Vec::with_capacity(genome_size);
+    let mut start = 0;
+    for (i, end) in points.into_iter().enumerate() {
+        let parent = if i % 2 == 0 { parents.0 } else { parents.1 };
+        offspring.extend_from_slice(&parent[start..end]);
+        start = end;
+    }
+    offspring
+}
+
+#[cfg(test)]
+mod tests {
+    use common_test::get_seeded_rng;
+
+    use super::k_point_crossover;
+
+    #[test]
+    fn test_k_point_crossover() {
+        // Given
+        let mut rng = get_seeded_rng().unwrap();
+        let parents = (&vec![0; 16], &vec![1; 16]);
+
+        for k in 1..16 {
+            // When
+            let result = k_point_crossover(parents, 16, k, &mut rng);
+
+            // Then
+            assert_eq!(16, result.len());
+            assert_eq!(
+                Some(&0),
+                result.first(),
+                "Should start with the first parent"
+            );
+            assert_eq!(
+                k,
+                result.windows(2).filter(|pair| pair[0] != pair[1]).count(),
+                "Should switch parent at each of the {k} points"
+            );
+        }
+    }
+}
@@ -0,0 +1,350 @@
+mod arithmetic;
+mod blend;
+mod k_point;
+mod order;
+mod partially_mapped;
+mod single_point;
+mod two_point;
+mod uniform;
+mod uniform_bit;
+
+use std::{fmt::Debug, sync::Arc};
+
+use arithmetic::arithmetic_crossover;
+use blend::blend_crossover;
+use k_point::k_point_crossover;
+use order::order_crossover;
+use partially_mapped::partially_mapped_crossover;
+use rand::{Rng, RngCore};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use single_point::single_point_crossover;
+use thiserror::Error;
+use two_point::two_point_crossover;
+use uniform::uniform_crossover;
+use uniform_bit::uniform_bit_crossover;
+
+use crate::{real::REAL_GENE_SIZE, Genome};
+
+#[derive(Error, Debug, PartialEq)]
+pub enum CrossoverError {
+    #[error("Unable to cut a genome of size {1} at {0} distinct points")]
+    TooManyPoints(usize, usize),
+    #[error("Unable to read real-valued genes from a genome of size {0}")]
+    MisalignedGenome(usize),
+}
+
+/// A recombination scheme that can be plugged into [`CrossoverType::Custom`], e.g. to implement
+/// domain-aware recombination.
+pub trait CrossoverOperator: Debug + Send + Sync {
+    fn crossover(&self, a: &Genome, b: &Genome, rng: &mut dyn RngCore) -> Genome;
+}
+
+/// Operator combining two parent genomes into an offspring.
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum CrossoverType {
+    /// Head of the first parent followed by the tail of the second one.
+    #[default]
+    SinglePoint,
+    /// Segment between two points taken from the second parent, the rest from the first one.
+    TwoPoint,
+    /// Each gene taken from the second parent with the given probability (0.5 for no bias), which
+    /// suits genomes where gene position has no locality.
+    Uniform(f32),
+    /// Each bit taken from the second parent with the given probability, the bitstring
+    /// counterpart of [`CrossoverType::Uniform`], see [`crate::bits`].
+    UniformBit(f32),
+    /// Parents alternated between the given count of distinct cut points.
+    KPoint(usize),
+    /// Order crossover (OX), keeping genomes encoding an ordering valid permutations.
+    Order,
+    /// Partially mapped crossover (PMX), keeping genomes encoding an ordering valid permutations
+    /// while preserving more absolute positions than [`CrossoverType::Order`].
+    PartiallyMapped,
+    /// Blend crossover (BLX-α) of real-valued genomes with the given α, see [`crate::real`].
+    Blend(f32),
+    /// Weighted average of real-valued genomes, see [`crate::real`].
+    Arithmetic,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    Custom(Arc<dyn CrossoverOperator>),
+}
+
+impl PartialEq for CrossoverType {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::SinglePoint, Self::SinglePoint)
+            | (Self::TwoPoint, Self::TwoPoint)
+            | (Self::Order, Self::Order)
+            | (Self::PartiallyMapped, Self::PartiallyMapped)
+            | (Self::Arithmetic, Self::Arithmetic) => true,
+            (Self::Uniform(a), Self::Uniform(b))
+            | (Self::UniformBit(a), Self::UniformBit(b))
+            | (Self::Blend(a), Self::Blend(b)) => a == b,
+            (Self::KPoint(a), Self::KPoint(b)) => a == b,
+            (Self::Custom(a), Self::Custom(b)) => Arc::ptr_eq(a, b),
+            _ => false,
+        }
+    }
+}
+
+impl CrossoverType {
+    /// Checks that the operator can be applied to genomes of the given size.
+    pub fn validate(&self, genome_size: usize) -> Result<(), CrossoverError> {
+        match self {
+            CrossoverType::KPoint(k) if *k >= genome_size => {
+                Err(CrossoverError::TooManyPoints(*k, genome_size))
+            }
+            CrossoverType::Blend(_) | CrossoverType::Arithmetic
+                if genome_size.rem_euclid(REAL_GENE_SIZE) != 0 =>
+            {
+                Err(CrossoverError::MisalignedGenome(genome_size))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+pub fn crossover(
+    parents: (&Genome, &Genome),
+    genome_size: usize,
+    crossover_type: &CrossoverType,
+    rng: &mut impl Rng,
+) -> Genome {
+    match crossover_type {
+        CrossoverType::SinglePoint => single_point_crossover(parents, genome_size, rng),
+        CrossoverType::TwoPoint => two_point_crossover(parents, genome_size, rng),
+        CrossoverType::Uniform(bias) => uniform_crossover(parents, *bias, rng),
+        CrossoverType::UniformBit(bias) => uniform_bit_crossover(parents, *bias, rng),
+        CrossoverType::KPoint(k) => k_point_crossover(parents, genome_size, *k, rng),
+        CrossoverType::Order => order_crossover(parents, genome_size, rng),
+        CrossoverType::PartiallyMapped => partially_mapped_crossover(parents, genome_size, rng),
+        CrossoverType::Blend(alpha) => blend_crossover(parents, *alpha, rng),
+        CrossoverType::Arithmetic => arithmetic_crossover(parents, rng),
+        CrossoverType::Custom(operator) => operator.crossover(parents.0, parents.1, rng),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use common_test::get_seeded_rng;
+    use rand::RngCore;
+
+    use crate::Genome;
+
+    use super::{
+        arithmetic::arithmetic_crossover, blend::blend_crossover, crossover,
+        k_point::k_point_crossover, order::order_crossover,
+        partially_mapped::partially_mapped_crossover, single_point::single_point_crossover,
+        two_point::two_point_crossover, uniform::uniform_crossover,
+        uniform_bit::uniform_bit_crossover, CrossoverError, CrossoverOperator, CrossoverType,
+    };
+
+    /// Interleaves the genes of the parents.
+    #[derive(Debug)]
+    struct Interleave;
+
+    impl CrossoverOperator for Interleave {
+        fn crossover(&self, a: &Genome, b: &Genome, _: &mut dyn RngCore) -> Genome {
+            a.iter()
+                .zip(b)
+                .enumerate()
+                .map(|(i, (&a, &b))| if i % 2 == 0 { a } else { b })
+                .collect()
+        }
+    }
+
+    #[test]
+    fn test_crossover_custom() {
+        // Given
+        let crossover_type = CrossoverType::Custom(Arc::new(Interleave));
+        let mut rng = get_seeded_rng().unwrap();
+
+        // When
+        let result = crossover((&vec![0; 4], &vec![1; 4]), 4, &crossover_type, &mut rng);
+
+        // Then
+        assert_eq!(
+            vec![0, 1, 0, 1],
+            result,
+            "Should use the custom operator to recombine"
+        );
+    }
+
+    #[test]
+    fn test_crossover_type_eq() {
+        let operator: Arc<dyn CrossoverOperator> = Arc::new(Interleave);
+
+        assert_eq!(CrossoverType::KPoint(3), CrossoverType::KPoint(3));
+        assert_ne!(CrossoverType::Uniform(0.5), CrossoverType::Uniform(0.4));
+        assert_eq!(
+            CrossoverType::Custom(operator.clone()),
+            CrossoverType::Custom(operator),
+            "Should compare custom operators by identity"
+        );
+        assert_ne!(
+            CrossoverType::Custom(Arc::new(Interleave)),
+            CrossoverType::Custom(Arc::new(Interleave))
+        );
+    }
+
+    #[test]
+    fn test_crossover() {
+        // Given
+        let parents = (&vec![0; 8], &vec![1; 8]);
+
+        // When
+        let result = crossover(
+            parents,
+            8,
+            &CrossoverType::SinglePoint,
+            &mut get_seeded_rng().unwrap(),
+        );
+
+        // Then
+        assert_eq!(
+            single_point_crossover(parents, 8, &mut get_seeded_rng().unwrap()),
+            result,
+            "Should use single_point_crossover to match crossover_type"
+        );
+
+        // When
+        let result = crossover(
+            parents,
+            8,
+            &CrossoverType::TwoPoint,
+            &mut get_seeded_rng().unwrap(),
+        );
+
+        // Then
+        assert_eq!(
+            two_point_crossover(parents, 8, &mut get_seeded_rng().unwrap()),
+            result,
+            "Should use two_point_crossover to match crossover_type"
+        );
+
+        // When
+        let result = crossover(
+            parents,
+            8,
+            &CrossoverType::Uniform(0.3),
+            &mut get_seeded_rng().unwrap(),
+        );
+
+        // Then
+        assert_eq!(
+            uniform_crossover(parents, 0.3, &mut get_seeded_rng().unwrap()),
+            result,
+            "Should use uniform_crossover to match crossover_type"
+        );
+
+        // When
+        let result = crossover(
+            parents,
+            8,
+            &CrossoverType::UniformBit(0.3),
+            &mut get_seeded_rng().unwrap(),
+        );
+
+        // Then
+        assert_eq!(
+            uniform_bit_crossover(parents, 0.3, &mut get_seeded_rng().unwrap()),
+            result,
+            "Should use uniform_bit_crossover to match crossover_type"
+        );
+
+        // When
+        let result = crossover(
+            parents,
+            8,
+            &CrossoverType::KPoint(3),
+            &mut get_seeded_rng().unwrap(),
+        );
+
+        // Then
+        assert_eq!(
+            k_point_crossover(parents, 8, 3, &mut get_seeded_rng().unwrap()),
+            result,
+            "Should use k_point_crossover to match crossover_type"
+        );
+
+        // When
+        let result = crossover(
+            parents,
+            8,
+            &CrossoverType::Order,
+            &mut get_seeded_rng().unwrap(),
+        );
+
+        // Then
+        assert_eq!(
+            order_crossover(parents, 8, &mut get_seeded_rng().unwrap()),
+            result,
+            "Should use order_crossover to match crossover_type"
+        );
+
+        // When
+        let result = crossover(
+            parents,
+            8,
+            &CrossoverType::PartiallyMapped,
+            &mut get_seeded_rng().unwrap(),
+        );
+
+        // Then
+        assert_eq!(
+            partially_mapped_crossover(parents, 8, &mut get_seeded_rng().unwrap()),
+            result,
+            "Should use partially_mapped_crossover to match crossover_type"
+        );
+
+        // When
+        let result = crossover(
+            parents,
+            8,
+            &CrossoverType::Blend(0.5),
+            &mut get_seeded_rng().unwrap(),
+        );
+
+        // Then
+        assert_eq!(
+            blend_crossover(parents, 0.5, &mut get_seeded_rng().unwrap()),
+            result,
+            "Should use blend_crossover to match crossover_type"
+        );
+
+        // When
+        let result = crossover(
+            parents,
+            8,
+            &CrossoverType::Arithmetic,
+            &mut get_seeded_rng().unwrap(),
+        );
+
+        // Then
+        assert_eq!(
+            arithmetic_crossover(parents, &mut get_seeded_rng().unwrap()),
+            result,
+            "Should use arithmetic_crossover to match crossover_type"
+        );
+    }
+
+    #[test]
+    fn test_crossover_type_validate() {
+        assert!(CrossoverType::SinglePoint.validate(4).is_ok());
+        assert!(CrossoverType::KPoint(3).validate(4).is_ok());
+        assert_eq!(
+            Err(CrossoverError::TooManyPoints(4, 4)),
+            CrossoverType::KPoint(4).validate(4),
+            "Should reject more points than genome cuts"
+        );
+        assert!(CrossoverType::Blend(0.5).validate(8).is_ok());
+        assert_eq!(
+            Err(CrossoverError::MisalignedGenome(6)),
+            CrossoverType::Arithmetic.validate(6),
+            "Should reject genomes not made of whole real-valued genes"
+        );
+    }
+}
@@ -0,0 +1,112 @@
+use rand::Rng;
+
+use crate::Genome;
+
+/// Order crossover (OX): keeps a random segment of the first parent in place and fills the other
+/// loci with the remaining genes in the order they appear in the second parent, starting after
+/// the segment.
+///
+/// Offsprings of two permutations of the same genes are permutations as well.
+pub fn order_crossover(
+    parents: (&Genome, &Genome),
+    genome_size: usize,
+    rng: &mut impl Rng,
+) -> Genome {
+    let first_point = rng.gen_range(0..=genome_size);
+    let second_point = rng.gen_range(0..=genome_size);
+    let (start, end) = if first_point <= second_point {
+        (first_point, second_point)
+    } else {
+        (second_point, first_point)
+    };
+    order_crossover_between(parents, start, end)
+}
+
+fn order_crossover_between(parents: (&Genome, &Genome), start: usize, end: usize) -> Genome {
+    let genome_size = parents.0.len();
+    // Occurrences of each gene already provided by the segment
+    let mut kept = [0usize; u8::MAX as usize + 1];
+    for &gene in &parents.0[start..end] {
+        kept[gene as usize] += 1;
+    }
+
+    let mut remaining = parents.1[end..]
+        .iter()
+        .chain(&parents.1[..end])
+        .filter(|&&gene| {
+            let is_kept = kept[gene as usize] > 0;
+            if is_kept {
+                kept[gene as usize] -= 1;
+            }
+            !is_kept
+        });
+
+    let mut offspring = parents.0.clone();
+    for locus in (end..genome_size).chain(0..start) {
+        if let Some(&gene) = remaining.next() {
+            offspring[locus] = gene;
+        }
+    }
+    offspring
+}
+
+#[cfg(test)]
+mod tests {
+    use common_test::get_seeded_rng;
+    use rand::seq::SliceRandom;
+
+    use super::{order_crossover, order_crossover_between};
+
+    #[test]
+    fn test_order_crossover_between() {
+        // Given
+        let parents = (&vec![1, 2, 3, 4, 5, 6, 7, 8], &vec![8, 6, 4, 2, 7, 5, 3, 1]);
+
+        // When
+        let result = order_crossover_between(parents, 2, 5);
+
+        // Then
+        assert_eq!(
+            vec![2, 7, 3, 4, 5, 1, 8, 6],
+            result,
+            "Should keep the segment and fill from the second parent after it"
+        );
+    }
+
+    #[test]
+    fn test_order_crossover_should_keep_permutations() {
+        // Given
+        let mut rng = get_seeded_rng().unwrap();
+        let mut first = (0..32).collect::<Vec<u8>>();
+        let mut second = first.clone();
+
+        for _ in 0..32 {
+            first.shuffle(&mut rng);
+            second.shuffle(&mut rng);
+
+            // When
+            let mut result = order_crossover((&first, &second), 32, &mut rng);
+
+            // Then
+            result.sort_unstable();
+            assert_eq!(
+                (0..32).collect::<Vec<u8>>(),
+                result,
+                "Should produce a permutation"
+            );
+        }
+    }
+
+    #[test]
+    fn test_order_crossover_should_handle_repeated_genes() {
+        // Given
+        let parents = (&vec![1, 1, 2, 2], &vec![2, 1, 2, 1]);
+
+        // When
+        let mut result = order_crossover_between(parents, 1, 2);
+
+        // Then
+        result.sort_unstable();
+        assert_eq!(vec![1, 1, 2, 2], result, "Should keep gene multiplicities");
+    }
+}
@@ -0,0 +1,111 @@
+use rand::Rng;
+
+use crate::Genome;
+
+/// Partially mapped crossover (PMX): keeps a random segment of the first parent in place and takes
+/// the other loci from the second parent, replacing the genes already provided by the segment
+/// through the mapping between the segments of both parents.
+///
+/// Offsprings of two permutations of the same genes are permutations as well.
+pub fn partially_mapped_crossover(
+    parents: (&Genome, &Genome),
+    genome_size: usize,
+    rng: &mut impl Rng,
+) -> Genome {
+    let first_point = rng.gen_range(0..=genome_size);
+    let second_point = rng.gen_range(0..=genome_size);
+    let (start, end) = if first_point <= second_point {
+        (first_point, second_point)
+    } else {
+        (second_point, first_point)
+    };
+    partially_mapped_crossover_between(parents, start, end)
+}
+
+fn partially_mapped_crossover_between(
+    parents: (&Genome, &Genome),
+    start: usize,
+    end: usize,
+) -> Genome {
+    // Locus of each gene within the segment of the first parent
+    let mut segment_locus = [None; u8::MAX as usize + 1];
+    for locus in start..end {
+        segment_locus[parents.0[locus] as usize] = Some(locus);
+    }
+
+    let mut offspring = parents.1.clone();
+    offspring[start..end].copy_from_slice(&parents.0[start..end]);
+    for locus in (0..start).chain(end..parents.1.len()) {
+        let mut gene = parents.1[locus];
+        // Mapping chains are at most as long as the segment for permutations, the bound only
+        // guards genomes with repeated genes
+        for _ in start..end {
+            match segment_locus[gene as usize] {
+                Some(mapped) => gene = parents.1[mapped],
+                None => break,
+            }
+        }
+        offspring[locus] = gene;
+    }
+    offspring
+}
+
+#[cfg(test)]
+mod tests {
+    use common_test::get_seeded_rng;
+    use rand::seq::SliceRandom;
+
+    use super::{partially_mapped_crossover, partially_mapped_crossover_between};
+
+    #[test]
+    fn test_partially_mapped_crossover_between() {
+        // Given
+        let parents = (&vec![1, 2, 3, 4, 5, 6, 7, 8], &vec![3, 7, 5, 1, 6, 8, 2, 4]);
+
+        // When
+        let result = partially_mapped_crossover_between(parents, 3, 6);
+
+        // Then
+        assert_eq!(
+            vec![3, 7, 8, 4, 5, 6, 2, 1],
+            result,
+            "Should keep the segment and map the conflicting genes of the second parent"
+        );
+    }
+
+    #[test]
+    fn test_partially_mapped_crossover_should_keep_permutations() {
+        // Given
+        let mut rng = get_seeded_rng().unwrap();
+        let mut first = (0..32).collect::<Vec<u8>>();
+        let mut second = first.clone();
+
+        for _ in 0..32 {
+            first.shuffle(&mut rng);
+            second.shuffle(&mut rng);
+
+            // When
+            let mut result = partially_mapped_crossover((&first, &second), 32, &mut rng);
+
+            // Then
+            result.sort_unstable();
+            assert_eq!(
+                (0..32).collect::<Vec<u8>>(),
+                result,
+                "Should produce a permutation"
+            );
+        }
+    }
+
+    #[test]
+    fn test_partially_mapped_crossover_should_terminate_with_repeated_genes() {
+        // Given
+        let parents = (&vec![1, 2, 2, 1], &vec![2, 1, 1, 2]);
+
+        // When
+        let result = partially_mapped_crossover_between(parents, 1, 3);
+
+        // Then
+        assert_eq!(vec![2, 2], result[1..3], "Should keep the segment");
+    }
+}
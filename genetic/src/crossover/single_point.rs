@@ -0,0 +1,37 @@
+use rand::Rng;
+
+use crate::Genome;
+
+pub fn single_point_crossover(
+    parents: (&Genome, &Genome),
+    genome_size: usize,
+    rng: &mut impl Rng,
+) -> Genome {
+    let crossover_point = rng.gen_range(0..genome_size);
+    [&parents.0[..crossover_point], &parents.1[crossover_point..]].concat()
+}
+
+#[cfg(test)]
+mod tests {
+    use common_test::get_seeded_rng;
+
+    use super::single_point_crossover;
+
+    #[test]
+    fn test_single_point_crossover() {
+        // Given
+        let mut rng = get_seeded_rng().unwrap();
+        let parents = (&vec![0; 16], &vec![1; 16]);
+
+        // When
+        let result = single_point_crossover(parents, 16, &mut rng);
+
+        // Then
+        assert_eq!(16, result.len());
+        let point = result.iter().position(|&g| g == 1).unwrap();
+        assert!(
+            result[point..].iter().all(|&g| g == 1),
+            "Should take the tail from the second parent"
+        );
+    }
+}
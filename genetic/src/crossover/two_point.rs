@@ -0,0 +1,53 @@
+use rand::Rng;
+
+use crate::Genome;
+
+pub fn two_point_crossover(
+    parents: (&Genome, &Genome),
+    genome_size: usize,
+    rng: &mut impl Rng,
+) -> Genome {
+    let first_point = rng.gen_range(0..genome_size);
+    let second_point = rng.gen_range(0..genome_size);
+    let (start, end) = if first_point <= second_point {
+        (first_point, second_point)
+    } else {
+        (second_point, first_point)
+    };
+    [
+        &parents.0[..start],
+        &parents.1[start..end],
+        &parents.0[end..],
+    ]
+    .concat()
+}
+
+#[cfg(test)]
+mod tests {
+    use common_test::get_seeded_rng;
+
+    use super::two_point_crossover;
+
+    #[test]
+    fn test_two_point_crossover() {
+        // Given
+        let mut rng = get_seeded_rng().unwrap();
+        let parents = (&vec![0; 16], &vec![1; 16]);
+
+        for _ in 0..32 {
+            // When
+            let result = two_point_crossover(parents, 16, &mut rng);
+
+            // Then
+            assert_eq!(16, result.len());
+            let second_parent_loci = (0..16).filter(|&i| result[i] == 1).collect::<Vec<_>>();
+            assert!(
+                second_parent_loci
+                    .windows(2)
+                    .all(|pair| pair[1] == pair[0] + 1),
+                "Should take a single segment from the second parent"
+            );
+            assert_eq!(Some(&0), result.last(), "Should end with the first parent");
+        }
+    }
+}
@@ -0,0 +1,59 @@
+use rand::Rng;
+
+use crate::Genome;
+
+/// Takes each gene from the second parent with probability `bias`, from the first one otherwise.
+pub fn uniform_crossover(parents: (&Genome, &Genome), bias: f32, rng: &mut impl Rng) -> Genome {
+    parents
+        .0
+        .iter()
+        .zip(parents.1)
+        .map(|(&first, &second)| {
+            if rng.gen_bool(bias as f64) {
+                second
+            } else {
+                first
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use common_test::get_seeded_rng;
+
+    use super::uniform_crossover;
+
+    #[test]
+    fn test_uniform_crossover() {
+        // Given
+        let mut rng = get_seeded_rng().unwrap();
+        let parents = (&vec![0; 1000], &vec![1; 1000]);
+
+        // When
+        let result = uniform_crossover(parents, 0.5, &mut rng);
+
+        // Then
+        assert_eq!(1000, result.len());
+        let second_parent_count = result.iter().filter(|&&g| g == 1).count();
+        assert!(
+            (400..600).contains(&second_parent_count),
+            "Should mix genes from both parents"
+        );
+    }
+
+    #[test]
+    fn test_uniform_crossover_with_bias() {
+        // Given
+        let mut rng = get_seeded_rng().unwrap();
+        let parents = (&vec![0; 8], &vec![1; 8]);
+
+        // Then
+        assert_eq!(vec![0; 8], uniform_crossover(parents, 0.0, &mut rng));
+        assert_eq!(
+            vec![1; 8],
+            uniform_crossover(parents, 1.0, &mut rng),
+            "Should follow the bias"
+        );
+    }
+}
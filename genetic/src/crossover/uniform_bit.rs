@@ -0,0 +1,59 @@
+use rand::Rng;
+
+use crate::Genome;
+
+/// Takes each bit from the second parent with probability `bias`, from the first one otherwise.
+pub fn uniform_bit_crossover(parents: (&Genome, &Genome), bias: f32, rng: &mut impl Rng) -> Genome {
+    parents
+        .0
+        .iter()
+        .zip(parents.1)
+        .map(|(&first, &second)| {
+            let mask = (0..u8::BITS)
+                .filter(|_| rng.gen_bool(bias as f64))
+                .fold(0u8, |mask, bit| mask | 1 << bit);
+            first & !mask | second & mask
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use common_test::get_seeded_rng;
+
+    use crate::bits::popcount;
+
+    use super::uniform_bit_crossover;
+
+    #[test]
+    fn test_uniform_bit_crossover() {
+        // Given
+        let mut rng = get_seeded_rng().unwrap();
+        let parents = (&vec![0; 125], &vec![0xff; 125]);
+
+        // When
+        let result = uniform_bit_crossover(parents, 0.5, &mut rng);
+
+        // Then
+        assert_eq!(125, result.len());
+        assert!(
+            (400..600).contains(&popcount(&result)),
+            "Should mix bits from both parents"
+        );
+    }
+
+    #[test]
+    fn test_uniform_bit_crossover_with_bias() {
+        // Given
+        let mut rng = get_seeded_rng().unwrap();
+        let parents = (&vec![0b1010_1010; 4], &vec![0b0101_0101; 4]);
+
+        // Then
+        assert_eq!(*parents.0, uniform_bit_crossover(parents, 0.0, &mut rng));
+        assert_eq!(
+            *parents.1,
+            uniform_bit_crossover(parents, 1.0, &mut rng),
+            "Should follow the bias"
+        );
+    }
+}
@@ -0,0 +1,166 @@
+use std::collections::HashSet;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{analysis::AlleleFrequencies, evolution::Snapshot};
+
+/// Diversity of the genomes of a population, see
+/// [`crate::evolution::EvolutionEngineBuilder::measure_diversity`].
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Diversity {
+    /// Mean number of loci whose genes differ between two genomes over all the pairs, genes
+    /// missing from the shorter genome included.
+    pub mean_hamming_distance: f32,
+    /// Shannon entropy in bits of the alleles found at each locus, see
+    /// [`AlleleFrequencies::entropy`].
+    pub gene_entropies: Vec<f32>,
+    /// Mean of the entropies of the loci.
+    pub mean_entropy: f32,
+    /// Number of distinct genomes.
+    pub unique_count: usize,
+    pub population_size: usize,
+}
+
+impl Diversity {
+    /// Computes the diversity of the genomes in `O(population size × genome size)`, the pairs
+    /// being counted from the allele frequencies of each locus.
+    pub fn compute(genomes: &[&[u8]]) -> Self {
+        let population_size = genomes.len();
+        let loci_count = genomes
+            .iter()
+            .map(|genome| genome.len())
+            .max()
+            .unwrap_or_default();
+        let mut frequencies = vec![AlleleFrequencies::default(); loci_count];
+        for genome in genomes {
+            for (locus, &allele) in genome.iter().enumerate() {
+                frequencies[locus].add(allele);
+            }
+        }
+
+        let pairs = |count: u64| count * count.saturating_sub(1) / 2;
+        let differing_pairs = frequencies
+            .iter()
+            .map(|frequencies| {
+                let covering = frequencies.total() as u64;
+                let matching = (0..=u8::MAX)
+                    .map(|allele| pairs(frequencies.count(allele) as u64))
+                    .sum::<u64>();
+                pairs(covering) - matching + covering * (population_size as u64 - covering)
+            })
+            .sum::<u64>();
+        let mean_hamming_distance = match pairs(population_size as u64) {
+            0 => 0.0,
+            count => differing_pairs as f32 / count as f32,
+        };
+
+        let gene_entropies = frequencies
+            .iter()
+            .map(AlleleFrequencies::entropy)
+            .collect::<Vec<_>>();
+        let mean_entropy = if gene_entropies.is_empty() {
+            0.0
+        } else {
+            gene_entropies.iter().sum::<f32>() / gene_entropies.len() as f32
+        };
+
+        Diversity {
+            mean_hamming_distance,
+            gene_entropies,
+            mean_entropy,
+            unique_count: genomes.iter().collect::<HashSet<_>>().len(),
+            population_size,
+        }
+    }
+
+    /// Computes the diversity of the population of the snapshot, leaving out the tail reserved
+    /// for the mutation operators according to its [`Snapshot::parameters`].
+    pub fn of_snapshot(snapshot: &Snapshot) -> Self {
+        let reserved_size = snapshot
+            .parameters
+            .as_ref()
+            .map_or(0, |parameters| parameters.reserved_size);
+        let genomes = snapshot
+            .evaluations
+            .iter()
+            .map(|e| &e.genome[..e.genome.len().saturating_sub(reserved_size)])
+            .collect::<Vec<_>>();
+        Self::compute(&genomes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        evolution::{EvolutionConfig, RunParameters, Snapshot},
+        Evaluation,
+    };
+
+    use super::Diversity;
+
+    #[test]
+    fn test_diversity_compute() {
+        // Given
+        let genomes: [&[u8]; 4] = [&[1, 2, 3], &[1, 2, 3], &[1, 4, 3], &[1, 5]];
+
+        // When
+        let result = Diversity::compute(&genomes);
+
+        // Then
+        assert_eq!(
+            8.0 / 6.0,
+            result.mean_hamming_distance,
+            "Should count the genes missing from the shorter genomes as differing"
+        );
+        assert_eq!(3, result.gene_entropies.len());
+        assert_eq!(0.0, result.gene_entropies[0]);
+        assert_eq!(1.5, result.gene_entropies[1]);
+        assert_eq!(0.5, result.mean_entropy);
+        assert_eq!((3, 4), (result.unique_count, result.population_size));
+    }
+
+    #[test]
+    fn test_diversity_compute_should_be_null_without_pairs() {
+        assert_eq!(Diversity::default(), Diversity::compute(&[]));
+        let result = Diversity::compute(&[&[1, 2]]);
+        assert_eq!(0.0, result.mean_hamming_distance);
+        assert_eq!(0.0, result.mean_entropy);
+        assert_eq!(1, result.unique_count);
+    }
+
+    #[test]
+    fn test_diversity_of_snapshot() {
+        // Given
+        let snapshot = Snapshot {
+            evaluations: vec![
+                Evaluation::from(vec![1, 2, 50]),
+                Evaluation::from(vec![1, 2, 60]),
+            ],
+            parameters: Some(RunParameters {
+                seed: None,
+                config: EvolutionConfig {
+                    population_size: 2,
+                    generation_renewal_config: None,
+                    max_duration: None,
+                },
+                cloning_count: 0,
+                crossover_count: 0,
+                randoms_count: 2,
+                reserved_size: 1,
+            }),
+            ..Default::default()
+        };
+
+        // When
+        let result = Diversity::of_snapshot(&snapshot);
+
+        // Then
+        assert_eq!(
+            0.0, result.mean_hamming_distance,
+            "Should leave the reserved tail out"
+        );
+        assert_eq!(1, result.unique_count);
+    }
+}
@@ -0,0 +1,252 @@
+use common::subject_observer::Subject;
+use log::{debug, warn};
+use rand::Rng;
+use uuid::Uuid;
+
+use crate::{diversity::Diversity, Evaluation};
+
+use super::{
+    generation_dump::{GenerationDump, Origin},
+    generation_renewal::ExecutionSettings,
+    EventType, EvolutionEngine, EvolutionError, Snapshot,
+};
+#[cfg(feature = "checkpoint")]
+use super::{Checkpoint, EvolutionConfig};
+
+impl EvolutionEngine {
+    /// Records the evaluated generation in its lineage, the hall of fame and the trackers of the
+    /// best fitness, returning whether the run restarts on its stagnation.
+    pub(super) fn record_evaluated(&mut self, fitnesses: &[f32]) -> bool {
+        if let Some(lineage) = self.snapshot.lineage.as_mut() {
+            lineage.evaluate(&self.snapshot.evaluations);
+        }
+
+        if self.hall_of_fame_size > 0 {
+            update_hall_of_fame(
+                &mut self.snapshot.hall_of_fame,
+                &self.snapshot.evaluations,
+                self.hall_of_fame_size,
+            );
+        }
+
+        if let Some(max) = self.snapshot.stats.map(|stats| stats.max) {
+            if !self.best_fitness.is_some_and(|best| best >= max) {
+                self.best_fitness = Some(max);
+                self.notify_observers(EventType::BestImproved {
+                    fitness: max,
+                    generation: self.snapshot.generation,
+                });
+            }
+        }
+
+        let mut restart = false;
+        if let Some(stagnation) = self.stagnation.as_mut() {
+            let generations = stagnation.generations();
+            if stagnation.update(fitnesses) == generations {
+                debug!("Stagnation over {} generations", generations);
+                restart = self.restart_policy.is_some_and(|policy| {
+                    policy
+                        .max_restarts
+                        .map_or(true, |max_restarts| self.restarts < max_restarts)
+                });
+                self.notify_observers(EventType::StagnationDetected);
+            }
+        }
+        restart
+    }
+
+    /// Diversity of the genomes of the current generation, see
+    /// [`super::EvolutionEngineBuilder::measure_diversity`].
+    pub(super) fn diversity(&self, genome_size: usize) -> Option<Diversity> {
+        self.diversity_measuring.then(|| {
+            let genomes = self
+                .snapshot
+                .evaluations
+                .iter()
+                .map(|e| &e.genome[..genome_size.min(e.genome.len())])
+                .collect::<Vec<_>>();
+            Diversity::compute(&genomes)
+        })
+    }
+
+    #[cfg(feature = "checkpoint")]
+    pub(super) fn save_checkpoint(&self, config: &EvolutionConfig, rng: &mut impl Rng) {
+        let Some((_, path)) = self.checkpoint.as_ref() else {
+            return;
+        };
+
+        // Record the population size of the snapshot, grown by the restarts
+        let checkpoint = Checkpoint {
+            snapshot: self.snapshot.clone(),
+            config: EvolutionConfig {
+                population_size: self.snapshot.evaluations.len(),
+                ..config.clone()
+            },
+            seed: rng.gen(),
+        };
+        match checkpoint.save(path) {
+            Ok(()) => debug!(
+                "Saved generation {} to {}",
+                self.snapshot.generation,
+                path.display()
+            ),
+            Err(e) => warn!(
+                "Unable to save generation {} to {}: {e}",
+                self.snapshot.generation,
+                path.display()
+            ),
+        }
+    }
+
+    pub(super) fn dump_renewal(
+        &self,
+        settings: &ExecutionSettings,
+        evaluations: &[Evaluation],
+        origins: &[Origin],
+    ) {
+        let Some((_, path)) = self
+            .generation_dump
+            .as_ref()
+            .filter(|(generation, _)| *generation == self.snapshot.generation)
+        else {
+            return;
+        };
+
+        let dump = GenerationDump {
+            generation: self.snapshot.generation,
+            cloning_pool: &settings.cloning_pool,
+            crossover_pool: &settings.crossover_pool,
+            before: &self.snapshot.evaluations,
+            after: evaluations,
+            origins,
+        };
+        match dump.write_to_file(path) {
+            Ok(()) => debug!(
+                "Dumped generation {} to {}",
+                self.snapshot.generation,
+                path.display()
+            ),
+            Err(e) => warn!(
+                "Unable to dump generation {} to {}: {e}",
+                self.snapshot.generation,
+                path.display()
+            ),
+        }
+    }
+}
+
+/// Merges the evaluated individuals into the hall of fame, sorted by decreasing fitness, keeping
+/// the first individual evaluated among those sharing a genome.
+fn update_hall_of_fame(
+    hall_of_fame: &mut Vec<Evaluation>,
+    evaluations: &[Evaluation],
+    size: usize,
+) {
+    for evaluation in evaluations.iter().filter(|e| e.evaluated) {
+        let fits = hall_of_fame.len() < size
+            || hall_of_fame
+                .last()
+                .is_some_and(|last| last.fitness < evaluation.fitness);
+        if fits && !hall_of_fame.iter().any(|e| e.genome == evaluation.genome) {
+            let index = hall_of_fame.partition_point(|e| e.fitness >= evaluation.fitness);
+            hall_of_fame.insert(index, evaluation.clone());
+            hall_of_fame.truncate(size);
+        }
+    }
+}
+
+/// Checks that a snapshot to resume from matches the population size of the configuration and
+/// the genome size of the strategy, with or without the reserved tail.
+pub(super) fn check_snapshot(
+    snapshot: &Snapshot,
+    genome_size: usize,
+    reserved_size: usize,
+    population_size: usize,
+) -> Result<(), EvolutionError> {
+    if snapshot.evaluations.len() != population_size {
+        return Err(EvolutionError::IncompatibleSnapshot(format!(
+            "{} individuals while the population size is {}",
+            snapshot.evaluations.len(),
+            population_size
+        )));
+    }
+    let sizes = [genome_size, genome_size + reserved_size];
+    if let Some((index, evaluation)) = snapshot
+        .evaluations
+        .iter()
+        .enumerate()
+        .find(|(_, e)| !sizes.contains(&e.genome.len()))
+    {
+        let expected = if reserved_size > 0 {
+            format!("{} or {}", genome_size, genome_size + reserved_size)
+        } else {
+            genome_size.to_string()
+        };
+        return Err(EvolutionError::IncompatibleSnapshot(format!(
+            "genome {} has {} genes while {} are expected",
+            index,
+            evaluation.genome.len(),
+            expected
+        )));
+    }
+    Ok(())
+}
+
+/// Random id of a run, drawn from the entropy of the system with the `getrandom` feature and
+/// from the generator of the run otherwise, mixed with a count of the runs so that runs of the
+/// same seed are told apart.
+#[cfg(feature = "getrandom")]
+pub(super) fn run_id(_rng: &mut impl Rng) -> Uuid {
+    Uuid::new_v4()
+}
+
+#[cfg(not(feature = "getrandom"))]
+pub(super) fn run_id(rng: &mut impl Rng) -> Uuid {
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    static RUNS: AtomicU64 = AtomicU64::new(0);
+    let count = RUNS.fetch_add(1, Ordering::Relaxed) as u128;
+    uuid::Builder::from_random_bytes((rng.gen::<u128>() ^ count).to_le_bytes()).into_uuid()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Evaluation, Genome};
+
+    use super::update_hall_of_fame;
+
+    #[test]
+    fn test_update_hall_of_fame() {
+        // Given
+        let evaluation = |genome: Genome, fitness| Evaluation {
+            genome,
+            fitness,
+            evaluated: true,
+            ..Default::default()
+        };
+        let mut hall_of_fame = vec![evaluation(vec![1], 0.9), evaluation(vec![2], 0.5)];
+        let evaluations = vec![
+            evaluation(vec![3], 0.7),
+            evaluation(vec![1], 0.9),
+            evaluation(vec![4], 0.2),
+            Evaluation {
+                evaluated: false,
+                ..evaluation(vec![5], 1.0)
+            },
+            evaluation(vec![6], 0.95),
+        ];
+
+        // When
+        update_hall_of_fame(&mut hall_of_fame, &evaluations, 3);
+
+        // Then
+        assert_eq!(
+            vec![vec![6], vec![1], vec![3]],
+            hall_of_fame
+                .iter()
+                .map(|e| e.genome.clone())
+                .collect::<Vec<_>>(),
+            "Should keep the fittest distinct evaluated genomes"
+        );
+    }
+}
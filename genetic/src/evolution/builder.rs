@@ -0,0 +1,652 @@
+use std::{
+    path::PathBuf,
+    sync::{Arc, PoisonError},
+    time::Duration,
+};
+
+use common::subject_observer::{SharedObserver, Subject};
+#[cfg(feature = "getrandom")]
+use rand::{rngs::OsRng, RngCore};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+#[cfg(feature = "validation")]
+use validator::Validate;
+
+#[cfg(feature = "async")]
+use crate::adaptation::AsyncStrategy;
+use crate::{
+    adaptation::{BatchStrategy, Strategy},
+    clock::Clock,
+    local_search::LocalSearch,
+    selection::FitnessScaling,
+    Evaluation,
+};
+
+#[cfg(feature = "async")]
+use super::{challenger::EvaluationTimeout, TimeoutPolicy};
+use super::{
+    challenger::LocalSearchSettings,
+    history::History,
+    hook::{OnGenerationEnd, OnGenerationStart},
+    observer_timing::ObserverBudget,
+    operator_stats::OperatorTracker,
+    speciation::Speciation,
+    AdaptiveSelection, CompatibilityDistance, Deduplication, EventType, EvolutionConfig,
+    EvolutionEngine, EvolutionError, EvolutionResult, GenerationHook, GenerationRenewalConfig,
+    GeneticRenewalParam, HistoryDetail, InitialPopulation, RenewalStage, RestartPolicy, Snapshot,
+    Stagnation, TerminationCriterion,
+};
+
+/// Gathers the settings, observers and random generator of a run, see
+/// [`EvolutionEngine::builder`].
+///
+/// The run owns its random generator of type `R`, by default a [`StdRng`] seeded from the
+/// entropy of the system with the `getrandom` feature, the seed being recorded in
+/// [`super::RunParameters::seed`].
+pub struct EvolutionEngineBuilder<R = StdRng> {
+    config: EvolutionConfig,
+    /// Engine holding the options of the run as they are set.
+    engine: EvolutionEngine,
+    rng: R,
+    seed: Option<u64>,
+}
+
+#[cfg(feature = "getrandom")]
+impl Default for EvolutionEngineBuilder {
+    fn default() -> Self {
+        let seed = OsRng.next_u64();
+        let mut builder = Self::new(StdRng::seed_from_u64(seed));
+        builder.seed = Some(seed);
+        builder
+    }
+}
+
+impl<R> EvolutionEngineBuilder<R> {
+    /// Builder running with the given random generator, whose seed is not recorded, e.g. where
+    /// the entropy of the system is not available.
+    pub fn new(rng: R) -> Self {
+        EvolutionEngineBuilder {
+            config: EvolutionConfig {
+                population_size: 0,
+                generation_renewal_config: None,
+                max_duration: None,
+            },
+            engine: EvolutionEngine::default(),
+            rng,
+            seed: None,
+        }
+    }
+
+    /// Takes the population size, the renewal and the time budget of a configuration, replacing
+    /// the ones set so far.
+    pub fn config(mut self, config: EvolutionConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Number of individuals of each generation, required.
+    pub fn population_size(mut self, population_size: usize) -> Self {
+        self.config.population_size = population_size;
+        self
+    }
+
+    pub fn cloning(mut self, cloning: GeneticRenewalParam) -> Self {
+        self.renewal().cloning = Some(cloning);
+        self
+    }
+
+    pub fn crossover(mut self, crossover: GeneticRenewalParam) -> Self {
+        self.renewal().crossover = Some(crossover);
+        self
+    }
+
+    /// See [`GenerationRenewalConfig::random_ratio`].
+    pub fn random_ratio(mut self, random_ratio: f32) -> Self {
+        self.renewal().random_ratio = Some(random_ratio);
+        self
+    }
+
+    /// See [`EvolutionConfig::max_duration`].
+    pub fn max_duration(mut self, max_duration: Duration) -> Self {
+        self.config.max_duration = Some(max_duration);
+        self
+    }
+
+    /// Replaces the genomes found more than once in each new generation, so that copies do not
+    /// take over small search spaces. Duplicates of the previous generations are not looked for.
+    pub fn deduplicate(mut self, deduplication: Deduplication) -> Self {
+        self.engine.deduplication = Some(deduplication);
+        self
+    }
+
+    /// Evaluates each genome `samples` times, e.g. for strategies with a noisy fitness, keeping
+    /// the mean as its fitness and the variance in [`Evaluation::fitness_variance`]. Each sample
+    /// counts as an evaluation.
+    pub fn resampling(mut self, samples: usize) -> Self {
+        self.engine.resampling = samples.max(1);
+        self
+    }
+
+    /// Limits the number of evaluations awaited at once by [`EvolutionEngine::start_async`], e.g.
+    /// to spare the service evaluating the genomes. All the genomes of a generation are
+    /// evaluated at once by default.
+    #[cfg(feature = "async")]
+    pub fn evaluation_concurrency(mut self, limit: usize) -> Self {
+        self.engine.evaluation_concurrency = Some(limit.max(1));
+        self
+    }
+
+    /// Cancels the evaluations of [`EvolutionEngine::start_async`] lasting more than `timeout`,
+    /// settling them with the policy, so that a pathological genome does not weigh on its
+    /// generation.
+    ///
+    /// Synchronous evaluations cannot be interrupted, so the other runs have no timeout.
+    #[cfg(feature = "async")]
+    pub fn evaluation_timeout(mut self, timeout: Duration, policy: TimeoutPolicy) -> Self {
+        let policy = match policy {
+            TimeoutPolicy::Fitness(fitness) => TimeoutPolicy::Fitness(fitness.clamp(0.0, 1.0)),
+            TimeoutPolicy::Invalid => TimeoutPolicy::Invalid,
+        };
+        self.engine.evaluation_timeout = Some(EvaluationTimeout {
+            duration: timeout,
+            policy,
+        });
+        self
+    }
+
+    /// Selects the clones and the parents on the fitnesses scaled, e.g. to keep the selection
+    /// pressure of weight selections steady as the fitnesses of the population get closer. The
+    /// individuals keep their fitness.
+    pub fn fitness_scaling(mut self, scaling: FitnessScaling) -> Self {
+        self.engine.fitness_scaling = Some(scaling);
+        self
+    }
+
+    /// Refines the `ratio` fittest individuals of each evaluated generation with `iterations`
+    /// rounds of the local search, a fitter neighbour taking the place of its genome, so that
+    /// the evolution becomes a memetic algorithm. Each neighbour counts as an evaluation.
+    pub fn local_search(
+        mut self,
+        search: Box<dyn LocalSearch>,
+        ratio: f32,
+        iterations: usize,
+    ) -> Self {
+        self.engine.local_search = Some(LocalSearchSettings {
+            search,
+            ratio: ratio.clamp(0.0, 1.0),
+            iterations,
+        });
+        self
+    }
+
+    /// Builds each new generation by running the stages in turn, e.g. the
+    /// [`super::default_renewal_stages`] with custom ones inserted, the individuals beyond the
+    /// population size being dropped.
+    pub fn renewal_stages(mut self, stages: Vec<Box<dyn RenewalStage>>) -> Self {
+        self.engine.renewal_stages = stages;
+        self
+    }
+
+    /// Clusters each evaluated generation into species of genomes within `threshold` of the
+    /// `distance`, the parents being selected within their species and each species breeding in
+    /// proportion to the fitness of its members shared by their number, so that a niche is not
+    /// taken over by a fitter one before maturing.
+    pub fn speciate(mut self, distance: Box<dyn CompatibilityDistance>, threshold: f32) -> Self {
+        self.engine.speciation = Some(Speciation::new(distance, threshold));
+        self
+    }
+
+    /// Keeps the `size` fittest distinct individuals evaluated during the run in
+    /// [`Snapshot::hall_of_fame`], so that they are not lost to the renewal of the population.
+    /// Resumed runs carry on with the hall of fame of their snapshot.
+    pub fn hall_of_fame(mut self, size: usize) -> Self {
+        self.engine.hall_of_fame_size = size;
+        self
+    }
+
+    /// Computes the [`Snapshot::diversity`] of each evaluated generation, the tail reserved for
+    /// the mutation operators left out, so that observers and criteria such as
+    /// [`super::MinDiversity`] can follow the convergence of the population.
+    pub fn measure_diversity(mut self) -> Self {
+        self.engine.diversity_measuring = true;
+        self
+    }
+
+    /// Records the parents and the operator of each individual in [`Snapshot::lineage`], the
+    /// snapshot of the current generation holding the genealogy up to it, i.e. the ancestors of
+    /// its individuals. Resumed runs carry on with the lineage of their snapshot.
+    pub fn track_lineage(mut self) -> Self {
+        self.engine.lineage_tracking = true;
+        self
+    }
+
+    /// Assesses the renewal operators on how often their offspring improve on their parents,
+    /// notifying [`EventType::OperatorsAssessed`] once their offspring are evaluated. Given an
+    /// adaptive selection, the individuals renewed by the cloning and crossover pools are split
+    /// according to the success of their operators, the pools left out by the configuration
+    /// staying so.
+    pub fn track_operators(mut self, adaptive: Option<AdaptiveSelection>) -> Self {
+        let adaptive = adaptive.map(|adaptive| AdaptiveSelection {
+            adaptation_rate: adaptive.adaptation_rate.clamp(0.0, 1.0),
+            min_share: adaptive.min_share.clamp(0.0, 1.0),
+        });
+        self.engine.operator_tracker = Some(OperatorTracker::new(adaptive));
+        self
+    }
+
+    /// Keeps a record of the last `capacity` evaluated generations of each run, see
+    /// [`EvolutionEngine::history`]. Recording snapshots clones the population of every
+    /// generation.
+    pub fn record_history(mut self, capacity: usize, detail: HistoryDetail) -> Self {
+        self.engine.history = Some(History::new(capacity, detail));
+        self
+    }
+
+    /// Notifies [`EventType::StagnationDetected`] once the best fitness did not improve by more
+    /// than `epsilon` for `generations` generations, then again after each improvement followed
+    /// by a new plateau. Use [`Stagnation`] as criterion to complete the run instead.
+    pub fn detect_stagnation(mut self, generations: u64, epsilon: f32) -> Self {
+        self.engine.stagnation = Some(Stagnation::new(generations, epsilon));
+        self
+    }
+
+    /// Starts the run over each time [`EventType::StagnationDetected`] is notified, see
+    /// [`EvolutionEngineBuilder::detect_stagnation`]: the next generation keeps the elites of the
+    /// stagnating one and is completed with random genomes, up to its grown size. Unlike a new
+    /// run, the generations, the evaluation count, the hall of fame and the observers carry on.
+    pub fn restart_on_stagnation(mut self, policy: RestartPolicy) -> Self {
+        self.engine.restart_policy = Some(RestartPolicy {
+            elite_ratio: policy.elite_ratio.clamp(0.0, 1.0),
+            population_growth: policy.population_growth.max(0.0),
+            max_restarts: policy.max_restarts,
+        });
+        self
+    }
+
+    /// Starts the runs not resumed from a snapshot with the given genomes, the ones not matching
+    /// the genome size of the strategy being dropped and the population being completed with
+    /// random genomes.
+    pub fn initial_population(mut self, initial_population: InitialPopulation) -> Self {
+        self.engine.initial_population = initial_population;
+        self
+    }
+
+    /// Labels the runs, e.g. with the experiment they belong to, replacing the value of a known
+    /// key, see [`super::RunMetadata::labels`].
+    pub fn label(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.engine.labels.insert(key.into(), value.into());
+        self
+    }
+
+    /// Registers an observer to the engine, in call order.
+    pub fn observer(mut self, observer: SharedObserver<EvolutionEngine, EventType>) -> Self {
+        self.engine.register_observer(observer);
+        self
+    }
+
+    /// Warns about observers whose updates take longer than `budget` on `tolerance` consecutive
+    /// notifications.
+    pub fn observer_budget(mut self, budget: Duration, tolerance: u32) -> Self {
+        self.engine.observer_budget = Some(ObserverBudget {
+            budget,
+            tolerance: tolerance.max(1),
+        });
+        self
+    }
+
+    /// Registers a hook run around each generation with mutable access to its individuals, e.g.
+    /// to transform the fitnesses before the selection, hooks running in call order.
+    pub fn hook(mut self, hook: Box<dyn GenerationHook>) -> Self {
+        self.engine
+            .hooks
+            .get_mut()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push(hook);
+        self
+    }
+
+    /// Registers a closure run once each generation is created, before its evaluation, see
+    /// [`GenerationHook::on_generation_start`].
+    pub fn on_generation_start<F>(self, hook: F) -> Self
+    where
+        F: FnMut(u64, &mut [Evaluation]) + Send + 'static,
+    {
+        self.hook(Box::new(OnGenerationStart(hook)))
+    }
+
+    /// Registers a closure run once each generation is evaluated, before the selection of the
+    /// next one, see [`GenerationHook::on_generation_end`].
+    pub fn on_generation_end<F>(self, hook: F) -> Self
+    where
+        F: FnMut(u64, &mut [Evaluation]) + Send + 'static,
+    {
+        self.hook(Box::new(OnGenerationEnd(hook)))
+    }
+
+    /// Writes the population of the given generation before and after its renewal to a file,
+    /// with the parents of each renewed individual and the operators of the pools.
+    ///
+    /// Meant to reproduce operator issues offline, a failure to write the dump is logged and
+    /// does not interrupt the evolution. Generations completing the run are not renewed, thus
+    /// not dumped.
+    pub fn dump_generation(mut self, generation: u64, path: impl Into<PathBuf>) -> Self {
+        self.engine.generation_dump = Some((generation, path.into()));
+        self
+    }
+
+    /// Saves the run to a file every `every` generations and when it is halted, before the
+    /// evaluation of the generation, see [`EvolutionEngine::resume_from_checkpoint`].
+    ///
+    /// Each checkpoint draws the seed of its random generator from the one of the run. A failure
+    /// to write the checkpoint is logged and does not interrupt the evolution.
+    #[cfg(feature = "checkpoint")]
+    pub fn checkpoint(mut self, every: u64, path: impl Into<PathBuf>) -> Self {
+        self.engine.checkpoint = Some((every.max(1), path.into()));
+        self
+    }
+
+    /// Reads the time from the given clock, e.g. a [`crate::clock::MockClock`] to test time
+    /// dependent behaviors.
+    pub fn clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.engine.clock = clock;
+        self
+    }
+
+    /// Runs with a [`StdRng`] seeded with `seed`, recorded to reproduce the run.
+    pub fn seed(self, seed: u64) -> EvolutionEngineBuilder<StdRng> {
+        let mut builder = self.rng(StdRng::seed_from_u64(seed));
+        builder.seed = Some(seed);
+        builder
+    }
+
+    /// Runs with the given random generator, whose seed is not recorded.
+    pub fn rng<S: Rng>(self, rng: S) -> EvolutionEngineBuilder<S> {
+        EvolutionEngineBuilder {
+            config: self.config,
+            engine: self.engine,
+            rng,
+            seed: None,
+        }
+    }
+
+    fn renewal(&mut self) -> &mut GenerationRenewalConfig {
+        self.config
+            .generation_renewal_config
+            .get_or_insert(GenerationRenewalConfig {
+                cloning: None,
+                crossover: None,
+                random_ratio: None,
+            })
+    }
+}
+
+impl<R: Rng> EvolutionEngineBuilder<R> {
+    /// Validates the settings and creates the engine with its observers registered.
+    ///
+    /// Operators depending on the genome size of the strategy are checked when the run starts.
+    pub fn build(self) -> Result<EvolutionRun<R>, EvolutionError> {
+        #[cfg(feature = "validation")]
+        self.config.validate()?;
+
+        let mut engine = self.engine;
+        engine.seed = self.seed;
+        Ok(EvolutionRun {
+            engine,
+            config: self.config,
+            rng: self.rng,
+        })
+    }
+}
+
+/// Engine built with its settings and random generator, ready to run.
+pub struct EvolutionRun<R> {
+    engine: EvolutionEngine,
+    config: EvolutionConfig,
+    rng: R,
+}
+
+impl<R: Rng> EvolutionRun<R> {
+    /// Engine of the run, e.g. to get a handle or annotate it.
+    pub fn engine(&self) -> &EvolutionEngine {
+        &self.engine
+    }
+
+    pub fn engine_mut(&mut self) -> &mut EvolutionEngine {
+        &mut self.engine
+    }
+
+    pub fn config(&self) -> &EvolutionConfig {
+        &self.config
+    }
+
+    /// See [`EvolutionEngine::start`].
+    pub async fn start<T, C>(&mut self, strategy: &T, is_complete: C) -> EvolutionResult
+    where
+        T: Strategy,
+        C: TerminationCriterion,
+    {
+        self.engine
+            .start(strategy, &self.config, is_complete, &mut self.rng)
+            .await
+    }
+
+    /// See [`EvolutionEngine::start_from`].
+    pub async fn start_from<T, C>(
+        &mut self,
+        strategy: &T,
+        is_complete: C,
+        snapshot: Snapshot,
+    ) -> EvolutionResult
+    where
+        T: Strategy,
+        C: TerminationCriterion,
+    {
+        self.engine
+            .start_from(strategy, &self.config, is_complete, &mut self.rng, snapshot)
+            .await
+    }
+
+    /// See [`EvolutionEngine::start_async`].
+    #[cfg(feature = "async")]
+    pub async fn start_async<T, C>(&mut self, strategy: Arc<T>, is_complete: C) -> EvolutionResult
+    where
+        T: AsyncStrategy,
+        C: TerminationCriterion,
+    {
+        self.engine
+            .start_async(strategy, &self.config, is_complete, &mut self.rng)
+            .await
+    }
+
+    /// See [`EvolutionEngine::start_parallel`].
+    #[cfg(feature = "parallel")]
+    pub async fn start_parallel<T, C>(&mut self, strategy: &T, is_complete: C) -> EvolutionResult
+    where
+        T: Strategy + Sync,
+        C: TerminationCriterion,
+    {
+        self.engine
+            .start_parallel(strategy, &self.config, is_complete, &mut self.rng)
+            .await
+    }
+
+    /// See [`EvolutionEngine::start_batch`].
+    pub async fn start_batch<T, C>(&mut self, strategy: &T, is_complete: C) -> EvolutionResult
+    where
+        T: BatchStrategy,
+        C: TerminationCriterion,
+    {
+        self.engine
+            .start_batch(strategy, &self.config, is_complete, &mut self.rng)
+            .await
+    }
+
+    /// See [`EvolutionEngine::prepare`].
+    pub fn prepare<T: Strategy>(
+        &mut self,
+        strategy: &T,
+        snapshot: Option<Snapshot>,
+    ) -> Result<(), EvolutionError> {
+        self.engine
+            .prepare(strategy, &self.config, &mut self.rng, snapshot)
+    }
+
+    /// See [`EvolutionEngine::step`].
+    pub async fn step<T: Strategy>(&mut self, strategy: &T) -> EvolutionResult {
+        self.engine.step(strategy, &mut self.rng).await
+    }
+
+    pub fn into_engine(self) -> EvolutionEngine {
+        self.engine
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        cell::Cell,
+        sync::{Arc, Mutex},
+        time::Duration,
+    };
+
+    use common::subject_observer::Observer;
+    use common_test::get_seeded_rng;
+    use futures::executor::block_on;
+
+    use crate::{
+        adaptation::Strategy,
+        evolution::{
+            EventType, EvolutionEngine, EvolutionEngineBuilder, EvolutionError,
+            GeneticRenewalParam, MaxGenerations,
+        },
+        selection::SelectionType,
+        Genome,
+    };
+
+    struct Zeros;
+
+    impl Strategy for Zeros {
+        type Phenotype = usize;
+
+        fn genome_size(&self) -> usize {
+            4
+        }
+
+        fn decode(&self, genome: &Genome) -> usize {
+            genome.iter().filter(|&&gene| gene == 0).count()
+        }
+
+        fn evaluate(&self, zeros: &usize) -> f32 {
+            *zeros as f32 / 4.0
+        }
+    }
+
+    /// Counts the evaluated generations, from a single thread.
+    #[derive(Default)]
+    struct Evaluations(Cell<u64>);
+
+    impl Observer<EvolutionEngine, EventType> for Evaluations {
+        fn update(&self, _: &EvolutionEngine, event: EventType) {
+            if event == EventType::Evaluated {
+                self.0.set(self.0.get() + 1);
+            }
+        }
+    }
+
+    #[test]
+    fn test_evolution_engine_builder() -> Result<(), EvolutionError> {
+        // Given
+        let observer = Arc::new(Mutex::new(Evaluations::default()));
+        let builder = EvolutionEngineBuilder::new(get_seeded_rng().unwrap())
+            .population_size(10)
+            .crossover(GeneticRenewalParam {
+                ratio: 0.8,
+                selection_type: SelectionType::Ranking(5),
+                ..Default::default()
+            })
+            .max_duration(Duration::from_secs(10))
+            .hall_of_fame(2)
+            .label("strategy", "zeros")
+            .observer(observer.clone());
+
+        // When
+        let mut run = builder.build()?;
+        let result = block_on(run.start(&Zeros, MaxGenerations(3)))?;
+
+        // Then
+        assert_eq!(3, result.generation);
+        assert_eq!(10, result.evaluations.len());
+        assert_eq!(2, result.hall_of_fame.len());
+        assert!(result
+            .metadata
+            .is_some_and(|metadata| metadata.labels["strategy"] == "zeros"));
+        assert_eq!(
+            4,
+            observer.lock().unwrap().0.get(),
+            "Should register the observers"
+        );
+        assert_eq!(Some(Duration::from_secs(10)), run.config().max_duration);
+        assert!(run
+            .config()
+            .generation_renewal_config
+            .as_ref()
+            .is_some_and(|config| config.cloning.is_none()));
+
+        Ok(())
+    }
+
+    #[cfg(feature = "getrandom")]
+    #[test]
+    fn test_evolution_engine_builder_seed() -> Result<(), EvolutionError> {
+        // Given
+        let build = || EvolutionEngine::builder().population_size(10);
+
+        // When
+        let result = block_on(build().build()?.start(&Zeros, MaxGenerations(3)))?;
+        let seed = result.parameters.as_ref().and_then(|p| p.seed);
+
+        // Then
+        assert!(seed.is_some(), "Should record the seed drawn by default");
+
+        // When
+        let replayed = block_on(
+            build()
+                .seed(seed.unwrap())
+                .build()?
+                .start(&Zeros, MaxGenerations(3)),
+        )?;
+
+        // Then
+        assert_eq!(
+            result.evaluations, replayed.evaluations,
+            "Should reproduce the run from its seed"
+        );
+
+        // When
+        let result = block_on(
+            build()
+                .rng(get_seeded_rng().unwrap())
+                .build()?
+                .start(&Zeros, MaxGenerations(1)),
+        )?;
+
+        // Then
+        assert_eq!(
+            None,
+            result.parameters.and_then(|p| p.seed),
+            "Should not record a seed for a given generator"
+        );
+
+        Ok(())
+    }
+
+    #[cfg(feature = "validation")]
+    #[test]
+    fn test_evolution_engine_builder_should_validate() {
+        let result = EvolutionEngineBuilder::new(get_seeded_rng().unwrap()).build();
+
+        assert!(
+            matches!(result, Err(EvolutionError::InvalidSettings(_))),
+            "Should require a population"
+        );
+    }
+}
@@ -0,0 +1,550 @@
+use std::collections::{BTreeMap, HashSet};
+#[cfg(feature = "async")]
+use std::{sync::Arc, time::Duration};
+
+use futures::future::join_all;
+use rand::Rng;
+#[cfg(feature = "parallel")]
+use rayon::iter::{IntoParallelIterator, ParallelIterator};
+#[cfg(feature = "async")]
+use tokio::sync::Semaphore;
+
+#[cfg(feature = "async")]
+use crate::adaptation::AsyncStrategy;
+use crate::{
+    adaptation::{BatchStrategy, EvalContext, Strategy},
+    local_search::LocalSearch,
+    Genome,
+};
+
+#[cfg(feature = "async")]
+use super::TimeoutPolicy;
+use super::{
+    generation_renewal::ExecutionSettings, renewal::Repairer, EvolutionEngine, EvolutionError,
+};
+
+impl EvolutionEngine {
+    /// Evaluates the current generation, returning the fitnesses of its individuals.
+    pub(super) async fn evaluate<T: Challenger>(
+        &mut self,
+        strategy: &T,
+        settings: &ExecutionSettings,
+        rng: &mut impl Rng,
+    ) -> Result<Vec<f32>, EvolutionError> {
+        let strategy_genome_size = strategy.genome_size();
+        let contexts = self.contexts(self.snapshot.evaluations.len(), rng);
+        let challenges = if settings.reserved_size > 0 {
+            // Strategies only see their own genes
+            let genomes = self
+                .snapshot
+                .evaluations
+                .iter()
+                .map(|e| e.genome[..strategy_genome_size].to_vec())
+                .collect::<Vec<_>>();
+            run_samples(
+                strategy,
+                genomes.iter().zip(contexts).collect(),
+                self.resampling,
+            )
+            .await?
+        } else {
+            let genomes = self
+                .snapshot
+                .evaluations
+                .iter()
+                .map(|e| &e.genome)
+                .zip(contexts)
+                .collect();
+            run_samples(strategy, genomes, self.resampling).await?
+        };
+
+        self.snapshot.evaluation_count += (challenges.len() * self.resampling) as u64;
+        let mut fitnesses = Vec::with_capacity(challenges.len());
+        for (evaluation, challenge) in self.snapshot.evaluations.iter_mut().zip(challenges) {
+            evaluation.fitness = challenge.fitness;
+            evaluation.fitness_variance = challenge.fitness_variance;
+            evaluation.objectives = challenge.objectives;
+            evaluation.evaluated = challenge.evaluated;
+            evaluation.phenotype = challenge.phenotype;
+            evaluation.metadata = challenge.metadata;
+            fitnesses.push(challenge.fitness);
+        }
+        Ok(fitnesses)
+    }
+
+    /// Contexts of the evaluation of `count` genomes of the current generation, by position.
+    pub(super) fn contexts(&self, count: usize, rng: &mut impl Rng) -> Vec<EvalContext> {
+        (0..count)
+            .map(|index| EvalContext {
+                generation: self.snapshot.generation,
+                index,
+                rng_seed: rng.gen(),
+                samples: self.resampling,
+            })
+            .collect()
+    }
+
+    /// Runs the local search on the fittest evaluated individuals, returning how many it
+    /// improved.
+    pub(super) async fn search_locally<T: Challenger>(
+        &mut self,
+        strategy: &T,
+        rng: &mut impl Rng,
+    ) -> Result<usize, EvolutionError> {
+        let Some(local_search) = self.local_search.as_ref() else {
+            return Ok(0);
+        };
+        let evaluations = &self.snapshot.evaluations;
+        let count = (evaluations.len() as f32 * local_search.ratio).round() as usize;
+        let mut indices = (0..evaluations.len())
+            .filter(|&index| evaluations[index].evaluated)
+            .collect::<Vec<_>>();
+        indices.sort_by(|&a, &b| evaluations[b].fitness.total_cmp(&evaluations[a].fitness));
+        indices.truncate(count);
+
+        let genome_size = strategy.genome_size();
+        let mut improved = HashSet::new();
+        for _ in 0..local_search.iterations {
+            if indices.is_empty() {
+                break;
+            }
+            let neighbours = indices
+                .iter()
+                .map(|&index| {
+                    let mut genome =
+                        self.snapshot.evaluations[index].genome[..genome_size].to_vec();
+                    local_search.search.neighbour(&mut genome, rng);
+                    strategy.repair(&mut genome);
+                    genome
+                })
+                .collect::<Vec<_>>();
+            let contexts = indices.iter().map(|&index| EvalContext {
+                generation: self.snapshot.generation,
+                index,
+                rng_seed: rng.gen(),
+                samples: self.resampling,
+            });
+            let challenges = run_samples(
+                strategy,
+                neighbours.iter().zip(contexts).collect(),
+                self.resampling,
+            )
+            .await?;
+            self.snapshot.evaluation_count += (challenges.len() * self.resampling) as u64;
+            for ((&index, neighbour), challenge) in indices.iter().zip(neighbours).zip(challenges) {
+                let evaluation = &mut self.snapshot.evaluations[index];
+                if challenge.evaluated && challenge.fitness > evaluation.fitness {
+                    evaluation.genome.splice(..genome_size, neighbour);
+                    evaluation.fitness = challenge.fitness;
+                    evaluation.fitness_variance = challenge.fitness_variance;
+                    evaluation.objectives = challenge.objectives;
+                    evaluation.phenotype = challenge.phenotype;
+                    evaluation.metadata = challenge.metadata;
+                    improved.insert(index);
+                }
+            }
+        }
+        Ok(improved.len())
+    }
+}
+
+/// Outcome of the evaluation of a genome.
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct Challenge {
+    pub(super) fitness: f32,
+    /// Variance of the fitness over the samples of the genome, see [`run_samples`].
+    pub(super) fitness_variance: Option<f32>,
+    pub(super) objectives: Vec<f32>,
+    /// Description of the phenotype.
+    pub(super) phenotype: Option<String>,
+    /// Auxiliary data of the evaluation.
+    pub(super) metadata: BTreeMap<String, String>,
+    /// Whether the fitness counts, see [`super::TimeoutPolicy::Invalid`].
+    pub(super) evaluated: bool,
+}
+
+impl Challenge {
+    /// Outcome of an evaluation exceeding its timeout.
+    #[cfg(feature = "async")]
+    fn timed_out(policy: TimeoutPolicy) -> Self {
+        let (fitness, evaluated) = match policy {
+            TimeoutPolicy::Fitness(fitness) => (fitness, true),
+            TimeoutPolicy::Invalid => (0.0, false),
+        };
+        Challenge {
+            fitness,
+            fitness_variance: None,
+            objectives: vec![],
+            phenotype: None,
+            metadata: BTreeMap::new(),
+            evaluated,
+        }
+    }
+}
+
+/// Time allowed to the evaluation of a genome, see
+/// [`super::EvolutionEngineBuilder::evaluation_timeout`].
+#[cfg(feature = "async")]
+#[derive(Debug, Clone, Copy)]
+pub(super) struct EvaluationTimeout {
+    pub(super) duration: Duration,
+    pub(super) policy: TimeoutPolicy,
+}
+
+/// Local search of the fittest individuals, see
+/// [`super::EvolutionEngineBuilder::local_search`].
+#[derive(Debug)]
+pub(super) struct LocalSearchSettings {
+    pub(super) search: Box<dyn LocalSearch>,
+    pub(super) ratio: f32,
+    pub(super) iterations: usize,
+}
+
+/// Evaluation of the genomes of a run, whether the strategy is synchronous or not.
+pub(super) trait Challenger: Repairer {
+    async fn run_challenges(
+        &self,
+        genomes: Vec<(&Genome, EvalContext)>,
+    ) -> Result<Vec<Challenge>, EvolutionError>;
+}
+
+impl<T: Strategy> Repairer for T {
+    fn genome_size(&self) -> usize {
+        Strategy::genome_size(self)
+    }
+
+    fn repair(&self, genome: &mut Genome) {
+        Strategy::repair(self, genome);
+    }
+}
+
+impl<T: Strategy> Challenger for T {
+    async fn run_challenges(
+        &self,
+        genomes: Vec<(&Genome, EvalContext)>,
+    ) -> Result<Vec<Challenge>, EvolutionError> {
+        run_challenges(genomes, self).await
+    }
+}
+
+/// Runs the challenges of the genomes `samples` times, averaging their fitness and objectives.
+/// The phenotypes are described once and a genome timing out once is settled as timed out.
+pub(super) async fn run_samples<T: Challenger>(
+    strategy: &T,
+    mut genomes: Vec<(&Genome, EvalContext)>,
+    samples: usize,
+) -> Result<Vec<Challenge>, EvolutionError> {
+    let mut challenges = strategy.run_challenges(genomes.clone()).await?;
+    if samples <= 1 {
+        return Ok(challenges);
+    }
+
+    // Welford's online algorithm, the variance summing the squared deviations until the end
+    for challenge in challenges.iter_mut() {
+        challenge.fitness_variance = Some(0.0);
+    }
+    for count in 2..=samples {
+        for (_, context) in genomes.iter_mut() {
+            context.rng_seed = next_sample_seed(context.rng_seed);
+        }
+        let resamples = strategy.run_challenges(genomes.clone()).await?;
+        for (challenge, resample) in challenges.iter_mut().zip(resamples) {
+            challenge.evaluated &= resample.evaluated;
+            let delta = resample.fitness - challenge.fitness;
+            challenge.fitness += delta / count as f32;
+            if let Some(deviations) = challenge.fitness_variance.as_mut() {
+                *deviations += delta * (resample.fitness - challenge.fitness);
+            }
+            for (objective, value) in challenge.objectives.iter_mut().zip(resample.objectives) {
+                *objective += (value - *objective) / count as f32;
+            }
+        }
+    }
+    for challenge in challenges.iter_mut() {
+        challenge.fitness = challenge.fitness.clamp(0.0, 1.0);
+        challenge.fitness_variance = challenge
+            .fitness_variance
+            .map(|deviations| deviations / samples as f32);
+    }
+    Ok(challenges)
+}
+
+/// Seed of the next sample of an evaluation, mixed from the previous one (SplitMix64).
+fn next_sample_seed(seed: u64) -> u64 {
+    let mut z = seed.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+/// Evaluates the genomes of a `Sync` [`Strategy`] on the thread pool.
+#[cfg(feature = "parallel")]
+pub(super) struct ParallelChallenger<'a, T>(pub(super) &'a T);
+
+#[cfg(feature = "parallel")]
+impl<T: Strategy> Repairer for ParallelChallenger<'_, T> {
+    fn genome_size(&self) -> usize {
+        self.0.genome_size()
+    }
+
+    fn repair(&self, genome: &mut Genome) {
+        self.0.repair(genome);
+    }
+}
+
+#[cfg(feature = "parallel")]
+impl<T: Strategy + Sync> Challenger for ParallelChallenger<'_, T> {
+    async fn run_challenges(
+        &self,
+        genomes: Vec<(&Genome, EvalContext)>,
+    ) -> Result<Vec<Challenge>, EvolutionError> {
+        genomes
+            .into_par_iter()
+            .map(|(genome, context)| challenge(genome, context, self.0))
+            .collect()
+    }
+}
+
+/// Evaluates the genomes of a [`BatchStrategy`] in a single call.
+pub(super) struct BatchChallenger<'a, T: ?Sized>(pub(super) &'a T);
+
+impl<T: BatchStrategy> Repairer for BatchChallenger<'_, T> {
+    fn genome_size(&self) -> usize {
+        self.0.genome_size()
+    }
+
+    fn repair(&self, genome: &mut Genome) {
+        self.0.repair(genome);
+    }
+}
+
+impl<T: BatchStrategy> Challenger for BatchChallenger<'_, T> {
+    async fn run_challenges(
+        &self,
+        genomes: Vec<(&Genome, EvalContext)>,
+    ) -> Result<Vec<Challenge>, EvolutionError> {
+        let genomes = genomes
+            .into_iter()
+            .map(|(genome, _)| genome)
+            .collect::<Vec<_>>();
+        let fitnesses = self
+            .0
+            .evaluate_batch(&genomes)
+            .map_err(|e| EvolutionError::BatchEvaluation(e.to_string()))?;
+        if fitnesses.len() != genomes.len() {
+            return Err(EvolutionError::BatchEvaluation(format!(
+                "{} fitnesses for {} genomes",
+                fitnesses.len(),
+                genomes.len()
+            )));
+        }
+        fitnesses
+            .into_iter()
+            .map(|fitness| {
+                if (0.0..=1.0).contains(&fitness) {
+                    Ok(Challenge {
+                        fitness,
+                        fitness_variance: None,
+                        objectives: vec![],
+                        phenotype: None,
+                        metadata: BTreeMap::new(),
+                        evaluated: true,
+                    })
+                } else {
+                    Err(EvolutionError::InvalidEvaluation(fitness))
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(feature = "async")]
+pub(super) struct AsyncChallenger<T> {
+    pub(super) strategy: Arc<T>,
+    /// Permits of the evaluations running at once.
+    pub(super) semaphore: Arc<Semaphore>,
+    pub(super) timeout: Option<EvaluationTimeout>,
+}
+
+#[cfg(feature = "async")]
+impl<T: AsyncStrategy> Repairer for AsyncChallenger<T> {
+    fn genome_size(&self) -> usize {
+        self.strategy.genome_size()
+    }
+
+    fn repair(&self, genome: &mut Genome) {
+        self.strategy.repair(genome);
+    }
+}
+
+#[cfg(feature = "async")]
+impl<T: AsyncStrategy> Challenger for AsyncChallenger<T> {
+    async fn run_challenges(
+        &self,
+        genomes: Vec<(&Genome, EvalContext)>,
+    ) -> Result<Vec<Challenge>, EvolutionError> {
+        let timeout = self.timeout;
+        let tasks = genomes
+            .into_iter()
+            .map(|(genome, _)| {
+                let strategy = self.strategy.clone();
+                let semaphore = self.semaphore.clone();
+                let genome = genome.clone();
+                tokio::spawn(async move {
+                    // The semaphore is never closed
+                    let _permit = semaphore.acquire_owned().await;
+                    match timeout {
+                        Some(timeout) => {
+                            tokio::time::timeout(timeout.duration, strategy.evaluate(&genome))
+                                .await
+                                .map_err(|_| timeout.policy)
+                        }
+                        None => Ok(strategy.evaluate(&genome).await),
+                    }
+                })
+            })
+            .collect::<Vec<_>>();
+
+        let mut challenges = Vec::with_capacity(tasks.len());
+        for task in tasks {
+            let fitness = match task
+                .await
+                .map_err(|e| EvolutionError::EvaluationTask(e.to_string()))?
+            {
+                Ok(fitness) => fitness,
+                Err(policy) => {
+                    challenges.push(Challenge::timed_out(policy));
+                    continue;
+                }
+            };
+            if !(0.0..=1.0).contains(&fitness) {
+                return Err(EvolutionError::InvalidEvaluation(fitness));
+            }
+            challenges.push(Challenge {
+                fitness,
+                fitness_variance: None,
+                objectives: vec![],
+                phenotype: None,
+                metadata: BTreeMap::new(),
+                evaluated: true,
+            });
+        }
+        Ok(challenges)
+    }
+}
+
+async fn run_challenge<T: Strategy>(
+    genome: &Genome,
+    context: EvalContext,
+    strategy: &T,
+) -> Result<Challenge, EvolutionError> {
+    challenge(genome, context, strategy)
+}
+
+fn challenge<T: Strategy>(
+    genome: &Genome,
+    context: EvalContext,
+    strategy: &T,
+) -> Result<Challenge, EvolutionError> {
+    let phenotype = strategy.decode(genome);
+    let (fitness, objectives, metadata) = strategy.evaluate_in_context(&phenotype, &context);
+    if (0.0..=1.0).contains(&fitness) {
+        Ok(Challenge {
+            fitness,
+            fitness_variance: None,
+            objectives,
+            phenotype: strategy.describe(&phenotype),
+            metadata,
+            evaluated: true,
+        })
+    } else {
+        Err(EvolutionError::InvalidEvaluation(fitness))
+    }
+}
+
+async fn run_challenges<T: Strategy>(
+    genomes: Vec<(&Genome, EvalContext)>,
+    strategy: &T,
+) -> Result<Vec<Challenge>, EvolutionError> {
+    join_all(
+        genomes
+            .into_iter()
+            .map(|(genome, context)| run_challenge(genome, context, strategy)),
+    )
+    .await
+    .into_iter()
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use common_test::get_seeded_rng;
+    use futures::executor::block_on;
+    use mockall::{mock, predicate::eq};
+    use rand::Rng;
+
+    use crate::{
+        adaptation::{EvalContext, Strategy},
+        evolution::EvolutionError,
+        Genome,
+    };
+
+    use super::run_challenge;
+
+    mock! {
+        TestStrategy {}
+
+        impl Strategy for TestStrategy {
+            type Phenotype = Genome;
+
+            fn genome_size(&self) -> usize;
+
+            fn decode(&self, genome: &Genome) -> Genome;
+
+            fn evaluate<'a>(&self, genome: &'a Genome) -> f32;
+
+        }
+
+    }
+
+    #[test]
+    fn test_run_challenges() {
+        // Given
+        let mut rng = get_seeded_rng().unwrap();
+        let genome = vec![1, 2];
+
+        // When
+        let mut strategy = MockTestStrategy::new();
+        strategy.expect_decode().returning(|genome| genome.clone());
+        let fitness = 2.0;
+        strategy
+            .expect_evaluate()
+            .with(eq(genome.clone()))
+            .return_const(fitness);
+
+        let result = block_on(run_challenge(&genome, EvalContext::default(), &strategy));
+        // Then
+        assert!(
+            matches!(result, Err(EvolutionError::InvalidEvaluation(_))),
+            "Should return error when evaluation is not standardized"
+        );
+
+        // When
+        let mut strategy = MockTestStrategy::new();
+        strategy.expect_decode().returning(|genome| genome.clone());
+        let fitness = rng.gen_range(0.0..=1.0);
+        strategy
+            .expect_evaluate()
+            .with(eq(genome.clone()))
+            .return_const(fitness);
+
+        let result = block_on(run_challenge(&genome, EvalContext::default(), &strategy)).unwrap();
+
+        // Then
+        assert_eq!(
+            (fitness, None),
+            (result.fitness, result.phenotype),
+            "Should call strategy evaluation"
+        );
+    }
+}
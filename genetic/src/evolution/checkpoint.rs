@@ -0,0 +1,102 @@
+use std::{
+    fs::{self, File},
+    io::{self, BufReader, BufWriter, Write},
+    path::Path,
+};
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use super::{EvolutionConfig, Snapshot};
+
+#[derive(Error, Debug)]
+pub enum CheckpointError {
+    #[error("Unable to access the checkpoint file: {0}")]
+    Io(#[from] io::Error),
+    #[error("Invalid checkpoint: {0}")]
+    Format(#[from] serde_json::Error),
+}
+
+/// Saved state of a run, written as JSON by [`super::EvolutionEngineBuilder::checkpoint`] and
+/// resumed by [`super::EvolutionEngine::resume_from_checkpoint`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Checkpoint {
+    /// Population about to be evaluated.
+    pub snapshot: Snapshot,
+    pub config: EvolutionConfig,
+    /// Seed of the random generator resuming the run, drawn from the generator of the run.
+    pub seed: u64,
+}
+
+impl Checkpoint {
+    /// Writes the checkpoint next to the file first so that an interrupted save keeps the
+    /// previous checkpoint.
+    pub fn save(&self, path: &Path) -> Result<(), CheckpointError> {
+        let mut partial = path.as_os_str().to_owned();
+        partial.push(".partial");
+        let mut writer = BufWriter::new(File::create(&partial)?);
+        serde_json::to_writer(&mut writer, self)?;
+        writer.flush()?;
+        drop(writer);
+        fs::rename(&partial, path)?;
+        Ok(())
+    }
+
+    pub fn load(path: &Path) -> Result<Self, CheckpointError> {
+        let reader = BufReader::new(File::open(path)?);
+        Ok(serde_json::from_reader(reader)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, fs, process, time::Duration};
+
+    use crate::{
+        evolution::{EvolutionConfig, Snapshot},
+        Evaluation,
+    };
+
+    use super::{Checkpoint, CheckpointError};
+
+    #[test]
+    fn test_checkpoint_save_load() {
+        // Given
+        let path = env::temp_dir().join(format!("evolution-lab-checkpoint-{}", process::id()));
+        let checkpoint = Checkpoint {
+            snapshot: Snapshot {
+                generation: 4,
+                evaluations: vec![Evaluation::from(vec![1, 2, 3])],
+                evaluation_count: 40,
+                hall_of_fame: vec![],
+                stats: None,
+                diversity: None,
+                parameters: None,
+                metadata: None,
+                lineage: None,
+            },
+            config: EvolutionConfig {
+                population_size: 10,
+                generation_renewal_config: None,
+                max_duration: Some(Duration::from_secs(5)),
+            },
+            seed: 42,
+        };
+
+        // When
+        checkpoint.save(&path).unwrap();
+        let result = Checkpoint::load(&path);
+        fs::remove_file(&path).unwrap();
+
+        // Then
+        let result = result.unwrap();
+        assert_eq!(checkpoint.snapshot, result.snapshot);
+        assert_eq!(42, result.seed);
+        assert_eq!(10, result.config.population_size);
+        assert_eq!(Some(Duration::from_secs(5)), result.config.max_duration);
+        assert!(
+            matches!(Checkpoint::load(&path), Err(CheckpointError::Io(_))),
+            "Should fail on missing files"
+        );
+    }
+}
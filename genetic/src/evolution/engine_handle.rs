@@ -0,0 +1,114 @@
+use std::sync::mpsc::{channel, Receiver, Sender};
+
+use futures::channel::mpsc::UnboundedSender;
+
+use crate::Genome;
+
+use super::{EvolutionError, ReplacementPolicy, Snapshot};
+
+/// Control action applied by a running engine at the end of each generation evaluation.
+#[derive(Debug)]
+pub enum Command {
+    Halt,
+    /// Suspends the run until a [`Command::Resume`] or a [`Command::Halt`] is received.
+    Pause,
+    Resume,
+    Inject(Vec<Genome>, ReplacementPolicy),
+    /// Overrides the mutation rate of every renewal pool.
+    SetMutationRate(f32),
+    RequestSnapshot(Sender<Snapshot>),
+}
+
+/// Cloneable access to an engine from other threads or tasks, see
+/// [`super::EvolutionEngine::handle`].
+#[derive(Clone, Debug)]
+pub struct EngineHandle {
+    sender: UnboundedSender<Command>,
+}
+
+impl EngineHandle {
+    pub(super) fn new(sender: UnboundedSender<Command>) -> Self {
+        EngineHandle { sender }
+    }
+
+    pub fn send(&self, command: Command) -> Result<(), EvolutionError> {
+        self.sender
+            .unbounded_send(command)
+            .map_err(|_| EvolutionError::Disconnected)
+    }
+
+    pub fn halt(&self) -> Result<(), EvolutionError> {
+        self.send(Command::Halt)
+    }
+
+    pub fn pause(&self) -> Result<(), EvolutionError> {
+        self.send(Command::Pause)
+    }
+
+    pub fn resume(&self) -> Result<(), EvolutionError> {
+        self.send(Command::Resume)
+    }
+
+    /// Queues genomes for insertion once the current generation is evaluated, see
+    /// [`super::EvolutionEngine::inject`].
+    pub fn inject(
+        &self,
+        genomes: Vec<Genome>,
+        policy: ReplacementPolicy,
+    ) -> Result<(), EvolutionError> {
+        self.send(Command::Inject(genomes, policy))
+    }
+
+    pub fn set_mutation_rate(&self, mutation_rate: f32) -> Result<(), EvolutionError> {
+        self.send(Command::SetMutationRate(mutation_rate))
+    }
+
+    /// Asks for the snapshot of the next evaluated generation, delivered on the returned receiver.
+    pub fn request_snapshot(&self) -> Result<Receiver<Snapshot>, EvolutionError> {
+        let (sender, receiver) = channel();
+        self.send(Command::RequestSnapshot(sender))?;
+        Ok(receiver)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::channel::mpsc;
+
+    use crate::evolution::{EvolutionError, ReplacementPolicy};
+
+    use super::{Command, EngineHandle};
+
+    #[test]
+    fn test_engine_handle_send() {
+        // Given
+        let (sender, mut receiver) = mpsc::unbounded();
+        let handle = EngineHandle::new(sender);
+
+        // When
+        handle.pause().unwrap();
+        handle
+            .inject(vec![vec![1]], ReplacementPolicy::Append)
+            .unwrap();
+
+        // Then
+        assert!(matches!(receiver.try_next(), Ok(Some(Command::Pause))));
+        assert!(
+            matches!(
+                receiver.try_next(),
+                Ok(Some(Command::Inject(genomes, ReplacementPolicy::Append))) if genomes == vec![vec![1]]
+            ),
+            "Should forward commands in order"
+        );
+
+        // When
+        drop(receiver);
+
+        // Then
+        assert_eq!(
+            Err(EvolutionError::Disconnected),
+            handle.halt(),
+            "Should fail once the engine is gone"
+        );
+    }
+}
@@ -1,97 +1,546 @@
+#[cfg(feature = "checkpoint")]
+use std::path::Path;
 use std::{
-    rc::Rc,
-    sync::{Arc, Mutex},
+    collections::BTreeMap,
+    path::PathBuf,
+    sync::{Arc, Mutex, PoisonError},
+    time::{Duration, SystemTime},
 };
 
-use common::subject_observer::{Observer, SharedObservers, Subject};
-use futures::future::join_all;
-use log::{debug, trace};
-use rand::{distributions::Standard, Rng};
+use common::subject_observer::{SharedObserver, SharedObservers, Subject};
+use futures::{
+    channel::mpsc::{self, UnboundedReceiver, UnboundedSender},
+    StreamExt,
+};
+use log::{debug, trace, warn};
+#[cfg(feature = "checkpoint")]
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+#[cfg(feature = "async")]
+use tokio::sync::Semaphore;
+#[cfg(feature = "validation")]
 use validator::Validate;
 
+#[cfg(feature = "async")]
+use crate::adaptation::AsyncStrategy;
 use crate::{
-    adaptation::Strategy,
-    selection::{select, select_couples},
-    Genome, IntoEvaluations,
+    adaptation::{BatchStrategy, Strategy},
+    analysis::GenerationStats,
+    clock::{Clock, SystemClock},
+    selection::FitnessScaling,
+    Evaluation, Genome, IntoEvaluations,
 };
 
+#[cfg(feature = "parallel")]
+use super::challenger::ParallelChallenger;
+#[cfg(feature = "async")]
+use super::challenger::{AsyncChallenger, EvaluationTimeout};
+#[cfg(feature = "checkpoint")]
+use super::Checkpoint;
+#[cfg(feature = "getrandom")]
+use super::EvolutionEngineBuilder;
 use super::{
-    genetic_pool::GeneticPool, EventType, EvolutionConfig, EvolutionError, EvolutionResult,
-    EvolutionStatus, GenerationRenewalConfig, Snapshot,
+    bookkeeping::{check_snapshot, run_id},
+    challenger::{run_samples, BatchChallenger, Challenger, LocalSearchSettings},
+    engine_handle::{Command, EngineHandle},
+    generation_renewal::{get_random_genomes_iter, resolve_settings, ExecutionSettings},
+    history::History,
+    hook::GenerationHook,
+    injection::{insert, Injection},
+    lineage::Lineage,
+    observer_timing::{ObserverBudget, ObserverTiming},
+    operator_stats::OperatorTracker,
+    renewal::{default_renewal_stages, RenewalStage},
+    speciation::{Speciation, Species},
+    termination::duration_share,
+    Annotation, Any, Deduplication, EventType, EvolutionConfig, EvolutionError, EvolutionResult,
+    EvolutionStatus, GenerationRecord, InitialPopulation, OperatorStats, Progress,
+    ReplacementPolicy, RestartPolicy, RunMetadata, RunParameters, RunProgress, Snapshot,
+    Stagnation, TerminationCriterion,
 };
 
-#[derive(Debug, Clone, Copy)]
-struct ExecutionSettings {
-    cloning_pool: GeneticPool,
-    crossover_pool: GeneticPool,
-    randoms_count: usize,
+pub struct EvolutionEngine {
+    pub(super) annotations: Arc<Mutex<Vec<Annotation>>>,
+    /// Best fitness of the current run, see [`EventType::BestImproved`].
+    pub(super) best_fitness: Option<f32>,
+    /// Generations between two checkpoints, with the file to write them to.
+    #[cfg(feature = "checkpoint")]
+    pub(super) checkpoint: Option<(u64, PathBuf)>,
+    pub(super) clock: Arc<dyn Clock>,
+    pub(super) command_receiver: Mutex<UnboundedReceiver<Command>>,
+    pub(super) command_sender: UnboundedSender<Command>,
+    /// Replacement of the duplicated genomes of new generations, kept if none.
+    pub(super) deduplication: Option<Deduplication>,
+    /// Whether [`Snapshot::diversity`] is computed for each generation.
+    pub(super) diversity_measuring: bool,
+    /// Maximum number of evaluations of an [`AsyncStrategy`] awaited at once, unbounded if none.
+    #[cfg(feature = "async")]
+    pub(super) evaluation_concurrency: Option<usize>,
+    #[cfg(feature = "async")]
+    pub(super) evaluation_timeout: Option<EvaluationTimeout>,
+    /// Scaling of the fitnesses the parents are selected on.
+    pub(super) fitness_scaling: Option<FitnessScaling>,
+    /// Generation whose renewal is dumped, with the file to write it to.
+    pub(super) generation_dump: Option<(u64, PathBuf)>,
+    /// Number of individuals kept in [`Snapshot::hall_of_fame`], none if zero.
+    pub(super) hall_of_fame_size: usize,
+    /// Hooks run around each generation, in registration order.
+    pub(super) hooks: Mutex<Vec<Box<dyn GenerationHook>>>,
+    /// Last generations recorded by [`super::EvolutionEngineBuilder::record_history`].
+    pub(super) history: Option<History>,
+    /// Genomes of the first generation of the runs not resumed from a snapshot.
+    pub(super) initial_population: InitialPopulation,
+    pub(super) injections: Arc<Mutex<Vec<Injection>>>,
+    /// Labels of the next runs, see [`RunMetadata::labels`].
+    pub(super) labels: BTreeMap<String, String>,
+    /// Whether the runs record the [`Snapshot::lineage`] of their individuals.
+    pub(super) lineage_tracking: bool,
+    pub(super) local_search: Option<LocalSearchSettings>,
+    pub(super) observer_budget: Option<ObserverBudget>,
+    pub(super) observer_timings: Mutex<Vec<ObserverTiming>>,
+    pub(super) observers: SharedObservers<Self, EventType>,
+    /// Assessment of the renewal operators, see [`super::EvolutionEngineBuilder::track_operators`].
+    pub(super) operator_tracker: Option<OperatorTracker>,
+    /// Progress of the current run as of its last completed generation.
+    pub(super) progress: Option<RunProgress>,
+    /// Stages building each new generation, see [`super::EvolutionEngineBuilder::renewal_stages`].
+    pub(super) renewal_stages: Vec<Box<dyn RenewalStage>>,
+    /// Evaluations of each genome averaged into its fitness.
+    pub(super) resampling: usize,
+    pub(super) restart_policy: Option<RestartPolicy>,
+    /// Restarts of the current run, see [`super::EvolutionEngineBuilder::restart_on_stagnation`].
+    pub(super) restarts: u32,
+    /// Seed of the random generator of the next run, see [`EvolutionEngine::start_seeded`].
+    pub(super) seed: Option<u64>,
+    pub(super) snapshot: Snapshot,
+    /// Species the parents are selected within, see [`super::EvolutionEngineBuilder::speciate`].
+    pub(super) speciation: Option<Speciation>,
+    /// Tracker of the best fitness notifying [`EventType::StagnationDetected`].
+    pub(super) stagnation: Option<Stagnation>,
+    pub(super) started_at: Option<SystemTime>,
+    /// Generation and evaluation count the current run started from, see [`RunProgress`].
+    pub(super) started_from: (u64, u64),
+    pub(super) status: Arc<Mutex<EvolutionStatus>>,
+    /// Settings of a run driven by [`EvolutionEngine::step`].
+    pub(super) stepping: Option<(EvolutionConfig, ExecutionSettings)>,
 }
 
-#[derive(Default)]
-pub struct EvolutionEngine {
-    observers: SharedObservers<Self, EventType>,
-    snapshot: Snapshot,
-    status: Arc<Mutex<EvolutionStatus>>,
+impl Default for EvolutionEngine {
+    fn default() -> Self {
+        let (command_sender, command_receiver) = mpsc::unbounded();
+        Self {
+            annotations: Default::default(),
+            best_fitness: None,
+            #[cfg(feature = "checkpoint")]
+            checkpoint: None,
+            clock: Arc::new(SystemClock),
+            command_receiver: Mutex::new(command_receiver),
+            command_sender,
+            deduplication: None,
+            diversity_measuring: false,
+            #[cfg(feature = "async")]
+            evaluation_concurrency: None,
+            #[cfg(feature = "async")]
+            evaluation_timeout: None,
+            fitness_scaling: None,
+            generation_dump: None,
+            hall_of_fame_size: 0,
+            hooks: Default::default(),
+            history: None,
+            initial_population: InitialPopulation::Random,
+            injections: Default::default(),
+            labels: Default::default(),
+            lineage_tracking: false,
+            local_search: None,
+            observer_budget: None,
+            observer_timings: Default::default(),
+            observers: Default::default(),
+            operator_tracker: None,
+            progress: None,
+            renewal_stages: default_renewal_stages(),
+            resampling: 1,
+            restart_policy: None,
+            restarts: 0,
+            seed: None,
+            snapshot: Default::default(),
+            speciation: None,
+            stagnation: None,
+            started_at: None,
+            started_from: (0, 0),
+            status: Default::default(),
+            stepping: None,
+        }
+    }
 }
 
 impl Subject<EventType> for EvolutionEngine {
-    fn register_observer(&mut self, observer: Rc<dyn Observer<Self, EventType>>) {
+    fn register_observer(&mut self, observer: SharedObserver<Self, EventType>) {
+        self.observer_timings
+            .get_mut()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push(ObserverTiming::new(observer.name()));
         self.observers.push(observer);
     }
 
-    fn unregister_observer(&mut self, observer: Rc<dyn Observer<Self, EventType>>) {
-        self.observers.retain(|obs| !Rc::ptr_eq(obs, &observer));
+    fn unregister_observer(&mut self, observer: SharedObserver<Self, EventType>) {
+        let timings = self
+            .observer_timings
+            .get_mut()
+            .unwrap_or_else(PoisonError::into_inner);
+        let mut index = 0;
+        self.observers.retain(|obs| {
+            let keep = !Arc::ptr_eq(obs, &observer);
+            if keep {
+                index += 1;
+            } else {
+                timings.remove(index);
+            }
+            keep
+        });
     }
 
     fn notify_observers(&self, event: EventType) {
-        for obs in &self.observers {
+        for (index, obs) in self.observers.iter().enumerate() {
+            let start = self.clock.now();
             obs.update(self, event.clone());
+            let elapsed = self.clock.since(start);
+
+            let mut timings = self
+                .observer_timings
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner);
+            if timings[index].record(elapsed, self.observer_budget.as_ref()) {
+                warn!(
+                    "Observer {} exceeded its {:?} budget on {} consecutive updates, last one took {:?}",
+                    timings[index].name,
+                    self.observer_budget.map(|b| b.budget).unwrap_or_default(),
+                    timings[index].slow_streak,
+                    elapsed
+                );
+            }
         }
     }
 }
 
 impl EvolutionEngine {
-    pub fn snapshot(&self) -> Snapshot {
-        self.snapshot.clone()
+    /// Gathers the settings, observers and random generator of a run before creating the
+    /// engine, see [`EvolutionEngineBuilder`].
+    #[cfg(feature = "getrandom")]
+    pub fn builder() -> EvolutionEngineBuilder {
+        EvolutionEngineBuilder::default()
+    }
+
+    /// Current generation of the run, borrowed so that observers read it without copying the
+    /// population.
+    pub fn snapshot(&self) -> &Snapshot {
+        &self.snapshot
+    }
+
+    /// Attaches a note to the run at the current generation.
+    pub fn annotate(&self, text: impl Into<String>) -> Result<Annotation, EvolutionError> {
+        let annotation = Annotation::new(self.snapshot.generation, text, self.clock.now());
+        self.annotations.lock()?.push(annotation.clone());
+        Ok(annotation)
+    }
+
+    pub fn annotations(&self) -> Result<Vec<Annotation>, EvolutionError> {
+        Ok(self.annotations.lock()?.clone())
+    }
+
+    /// Update latencies of the registered observers, in registration order.
+    pub fn observer_timings(&self) -> Result<Vec<ObserverTiming>, EvolutionError> {
+        Ok(self.observer_timings.lock()?.clone())
+    }
+
+    /// Species of the last evaluated generation, none without
+    /// [`super::EvolutionEngineBuilder::speciate`].
+    pub fn species(&self) -> &[Species] {
+        self.speciation
+            .as_ref()
+            .map_or(&[], |speciation| speciation.species())
+    }
+
+    /// Identity of the current run, none before its start.
+    pub fn metadata(&self) -> Option<&RunMetadata> {
+        self.snapshot.metadata.as_ref()
+    }
+
+    /// Success of the renewal operators of the run so far, empty unless tracked.
+    pub fn operator_stats(&self) -> Vec<OperatorStats> {
+        self.operator_tracker
+            .as_ref()
+            .map(|tracker| tracker.stats().to_vec())
+            .unwrap_or_default()
+    }
+
+    /// Last evaluated generations of the run, oldest first, empty unless recorded.
+    pub fn history(&self) -> Vec<GenerationRecord> {
+        self.history
+            .as_ref()
+            .map(|history| history.records().iter().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    /// Restarts of the current run, see [`super::EvolutionEngineBuilder::restart_on_stagnation`].
+    pub fn restarts(&self) -> u32 {
+        self.restarts
+    }
+
+    /// Number of registered observers.
+    pub fn observer_count(&self) -> usize {
+        self.observers.len()
+    }
+
+    /// Time spent since the run started, zero before it.
+    pub fn elapsed(&self) -> Duration {
+        self.started_at
+            .map(|started_at| self.clock.since(started_at))
+            .unwrap_or_default()
+    }
+
+    /// Current generation, without cloning the snapshot.
+    pub fn generation(&self) -> u64 {
+        self.snapshot.generation
+    }
+
+    /// Fitness statistics of the current generation once evaluated, see [`Snapshot::stats`].
+    pub fn stats(&self) -> Option<GenerationStats> {
+        self.snapshot.stats
+    }
+
+    /// Throughput and estimated end of the current run as of its last completed generation,
+    /// none before, also notified as [`EventType::Progress`].
+    pub fn progress(&self) -> Option<RunProgress> {
+        self.progress
+    }
+
+    /// Number of genomes evaluated so far, see [`Snapshot::evaluation_count`].
+    pub fn evaluation_count(&self) -> u64 {
+        self.snapshot.evaluation_count
+    }
+
+    /// Queues genomes to insert into the population once the current generation is evaluated.
+    ///
+    /// Injected genomes are evaluated before taking their place so that they compete with the
+    /// rest of the population; those not matching the genome size of the strategy are dropped.
+    /// See [`EngineHandle::inject`] to inject genomes from other threads while the engine runs.
+    pub fn inject(
+        &self,
+        genomes: Vec<Genome>,
+        policy: ReplacementPolicy,
+    ) -> Result<(), EvolutionError> {
+        self.injections.lock()?.push(Injection { genomes, policy });
+        Ok(())
+    }
+
+    /// Gives other threads or tasks control over the run, see [`Command`].
+    pub fn handle(&self) -> EngineHandle {
+        EngineHandle::new(self.command_sender.clone())
+    }
+
+    pub fn status(&self) -> Result<EvolutionStatus, EvolutionError> {
+        Ok(*self.status.lock()?)
+    }
+
+    /// Completes a run driven by [`EvolutionEngine::step`] whose caller decided to stop.
+    #[cfg(feature = "island")]
+    pub(super) fn complete(&mut self) -> Result<bool, EvolutionError> {
+        self.end::<fn(EvolutionStatus) -> bool>(EvolutionStatus::Completed, None)
     }
 
-    pub fn halt(&mut self) -> Result<bool, EvolutionError> {
+    /// Asks a running or paused engine to stop at the end of its current generation, returning
+    /// whether it was running. See [`EngineHandle::halt`] to halt it from other threads or tasks
+    /// while it runs.
+    pub fn halt(&self) -> Result<bool, EvolutionError> {
         self.change_status(
             EvolutionStatus::Halting,
-            Some(&|status| status == EvolutionStatus::Running),
+            Some(&|status| status == EvolutionStatus::Running || status == EvolutionStatus::Paused),
         )
     }
 
-    pub async fn start<T, F>(
+    /// Makes a halted or completed engine ready for a new run, keeping its observers and
+    /// options while dropping the state of the previous run: population, annotations, pending
+    /// injections and commands, observer timings.
+    ///
+    /// Resetting an engine which is running fails with [`EvolutionError::InvalidStatus`].
+    pub fn reset(&mut self) -> Result<(), EvolutionError> {
+        let status = self.status.lock()?.to_owned();
+        if !matches!(
+            status,
+            EvolutionStatus::New | EvolutionStatus::Halted | EvolutionStatus::Completed
+        ) {
+            debug!("Cannot reset evolution from {} engine state", status);
+            return Err(EvolutionError::InvalidStatus(status));
+        }
+
+        self.snapshot = Snapshot::default();
+        self.started_at = None;
+        self.best_fitness = None;
+        self.progress = None;
+        self.restarts = 0;
+        self.stepping = None;
+        if let Some(history) = self.history.as_mut() {
+            history.clear();
+        }
+        self.annotations.lock()?.clear();
+        self.injections.lock()?.clear();
+        while let Ok(Some(_)) = self.command_receiver.get_mut()?.try_next() {}
+        self.change_status::<fn(EvolutionStatus) -> bool>(EvolutionStatus::New, None)?;
+        for timing in self.observer_timings.get_mut()?.iter_mut() {
+            *timing = ObserverTiming::new(timing.name);
+        }
+        Ok(())
+    }
+
+    pub async fn start<T, C>(
         &mut self,
         strategy: &T,
         config: &EvolutionConfig,
-        is_complete: F,
+        is_complete: C,
         rng: &mut impl Rng,
     ) -> EvolutionResult
     where
         T: Strategy,
-        F: Fn(u64, &[f32]) -> bool,
+        C: TerminationCriterion,
     {
         self.run(strategy, config, is_complete, rng, None).await
     }
 
-    pub async fn start_from<T, F>(
+    pub async fn start_from<T, C>(
         &mut self,
         strategy: &T,
         config: &EvolutionConfig,
-        is_complete: F,
+        is_complete: C,
         rng: &mut impl Rng,
         snapshot: Snapshot,
     ) -> EvolutionResult
     where
         T: Strategy,
-        F: Fn(u64, &[f32]) -> bool,
+        C: TerminationCriterion,
     {
         self.run(strategy, config, is_complete, rng, Some(snapshot))
             .await
     }
 
+    /// Runs an evolution with a random generator of type `R` seeded with `seed`, which is
+    /// recorded in [`Snapshot::parameters`] to reproduce the run.
+    pub async fn start_seeded<R, T, C>(
+        &mut self,
+        strategy: &T,
+        config: &EvolutionConfig,
+        is_complete: C,
+        seed: u64,
+    ) -> EvolutionResult
+    where
+        R: Rng + SeedableRng,
+        T: Strategy,
+        C: TerminationCriterion,
+    {
+        self.seed = Some(seed);
+        self.run(
+            strategy,
+            config,
+            is_complete,
+            &mut R::seed_from_u64(seed),
+            None,
+        )
+        .await
+    }
+
+    /// Runs an evolution whose genomes are evaluated as concurrent Tokio tasks, see
+    /// [`super::EvolutionEngineBuilder::evaluation_concurrency`].
+    ///
+    /// Must be awaited within a Tokio runtime, which spreads the evaluations over its threads.
+    #[cfg(feature = "async")]
+    pub async fn start_async<T, C>(
+        &mut self,
+        strategy: Arc<T>,
+        config: &EvolutionConfig,
+        is_complete: C,
+        rng: &mut impl Rng,
+    ) -> EvolutionResult
+    where
+        T: AsyncStrategy,
+        C: TerminationCriterion,
+    {
+        let challenger = AsyncChallenger {
+            strategy,
+            semaphore: Arc::new(Semaphore::new(
+                self.evaluation_concurrency
+                    .unwrap_or(Semaphore::MAX_PERMITS),
+            )),
+            timeout: self.evaluation_timeout,
+        };
+        self.run(&challenger, config, is_complete, rng, None).await
+    }
+
+    /// Runs an evolution whose genomes are evaluated on the Rayon thread pool.
+    #[cfg(feature = "parallel")]
+    pub async fn start_parallel<T, C>(
+        &mut self,
+        strategy: &T,
+        config: &EvolutionConfig,
+        is_complete: C,
+        rng: &mut impl Rng,
+    ) -> EvolutionResult
+    where
+        T: Strategy + Sync,
+        C: TerminationCriterion,
+    {
+        self.run(
+            &ParallelChallenger(strategy),
+            config,
+            is_complete,
+            rng,
+            None,
+        )
+        .await
+    }
+
+    /// Runs an evolution whose genomes are evaluated a generation at a time, e.g. dispatched to
+    /// remote workers.
+    ///
+    /// The evaluation timeout does not apply, the strategy handling its stragglers itself.
+    pub async fn start_batch<T, C>(
+        &mut self,
+        strategy: &T,
+        config: &EvolutionConfig,
+        is_complete: C,
+        rng: &mut impl Rng,
+    ) -> EvolutionResult
+    where
+        T: BatchStrategy,
+        C: TerminationCriterion,
+    {
+        self.run(&BatchChallenger(strategy), config, is_complete, rng, None)
+            .await
+    }
+
+    /// Resumes the run saved to the file by [`super::EvolutionEngineBuilder::checkpoint`], with its
+    /// settings and a random generator seeded from the checkpoint.
+    #[cfg(feature = "checkpoint")]
+    pub async fn resume_from_checkpoint<T, C>(
+        &mut self,
+        strategy: &T,
+        path: impl AsRef<Path>,
+        is_complete: C,
+    ) -> EvolutionResult
+    where
+        T: Strategy,
+        C: TerminationCriterion,
+    {
+        let Checkpoint {
+            snapshot,
+            config,
+            seed,
+        } = Checkpoint::load(path.as_ref())
+            .map_err(|e| EvolutionError::Checkpoint(e.to_string()))?;
+        let mut rng = StdRng::seed_from_u64(seed);
+        self.seed = Some(seed);
+        self.run(strategy, &config, is_complete, &mut rng, Some(snapshot))
+            .await
+    }
+
     fn change_status<F>(
         &self,
         new_status: EvolutionStatus,
@@ -114,20 +563,129 @@ impl EvolutionEngine {
         Ok(result)
     }
 
-    async fn run<T, F>(
+    /// Changes the status of the run to its last one, stamping its end beforehand so that
+    /// observers notified of the status see it.
+    fn end<F>(
+        &mut self,
+        new_status: EvolutionStatus,
+        additional_check: Option<&F>,
+    ) -> Result<bool, EvolutionError>
+    where
+        F: Fn(EvolutionStatus) -> bool,
+    {
+        let now = self.clock.now();
+        let previous = self
+            .snapshot
+            .metadata
+            .as_mut()
+            .and_then(|metadata| metadata.ended_at.replace(now));
+        let changed = self.change_status(new_status, additional_check)?;
+        if !changed {
+            if let Some(metadata) = self.snapshot.metadata.as_mut() {
+                metadata.ended_at = previous;
+            }
+        }
+        Ok(changed)
+    }
+
+    /// Prepares a run driven generation by generation with [`EvolutionEngine::step`], from
+    /// the given population or a random one.
+    ///
+    /// Only the time budget of the settings completes such runs, the caller deciding when to
+    /// stop otherwise.
+    pub fn prepare<T: Strategy>(
+        &mut self,
+        strategy: &T,
+        config: &EvolutionConfig,
+        rng: &mut impl Rng,
+        snapshot: Option<Snapshot>,
+    ) -> Result<(), EvolutionError> {
+        let settings = self.initialize(strategy, config, rng, snapshot)?;
+        self.stepping = Some((config.clone(), settings));
+        Ok(())
+    }
+
+    /// Evaluates the current generation of a run prepared with [`EvolutionEngine::prepare`]
+    /// then renews the population unless the run is over, returning the evaluated generation.
+    ///
+    /// A halted run returns its unevaluated generation instead. Stepping a run which is not
+    /// prepared, halted or completed fails with [`EvolutionError::InvalidStatus`].
+    pub async fn step<T: Strategy>(&mut self, strategy: &T, rng: &mut impl Rng) -> EvolutionResult {
+        let status = self.status.lock()?.to_owned();
+        let Some((config, mut settings)) = self.stepping.take().filter(|_| {
+            matches!(
+                status,
+                EvolutionStatus::Running | EvolutionStatus::Paused | EvolutionStatus::Halting
+            )
+        }) else {
+            debug!("Cannot step evolution from {} engine state", status);
+            return Err(EvolutionError::InvalidStatus(status));
+        };
+
+        let result = self
+            .advance(strategy, &config, &mut settings, &mut Any(vec![]), rng)
+            .await;
+        self.stepping = Some((config, settings));
+        result
+    }
+
+    async fn run<T, C>(
         &mut self,
         strategy: &T,
         config: &EvolutionConfig,
-        is_complete: F,
+        mut is_complete: C,
         rng: &mut impl Rng,
         snapshot: Option<Snapshot>,
     ) -> EvolutionResult
     where
-        T: Strategy,
-        F: Fn(u64, &[f32]) -> bool,
+        T: Challenger,
+        C: TerminationCriterion,
     {
+        let mut settings = self.initialize(strategy, config, rng, snapshot)?;
+        loop {
+            let snapshot = self
+                .advance(strategy, config, &mut settings, &mut is_complete, rng)
+                .await?;
+            if matches!(
+                *self.status.lock()?,
+                EvolutionStatus::Halted | EvolutionStatus::Completed
+            ) {
+                return Ok(snapshot);
+            }
+        }
+    }
+
+    /// Validates the settings and sets up the initial population, leaving the engine running.
+    fn initialize<T: Challenger>(
+        &mut self,
+        strategy: &T,
+        config: &EvolutionConfig,
+        rng: &mut impl Rng,
+        snapshot: Option<Snapshot>,
+    ) -> Result<ExecutionSettings, EvolutionError> {
+        let seed = self.seed.take();
+
         // Validate configuration
+        #[cfg(feature = "validation")]
         config.validate()?;
+        if let Some(crossover) = config
+            .generation_renewal_config
+            .as_ref()
+            .and_then(|c| c.crossover.as_ref())
+        {
+            crossover.crossover_type.validate(strategy.genome_size())?;
+        }
+        let generation_renewal_config = config.generation_renewal_config.as_ref();
+        let settings = resolve_settings(generation_renewal_config, config.population_size);
+        let strategy_genome_size = strategy.genome_size();
+        if let Some(snapshot) = snapshot.as_ref() {
+            check_snapshot(
+                snapshot,
+                strategy_genome_size,
+                settings.reserved_size,
+                config.population_size,
+            )?;
+        }
 
         // Run only from fresh engine
         if !self.change_status(
@@ -139,268 +697,467 @@ impl EvolutionEngine {
             return Err(EvolutionError::InvalidStatus(status));
         }
 
-        let generation_renewal_config = config.generation_renewal_config.as_ref();
-        let settings = resolve_settings(generation_renewal_config, config.population_size);
         debug!("Running evolution with settings: {:?}", settings);
 
-        let genome_size = strategy.genome_size();
-
         self.snapshot = snapshot.unwrap_or_else(|| {
-            let evaluations = get_random_genomes_iter(config.population_size, genome_size, rng)
+            let seeds = self.initial_genomes(strategy_genome_size, config.population_size);
+            let randoms = get_random_genomes_iter(
+                config.population_size - seeds.len(),
+                strategy_genome_size,
+                rng,
+            )
+            .map(|mut genome| {
+                strategy.repair(&mut genome);
+                genome
+            });
+            let evaluations = seeds
+                .into_iter()
+                .chain(randoms)
                 .into_evaluations()
                 .collect();
             Snapshot {
                 evaluations,
                 generation: 0,
+                evaluation_count: 0,
+                hall_of_fame: vec![],
+                stats: None,
+                diversity: None,
+                parameters: None,
+                metadata: None,
+                lineage: None,
             }
         });
-        self.change_status::<fn(EvolutionStatus) -> bool>(EvolutionStatus::Running, None)?;
-        loop {
-            trace!("Running generation {}", self.snapshot.generation);
-            // Try to halt the evolution if status Halting is set
-            if self.change_status(
-                EvolutionStatus::Halted,
-                Some(&|s| s == EvolutionStatus::Halting),
-            )? {
-                debug!("Interruption of evolution by detecting halt request");
-                break;
+        for evaluation in self.snapshot.evaluations.iter_mut() {
+            if evaluation.genome.len() == strategy_genome_size {
+                settings.reserve_tail(&mut evaluation.genome);
+            }
+        }
+        self.snapshot.parameters = Some(RunParameters {
+            seed,
+            config: config.clone(),
+            cloning_count: settings.cloning_pool.count,
+            crossover_count: settings.crossover_pool.count,
+            randoms_count: settings.randoms_count,
+            reserved_size: settings.reserved_size,
+        });
+        let started_at = self.clock.now();
+        self.snapshot.metadata = Some(RunMetadata {
+            id: run_id(rng),
+            started_at,
+            ended_at: None,
+            labels: self.labels.clone(),
+        });
+        self.started_at = Some(started_at);
+        let population_size = self.snapshot.evaluations.len();
+        self.snapshot.lineage = match self.snapshot.lineage.take() {
+            Some(lineage)
+                if self.lineage_tracking && lineage.population.len() == population_size =>
+            {
+                Some(lineage)
+            }
+            _ if self.lineage_tracking => {
+                Some(Lineage::new(self.snapshot.generation, population_size))
             }
+            _ => None,
+        };
+        if let Some(speciation) = self.speciation.as_mut() {
+            speciation.clear();
+        }
+        if let Some(tracker) = self.operator_tracker.as_mut() {
+            tracker.clear(&settings);
+        }
+        self.started_from = (self.snapshot.generation, self.snapshot.evaluation_count);
+        self.best_fitness = None;
+        self.progress = None;
+        self.restarts = 0;
+        if let Some(history) = self.history.as_mut() {
+            history.clear();
+        }
+        if let Some(stagnation) = self.stagnation.as_mut() {
+            *stagnation = Stagnation::new(stagnation.generations(), stagnation.epsilon());
+        }
+        self.change_status::<fn(EvolutionStatus) -> bool>(EvolutionStatus::Running, None)?;
+        Ok(settings)
+    }
+
+    /// Seeded genomes of the first generation, up to the population size.
+    fn initial_genomes(&self, genome_size: usize, population_size: usize) -> Vec<Genome> {
+        let InitialPopulation::Seeded(genomes) = &self.initial_population else {
+            return vec![];
+        };
+        let (genomes, invalids): (Vec<_>, Vec<_>) = genomes
+            .iter()
+            .cloned()
+            .partition(|genome| genome.len() == genome_size);
+        if !invalids.is_empty() {
+            warn!(
+                "Dropping {} seeded genome(s) not matching genome size {}",
+                invalids.len(),
+                genome_size
+            );
+        }
+        genomes.into_iter().take(population_size).collect()
+    }
+
+    /// Evaluates the current generation and renews it unless the run is halted or completed,
+    /// returning the evaluated generation.
+    async fn advance<T, C>(
+        &mut self,
+        strategy: &T,
+        config: &EvolutionConfig,
+        settings: &mut ExecutionSettings,
+        is_complete: &mut C,
+        rng: &mut impl Rng,
+    ) -> EvolutionResult
+    where
+        T: Challenger,
+        C: TerminationCriterion,
+    {
+        trace!("Running generation {}", self.snapshot.generation);
+        // Try to halt the evolution if status Halting is set
+        if self.end(
+            EvolutionStatus::Halted,
+            Some(&|s| s == EvolutionStatus::Halting),
+        )? {
+            debug!("Interruption of evolution by detecting halt request");
+            #[cfg(feature = "checkpoint")]
+            self.save_checkpoint(config, rng);
+            return Ok(self.snapshot.clone());
+        }
+        #[cfg(feature = "checkpoint")]
+        if self.checkpoint.as_ref().is_some_and(|(every, _)| {
+            self.snapshot.generation > 0 && self.snapshot.generation.checked_rem(*every) == Some(0)
+        }) {
+            self.save_checkpoint(config, rng);
+        }
 
-            self.notify_observers(EventType::GenerationCreated);
-            let challenge_runs = self
+        let created_at = self.clock.now();
+        self.notify_observers(EventType::GenerationCreated);
+        for hook in self.hooks.get_mut()?.iter_mut() {
+            hook.on_generation_start(self.snapshot.generation, &mut self.snapshot.evaluations);
+        }
+        let strategy_genome_size = strategy.genome_size();
+        let mut fitnesses = self.evaluate(strategy, settings, rng).await?;
+        // Assessed before the local search refines the offspring
+        let assessed = match self.operator_tracker.as_mut() {
+            Some(tracker) => {
+                let assessed = tracker.assess(&self.snapshot.evaluations);
+                tracker.apply(settings);
+                assessed.then(|| tracker.stats().to_vec())
+            }
+            None => None,
+        };
+        let improved_count = self.search_locally(strategy, rng).await?;
+        if improved_count > 0 {
+            debug!("Local search improved {} individual(s)", improved_count);
+            fitnesses = self
                 .snapshot
                 .evaluations
                 .iter()
-                .map(|evaluation| run_challenge(&evaluation.genome, strategy));
-
-            let fitnesses = join_all(challenge_runs)
-                .await
-                .into_iter()
-                .collect::<Result<Vec<_>, EvolutionError>>()?;
+                .map(|e| e.fitness)
+                .collect();
+        }
+        self.snapshot.stats = GenerationStats::compute(&self.snapshot.evaluations);
+        self.snapshot.diversity = self.diversity(strategy_genome_size);
+        self.notify_observers(EventType::Evaluated);
+        if let Some(stats) = assessed {
+            self.notify_observers(EventType::OperatorsAssessed(stats));
+        }
 
-            fitnesses
+        self.process_commands(settings).await?;
+        let injected = self.apply_injections(strategy, settings, rng).await?;
+        let injected_count = injected.len();
+        if let Some(lineage) = self.snapshot.lineage.as_mut() {
+            lineage.inject(self.snapshot.generation, &injected);
+        }
+        if injected_count > 0 {
+            debug!("Injected {} genome(s)", injected_count);
+            fitnesses = self
+                .snapshot
+                .evaluations
                 .iter()
-                .enumerate()
-                .for_each(|(i, &f)| self.snapshot.evaluations[i].fitness = f);
-            self.notify_observers(EventType::Evaluated);
+                .map(|e| e.fitness)
+                .collect();
+            self.snapshot.stats = GenerationStats::compute(&self.snapshot.evaluations);
+            self.snapshot.diversity = self.diversity(strategy_genome_size);
+            self.notify_observers(EventType::Injected(injected_count));
+        }
 
-            if (is_complete)(self.snapshot.generation, &fitnesses) {
-                debug!("Completion reached");
-                self.change_status::<fn(EvolutionStatus) -> bool>(
-                    EvolutionStatus::Completed,
-                    None,
-                )?;
-                break;
+        let hooks = self.hooks.get_mut()?;
+        if !hooks.is_empty() {
+            for hook in hooks.iter_mut() {
+                hook.on_generation_end(self.snapshot.generation, &mut self.snapshot.evaluations);
             }
-
-            self.snapshot.evaluations = self
-                .get_new_generation(genome_size, &settings, rng)?
-                .into_iter()
-                .into_evaluations()
+            fitnesses = self
+                .snapshot
+                .evaluations
+                .iter()
+                .map(|e| e.fitness)
                 .collect();
-            self.snapshot.generation += 1;
+            self.snapshot.stats = GenerationStats::compute(&self.snapshot.evaluations);
+            self.snapshot.diversity = self.diversity(strategy_genome_size);
         }
-        Ok(self.snapshot.clone())
-    }
+        let restart = self.record_evaluated(&fitnesses);
+        if let Some(speciation) = self.speciation.as_mut() {
+            speciation.speciate(&self.snapshot.evaluations, strategy_genome_size);
+        }
+        self.notify_observers(EventType::GenerationCompleted {
+            duration: self.clock.since(created_at),
+        });
 
-    fn get_clones(
-        &self,
-        pool: &GeneticPool,
-        rng: &mut impl Rng,
-    ) -> Result<Vec<Genome>, EvolutionError> {
-        let clones = if pool.count > 0 {
-            let selected_indexes_iter = select(
-                &self.snapshot.evaluations,
-                pool.count,
-                pool.selection_type,
-                rng,
-            )?
-            .into_iter();
-
-            if pool.mutation_rate > 0.0 {
-                selected_indexes_iter
-                    .map(|index| {
-                        let mut genome = self.snapshot.evaluations[index].genome.clone();
-                        mutate(&mut genome, pool.mutation_rate, rng);
-                        genome
-                    })
-                    .collect()
-            } else {
-                selected_indexes_iter
-                    .map(|index| self.snapshot.evaluations[index].genome.clone())
-                    .collect()
-            }
-        } else {
-            vec![]
+        let elapsed = self.elapsed();
+        if let Some(history) = self.history.as_mut() {
+            history.record(&self.snapshot, elapsed);
+        }
+
+        let progress = Progress {
+            generation: self.snapshot.generation,
+            fitnesses: &fitnesses,
+            evaluation_count: self.snapshot.evaluation_count,
+            elapsed,
+            diversity: self.snapshot.diversity.as_ref(),
+        };
+        let out_of_time = config
+            .max_duration
+            .is_some_and(|max_duration| progress.elapsed >= max_duration);
+        if out_of_time {
+            debug!("Time budget of {:?} exhausted", config.max_duration);
+        }
+        let completion = is_complete
+            .completion(&progress)
+            .into_iter()
+            .chain(
+                config
+                    .max_duration
+                    .map(|max_duration| duration_share(progress.elapsed, max_duration)),
+            )
+            .reduce(f32::max);
+        let (generation, evaluation_count) = self.started_from;
+        let run_progress = RunProgress::new(
+            progress.generation,
+            progress.evaluation_count,
+            (
+                progress
+                    .generation
+                    .saturating_add(1)
+                    .saturating_sub(generation),
+                progress.evaluation_count.saturating_sub(evaluation_count),
+            ),
+            progress.elapsed,
+            completion,
+        );
+        self.progress = Some(run_progress);
+        self.notify_observers(EventType::Progress(run_progress));
+        if is_complete.try_is_met(&progress)? || out_of_time {
+            debug!("Completion reached");
+            self.end::<fn(EvolutionStatus) -> bool>(EvolutionStatus::Completed, None)?;
+            return Ok(self.snapshot.clone());
+        }
+
+        let (evaluations, origins) = match self.restart_policy.filter(|_| restart) {
+            Some(policy) => self.restart(strategy, config, policy, settings, rng),
+            None => self.get_new_generation(strategy, settings, rng)?,
+        };
+        self.dump_renewal(settings, &evaluations, &origins);
+        if let Some(tracker) = self.operator_tracker.as_mut() {
+            tracker.renew(&self.snapshot.evaluations, &evaluations, &origins);
+        }
+        let generation = self.snapshot.generation + 1;
+        let lineage = self.snapshot.lineage.take().map(|mut lineage| {
+            lineage.renew(generation, &origins);
+            lineage
+        });
+        let renewed = Snapshot {
+            generation,
+            evaluations,
+            evaluation_count: self.snapshot.evaluation_count,
+            hall_of_fame: self.snapshot.hall_of_fame.clone(),
+            stats: None,
+            diversity: None,
+            parameters: self.snapshot.parameters.clone(),
+            metadata: self.snapshot.metadata.clone(),
+            lineage,
         };
-        Ok(clones)
+        Ok(std::mem::replace(&mut self.snapshot, renewed))
+    }
+
+    /// Applies the commands received through the handles, waiting for the run to be resumed
+    /// or halted when paused without blocking the executor.
+    async fn process_commands(
+        &mut self,
+        settings: &mut ExecutionSettings,
+    ) -> Result<(), EvolutionError> {
+        while let Ok(Some(command)) = self.command_receiver.get_mut()?.try_next() {
+            self.process_command(command, settings)?;
+        }
+        while *self.status.lock()? == EvolutionStatus::Paused {
+            let command = self
+                .command_receiver
+                .get_mut()?
+                .next()
+                .await
+                .ok_or(EvolutionError::Disconnected)?;
+            self.process_command(command, settings)?;
+        }
+        Ok(())
     }
 
-    fn get_offsprings(
+    fn process_command(
         &self,
-        genome_size: usize,
-        pool: &GeneticPool,
-        rng: &mut impl Rng,
-    ) -> Result<Vec<Genome>, EvolutionError> {
-        let offsprings = if pool.count > 0 {
-            let selected_indexes_iter = select_couples(
-                &self.snapshot.evaluations,
-                pool.count,
-                pool.selection_type,
-                rng,
-            )?
-            .into_iter();
-
-            if pool.mutation_rate > 0.0 {
-                selected_indexes_iter
-                    .map(|(p1, p2)| {
-                        let mut offspring = crossover(
-                            (
-                                &self.snapshot.evaluations[p1].genome,
-                                &self.snapshot.evaluations[p2].genome,
-                            ),
-                            genome_size,
-                            rng,
-                        );
-                        mutate(&mut offspring, pool.mutation_rate, rng);
-
-                        offspring
-                    })
-                    .collect()
-            } else {
-                selected_indexes_iter
-                    .map(|(p1, p2)| {
-                        crossover(
-                            (
-                                &self.snapshot.evaluations[p1].genome,
-                                &self.snapshot.evaluations[p2].genome,
-                            ),
-                            genome_size,
-                            rng,
-                        )
-                    })
-                    .collect()
+        command: Command,
+        settings: &mut ExecutionSettings,
+    ) -> Result<(), EvolutionError> {
+        trace!("Processing command {:?}", command);
+        match command {
+            Command::Halt => {
+                self.halt()?;
             }
-        } else {
-            vec![]
-        };
-        Ok(offsprings)
+            Command::Pause => {
+                self.change_status(
+                    EvolutionStatus::Paused,
+                    Some(&|s| s == EvolutionStatus::Running),
+                )?;
+            }
+            Command::Resume => {
+                self.change_status(
+                    EvolutionStatus::Running,
+                    Some(&|s| s == EvolutionStatus::Paused),
+                )?;
+            }
+            Command::Inject(genomes, policy) => self.inject(genomes, policy)?,
+            Command::SetMutationRate(mutation_rate) => {
+                if (0.0..=1.0).contains(&mutation_rate) {
+                    settings.cloning_pool.mutation_rate = mutation_rate;
+                    settings.crossover_pool.mutation_rate = mutation_rate;
+                } else {
+                    warn!("Ignoring invalid mutation rate {}", mutation_rate);
+                }
+            }
+            Command::RequestSnapshot(reply) => {
+                // The requester may have stopped waiting
+                let _ = reply.send(self.snapshot.clone());
+            }
+        }
+        Ok(())
     }
 
-    fn get_new_generation(
-        &self,
-        genome_size: usize,
+    async fn apply_injections<T: Challenger>(
+        &mut self,
+        strategy: &T,
         settings: &ExecutionSettings,
         rng: &mut impl Rng,
-    ) -> Result<Vec<Genome>, EvolutionError> {
-        // Get clones
-        let clones = self.get_clones(&settings.cloning_pool, rng)?;
-
-        // Get offsprings
-        let offsprings = self.get_offsprings(genome_size, &settings.crossover_pool, rng)?;
+    ) -> Result<Vec<usize>, EvolutionError> {
+        let injections = std::mem::take(&mut *self.injections.lock()?);
+        let genome_size = strategy.genome_size();
 
-        // Get random genomes
-        let randoms = if settings.randoms_count > 0 {
-            get_random_genomes_iter(settings.randoms_count, genome_size, rng).collect()
-        } else {
-            vec![]
-        };
+        let mut positions = vec![];
+        for Injection { genomes, policy } in injections {
+            let (genomes, invalids): (Vec<_>, Vec<_>) = genomes
+                .into_iter()
+                .partition(|genome| genome.len() == genome_size);
+            if !invalids.is_empty() {
+                warn!(
+                    "Dropping {} injected genome(s) not matching genome size {}",
+                    invalids.len(),
+                    genome_size
+                );
+            }
 
-        Ok([clones.as_slice(), offsprings.as_slice(), randoms.as_slice()].concat())
-    }
-}
-
-fn crossover(parents: (&Genome, &Genome), genome_size: usize, rng: &mut impl Rng) -> Genome {
-    let crossover_point = rng.gen_range(0..genome_size);
-    [&parents.0[..crossover_point], &parents.1[crossover_point..]].concat()
-}
-
-fn get_random_genomes_iter(
-    count: usize,
-    genome_size: usize,
-    rng: &mut impl Rng,
-) -> impl Iterator<Item = Genome> + '_ {
-    (0..count).map(move |_| {
-        (&mut *rng)
-            .sample_iter(Standard)
-            .take(genome_size)
-            .collect()
-    })
-}
-
-fn mutate(genome: &mut Genome, mutation_rate: f32, rng: &mut impl Rng) {
-    for part in genome.iter_mut() {
-        if rng.gen::<f32>() < mutation_rate {
-            *part = rng.gen();
-        }
-    }
-}
-
-fn resolve_settings(
-    generation_renewal_config: Option<&GenerationRenewalConfig>,
-    population_size: usize,
-) -> ExecutionSettings {
-    let cloning_pool = GeneticPool::from_optional_params(
-        generation_renewal_config.and_then(|c| c.cloning.as_ref()),
-        population_size,
-    );
-    let crossover_pool = GeneticPool::from_optional_params(
-        generation_renewal_config.and_then(|c| c.crossover.as_ref()),
-        population_size,
-    );
-
-    ExecutionSettings {
-        cloning_pool,
-        crossover_pool,
-        randoms_count: population_size - (cloning_pool.count + crossover_pool.count),
-    }
-}
-
-async fn run_challenge<T: Strategy>(genome: &Genome, strategy: &T) -> Result<f32, EvolutionError> {
-    let fitness = strategy.evaluate(genome);
-    if (0.0..=1.0).contains(&fitness) {
-        Ok(fitness)
-    } else {
-        Err(EvolutionError::InvalidEvaluation(fitness))
+            let contexts = self.contexts(genomes.len(), rng);
+            let challenges = run_samples(
+                strategy,
+                genomes.iter().zip(contexts).collect(),
+                self.resampling,
+            )
+            .await?;
+            self.snapshot.evaluation_count += (challenges.len() * self.resampling) as u64;
+            let evaluations = genomes
+                .into_iter()
+                .zip(challenges)
+                .map(|(mut genome, challenge)| {
+                    settings.reserve_tail(&mut genome);
+                    Evaluation {
+                        genome,
+                        fitness: challenge.fitness,
+                        fitness_variance: challenge.fitness_variance,
+                        objectives: challenge.objectives,
+                        age: 0,
+                        evaluated: challenge.evaluated,
+                        phenotype: challenge.phenotype,
+                        metadata: challenge.metadata,
+                    }
+                })
+                .collect();
+            positions.extend(insert(
+                &mut self.snapshot.evaluations,
+                evaluations,
+                policy,
+                rng,
+            ));
+        }
+        Ok(positions)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use std::{
-        rc::Rc,
-        sync::{Arc, Mutex},
+        collections::{BTreeMap, HashSet},
+        env,
+        error::Error,
+        fs, iter, process,
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc, Mutex,
+        },
+        thread,
+        time::{Duration, SystemTime},
     };
 
+    #[cfg(feature = "checkpoint")]
+    use crate::evolution::Checkpoint;
+    #[cfg(feature = "async")]
+    use crate::{adaptation::AsyncStrategy, evolution::TimeoutPolicy};
     use crate::{
-        adaptation::Strategy,
+        adaptation::{BatchStrategy, DynStrategy, Erased, EvalContext, Strategy},
+        clock::{Clock, MockClock},
+        crossover::CrossoverType,
         evolution::{
-            evolution_engine::get_random_genomes_iter, genetic_pool::GeneticPool, EventType,
-            EvolutionConfig, EvolutionError, EvolutionStatus, GenerationRenewalConfig,
-            GeneticRenewalParam, Snapshot,
+            default_renewal_stages, engine_handle::Command, genetic_pool::GeneticPool,
+            AdaptiveSelection, Deduplication, Elitism, EventType, EvolutionConfig,
+            EvolutionEngineBuilder, EvolutionError, EvolutionStatus, Fallible, GeneDistance,
+            GenerationRenewalConfig, GeneticRenewalParam, HistoryDetail, InitialPopulation,
+            LineageNode, MaxEvaluations, MaxGenerations, MinDiversity, Operator, Progress, Renewal,
+            RenewalStage, ReplacementPolicy, RestartPolicy, RunMetadata, RunParameters,
+            RunProgress, Snapshot, Stagnation, TerminationCriterion,
         },
+        local_search::HillClimbing,
+        mutation::{decode_mutation_rate, MutationType, MUTATION_RATE_GENE_SIZE},
         selection::SelectionType,
-        Evaluation, Genome,
+        Genome,
     };
-    use common::subject_observer::{Observer, Subject};
+    use common::subject_observer::{Observer, SharedObserver, Subject};
     use common_test::get_seeded_rng;
-    use futures::executor::block_on;
+    use futures::{channel::oneshot, executor::block_on};
     use mockall::{
         mock,
         predicate::{always, eq},
     };
-    use rand::{distributions::Standard, seq::IteratorRandom, Rng};
+    use rand::{rngs::StdRng, seq::IteratorRandom, Rng, RngCore};
     use strum::IntoEnumIterator;
 
-    use super::{resolve_settings, run_challenge, EvolutionEngine};
+    use super::{resolve_settings, EvolutionEngine};
 
     mock! {
         TestStrategy {}
 
         impl Strategy for TestStrategy {
+            type Phenotype = Genome;
 
             fn genome_size(&self) -> usize;
 
+            fn decode(&self, genome: &Genome) -> Genome;
+
             fn evaluate<'a>(&self, genome: &'a Genome) -> f32;
 
         }
@@ -422,7 +1179,7 @@ mod tests {
         let mut rng = get_seeded_rng().unwrap();
         let mut engine = EvolutionEngine::default();
         let not_running = EvolutionStatus::iter()
-            .filter(|s| *s != EvolutionStatus::Running)
+            .filter(|s| ![EvolutionStatus::Running, EvolutionStatus::Paused].contains(s))
             .choose(&mut rng)
             .unwrap();
 
@@ -430,7 +1187,7 @@ mod tests {
         engine.status = Arc::new(Mutex::new(not_running));
         let result = engine.halt()?;
         // Then
-        assert!(!result, "Should not halt when not running");
+        assert!(!result, "Should not halt when not running nor paused");
 
         // When
         engine.status = Arc::new(Mutex::new(EvolutionStatus::Running));
@@ -454,7 +1211,7 @@ mod tests {
         let statuses = EvolutionStatus::iter().choose_multiple(&mut rng, 3);
         let mut observer = MockTestObserver::new();
         observer.expect_update().times(2).return_const(());
-        engine.register_observer(Rc::new(observer));
+        engine.register_observer(Arc::new(observer));
 
         // When
         engine.status = Arc::new(Mutex::new(statuses[0]));
@@ -493,62 +1250,117 @@ mod tests {
         let population_size = rng.gen_range(10..128);
         let genome_size = rng.gen_range(1usize..10);
         let mut strategy = MockTestStrategy::new();
+        strategy.expect_decode().returning(|genome| genome.clone());
+        let mut engine = EvolutionEngine::default();
+        #[cfg(feature = "validation")]
+        {
+            let config = EvolutionConfig {
+                generation_renewal_config: Some(GenerationRenewalConfig {
+                    cloning: Some(GeneticRenewalParam {
+                        mutation_rate: None,
+                        ratio: 2.0,
+                        selection_type: SelectionType::Chance,
+                        ..Default::default()
+                    }),
+                    crossover: None,
+                    random_ratio: None,
+                }),
+                population_size,
+                max_duration: None,
+            };
+
+            // When
+            let result =
+                block_on(engine.run(&strategy, &config, MaxGenerations(2), &mut rng, None));
+
+            // Then
+            assert!(
+                matches!(result, Err(EvolutionError::InvalidSettings(_))),
+                "Should validate configuration"
+            );
+        }
+
+        // Given
         let config = EvolutionConfig {
             generation_renewal_config: Some(GenerationRenewalConfig {
-                cloning: Some(GeneticRenewalParam {
-                    mutation_rate: None,
-                    ratio: 2.0,
-                    selection_type: SelectionType::Chance,
+                cloning: None,
+                crossover: Some(GeneticRenewalParam {
+                    ratio: 0.5,
+                    crossover_type: CrossoverType::KPoint(genome_size),
+                    ..Default::default()
                 }),
-                crossover: None,
+                random_ratio: None,
             }),
             population_size,
+            max_duration: None,
         };
-        let mut engine = EvolutionEngine::default();
+        let mut invalid_strategy = MockTestStrategy::new();
+        invalid_strategy
+            .expect_genome_size()
+            .return_const(genome_size);
 
         // When
         let result = block_on(engine.run(
-            &strategy,
+            &invalid_strategy,
             &config,
-            |generation, _| generation > 1,
+            MaxGenerations(2),
             &mut rng,
             None,
         ));
 
         // Then
         assert!(
-            matches!(result, Err(EvolutionError::InvalidSettings(_))),
-            "Should validate configuration"
+            matches!(result, Err(EvolutionError::InvalidCrossover(_))),
+            "Should validate crossover against genome size"
         );
 
         // Given
         let config = EvolutionConfig {
             generation_renewal_config: None,
             population_size,
+            max_duration: None,
         };
         strategy
             .expect_evaluate()
             .times(2 * population_size)
             .return_const(0.5);
         strategy.expect_genome_size().return_const(genome_size);
-        let mut engine = EvolutionEngine::default();
+        let mut engine = EvolutionEngine {
+            clock: Arc::new(MockClock::default()),
+            ..Default::default()
+        };
         let observer = build_observer_mock(&vec![
             EventType::StatusChanged(EvolutionStatus::Initializing),
             EventType::StatusChanged(EvolutionStatus::Running),
             EventType::GenerationCreated,
             EventType::Evaluated,
+            EventType::BestImproved {
+                fitness: 0.5,
+                generation: 0,
+            },
+            EventType::GenerationCompleted {
+                duration: Duration::ZERO,
+            },
+            EventType::Progress(RunProgress {
+                generation: 0,
+                evaluation_count: population_size as u64,
+                completion: Some(0.5),
+                eta: Some(Duration::ZERO),
+                ..Default::default()
+            }),
+            EventType::Progress(RunProgress {
+                generation: 1,
+                evaluation_count: 2 * population_size as u64,
+                completion: Some(1.0),
+                eta: Some(Duration::ZERO),
+                ..Default::default()
+            }),
             EventType::StatusChanged(EvolutionStatus::Completed),
         ]);
-        engine.register_observer(Rc::new(observer));
+        engine.register_observer(Arc::new(observer));
 
         // When
-        let result = block_on(engine.run(
-            &strategy,
-            &config,
-            |generation, _| generation > 0,
-            &mut rng,
-            None,
-        ));
+        let result = block_on(engine.run(&strategy, &config, MaxGenerations(1), &mut rng, None));
 
         // Then
         assert!(
@@ -557,13 +1369,7 @@ mod tests {
         );
 
         // When
-        let result = block_on(engine.run(
-            &strategy,
-            &config,
-            |generation, _| generation > 0,
-            &mut rng,
-            None,
-        ));
+        let result = block_on(engine.run(&strategy, &config, MaxGenerations(1), &mut rng, None));
 
         // Then
         assert!(
@@ -573,232 +1379,2539 @@ mod tests {
     }
 
     #[test]
-    fn test_evolution_engine_get_clones() {
-        let mut rng = get_seeded_rng().unwrap();
-        let mut engine = EvolutionEngine::default();
-        engine.snapshot = Snapshot {
-            evaluations: vec![
-                Evaluation {
-                    fitness: 0.5,
-                    genome: vec![3],
-                },
-                Evaluation {
-                    fitness: 0.2,
-                    genome: vec![5, 1],
-                },
-                Evaluation {
-                    fitness: 0.8,
-                    genome: vec![6, 3],
-                },
-            ],
-            generation: 0,
-        };
-
+    fn test_evolution_engine_inject() -> Result<(), EvolutionError> {
         // Given
-        let pool = GeneticPool {
-            count: 2,
-            mutation_rate: 0.0,
-            selection_type: SelectionType::Chance,
+        let mut rng = get_seeded_rng().unwrap();
+        let population_size = 10;
+        let genome_size = 4;
+        let injected: Genome = vec![42; genome_size];
+        let mut strategy = MockTestStrategy::new();
+        strategy.expect_decode().returning(|genome| genome.clone());
+        strategy.expect_genome_size().return_const(genome_size);
+        let expected = injected.clone();
+        strategy
+            .expect_evaluate()
+            .returning(move |genome| if *genome == expected { 0.9 } else { 0.5 });
+        let config = EvolutionConfig {
+            generation_renewal_config: None,
+            population_size,
+            max_duration: None,
         };
+        let mut engine = EvolutionEngine::default();
+        let mut observer = MockTestObserver::new();
+        observer
+            .expect_update()
+            .with(always(), eq(EventType::Injected(1)))
+            .times(1)
+            .return_const(());
+        observer.expect_update().return_const(());
+        engine.register_observer(Arc::new(observer));
 
         // When
-        let result = engine.get_clones(&pool, &mut rng).unwrap();
+        engine.inject(
+            vec![injected.clone(), vec![1; genome_size + 1]],
+            ReplacementPolicy::Worst,
+        )?;
+        let result = block_on(engine.run(&strategy, &config, MaxGenerations(0), &mut rng, None))?;
 
         // Then
         assert_eq!(
-            pool.count,
-            result.len(),
-            "Should return the count of clone defined by the pool when mutation rate is 0"
+            population_size,
+            result.evaluations.len(),
+            "Should replace individuals of the population"
+        );
+        assert_eq!(
+            1,
+            result
+                .evaluations
+                .iter()
+                .filter(|e| e.genome == injected && e.fitness == 0.9)
+                .count(),
+            "Should evaluate the injected genome and drop the invalid one"
+        );
+        assert!(
+            engine.injections.lock()?.is_empty(),
+            "Should consume the injections"
         );
 
+        Ok(())
+    }
+
+    #[test]
+    fn test_evolution_engine_handle() -> Result<(), EvolutionError> {
         // Given
-        let pool = GeneticPool {
-            count: 2,
-            mutation_rate: 0.5,
-            selection_type: SelectionType::Chance,
+        let mut rng = get_seeded_rng().unwrap();
+        let population_size = 10;
+        let mut strategy = MockTestStrategy::new();
+        strategy.expect_decode().returning(|genome| genome.clone());
+        strategy.expect_genome_size().return_const(4usize);
+        strategy.expect_evaluate().return_const(0.5);
+        let config = EvolutionConfig {
+            generation_renewal_config: None,
+            population_size,
+            max_duration: None,
         };
+        let mut engine = EvolutionEngine {
+            clock: Arc::new(MockClock::default()),
+            ..Default::default()
+        };
+        engine.register_observer(Arc::new(build_observer_mock(&[
+            EventType::StatusChanged(EvolutionStatus::Initializing),
+            EventType::StatusChanged(EvolutionStatus::Running),
+            EventType::GenerationCreated,
+            EventType::Evaluated,
+            EventType::BestImproved {
+                fitness: 0.5,
+                generation: 0,
+            },
+            EventType::GenerationCompleted {
+                duration: Duration::ZERO,
+            },
+            EventType::Progress(RunProgress {
+                generation: 0,
+                evaluation_count: population_size as u64,
+                completion: Some(1.0 / u64::MAX as f32),
+                eta: Some(Duration::ZERO),
+                ..Default::default()
+            }),
+            EventType::StatusChanged(EvolutionStatus::Paused),
+            EventType::StatusChanged(EvolutionStatus::Halting),
+            EventType::StatusChanged(EvolutionStatus::Halted),
+        ])));
+        let handle = engine.handle();
+        handle.pause()?;
 
         // When
-        let result = engine.get_clones(&pool, &mut rng).unwrap();
+        let controller = thread::spawn(move || {
+            let snapshot = handle.request_snapshot()?.recv().unwrap();
+            handle.halt()?;
+            Ok::<_, EvolutionError>(snapshot)
+        });
+        let result =
+            block_on(engine.run(&strategy, &config, MaxGenerations(u64::MAX), &mut rng, None))?;
 
         // Then
+        let snapshot = controller.join().unwrap()?;
+        assert_eq!(
+            0, snapshot.generation,
+            "Should answer requests while paused"
+        );
         assert_eq!(
-            pool.count,
-            result.len(),
-            "Should return the count of clone defined by the pool when mutation rate is greater than 0"
+            1, result.generation,
+            "Should halt at the end of the generation"
         );
+        assert_eq!(EvolutionStatus::Halted, *engine.status.lock()?);
+
+        Ok(())
+    }
+
+    /// Signals the first pause of the run.
+    struct PauseSignal(Mutex<Option<oneshot::Sender<()>>>);
+
+    impl Observer<EvolutionEngine, EventType> for PauseSignal {
+        fn update(&self, _: &EvolutionEngine, event: EventType) {
+            if event == EventType::StatusChanged(EvolutionStatus::Paused) {
+                if let Some(sender) = self.0.lock().unwrap().take() {
+                    sender.send(()).unwrap();
+                }
+            }
+        }
     }
 
     #[test]
-    fn test_evolution_engine_get_offsprings() {
+    fn test_evolution_engine_pause_should_not_block_executor() -> Result<(), EvolutionError> {
+        // Given
         let mut rng = get_seeded_rng().unwrap();
-        let genome_size = rng.gen_range(1usize..10);
+        let config = EvolutionConfig {
+            generation_renewal_config: None,
+            population_size: 10,
+            max_duration: None,
+        };
+        let (sender, paused) = oneshot::channel();
         let mut engine = EvolutionEngine::default();
-        engine.snapshot = Snapshot {
-            evaluations: vec![
-                Evaluation {
-                    fitness: 0.5,
-                    genome: rng
-                        .clone()
-                        .sample_iter(Standard)
-                        .take(genome_size)
-                        .collect(),
-                },
-                Evaluation {
-                    fitness: 0.2,
-                    genome: rng
-                        .clone()
-                        .sample_iter(Standard)
-                        .take(genome_size)
-                        .collect(),
-                },
-                Evaluation {
-                    fitness: 0.8,
-                    genome: rng
-                        .clone()
-                        .sample_iter(Standard)
-                        .take(genome_size)
-                        .collect(),
-                },
-            ],
-            generation: 0,
+        engine.register_observer(Arc::new(PauseSignal(Mutex::new(Some(sender)))));
+        let handle = engine.handle();
+        handle.pause()?;
+
+        // When
+        let controller = async {
+            paused.await.unwrap();
+            handle.halt()
         };
+        let (result, halted) = block_on(async {
+            futures::join!(
+                engine.start(&Sum, &config, MaxGenerations(u64::MAX), &mut rng),
+                controller
+            )
+        });
+
+        // Then
+        halted?;
+        assert_eq!(
+            1, result?.generation,
+            "Should let the executor run the other tasks while paused"
+        );
+
+        Ok(())
+    }
 
+    #[test]
+    fn test_evolution_engine_handle_inject() -> Result<(), EvolutionError> {
         // Given
-        let pool = GeneticPool {
-            count: 2,
-            mutation_rate: 0.0,
-            selection_type: SelectionType::Chance,
+        let config = EvolutionConfig {
+            generation_renewal_config: None,
+            population_size: 10,
+            max_duration: None,
         };
+        let mut engine = EvolutionEngine::default();
+        let handle = engine.handle();
+        handle.pause()?;
 
         // When
-        let result = engine.get_offsprings(genome_size, &pool, &mut rng).unwrap();
+        let controller = thread::spawn(move || {
+            handle.request_snapshot()?.recv().unwrap();
+            handle.inject(vec![vec![255; 4]], ReplacementPolicy::Worst)?;
+            handle.resume()
+        });
+        let result = block_on(engine.start(
+            &Sum,
+            &config,
+            MaxGenerations(0),
+            &mut get_seeded_rng().unwrap(),
+        ))?;
 
         // Then
-        assert_eq!(
-            pool.count,
-            result.len(),
-            "Should return the count of clone defined by the pool when mutation rate is 0"
+        controller.join().unwrap()?;
+        assert!(
+            result
+                .evaluations
+                .iter()
+                .any(|e| e.genome == vec![255; 4] && e.fitness == 1.0),
+            "Should insert the genomes injected while running"
         );
 
+        Ok(())
+    }
+
+    #[test]
+    fn test_evolution_engine_run_with_self_adaptive_mutation() -> Result<(), EvolutionError> {
         // Given
-        let pool = GeneticPool {
-            count: 2,
-            mutation_rate: 0.5,
-            selection_type: SelectionType::Chance,
+        let mut rng = get_seeded_rng().unwrap();
+        let mut strategy = MockTestStrategy::new();
+        strategy.expect_decode().returning(|genome| genome.clone());
+        strategy.expect_genome_size().return_const(4usize);
+        strategy
+            .expect_evaluate()
+            .withf(|genome| genome.len() == 4)
+            .return_const(0.5);
+        let config = EvolutionConfig {
+            generation_renewal_config: Some(GenerationRenewalConfig {
+                cloning: None,
+                crossover: Some(GeneticRenewalParam {
+                    mutation_rate: Some(0.05),
+                    mutation_type: MutationType::SelfAdaptive {
+                        tau: 0.2,
+                        mutation: Box::new(MutationType::Random),
+                    },
+                    ratio: 0.8,
+                    ..Default::default()
+                }),
+                random_ratio: None,
+            }),
+            population_size: 10,
+            max_duration: None,
         };
+        let mut engine = EvolutionEngine::default();
 
         // When
-        let result = engine.get_offsprings(genome_size, &pool, &mut rng).unwrap();
+        let result = block_on(engine.run(&strategy, &config, MaxGenerations(3), &mut rng, None))?;
 
         // Then
-        assert_eq!(
-            pool.count,
-            result.len(),
-            "Should return the count of clone defined by the pool when mutation rate is greater than 0"
+        assert!(
+            result
+                .evaluations
+                .iter()
+                .all(|e| e.genome.len() == 4 + MUTATION_RATE_GENE_SIZE),
+            "Should reserve the rate tail of the genomes"
+        );
+        assert!(
+            result
+                .evaluations
+                .iter()
+                .filter_map(|e| decode_mutation_rate(&e.genome))
+                .any(|rate| rate != 0.05),
+            "Should evolve the mutation rates carried by genomes"
         );
+
+        Ok(())
     }
 
     #[test]
-    fn test_evolution_engine_snapshot_should_be_defaulted_before_run() {
+    fn test_evolution_engine_process_command_set_mutation_rate() -> Result<(), EvolutionError> {
         // Given
         let engine = EvolutionEngine::default();
+        let mut settings = resolve_settings(None, 10);
 
         // When
-        let result = engine.snapshot();
+        engine.process_command(Command::SetMutationRate(0.3), &mut settings)?;
+        engine.process_command(Command::SetMutationRate(1.3), &mut settings)?;
 
         // Then
-        assert_eq!(Snapshot::default(), result);
+        assert_eq!(0.3, settings.cloning_pool.mutation_rate);
+        assert_eq!(
+            0.3, settings.crossover_pool.mutation_rate,
+            "Should ignore invalid rates"
+        );
+
+        Ok(())
+    }
+
+    struct SlowObserver {
+        clock: Arc<MockClock>,
+        latency: Duration,
+    }
+
+    impl Observer<EvolutionEngine, EventType> for SlowObserver {
+        fn update(&self, _: &EvolutionEngine, _: EventType) {
+            self.clock.advance(self.latency);
+        }
+    }
+
+    /// Records the events notified, taking a millisecond for each.
+    struct EventRecorder {
+        clock: Arc<MockClock>,
+        events: Mutex<Vec<EventType>>,
+    }
+
+    impl Observer<EvolutionEngine, EventType> for EventRecorder {
+        fn update(&self, _: &EvolutionEngine, event: EventType) {
+            self.clock.advance(Duration::from_millis(1));
+            self.events.lock().unwrap().push(event);
+        }
     }
 
     #[test]
-    fn test_get_random_genomes_iter() {
+    fn test_evolution_engine_metadata() -> Result<(), EvolutionError> {
         // Given
-        let mut rng = get_seeded_rng().unwrap();
-        let count = rng.gen_range(0..64);
-        let genome_size = rng.gen_range(0..10);
+        /// Records the metadata of the run seen when it completes.
+        #[derive(Default)]
+        struct CompletionRecorder(Mutex<Option<RunMetadata>>);
+
+        impl Observer<EvolutionEngine, EventType> for CompletionRecorder {
+            fn update(&self, source: &EvolutionEngine, event: EventType) {
+                if event == EventType::StatusChanged(EvolutionStatus::Completed) {
+                    *self.0.lock().unwrap() = source.metadata().cloned();
+                }
+            }
+        }
+
+        let clock = Arc::new(MockClock::default());
+        let recorder = Arc::new(CompletionRecorder::default());
+        let mut run = EvolutionEngineBuilder::new(get_seeded_rng().unwrap())
+            .population_size(10)
+            .clock(clock.clone())
+            .label("experiment", "sum")
+            .on_generation_end(move |_, _| clock.advance(Duration::from_secs(1)))
+            .observer(recorder.clone())
+            .build()?;
 
         // When
-        let result: Vec<Genome> = get_random_genomes_iter(count, genome_size, &mut rng).collect();
+        let result = block_on(run.start(&Sum, MaxGenerations(2)))?;
+        let seen = recorder.0.lock()?.clone();
+        run.engine_mut().reset()?;
+        let next = block_on(run.start(&Sum, MaxGenerations(0)))?;
 
         // Then
+        let metadata = result.metadata.unwrap();
+        assert_eq!(SystemTime::UNIX_EPOCH, metadata.started_at);
         assert_eq!(
-            count,
-            result.len(),
-            "Should return the required count of genomes"
+            Some(SystemTime::UNIX_EPOCH + Duration::from_secs(3)),
+            metadata.ended_at
         );
-        assert!(
-            result.iter().all(|g| g.len() == genome_size),
-            "Should generate genomes with the required length"
+        assert_eq!(
+            Some("sum"),
+            metadata.labels.get("experiment").map(String::as_str)
+        );
+        assert_eq!(
+            Some(&metadata),
+            seen.as_ref(),
+            "Should stamp the end before notifying the completion"
+        );
+        assert_ne!(
+            metadata.id,
+            next.metadata.unwrap().id,
+            "Should tell the runs of an engine apart"
         );
+
+        Ok(())
     }
 
     #[test]
-    fn test_resolve_settings() {
+    fn test_evolution_engine_notify_generation_events() -> Result<(), EvolutionError> {
         // Given
-        let config = GenerationRenewalConfig {
-            cloning: Some(GeneticRenewalParam {
-                mutation_rate: None,
-                ratio: 0.5,
-                selection_type: SelectionType::Chance,
-            }),
-            crossover: None,
+        /// Gives both genomes of each generation the fitness of the generation.
+        struct Scripted(AtomicUsize);
+
+        impl Strategy for Scripted {
+            type Phenotype = ();
+
+            fn genome_size(&self) -> usize {
+                1
+            }
+
+            fn decode(&self, _: &Genome) {}
+
+            fn evaluate(&self, _: &()) -> f32 {
+                [0.5, 0.4, 0.8][self.0.fetch_add(1, Ordering::Relaxed) / 2]
+            }
+        }
+        let clock = Arc::new(MockClock::default());
+        let mut engine = EvolutionEngine {
+            clock: clock.clone(),
+            ..Default::default()
+        };
+        let recorder = Arc::new(EventRecorder {
+            clock,
+            events: Mutex::default(),
+        });
+        engine.register_observer(recorder.clone());
+        let config = EvolutionConfig {
+            population_size: 2,
+            generation_renewal_config: None,
+            max_duration: None,
         };
 
         // When
-        let result = resolve_settings(Some(&config), 64);
+        block_on(engine.start(
+            &Scripted(Default::default()),
+            &config,
+            MaxGenerations(2),
+            &mut get_seeded_rng().unwrap(),
+        ))?;
 
         // Then
-        assert_eq!(32, result.randoms_count)
+        let events = recorder.events.lock()?;
+        assert_eq!(
+            vec![(0.5, 0), (0.8, 2)],
+            events
+                .iter()
+                .filter_map(|event| match event {
+                    EventType::BestImproved {
+                        fitness,
+                        generation,
+                    } => Some((*fitness, *generation)),
+                    _ => None,
+                })
+                .collect::<Vec<_>>(),
+            "Should only notify the improvements of the best fitness"
+        );
+        assert_eq!(
+            vec![3, 2, 3],
+            events
+                .iter()
+                .filter_map(|event| match event {
+                    EventType::GenerationCompleted { duration } => Some(duration.as_millis()),
+                    _ => None,
+                })
+                .collect::<Vec<_>>(),
+            "Should time the generations with the engine clock"
+        );
+
+        Ok(())
     }
 
     #[test]
-    fn test_run_challenges() {
+    fn test_evolution_engine_progress() -> Result<(), EvolutionError> {
         // Given
-        let mut rng = get_seeded_rng().unwrap();
-        let genome = vec![1, 2];
+        /// Takes a second of the clock to evaluate each genome.
+        struct Ticking(Arc<MockClock>);
+
+        impl Strategy for Ticking {
+            type Phenotype = ();
+
+            fn genome_size(&self) -> usize {
+                1
+            }
+
+            fn decode(&self, _: &Genome) {}
+
+            fn evaluate(&self, _: &()) -> f32 {
+                self.0.advance(Duration::from_secs(1));
+                0.5
+            }
+        }
+        let clock = Arc::new(MockClock::default());
+        let strategy = Ticking(clock.clone());
+        let mut engine = EvolutionEngine {
+            clock,
+            ..Default::default()
+        };
+        let mut config = EvolutionConfig {
+            population_size: 2,
+            generation_renewal_config: None,
+            max_duration: None,
+        };
+        assert_eq!(None, engine.progress());
 
         // When
-        let mut strategy = MockTestStrategy::new();
-        let fitness = 2.0;
-        strategy
-            .expect_evaluate()
-            .with(eq(genome.clone()))
-            .return_const(fitness);
+        block_on(engine.start(
+            &strategy,
+            &config,
+            MaxGenerations(3),
+            &mut get_seeded_rng().unwrap(),
+        ))?;
 
-        let result = block_on(run_challenge(&genome, &strategy));
         // Then
-        assert!(
-            matches!(result, Err(EvolutionError::InvalidEvaluation(_))),
-            "Should return error when evaluation is not standardized"
+        assert_eq!(
+            Some(RunProgress {
+                generation: 3,
+                evaluation_count: 8,
+                elapsed: Duration::from_secs(8),
+                generations_per_second: 0.5,
+                evaluations_per_second: 1.0,
+                completion: Some(1.0),
+                eta: Some(Duration::ZERO),
+            }),
+            engine.progress()
         );
 
         // When
-        let mut strategy = MockTestStrategy::new();
-        let fitness = rng.gen_range(0.0..=1.0);
-        strategy
-            .expect_evaluate()
-            .with(eq(genome.clone()))
-            .return_const(fitness);
+        engine.reset()?;
+        config.max_duration = Some(Duration::from_secs(8));
+        block_on(engine.start(
+            &strategy,
+            &config,
+            |generation, _: &[f32]| generation >= 1,
+            &mut get_seeded_rng().unwrap(),
+        ))?;
+
+        // Then
+        assert_eq!(
+            Some((Some(0.5), Some(Duration::from_secs(4)))),
+            engine
+                .progress()
+                .map(|progress| (progress.completion, progress.eta)),
+            "Should estimate the end from the time budget"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_evolution_engine_run_from_another_thread() -> Result<(), EvolutionError> {
+        // Given
+        let clock = Arc::new(MockClock::default());
+        let mut engine = EvolutionEngine {
+            clock: clock.clone(),
+            ..Default::default()
+        };
+        let recorder = Arc::new(EventRecorder {
+            clock,
+            events: Mutex::default(),
+        });
+        engine.register_observer(recorder.clone());
+        let config = EvolutionConfig {
+            population_size: 10,
+            generation_renewal_config: None,
+            max_duration: None,
+        };
 
-        let result = block_on(run_challenge(&genome, &strategy)).unwrap();
+        // When
+        let runner = thread::spawn(move || {
+            let result = block_on(engine.start(
+                &Sum,
+                &config,
+                MaxGenerations(2),
+                &mut get_seeded_rng().unwrap(),
+            ));
+            (engine, result)
+        });
+        let (engine, result) = runner.join().unwrap();
 
         // Then
-        assert_eq!(fitness, result, "Should call strategy evaluation");
+        assert_eq!(2, result?.generation);
+        assert_eq!(EvolutionStatus::Completed, engine.status()?);
+        assert_eq!(
+            3,
+            recorder
+                .events
+                .lock()?
+                .iter()
+                .filter(|&event| *event == EventType::Evaluated)
+                .count(),
+            "Should notify the observers from the thread running the engine"
+        );
+
+        Ok(())
     }
 
     #[test]
-    fn test_generate_genomes() {
+    fn test_evolution_engine_local_search() -> Result<(), EvolutionError> {
         // Given
-        let mut rng = get_seeded_rng().unwrap();
-        let count = rng.gen_range(0..10);
-        let len = rng.gen_range(0..10);
+        let config = EvolutionConfig {
+            population_size: 10,
+            generation_renewal_config: None,
+            max_duration: None,
+        };
+        let baseline = block_on(EvolutionEngine::default().start(
+            &Sum,
+            &config,
+            MaxGenerations(0),
+            &mut get_seeded_rng().unwrap(),
+        ))?;
+        let mut run = EvolutionEngineBuilder::new(get_seeded_rng().unwrap())
+            .config(config)
+            .local_search(Box::new(HillClimbing), 0.2, 50)
+            .build()?;
 
         // When
-        let result: Vec<Genome> = get_random_genomes_iter(count, len, &mut rng).collect();
+        let result = block_on(run.start(&Sum, MaxGenerations(0)))?;
+
+        // Then
+        assert_eq!(
+            10 + 2 * 50,
+            result.evaluation_count,
+            "Should count the evaluations of the neighbours"
+        );
+        assert!(
+            result.stats.unwrap().max > baseline.stats.unwrap().max,
+            "Should improve the fittest individuals"
+        );
+        assert!(
+            result
+                .evaluations
+                .iter()
+                .all(|e| e.fitness == Sum.evaluate(&Sum.decode(&e.genome))),
+            "Should keep the genomes of the improvements"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_evolution_engine_speciate() -> Result<(), EvolutionError> {
+        // Given
+        let config = EvolutionConfig {
+            population_size: 20,
+            generation_renewal_config: Some(GenerationRenewalConfig {
+                cloning: Some(GeneticRenewalParam {
+                    ratio: 0.3,
+                    ..Default::default()
+                }),
+                crossover: Some(GeneticRenewalParam {
+                    ratio: 0.6,
+                    ..Default::default()
+                }),
+                random_ratio: Some(0.1),
+            }),
+            max_duration: None,
+        };
+        let mut run = EvolutionEngineBuilder::new(get_seeded_rng().unwrap())
+            .config(config)
+            .speciate(Box::new(GeneDistance), 0.5)
+            .build()?;
+
+        // When
+        let result = block_on(run.start(&Sum, MaxGenerations(5)))?;
+
+        // Then
+        assert_eq!(20, result.evaluations.len());
+        assert!(
+            run.engine().species().len() > 1,
+            "Should tell random genomes apart"
+        );
+        let mut members = run
+            .engine()
+            .species()
+            .iter()
+            .flat_map(|species| species.members.iter().copied())
+            .collect::<Vec<_>>();
+        members.sort_unstable();
+        assert_eq!(
+            (0..20).collect::<Vec<_>>(),
+            members,
+            "Should assign each individual of the last generation to a single species"
+        );
+        assert!(EvolutionEngine::default().species().is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_evolution_engine_renewal_stages() -> Result<(), EvolutionError> {
+        // Given
+        /// Resets the genomes of the individuals born with the new generation.
+        #[derive(Debug)]
+        struct Reset;
+
+        impl RenewalStage for Reset {
+            fn renew(
+                &self,
+                renewal: &mut Renewal,
+                _: &mut dyn RngCore,
+            ) -> Result<(), EvolutionError> {
+                for evaluation in renewal.evaluations_mut().iter_mut().filter(|e| e.age == 0) {
+                    evaluation.genome = vec![0; 4];
+                }
+                Ok(())
+            }
+        }
+
+        let config = EvolutionConfig {
+            population_size: 10,
+            generation_renewal_config: Some(GenerationRenewalConfig {
+                cloning: None,
+                crossover: Some(GeneticRenewalParam {
+                    ratio: 1.0,
+                    ..Default::default()
+                }),
+                random_ratio: None,
+            }),
+            max_duration: None,
+        };
+        let mut stages: Vec<Box<dyn RenewalStage>> = vec![Box::new(Elitism(1))];
+        stages.extend(default_renewal_stages());
+        stages.push(Box::new(Reset));
+        let generations = Arc::new(Mutex::new(vec![]));
+        let recorder = generations.clone();
+        let mut run = EvolutionEngineBuilder::new(get_seeded_rng().unwrap())
+            .config(config)
+            .renewal_stages(stages)
+            .on_generation_end(move |_, evaluations| {
+                recorder.lock().unwrap().push(evaluations.to_vec());
+            })
+            .build()?;
+
+        // When
+        block_on(run.start(&Sum, MaxGenerations(1)))?;
+
+        // Then
+        let generations = generations.lock()?;
+        let fittest = generations[0]
+            .iter()
+            .max_by(|a, b| a.fitness.total_cmp(&b.fitness))
+            .unwrap();
+        assert_eq!(
+            10,
+            generations[1].len(),
+            "Should drop the individuals in excess"
+        );
+        assert_eq!(
+            (&fittest.genome, 1),
+            (&generations[1][0].genome, generations[1][0].age),
+            "Should run the inserted stages first"
+        );
+        assert!(
+            generations[1][1..].iter().all(|e| e.genome == vec![0; 4]),
+            "Should run the inserted stages last"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_evolution_engine_hooks() -> Result<(), EvolutionError> {
+        // Given
+        let started = Arc::new(Mutex::new(vec![]));
+        let recorder = started.clone();
+        let mut run = EvolutionEngineBuilder::new(get_seeded_rng().unwrap())
+            .population_size(10)
+            .on_generation_start(move |generation, evaluations| {
+                recorder
+                    .lock()
+                    .unwrap()
+                    .push((generation, evaluations.len()));
+            })
+            .on_generation_end(|_, evaluations| {
+                for evaluation in evaluations.iter_mut() {
+                    evaluation.fitness = 1.0 - evaluation.fitness;
+                }
+            })
+            .build()?;
+
+        // When
+        let result = block_on(run.start(&Sum, MaxGenerations(2)))?;
+
+        // Then
+        assert_eq!(vec![(0, 10), (1, 10), (2, 10)], *started.lock()?);
+        assert!(
+            result
+                .evaluations
+                .iter()
+                .all(|e| e.fitness == 1.0 - Sum.evaluate(&Sum.decode(&e.genome))),
+            "Should keep the fitnesses transformed by the hooks"
+        );
+        assert_eq!(
+            result
+                .evaluations
+                .iter()
+                .map(|e| e.fitness)
+                .reduce(f32::max),
+            result.stats.map(|stats| stats.max),
+            "Should compute the statistics from the transformed fitnesses"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_evolution_engine_observer_timings() -> Result<(), EvolutionError> {
+        // Given
+        let clock = Arc::new(MockClock::default());
+        let mut engine = EvolutionEngineBuilder::new(get_seeded_rng().unwrap())
+            .population_size(1)
+            .clock(clock.clone())
+            .observer_budget(Duration::from_millis(5), 2)
+            .build()?
+            .into_engine();
+        let fast: SharedObserver<EvolutionEngine, EventType> = Arc::new(SlowObserver {
+            clock: clock.clone(),
+            latency: Duration::from_millis(1),
+        });
+        let slow = Arc::new(SlowObserver {
+            clock,
+            latency: Duration::from_millis(10),
+        });
+        engine.register_observer(fast.clone());
+        engine.register_observer(slow);
+
+        // When
+        engine.notify_observers(EventType::GenerationCreated);
+        engine.notify_observers(EventType::Evaluated);
+
+        // Then
+        let result = engine.observer_timings()?;
+        assert_eq!(2, result.len());
+        assert!(result[0].name.ends_with("SlowObserver"));
+        assert_eq!(
+            (2, Duration::from_millis(1), 0),
+            (result[0].calls, result[0].mean(), result[0].slow_streak)
+        );
+        assert_eq!(
+            (2, Duration::from_millis(10), 2),
+            (result[1].calls, result[1].max, result[1].slow_streak),
+            "Should measure each observer update with the engine clock"
+        );
+
+        // When
+        engine.unregister_observer(fast);
+
+        // Then
+        let result = engine.observer_timings()?;
+        assert_eq!(
+            vec![Duration::from_millis(20)],
+            result.iter().map(|t| t.total).collect::<Vec<_>>(),
+            "Should drop the timings of unregistered observers"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_evolution_engine_elapsed() -> Result<(), EvolutionError> {
+        // Given
+        let clock = Arc::new(MockClock::default());
+        let mut engine = EvolutionEngine {
+            clock: clock.clone(),
+            ..Default::default()
+        };
+        let mut strategy = MockTestStrategy::new();
+        strategy.expect_decode().returning(|genome| genome.clone());
+        strategy.expect_genome_size().return_const(4usize);
+        let evaluation_clock = clock.clone();
+        strategy.expect_evaluate().returning(move |_| {
+            evaluation_clock.advance(Duration::from_millis(10));
+            0.5
+        });
+        let config = EvolutionConfig {
+            population_size: 5,
+            generation_renewal_config: None,
+            max_duration: None,
+        };
+
+        // Then
+        assert_eq!(Duration::ZERO, engine.elapsed());
+
+        // When
+        block_on(engine.start(
+            &strategy,
+            &config,
+            MaxGenerations(2),
+            &mut get_seeded_rng().unwrap(),
+        ))?;
+
+        // Then
+        assert_eq!(
+            Duration::from_millis(150),
+            engine.elapsed(),
+            "Should measure the run from the engine clock"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_evolution_engine_run_should_respect_max_duration() -> Result<(), EvolutionError> {
+        // Given
+        let clock = Arc::new(MockClock::default());
+        let mut engine = EvolutionEngine {
+            clock: clock.clone(),
+            ..Default::default()
+        };
+        let mut strategy = MockTestStrategy::new();
+        strategy.expect_decode().returning(|genome| genome.clone());
+        strategy.expect_genome_size().return_const(4usize);
+        let evaluation_clock = clock.clone();
+        strategy.expect_evaluate().returning(move |_| {
+            evaluation_clock.advance(Duration::from_millis(10));
+            0.5
+        });
+        let config = EvolutionConfig {
+            population_size: 5,
+            generation_renewal_config: None,
+            max_duration: Some(Duration::from_millis(120)),
+        };
+
+        // When
+        let result = block_on(engine.start(
+            &strategy,
+            &config,
+            MaxGenerations(u64::MAX),
+            &mut get_seeded_rng().unwrap(),
+        ))?;
+
+        // Then
+        assert_eq!(
+            2, result.generation,
+            "Should complete with the generation exhausting the budget"
+        );
+        assert!(result.evaluations.iter().all(|e| e.evaluated));
+        assert_eq!(EvolutionStatus::Completed, *engine.status.lock()?);
+
+        Ok(())
+    }
+
+    #[cfg(feature = "checkpoint")]
+    #[test]
+    fn test_evolution_engine_checkpoint() -> Result<(), EvolutionError> {
+        // Given
+        let path = env::temp_dir().join(format!("evolution-lab-run-{}", process::id()));
+        let config = EvolutionConfig {
+            population_size: 10,
+            generation_renewal_config: None,
+            max_duration: None,
+        };
+        let mut run = EvolutionEngineBuilder::new(get_seeded_rng().unwrap())
+            .config(config.clone())
+            .checkpoint(2, &path)
+            .build()?;
+
+        // When
+        block_on(run.start(&Sum, MaxGenerations(5)))?;
+        let checkpoint = Checkpoint::load(&path).unwrap();
+        let result = block_on(EvolutionEngine::default().resume_from_checkpoint(
+            &Sum,
+            &path,
+            MaxGenerations(6),
+        ));
+        fs::remove_file(&path).unwrap();
+
+        // Then
+        assert_eq!(
+            (4, 40),
+            (
+                checkpoint.snapshot.generation,
+                checkpoint.snapshot.evaluation_count
+            ),
+            "Should save the last generation multiple of the period"
+        );
+        assert_eq!(
+            (6, 70),
+            result.map(|snapshot| (snapshot.generation, snapshot.evaluation_count))?,
+            "Should resume the saved generation"
+        );
+
+        // Given
+        let mut run = EvolutionEngineBuilder::new(get_seeded_rng().unwrap())
+            .config(config)
+            .checkpoint(100, &path)
+            .build()?;
+        run.engine().handle().send(Command::Halt)?;
+
+        // When
+        block_on(run.start(&Sum, MaxGenerations(5)))?;
+        let checkpoint = Checkpoint::load(&path);
+        fs::remove_file(&path).unwrap();
+
+        // Then
+        assert_eq!(
+            1,
+            checkpoint.unwrap().snapshot.generation,
+            "Should save the run when halted"
+        );
+        assert!(matches!(
+            block_on(EvolutionEngine::default().resume_from_checkpoint(
+                &Sum,
+                &path,
+                MaxGenerations(6)
+            )),
+            Err(EvolutionError::Checkpoint(_))
+        ));
+
+        Ok(())
+    }
+
+    /// Sorts genomes in place of penalizing unsorted ones.
+    struct Sorted;
+
+    impl Strategy for Sorted {
+        type Phenotype = Genome;
+
+        fn genome_size(&self) -> usize {
+            8
+        }
+
+        fn decode(&self, genome: &Genome) -> Genome {
+            genome.clone()
+        }
+
+        fn evaluate(&self, _: &Genome) -> f32 {
+            0.5
+        }
+
+        fn repair(&self, genome: &mut Genome) {
+            genome.sort();
+        }
+    }
+
+    #[test]
+    fn test_evolution_engine_run_should_repair_created_genomes() -> Result<(), EvolutionError> {
+        // Given
+        let mut engine = EvolutionEngine::default();
+        let config = EvolutionConfig {
+            population_size: 20,
+            generation_renewal_config: Some(GenerationRenewalConfig {
+                cloning: Some(GeneticRenewalParam {
+                    mutation_rate: Some(0.5),
+                    ratio: 0.3,
+                    selection_type: SelectionType::Chance,
+                    ..Default::default()
+                }),
+                crossover: Some(GeneticRenewalParam {
+                    mutation_rate: Some(0.5),
+                    ratio: 0.5,
+                    selection_type: SelectionType::Chance,
+                    ..Default::default()
+                }),
+                random_ratio: None,
+            }),
+            max_duration: None,
+        };
+
+        // When
+        let result = block_on(engine.start(
+            &Sorted,
+            &config,
+            MaxGenerations(3),
+            &mut get_seeded_rng().unwrap(),
+        ))?;
+
+        // Then
+        assert!(
+            result
+                .evaluations
+                .iter()
+                .all(|e| e.genome.windows(2).all(|genes| genes[0] <= genes[1])),
+            "Should repair random genomes, clones and offsprings"
+        );
+
+        Ok(())
+    }
+
+    /// Reads genomes as the sum of their genes.
+    struct Sum;
+
+    impl Strategy for Sum {
+        type Phenotype = u32;
+
+        fn genome_size(&self) -> usize {
+            4
+        }
+
+        fn decode(&self, genome: &Genome) -> u32 {
+            genome.iter().map(|&gene| gene as u32).sum()
+        }
+
+        fn evaluate(&self, sum: &u32) -> f32 {
+            *sum as f32 / 1020.0
+        }
+
+        fn describe(&self, sum: &u32) -> Option<String> {
+            Some(sum.to_string())
+        }
+    }
+
+    #[test]
+    fn test_evolution_engine_run_dyn_strategy() -> Result<(), EvolutionError> {
+        // Given
+        let pick = |name: &str| -> Box<DynStrategy> {
+            match name {
+                "sum" => Erased::boxed(Sum),
+                _ => Erased::boxed(Sorted),
+            }
+        };
+        let config = EvolutionConfig {
+            population_size: 10,
+            generation_renewal_config: None,
+            max_duration: None,
+        };
+
+        for (name, genome_size) in [("sum", 4), ("sorted", 8)] {
+            // When
+            let mut engine = EvolutionEngine::default();
+            let result = block_on(engine.start(
+                &pick(name),
+                &config,
+                MaxGenerations(2),
+                &mut get_seeded_rng().unwrap(),
+            ))?;
+
+            // Then
+            assert!(
+                result
+                    .evaluations
+                    .iter()
+                    .all(|e| e.genome.len() == genome_size),
+                "Should run the strategy picked by name"
+            );
+        }
+        let mut engine = EvolutionEngine::default();
+        let result = block_on(engine.start(
+            &pick("sum"),
+            &config,
+            MaxGenerations(1),
+            &mut get_seeded_rng().unwrap(),
+        ))?;
+        assert!(
+            result
+                .evaluations
+                .iter()
+                .all(|e| e.phenotype == Some(Sum.decode(&e.genome).to_string())),
+            "Should describe the phenotypes of the erased strategy"
+        );
+
+        Ok(())
+    }
+
+    /// Reports the steps of the simulation evaluating the sum of the genes.
+    struct Simulated;
+
+    impl Strategy for Simulated {
+        type Phenotype = u32;
+
+        fn genome_size(&self) -> usize {
+            4
+        }
+
+        fn decode(&self, genome: &Genome) -> u32 {
+            Sum.decode(genome)
+        }
+
+        fn evaluate(&self, sum: &u32) -> f32 {
+            Sum.evaluate(sum)
+        }
+
+        fn evaluate_with_metadata(&self, sum: &u32) -> (f32, Vec<f32>, BTreeMap<String, String>) {
+            let metadata = BTreeMap::from([("steps".to_string(), (sum % 7).to_string())]);
+            (self.evaluate(sum), vec![], metadata)
+        }
+    }
+
+    #[test]
+    fn test_evolution_engine_run_should_keep_metadata() -> Result<(), EvolutionError> {
+        // Given
+        let mut engine = EvolutionEngine::default();
+        let config = EvolutionConfig {
+            population_size: 10,
+            generation_renewal_config: Some(GenerationRenewalConfig {
+                cloning: Some(GeneticRenewalParam {
+                    mutation_rate: Some(1.0),
+                    ratio: 0.5,
+                    selection_type: SelectionType::Chance,
+                    ..Default::default()
+                }),
+                crossover: None,
+                random_ratio: None,
+            }),
+            max_duration: None,
+        };
+
+        // When
+        let result = block_on(engine.start(
+            &Erased::boxed(Simulated),
+            &config,
+            MaxGenerations(3),
+            &mut get_seeded_rng().unwrap(),
+        ))?;
+
+        // Then
+        assert!(
+            result
+                .evaluations
+                .iter()
+                .all(|e| e.metadata["steps"] == (Sum.decode(&e.genome) % 7).to_string()),
+            "Should keep the metadata of the evaluations"
+        );
+        let clones = engine.get_clones(
+            &GeneticPool {
+                count: 5,
+                mutation_rate: 1.0,
+                ..Default::default()
+            },
+            &mut get_seeded_rng().unwrap(),
+        )?;
+        assert!(
+            clones.iter().all(|(_, clone)| clone.metadata.is_empty()),
+            "Should drop the metadata of mutated clones"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_evolution_engine_run_should_track_lineage() -> Result<(), EvolutionError> {
+        // Given
+        let config = EvolutionConfig {
+            population_size: 10,
+            generation_renewal_config: Some(GenerationRenewalConfig {
+                cloning: Some(GeneticRenewalParam {
+                    ratio: 0.3,
+                    ..Default::default()
+                }),
+                crossover: Some(GeneticRenewalParam {
+                    ratio: 0.5,
+                    crossover_type: CrossoverType::KPoint(2),
+                    ..Default::default()
+                }),
+                random_ratio: None,
+            }),
+            max_duration: None,
+        };
+        let mut run = EvolutionEngineBuilder::new(get_seeded_rng().unwrap())
+            .config(config.clone())
+            .track_lineage()
+            .build()?;
+        run.engine_mut()
+            .inject(vec![vec![0; 4]], ReplacementPolicy::Append)?;
+
+        // When
+        let result = block_on(run.start(&Sum, MaxGenerations(3)))?;
+
+        // Then
+        let lineage = result.lineage.expect("Should track the lineage");
+        let descended = lineage
+            .population
+            .iter()
+            .flat_map(|&node| iter::once(node).chain(lineage.ancestors(node)))
+            .collect::<HashSet<_>>();
+        assert_eq!(
+            lineage.nodes.len(),
+            descended.len(),
+            "Should only keep the ancestors of the population"
+        );
+        assert_eq!(
+            result
+                .evaluations
+                .iter()
+                .map(|e| Some(e.fitness))
+                .collect::<Vec<_>>(),
+            lineage
+                .population
+                .iter()
+                .map(|&node| lineage.nodes[node].fitness)
+                .collect::<Vec<_>>()
+        );
+        for &node in lineage.population.iter() {
+            let LineageNode {
+                generation,
+                operator,
+                parents,
+                ..
+            } = &lineage.nodes[node];
+            assert_eq!(3, *generation);
+            let expected_parents = match operator {
+                Operator::Clone => 1,
+                Operator::Crossover => 2,
+                _ => 0,
+            };
+            assert_eq!(expected_parents, parents.len());
+            assert!(parents
+                .iter()
+                .all(|&parent| lineage.nodes[parent].generation == 2));
+            assert!(lineage
+                .ancestors(node)
+                .iter()
+                .all(|&ancestor| lineage.nodes[ancestor].generation < 3));
+        }
+
+        // When
+        let mut run = EvolutionEngineBuilder::new(get_seeded_rng().unwrap())
+            .config(config)
+            .build()?;
+        let result = block_on(run.start(&Sum, MaxGenerations(1)))?;
+
+        // Then
+        assert_eq!(
+            None, result.lineage,
+            "Should not track the lineage by default"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_evolution_engine_run_should_adapt_operators() -> Result<(), EvolutionError> {
+        // Given
+        let clock = Arc::new(MockClock::default());
+        let recorder = Arc::new(EventRecorder {
+            clock: clock.clone(),
+            events: Mutex::default(),
+        });
+        let config = EvolutionConfig {
+            population_size: 20,
+            generation_renewal_config: Some(GenerationRenewalConfig {
+                // Unchanged clones are never assessed
+                cloning: Some(GeneticRenewalParam {
+                    ratio: 0.5,
+                    mutation_rate: Some(0.0),
+                    ..Default::default()
+                }),
+                crossover: Some(GeneticRenewalParam {
+                    ratio: 0.5,
+                    ..Default::default()
+                }),
+                random_ratio: None,
+            }),
+            max_duration: None,
+        };
+        let mut run = EvolutionEngineBuilder::new(get_seeded_rng().unwrap())
+            .config(config)
+            .clock(clock)
+            .track_lineage()
+            .track_operators(Some(AdaptiveSelection {
+                adaptation_rate: 0.5,
+                min_share: 0.1,
+            }))
+            .observer(recorder.clone())
+            .build()?;
+
+        // When
+        let result = block_on(run.start(&Sum, MaxGenerations(5)))?;
+
+        // Then
+        let events = recorder.events.lock().unwrap();
+        let assessments = events
+            .iter()
+            .filter_map(|event| match event {
+                EventType::OperatorsAssessed(stats) => Some(stats),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            5,
+            assessments.len(),
+            "Should assess the renewed generations"
+        );
+        let first = assessments[0];
+        assert_eq!(
+            vec![(Operator::Clone, 0), (Operator::Crossover, 10)],
+            first
+                .iter()
+                .map(|stats| (stats.operator, stats.offspring))
+                .collect::<Vec<_>>()
+        );
+        let stats = run.engine().operator_stats();
+        assert!(
+            (stats[0].share - 0.1).abs() < 1e-6 && (stats[1].share - 0.9).abs() < 1e-6,
+            "Should favor the operator whose offspring improve"
+        );
+        let lineage = result.lineage.unwrap();
+        assert_eq!(
+            18,
+            lineage
+                .population
+                .iter()
+                .filter(|&&node| lineage.nodes[node].operator == Operator::Crossover)
+                .count(),
+            "Should renew with the adapted shares"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_evolution_engine_run_should_measure_diversity() -> Result<(), EvolutionError> {
+        // Given
+        let config = EvolutionConfig {
+            population_size: 10,
+            generation_renewal_config: Some(GenerationRenewalConfig {
+                cloning: Some(GeneticRenewalParam {
+                    ratio: 1.0,
+                    mutation_type: MutationType::SelfAdaptive {
+                        tau: 0.2,
+                        mutation: Box::new(MutationType::Random),
+                    },
+                    ..Default::default()
+                }),
+                crossover: None,
+                random_ratio: None,
+            }),
+            max_duration: None,
+        };
+        let mut run = EvolutionEngineBuilder::new(get_seeded_rng().unwrap())
+            .config(config.clone())
+            .measure_diversity()
+            .initial_population(InitialPopulation::Seeded(vec![vec![1, 2, 3, 4]; 10]))
+            .build()?;
+
+        // When
+        let result = block_on(run.start(&Sum, MinDiversity(0.5).or(MaxGenerations(5))))?;
+
+        // Then
+        assert_eq!(
+            0, result.generation,
+            "Should stop once the population converged"
+        );
+        let diversity = result.diversity.expect("Should measure the diversity");
+        assert_eq!(
+            (0.0, 0.0, 1, 10),
+            (
+                diversity.mean_hamming_distance,
+                diversity.mean_entropy,
+                diversity.unique_count,
+                diversity.population_size
+            )
+        );
+        assert_eq!(
+            4,
+            diversity.gene_entropies.len(),
+            "Should leave the reserved tail out"
+        );
+        assert!(result.evaluations[0].genome.len() > 4);
+
+        // When
+        let mut run = EvolutionEngineBuilder::new(get_seeded_rng().unwrap())
+            .config(config)
+            .build()?;
+        let result = block_on(run.start(&Sum, MaxGenerations(1)))?;
+
+        // Then
+        assert_eq!(
+            None, result.diversity,
+            "Should not measure the diversity by default"
+        );
+
+        Ok(())
+    }
+
+    /// Records the contexts of its evaluations.
+    #[derive(Default)]
+    struct Contextual(Mutex<Vec<EvalContext>>);
+
+    impl Strategy for Contextual {
+        type Phenotype = u32;
+
+        fn genome_size(&self) -> usize {
+            4
+        }
+
+        fn decode(&self, genome: &Genome) -> u32 {
+            Sum.decode(genome)
+        }
+
+        fn evaluate(&self, sum: &u32) -> f32 {
+            Sum.evaluate(sum)
+        }
+
+        fn evaluate_in_context(
+            &self,
+            sum: &u32,
+            context: &EvalContext,
+        ) -> (f32, Vec<f32>, BTreeMap<String, String>) {
+            self.0.lock().unwrap().push(*context);
+            (self.evaluate(sum), vec![], BTreeMap::new())
+        }
+    }
+
+    #[test]
+    fn test_evolution_engine_run_should_pass_contexts() -> Result<(), EvolutionError> {
+        // Given
+        let strategy = Contextual::default();
+        let mut run = EvolutionEngineBuilder::new(get_seeded_rng().unwrap())
+            .population_size(5)
+            .resampling(2)
+            .build()?;
+
+        // When
+        block_on(run.start(&strategy, MaxGenerations(1)))?;
+
+        // Then
+        let mut contexts = strategy.0.into_inner().unwrap();
+        assert_eq!(20, contexts.len());
+        contexts.sort_by_key(|context| (context.generation, context.index));
+        assert_eq!(
+            (0..2)
+                .flat_map(|generation| (0..5).flat_map(move |index| [(generation, index); 2]))
+                .collect::<Vec<_>>(),
+            contexts
+                .iter()
+                .map(|context| (context.generation, context.index))
+                .collect::<Vec<_>>(),
+            "Should evaluate each sample of each individual of each generation in context"
+        );
+        assert_eq!(
+            20,
+            contexts
+                .iter()
+                .map(|context| context.rng_seed)
+                .collect::<HashSet<_>>()
+                .len(),
+            "Should seed each evaluation differently"
+        );
+
+        Ok(())
+    }
+
+    /// Evaluates the sums of whole generations, counting its calls.
+    #[derive(Default)]
+    struct Batched(AtomicUsize);
+
+    impl BatchStrategy for Batched {
+        fn genome_size(&self) -> usize {
+            4
+        }
+
+        fn evaluate_batch(
+            &self,
+            genomes: &[&Genome],
+        ) -> Result<Vec<f32>, Box<dyn Error + Send + Sync>> {
+            if self.0.fetch_add(1, Ordering::SeqCst) == 5 {
+                return Err("worker unreachable".into());
+            }
+            Ok(genomes
+                .iter()
+                .map(|genome| Sum.evaluate(&Sum.decode(genome)))
+                .collect())
+        }
+    }
+
+    #[test]
+    fn test_evolution_engine_start_batch() -> Result<(), EvolutionError> {
+        // Given
+        let strategy = Batched::default();
+        let config = EvolutionConfig {
+            population_size: 10,
+            generation_renewal_config: None,
+            max_duration: None,
+        };
+
+        // When
+        let result = block_on(EvolutionEngine::default().start_batch(
+            &strategy,
+            &config,
+            MaxGenerations(2),
+            &mut get_seeded_rng().unwrap(),
+        ))?;
+
+        // Then
+        assert_eq!(
+            3,
+            strategy.0.load(Ordering::SeqCst),
+            "Should evaluate each generation in a single batch"
+        );
+        assert!(result
+            .evaluations
+            .iter()
+            .all(|e| e.evaluated && e.fitness == Sum.evaluate(&Sum.decode(&e.genome))));
+
+        // When
+        let result = block_on(EvolutionEngine::default().start_batch(
+            &strategy,
+            &config,
+            MaxGenerations(5),
+            &mut get_seeded_rng().unwrap(),
+        ));
+
+        // Then
+        assert_eq!(
+            Err(EvolutionError::BatchEvaluation(
+                "worker unreachable".to_string()
+            )),
+            result,
+            "Should interrupt the run when the batch fails"
+        );
+
+        Ok(())
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn test_evolution_engine_start_parallel() -> Result<(), EvolutionError> {
+        // Given
+        let config = EvolutionConfig {
+            population_size: 10,
+            generation_renewal_config: None,
+            max_duration: None,
+        };
+
+        // When
+        let result = block_on(EvolutionEngine::default().start_parallel(
+            &Sum,
+            &config,
+            MaxGenerations(2),
+            &mut get_seeded_rng().unwrap(),
+        ))?;
+
+        // Then
+        assert_eq!(2, result.generation);
+        assert!(result
+            .evaluations
+            .iter()
+            .all(|e| e.evaluated && e.fitness == Sum.evaluate(&Sum.decode(&e.genome))));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_evolution_engine_run_should_describe_phenotypes() -> Result<(), EvolutionError> {
+        // Given
+        let mut engine = EvolutionEngine::default();
+        let config = EvolutionConfig {
+            population_size: 10,
+            generation_renewal_config: None,
+            max_duration: None,
+        };
+
+        // When
+        let result = block_on(engine.start(
+            &Sum,
+            &config,
+            MaxGenerations(1),
+            &mut get_seeded_rng().unwrap(),
+        ))?;
+
+        // Then
+        assert!(
+            result
+                .evaluations
+                .iter()
+                .all(|e| e.phenotype == Some(Sum.decode(&e.genome).to_string())
+                    && e.fitness == Sum.evaluate(&Sum.decode(&e.genome))),
+            "Should expose the described phenotype of evaluated genomes"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_evolution_engine_run_should_count_evaluations() -> Result<(), EvolutionError> {
+        // Given
+        let mut engine = EvolutionEngine::default();
+        let config = EvolutionConfig {
+            population_size: 10,
+            generation_renewal_config: None,
+            max_duration: None,
+        };
+
+        // When
+        let result = block_on(engine.start(
+            &Sum,
+            &config,
+            MaxEvaluations(25).or(MaxGenerations(10)),
+            &mut get_seeded_rng().unwrap(),
+        ))?;
+
+        // Then
+        assert_eq!(
+            2, result.generation,
+            "Should complete once 30 genomes are evaluated"
+        );
+        assert_eq!(30, result.evaluation_count);
+        assert_eq!(30, engine.evaluation_count());
+
+        // When
+        let mut engine = EvolutionEngine::default();
+        let result = block_on(engine.start_from(
+            &Sum,
+            &config,
+            MaxEvaluations(45),
+            &mut get_seeded_rng().unwrap(),
+            result,
+        ))?;
+
+        // Then
+        assert_eq!(
+            (3, 50),
+            (result.generation, result.evaluation_count),
+            "Should carry on counting the evaluations of the resumed run"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_evolution_engine_start_from_incompatible_snapshot() {
+        // Given
+        let config = EvolutionConfig {
+            population_size: 10,
+            generation_renewal_config: None,
+            max_duration: None,
+        };
+        let snapshot = Snapshot {
+            evaluations: vec![vec![0, 0, 0, 0].into(); 10],
+            ..Default::default()
+        };
+
+        // When
+        let mut engine = EvolutionEngine::default();
+        let mut wrong_genome = snapshot.clone();
+        wrong_genome.evaluations[3] = vec![0, 0, 0].into();
+        let result = block_on(engine.start_from(
+            &Sum,
+            &config,
+            MaxGenerations(1),
+            &mut get_seeded_rng().unwrap(),
+            wrong_genome,
+        ));
+
+        // Then
+        assert!(
+            matches!(result, Err(EvolutionError::IncompatibleSnapshot(ref details)) if details.contains("genome 3 has 3 genes")),
+            "Should reject genomes not matching the strategy, got {result:?}"
+        );
+        assert_eq!(
+            EvolutionStatus::New,
+            engine.status().unwrap(),
+            "Should leave the engine ready to run"
+        );
+
+        // When
+        let mut wrong_population = snapshot.clone();
+        wrong_population.evaluations.truncate(8);
+        let result = block_on(engine.start_from(
+            &Sum,
+            &config,
+            MaxGenerations(1),
+            &mut get_seeded_rng().unwrap(),
+            wrong_population,
+        ));
+
+        // Then
+        assert!(
+            matches!(result, Err(EvolutionError::IncompatibleSnapshot(_))),
+            "Should reject populations not matching the configuration, got {result:?}"
+        );
+
+        // When
+        let result = block_on(engine.start_from(
+            &Sum,
+            &config,
+            MaxGenerations(1),
+            &mut get_seeded_rng().unwrap(),
+            snapshot,
+        ));
+
+        // Then
+        assert!(result.is_ok(), "Should run from a compatible snapshot");
+    }
+
+    #[test]
+    fn test_evolution_engine_step() -> Result<(), EvolutionError> {
+        // Given
+        let mut rng = get_seeded_rng().unwrap();
+        let mut engine = EvolutionEngine::default();
+        let config = EvolutionConfig {
+            population_size: 10,
+            generation_renewal_config: None,
+            max_duration: None,
+        };
+
+        // When
+        let result = block_on(engine.step(&Sum, &mut rng));
+
+        // Then
+        assert_eq!(
+            Err(EvolutionError::InvalidStatus(EvolutionStatus::New)),
+            result,
+            "Should not step an unprepared run"
+        );
+
+        // When
+        engine.prepare(&Sum, &config, &mut rng, None)?;
+        let first = block_on(engine.step(&Sum, &mut rng))?;
+        let second = block_on(engine.step(&Sum, &mut rng))?;
+
+        // Then
+        assert_eq!(
+            (0, 10),
+            (first.generation, first.evaluation_count),
+            "Should return the evaluated generation"
+        );
+        assert!(first.evaluations.iter().all(|e| e.evaluated));
+        assert!(
+            first.stats.is_some_and(
+                |stats| stats.count == 10 && stats.max == first.evaluations[stats.best].fitness
+            ),
+            "Should compute the statistics of the evaluated generation"
+        );
+        assert_eq!(None, engine.stats(), "Should wait for the next evaluation");
+        assert_eq!((1, 20), (second.generation, second.evaluation_count));
+        assert_eq!(
+            2,
+            engine.snapshot().generation,
+            "Should renew the population"
+        );
+
+        // When
+        engine.halt()?;
+        let halted = block_on(engine.step(&Sum, &mut rng))?;
+        let result = block_on(engine.step(&Sum, &mut rng));
+
+        // Then
+        assert_eq!(
+            (2, 20),
+            (halted.generation, halted.evaluation_count),
+            "Should return the unevaluated generation once halted"
+        );
+        assert_eq!(
+            Err(EvolutionError::InvalidStatus(EvolutionStatus::Halted)),
+            result
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_evolution_engine_reset() -> Result<(), EvolutionError> {
+        // Given
+        let mut rng = get_seeded_rng().unwrap();
+        let mut engine = EvolutionEngine::default();
+        let mut observer = MockTestObserver::new();
+        observer.expect_update().return_const(());
+        engine.register_observer(Arc::new(observer));
+        let config = EvolutionConfig {
+            population_size: 10,
+            generation_renewal_config: None,
+            max_duration: None,
+        };
+        block_on(engine.start(&Sum, &config, MaxGenerations(2), &mut rng))?;
+        engine.annotate("first run")?;
+
+        // When
+        let result = block_on(engine.start(&Sum, &config, MaxGenerations(2), &mut rng));
+
+        // Then
+        assert_eq!(
+            Err(EvolutionError::InvalidStatus(EvolutionStatus::Completed)),
+            result,
+            "Should not run again without reset"
+        );
+
+        // When
+        engine.reset()?;
+
+        // Then
+        assert_eq!(EvolutionStatus::New, *engine.status.lock().unwrap());
+        assert_eq!(&Snapshot::default(), engine.snapshot());
+        assert!(engine.annotations()?.is_empty());
+        assert_eq!(0, engine.observer_timings()?[0].calls);
+        assert_eq!(1, engine.observer_count(), "Should keep the observers");
+
+        // When
+        let result = block_on(engine.start(&Sum, &config, MaxGenerations(3), &mut rng))?;
+
+        // Then
+        assert_eq!(
+            (3, 40),
+            (result.generation, result.evaluation_count),
+            "Should run again from scratch"
+        );
+
+        // When
+        engine.reset()?;
+        engine.prepare(&Sum, &config, &mut rng, None)?;
+
+        // Then
+        assert_eq!(
+            Err(EvolutionError::InvalidStatus(EvolutionStatus::Running)),
+            engine.reset(),
+            "Should not reset a running engine"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_evolution_engine_hall_of_fame() -> Result<(), EvolutionError> {
+        // Given
+        let mut run = EvolutionEngineBuilder::new(get_seeded_rng().unwrap())
+            .population_size(10)
+            .hall_of_fame(3)
+            .build()?;
+
+        // When
+        run.prepare(&Sum, None)?;
+        let mut best = f32::MIN;
+        for _ in 0..5 {
+            let snapshot = block_on(run.step(&Sum))?;
+            best = snapshot
+                .evaluations
+                .iter()
+                .map(|e| e.fitness)
+                .fold(best, f32::max);
+        }
+        let result = run.engine().snapshot();
+
+        // Then
+        assert_eq!(3, result.hall_of_fame.len());
+        assert_eq!(
+            best, result.hall_of_fame[0].fitness,
+            "Should keep the best individual of the run"
+        );
+        assert!(result
+            .hall_of_fame
+            .windows(2)
+            .all(|pair| pair[0].fitness >= pair[1].fitness));
+        assert!(
+            EvolutionEngine::default()
+                .snapshot()
+                .hall_of_fame
+                .is_empty(),
+            "Should not keep a hall of fame by default"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_evolution_engine_record_history() -> Result<(), EvolutionError> {
+        // Given
+        let mut run = EvolutionEngineBuilder::new(get_seeded_rng().unwrap())
+            .population_size(10)
+            .record_history(3, HistoryDetail::Summary)
+            .build()?;
+
+        // When
+        let result = block_on(run.start(&Sum, MaxGenerations(4)))?;
+        let history = run.engine().history();
+
+        // Then
+        assert_eq!(
+            vec![(2, 30), (3, 40), (4, 50)],
+            history
+                .iter()
+                .map(|record| (record.generation, record.evaluation_count))
+                .collect::<Vec<_>>(),
+            "Should keep the last evaluated generations"
+        );
+        assert_eq!(result.stats, history[2].stats);
+        assert!(
+            EvolutionEngine::default().history().is_empty(),
+            "Should not record by default"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_evolution_engine_deduplicate() -> Result<(), EvolutionError> {
+        // Given
+        let config = EvolutionConfig {
+            population_size: 10,
+            generation_renewal_config: Some(GenerationRenewalConfig {
+                cloning: Some(GeneticRenewalParam {
+                    mutation_rate: Some(0.0),
+                    ratio: 0.99,
+                    selection_type: SelectionType::Weight,
+                    ..Default::default()
+                }),
+                crossover: None,
+                random_ratio: None,
+            }),
+            max_duration: None,
+        };
+        let mut run = EvolutionEngineBuilder::new(get_seeded_rng().unwrap())
+            .config(config)
+            .deduplicate(Deduplication::Mutate)
+            .build()?;
+
+        // When
+        let result = block_on(run.start(&Sum, MaxGenerations(3)))?;
+
+        // Then
+        let genomes = result
+            .evaluations
+            .iter()
+            .map(|e| &e.genome)
+            .collect::<HashSet<_>>();
+        assert_eq!(
+            10,
+            genomes.len(),
+            "Should not fill the population with clones of the best individual"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_evolution_engine_initial_population() -> Result<(), EvolutionError> {
+        // Given
+        let seeds = vec![vec![255, 255, 255, 255], vec![0, 0, 0, 0]];
+        let mut run = EvolutionEngineBuilder::new(get_seeded_rng().unwrap())
+            .population_size(10)
+            .initial_population(InitialPopulation::Seeded(
+                seeds.iter().cloned().chain([vec![1, 2]]).collect(),
+            ))
+            .build()?;
+
+        // When
+        let result = block_on(run.start(&Sum, MaxGenerations(0)))?;
+
+        // Then
+        assert_eq!(10, result.evaluations.len());
+        assert_eq!(
+            seeds,
+            result.evaluations[..2]
+                .iter()
+                .map(|e| e.genome.clone())
+                .collect::<Vec<_>>(),
+            "Should start from the seeds of the strategy genome size"
+        );
+        assert_eq!(Some(1.0), result.evaluations[0].evaluated_fitness());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_evolution_engine_start_seeded() -> Result<(), EvolutionError> {
+        // Given
+        let config = EvolutionConfig {
+            population_size: 10,
+            generation_renewal_config: Some(GenerationRenewalConfig {
+                cloning: None,
+                crossover: Some(GeneticRenewalParam {
+                    ratio: 0.6,
+                    ..Default::default()
+                }),
+                random_ratio: None,
+            }),
+            max_duration: None,
+        };
+
+        // When
+        let result = block_on(EvolutionEngine::default().start_seeded::<StdRng, _, _>(
+            &Sum,
+            &config,
+            MaxGenerations(3),
+            42,
+        ))?;
+        let parameters = result.parameters.clone().unwrap();
+        let replayed = block_on(EvolutionEngine::default().start_seeded::<StdRng, _, _>(
+            &Sum,
+            &parameters.config,
+            MaxGenerations(3),
+            parameters.seed.unwrap(),
+        ))?;
+
+        // Then
+        assert_eq!(
+            RunParameters {
+                seed: Some(42),
+                config,
+                cloning_count: 0,
+                crossover_count: 6,
+                randoms_count: 4,
+                reserved_size: 0,
+            },
+            parameters
+        );
+        assert_eq!(
+            result.evaluations, replayed.evaluations,
+            "Should reproduce the run from its parameters"
+        );
+
+        // When
+        let result = block_on(EvolutionEngine::default().start(
+            &Sum,
+            &parameters.config,
+            MaxGenerations(0),
+            &mut get_seeded_rng().unwrap(),
+        ))?;
+
+        // Then
+        assert_eq!(
+            None,
+            result.parameters.and_then(|parameters| parameters.seed),
+            "Should not know the seed of a given generator"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_evolution_engine_failing_criterion() {
+        // Given
+        let mut engine = EvolutionEngine::default();
+        let config = EvolutionConfig {
+            population_size: 10,
+            generation_renewal_config: None,
+            max_duration: None,
+        };
+        let criterion = Fallible(|progress: &Progress| match progress.generation {
+            0 => Ok(false),
+            _ => Err("database unavailable"),
+        });
+
+        // When
+        let result =
+            block_on(engine.start(&Sum, &config, criterion, &mut get_seeded_rng().unwrap()));
+
+        // Then
+        assert_eq!(
+            Err(EvolutionError::Termination(
+                "database unavailable".to_string()
+            )),
+            result,
+            "Should fail the run instead of panicking"
+        );
+    }
+
+    #[test]
+    fn test_evolution_engine_resampling() -> Result<(), EvolutionError> {
+        // Given
+        /// Gives all the genomes 0.2 or 0.6 in turns, from one pass over the population to the
+        /// next.
+        struct Noisy(AtomicUsize);
+
+        impl Strategy for Noisy {
+            type Phenotype = ();
+
+            fn genome_size(&self) -> usize {
+                2
+            }
+
+            fn decode(&self, _: &Genome) {}
+
+            fn evaluate(&self, _: &()) -> f32 {
+                let count = self.0.fetch_add(1, Ordering::Relaxed);
+                [0.2, 0.6][(count / 4) % 2]
+            }
+        }
+        let mut run = EvolutionEngineBuilder::new(get_seeded_rng().unwrap())
+            .population_size(4)
+            .resampling(2)
+            .build()?;
+
+        // When
+        let result = block_on(run.start(&Noisy(Default::default()), MaxGenerations(0)))?;
+
+        // Then
+        assert_eq!(8, result.evaluation_count, "Should count every sample");
+        assert!(result.evaluations.iter().all(|e| {
+            (e.fitness - 0.4).abs() < 1e-6
+                && e.fitness_variance
+                    .is_some_and(|variance| (variance - 0.04).abs() < 1e-6)
+        }));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_evolution_engine_detect_stagnation() -> Result<(), EvolutionError> {
+        // Given
+        let mut strategy = MockTestStrategy::new();
+        strategy.expect_decode().returning(|genome| genome.clone());
+        strategy.expect_genome_size().return_const(4usize);
+        strategy.expect_evaluate().return_const(0.5);
+        let config = EvolutionConfig {
+            generation_renewal_config: None,
+            population_size: 10,
+            max_duration: None,
+        };
+        let mut observer = MockTestObserver::new();
+        observer
+            .expect_update()
+            .with(always(), eq(EventType::StagnationDetected))
+            .times(1)
+            .return_const(());
+        observer.expect_update().return_const(());
+        let mut run = EvolutionEngineBuilder::new(get_seeded_rng().unwrap())
+            .config(config.clone())
+            .detect_stagnation(2, 0.0)
+            .observer(Arc::new(observer))
+            .build()?;
+
+        // When
+        let result = block_on(run.start(&strategy, MaxGenerations(6)))?;
+
+        // Then
+        assert_eq!(6, result.generation, "Should only notify the stagnation");
+
+        // When
+        let mut engine = EvolutionEngine::default();
+        let result = block_on(engine.start(
+            &strategy,
+            &config,
+            Stagnation::new(3, 0.0).or(MaxGenerations(10)),
+            &mut get_seeded_rng().unwrap(),
+        ))?;
+
+        // Then
+        assert_eq!(3, result.generation, "Should complete on stagnation");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_evolution_engine_restart_on_stagnation() -> Result<(), EvolutionError> {
+        // Given
+        let config = EvolutionConfig {
+            population_size: 10,
+            generation_renewal_config: Some(GenerationRenewalConfig {
+                cloning: Some(GeneticRenewalParam {
+                    ratio: 1.0,
+                    mutation_rate: Some(0.0),
+                    ..Default::default()
+                }),
+                crossover: None,
+                random_ratio: None,
+            }),
+            max_duration: None,
+        };
+        let clock = Arc::new(MockClock::default());
+        let recorder = Arc::new(EventRecorder {
+            clock: clock.clone(),
+            events: Mutex::default(),
+        });
+        let maxima = Arc::new(Mutex::new(vec![]));
+        let hook_maxima = maxima.clone();
+        let mut run = EvolutionEngineBuilder::new(get_seeded_rng().unwrap())
+            .config(config)
+            .clock(clock)
+            .detect_stagnation(2, 0.0)
+            .restart_on_stagnation(RestartPolicy {
+                elite_ratio: 0.2,
+                population_growth: 2.0,
+                max_restarts: Some(1),
+            })
+            .observer(recorder.clone())
+            .on_generation_end(move |_, evaluations| {
+                let max = evaluations.iter().map(|e| e.fitness).fold(0.0, f32::max);
+                hook_maxima.lock().unwrap().push(max);
+            })
+            .build()?;
+
+        // When
+        let result = block_on(run.start(&Sum, MaxGenerations(6)))?;
+
+        // Then
+        assert_eq!(6, result.generation, "Should carry the generations on");
+        assert_eq!(20, result.evaluations.len(), "Should grow the population");
+        assert_eq!(
+            1,
+            run.engine().restarts(),
+            "Should stop restarting after the maximum"
+        );
+        let events = recorder.events.lock()?;
+        assert_eq!(
+            vec![&EventType::Restarted {
+                restart: 1,
+                population_size: 20
+            }],
+            events
+                .iter()
+                .filter(|event| matches!(event, EventType::Restarted { .. }))
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            2,
+            events
+                .iter()
+                .filter(|&event| *event == EventType::StagnationDetected)
+                .count()
+        );
+        let maxima = maxima.lock()?;
+        assert!(
+            maxima[3] >= maxima[2],
+            "Should keep the elites through the restart"
+        );
+
+        Ok(())
+    }
+
+    /// Waits before scoring genomes, counting the evaluations awaited at once.
+    #[cfg(feature = "async")]
+    #[derive(Default)]
+    struct Remote {
+        running: AtomicUsize,
+        max_running: AtomicUsize,
+    }
+
+    #[cfg(feature = "async")]
+    impl AsyncStrategy for Remote {
+        fn genome_size(&self) -> usize {
+            4
+        }
+
+        async fn evaluate(&self, genome: &Genome) -> f32 {
+            let running = self.running.fetch_add(1, Ordering::SeqCst) + 1;
+            self.max_running.fetch_max(running, Ordering::SeqCst);
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            self.running.fetch_sub(1, Ordering::SeqCst);
+            genome[0] as f32 / 255.0
+        }
+    }
+
+    #[cfg(feature = "async")]
+    #[test]
+    fn test_evolution_engine_start_async() -> Result<(), EvolutionError> {
+        // Given
+        let runtime = tokio::runtime::Builder::new_multi_thread()
+            .enable_time()
+            .build()
+            .unwrap();
+
+        for (concurrency, expected) in [(None, 8), (Some(2), 2)] {
+            let strategy = Arc::new(Remote::default());
+            let mut builder =
+                EvolutionEngineBuilder::new(get_seeded_rng().unwrap()).population_size(8);
+            if let Some(limit) = concurrency {
+                builder = builder.evaluation_concurrency(limit);
+            }
+            let mut run = builder.build()?;
+
+            // When
+            let result = runtime.block_on(run.start_async(strategy.clone(), MaxGenerations(2)))?;
+
+            // Then
+            assert!(result
+                .evaluations
+                .iter()
+                .all(|e| e.evaluated && e.fitness == e.genome[0] as f32 / 255.0));
+            assert_eq!(
+                expected,
+                strategy.max_running.load(Ordering::SeqCst),
+                "Should await up to {expected} evaluations at once"
+            );
+        }
+
+        Ok(())
+    }
+
+    #[cfg(feature = "async")]
+    #[test]
+    fn test_evolution_engine_start_async_halt_from_task() -> Result<(), EvolutionError> {
+        // Given
+        let runtime = tokio::runtime::Builder::new_multi_thread()
+            .enable_time()
+            .build()
+            .unwrap();
+        let config = EvolutionConfig {
+            population_size: 8,
+            generation_renewal_config: None,
+            max_duration: None,
+        };
+        let mut engine = EvolutionEngine::default();
+        let handle = engine.handle();
+
+        // When
+        let canceller = runtime.spawn(async move {
+            tokio::time::sleep(Duration::from_millis(25)).await;
+            handle.halt()
+        });
+        let result = runtime.block_on(engine.start_async(
+            Arc::new(Remote::default()),
+            &config,
+            MaxGenerations(u64::MAX),
+            &mut get_seeded_rng().unwrap(),
+        ))?;
+
+        // Then
+        runtime.block_on(canceller).unwrap()?;
+        assert!(result.generation > 0);
+        assert_eq!(
+            EvolutionStatus::Halted,
+            engine.status()?,
+            "Should halt the run awaited by another task"
+        );
+
+        Ok(())
+    }
+
+    /// Stalls on the genomes starting with 0, cancelled once timed out.
+    #[cfg(feature = "async")]
+    struct Stalling;
+
+    #[cfg(feature = "async")]
+    impl AsyncStrategy for Stalling {
+        fn genome_size(&self) -> usize {
+            4
+        }
+
+        async fn evaluate(&self, genome: &Genome) -> f32 {
+            if genome[0] == 0 {
+                tokio::time::sleep(Duration::from_secs(60)).await;
+            }
+            genome[0] as f32 / 255.0
+        }
+    }
+
+    #[cfg(feature = "async")]
+    #[test]
+    fn test_evolution_engine_start_async_evaluation_timeout() -> Result<(), EvolutionError> {
+        // Given
+        let runtime = tokio::runtime::Builder::new_multi_thread()
+            .enable_time()
+            .build()
+            .unwrap();
+
+        for (policy, expected) in [
+            (TimeoutPolicy::Fitness(0.5), (0.5, true)),
+            (TimeoutPolicy::Fitness(2.0), (1.0, true)),
+            (TimeoutPolicy::Invalid, (0.0, false)),
+        ] {
+            let mut run = EvolutionEngineBuilder::new(get_seeded_rng().unwrap())
+                .population_size(2)
+                .initial_population(InitialPopulation::Seeded(vec![
+                    vec![0, 1, 2, 3],
+                    vec![255, 1, 2, 3],
+                ]))
+                .evaluation_timeout(Duration::from_millis(20), policy)
+                .build()?;
+
+            // When
+            let result =
+                runtime.block_on(run.start_async(Arc::new(Stalling), MaxGenerations(0)))?;
+
+            // Then
+            assert_eq!(
+                vec![expected, (1.0, true)],
+                result
+                    .evaluations
+                    .iter()
+                    .map(|e| (e.fitness, e.evaluated))
+                    .collect::<Vec<_>>(),
+                "Should cancel the stalled evaluation and settle it with {policy:?}"
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_evolution_engine_dump_generation() -> Result<(), EvolutionError> {
+        // Given
+        let path = env::temp_dir().join(format!("evolution-lab-generation-{}", process::id()));
+        let mut strategy = MockTestStrategy::new();
+        strategy.expect_decode().returning(|genome| genome.clone());
+        strategy.expect_genome_size().return_const(4usize);
+        strategy.expect_evaluate().return_const(0.5);
+        let config = EvolutionConfig {
+            population_size: 10,
+            generation_renewal_config: Some(GenerationRenewalConfig {
+                cloning: Some(GeneticRenewalParam {
+                    ratio: 0.2,
+                    selection_type: SelectionType::Chance,
+                    ..Default::default()
+                }),
+                crossover: Some(GeneticRenewalParam {
+                    ratio: 0.5,
+                    selection_type: SelectionType::Chance,
+                    ..Default::default()
+                }),
+                random_ratio: None,
+            }),
+            max_duration: None,
+        };
+        let mut run = EvolutionEngineBuilder::new(get_seeded_rng().unwrap())
+            .config(config)
+            .dump_generation(1, &path)
+            .build()?;
+
+        // When
+        block_on(run.start(&strategy, MaxGenerations(3)))?;
+
+        // Then
+        let content = fs::read_to_string(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        let lines = content.lines().collect::<Vec<_>>();
+        assert_eq!(
+            "generation 1", lines[0],
+            "Should dump the requested generation"
+        );
+        assert_eq!("before 10", lines[3]);
+        assert_eq!("after 10", lines[14]);
+        let origins = lines[15..]
+            .iter()
+            .map(|line| line.split('\t').nth(1).unwrap().split(' ').next().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            [vec!["clone"; 2], vec!["offspring"; 5], vec!["random"; 3]].concat(),
+            origins,
+            "Should trace the origin of each renewed individual"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_evolution_engine_annotate() -> Result<(), EvolutionError> {
+        // Given
+        let clock = Arc::new(MockClock::new(
+            SystemTime::UNIX_EPOCH + Duration::from_secs(60),
+        ));
+        let engine = EvolutionEngine {
+            snapshot: Snapshot {
+                generation: 7,
+                ..Default::default()
+            },
+            clock: clock.clone(),
+            ..Default::default()
+        };
+
+        // When
+        let result = engine.annotate("bumped mutation rate")?;
+
+        // Then
+        assert_eq!(
+            7, result.generation,
+            "Should annotate the current generation"
+        );
+        assert_eq!(
+            clock.now(),
+            result.timestamp,
+            "Should timestamp the annotation from the engine clock"
+        );
+        assert_eq!("bumped mutation rate", result.text);
+        assert_eq!(
+            vec![result],
+            engine.annotations()?,
+            "Should store the annotation"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_evolution_engine_snapshot_should_be_defaulted_before_run() {
+        // Given
+        let engine = EvolutionEngine::default();
+
+        // When
+        let result = engine.snapshot();
 
         // Then
-        assert_eq!(count, result.len(), "Should generate the requested count");
+        assert_eq!(&Snapshot::default(), result);
     }
 
     fn build_observer_mock(events: &[EventType]) -> MockTestObserver {
@@ -0,0 +1,169 @@
+use std::{
+    fmt::Write as _,
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::Path,
+};
+
+use crate::{Evaluation, Genome};
+
+use super::genetic_pool::GeneticPool;
+
+/// How an individual of a renewed population was produced, parents being indexes in the
+/// population before renewal.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(super) enum Origin {
+    Clone(usize),
+    Offspring(usize, usize),
+    Random,
+    /// Added by a custom renewal stage, see [`super::Renewal::push`].
+    Inserted,
+}
+
+/// Complete renewal of a generation, written for offline inspection of the operators.
+///
+/// The text format starts with the generation and the pools producing the individuals, then
+/// lists the population before renewal (index, fitness, age, genome) and after renewal (index,
+/// origin, genome). Genomes are written in hexadecimal.
+pub(super) struct GenerationDump<'a> {
+    pub generation: u64,
+    pub cloning_pool: &'a GeneticPool,
+    pub crossover_pool: &'a GeneticPool,
+    pub before: &'a [Evaluation],
+    pub after: &'a [Evaluation],
+    pub origins: &'a [Origin],
+}
+
+impl GenerationDump<'_> {
+    pub fn write(&self, writer: &mut impl Write) -> io::Result<()> {
+        writeln!(writer, "generation {}", self.generation)?;
+        writeln!(writer, "cloning {:?}", self.cloning_pool)?;
+        writeln!(writer, "crossover {:?}", self.crossover_pool)?;
+
+        writeln!(writer, "before {}", self.before.len())?;
+        for (index, evaluation) in self.before.iter().enumerate() {
+            writeln!(
+                writer,
+                "{index}\t{}\t{}\t{}",
+                evaluation.fitness,
+                evaluation.age,
+                to_hex(&evaluation.genome)
+            )?;
+        }
+
+        writeln!(writer, "after {}", self.after.len())?;
+        for (index, (evaluation, origin)) in self.after.iter().zip(self.origins).enumerate() {
+            let origin = match origin {
+                Origin::Clone(parent) => format!("clone {parent}"),
+                Origin::Offspring(p1, p2) => format!("offspring {p1} {p2}"),
+                Origin::Random => "random".to_string(),
+                Origin::Inserted => "inserted".to_string(),
+            };
+            writeln!(writer, "{index}\t{origin}\t{}", to_hex(&evaluation.genome))?;
+        }
+        Ok(())
+    }
+
+    pub fn write_to_file(&self, path: &Path) -> io::Result<()> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        self.write(&mut writer)?;
+        writer.flush()
+    }
+}
+
+fn to_hex(genome: &Genome) -> String {
+    genome.iter().fold(String::new(), |mut hex, gene| {
+        let _ = write!(hex, "{gene:02x}");
+        hex
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use crate::{
+        evolution::genetic_pool::GeneticPool, mutation::MutationType, selection::SelectionType,
+        Evaluation,
+    };
+
+    use super::{GenerationDump, Origin};
+
+    #[test]
+    fn test_generation_dump_write() {
+        // Given
+        let cloning_pool = GeneticPool {
+            count: 1,
+            selection_type: SelectionType::Ranking(1),
+            ..Default::default()
+        };
+        let crossover_pool = GeneticPool {
+            count: 1,
+            mutation_rate: 0.5,
+            mutation_type: MutationType::BitFlip,
+            ..Default::default()
+        };
+        let before = [
+            Evaluation {
+                genome: vec![0x0f, 0xa0],
+                fitness: 0.25,
+                fitness_variance: None,
+                objectives: vec![],
+                age: 3,
+                evaluated: true,
+                phenotype: None,
+                metadata: BTreeMap::new(),
+            },
+            Evaluation {
+                genome: vec![1, 2],
+                fitness: 0.75,
+                fitness_variance: None,
+                objectives: vec![],
+                age: 0,
+                evaluated: true,
+                phenotype: None,
+                metadata: BTreeMap::new(),
+            },
+        ];
+        let after = [
+            before[1].survive(),
+            Evaluation::from(vec![0x0f, 0x02]),
+            Evaluation::from(vec![0xff, 0xff]),
+        ];
+        let dump = GenerationDump {
+            generation: 4,
+            cloning_pool: &cloning_pool,
+            crossover_pool: &crossover_pool,
+            before: &before,
+            after: &after,
+            origins: &[Origin::Clone(1), Origin::Offspring(0, 1), Origin::Random],
+        };
+        let mut result = vec![];
+
+        // When
+        dump.write(&mut result).unwrap();
+
+        // Then
+        let result = String::from_utf8(result).unwrap();
+        let lines = result.lines().collect::<Vec<_>>();
+        assert_eq!("generation 4", lines[0]);
+        assert!(
+            lines[2].contains("BitFlip") && lines[2].contains("mutation_rate: 0.5"),
+            "Should describe the operators of the pools, got {}",
+            lines[2]
+        );
+        assert_eq!(
+            vec![
+                "before 2",
+                "0\t0.25\t3\t0fa0",
+                "1\t0.75\t0\t0102",
+                "after 3",
+                "0\tclone 1\t0102",
+                "1\toffspring 0 1\t0f02",
+                "2\trandom\tffff"
+            ],
+            lines[3..],
+            "Should list both populations with the parents of the renewed one"
+        );
+    }
+}
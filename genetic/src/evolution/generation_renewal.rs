@@ -0,0 +1,952 @@
+use std::{borrow::Cow, collections::HashSet};
+
+use common::subject_observer::Subject;
+use log::debug;
+use rand::{distributions::Standard, rngs::StdRng, Rng, SeedableRng};
+
+use crate::{
+    crossover::crossover,
+    mutation::{append_mutation_rate, mutate},
+    selection::{select, select_couples, select_couples_iter, select_iter, SelectionIter},
+    Evaluation, Genome,
+};
+
+use super::{
+    challenger::Challenger,
+    generation_dump::Origin,
+    genetic_pool::{largest_remainder, GeneticPool},
+    renewal::{Renewal, Repairer},
+    speciation::Species,
+    Deduplication, EventType, EvolutionConfig, EvolutionEngine, EvolutionError,
+    GenerationRenewalConfig, GeneticRenewalParam, RestartPolicy, Stagnation,
+};
+
+/// Mutations tried on a duplicated genome before replacing it with a random one.
+const MUTATION_ATTEMPTS: usize = 8;
+
+#[derive(Debug, Clone)]
+pub(super) struct ExecutionSettings {
+    pub(super) cloning_pool: GeneticPool,
+    pub(super) crossover_pool: GeneticPool,
+    pub(super) randoms_count: usize,
+    /// Bytes appended to the genomes of the strategy for the needs of the mutation operators.
+    pub(super) reserved_size: usize,
+}
+
+impl ExecutionSettings {
+    /// Appends the reserved tail to a genome of the strategy, seeding its mutation rate with the
+    /// one of the pools.
+    pub(super) fn reserve_tail(&self, genome: &mut Genome) {
+        if self.reserved_size > 0 {
+            let pool = if self.crossover_pool.mutation_type.reserved_size() > 0 {
+                &self.crossover_pool
+            } else {
+                &self.cloning_pool
+            };
+            append_mutation_rate(genome, pool.mutation_rate);
+        }
+    }
+}
+
+impl EvolutionEngine {
+    /// Returns the clones with the index of their parent, each one being selected when built.
+    pub(super) fn get_clones(
+        &self,
+        pool: &GeneticPool,
+        rng: &mut impl Rng,
+    ) -> Result<Vec<(usize, Evaluation)>, EvolutionError> {
+        if pool.count == 0 {
+            return Ok(vec![]);
+        }
+        let evaluations = self.selectable();
+        // The selection draws from its own generator while the clones are mutated
+        let mut selection_rng = StdRng::seed_from_u64(rng.gen());
+        self.select_clones(&evaluations, pool, &mut selection_rng)
+            .map(|selection| {
+                let index = selection?;
+                let mut clone = self.snapshot.evaluations[index].survive();
+                if pool.mutation_rate > 0.0 {
+                    mutate(
+                        &mut clone.genome,
+                        pool.mutation_rate,
+                        &pool.mutation_type,
+                        rng,
+                    );
+                    // The fitness of the parent no longer holds
+                    clone.evaluated = false;
+                    clone.phenotype = None;
+                    clone.metadata.clear();
+                }
+                Ok((index, clone))
+            })
+            .collect()
+    }
+
+    /// Returns the offsprings with the indexes of their parents, each couple being selected
+    /// when mated.
+    pub(super) fn get_offsprings(
+        &self,
+        genome_size: usize,
+        pool: &GeneticPool,
+        rng: &mut impl Rng,
+    ) -> Result<Vec<(Genome, Origin)>, EvolutionError> {
+        if pool.count == 0 {
+            return Ok(vec![]);
+        }
+        let evaluations = self.selectable();
+        // The selection draws from its own generator while the offsprings are bred
+        let mut selection_rng = StdRng::seed_from_u64(rng.gen());
+        self.select_parents(&evaluations, pool, &mut selection_rng)
+            .map(|selection| {
+                let (p1, p2) = selection?;
+                let mut offspring = crossover(
+                    (
+                        &self.snapshot.evaluations[p1].genome,
+                        &self.snapshot.evaluations[p2].genome,
+                    ),
+                    genome_size,
+                    &pool.crossover_type,
+                    rng,
+                );
+                if pool.mutation_rate > 0.0 {
+                    mutate(&mut offspring, pool.mutation_rate, &pool.mutation_type, rng);
+                }
+                Ok((offspring, Origin::Offspring(p1, p2)))
+            })
+            .collect()
+    }
+
+    /// Evaluations the individuals are selected on, see
+    /// [`super::EvolutionEngineBuilder::fitness_scaling`].
+    fn selectable(&self) -> Cow<'_, [Evaluation]> {
+        match self.fitness_scaling {
+            Some(scaling) => Cow::Owned(scaling.scale_evaluations(&self.snapshot.evaluations)),
+            None => Cow::Borrowed(&self.snapshot.evaluations),
+        }
+    }
+
+    /// Selects the individuals to clone, within their species when speciated, lazily
+    /// otherwise.
+    fn select_clones<'a>(
+        &self,
+        evaluations: &'a [Evaluation],
+        pool: &'a GeneticPool,
+        rng: &'a mut impl Rng,
+    ) -> SelectionIter<'a, usize> {
+        let Some(speciation) = self
+            .speciation
+            .as_ref()
+            .filter(|speciation| speciation.species().len() > 1)
+        else {
+            return select_iter(evaluations, pool.count, &pool.selection_type, rng);
+        };
+
+        let quotas = speciation.quotas(pool.count, |_| true);
+        let mut selected = Vec::with_capacity(pool.count);
+        for (species, quota) in speciation.species().iter().zip(quotas) {
+            if quota == 0 {
+                continue;
+            }
+            let members = species_members(evaluations, species);
+            match select(&members, quota, &pool.selection_type, rng) {
+                Ok(indexes) => selected.extend(indexes.into_iter().map(|i| species.members[i])),
+                Err(e) => debug!("Unable to select clones in species {}: {e}", species.id),
+            }
+        }
+        let missing = pool.count.saturating_sub(selected.len());
+        let selected = selected.into_iter().map(Ok);
+        if missing > 0 {
+            let missing = select_iter(evaluations, missing, &pool.selection_type, rng);
+            return Box::new(selected.chain(missing));
+        }
+        Box::new(selected)
+    }
+
+    /// Selects the couples of parents, both within the same species when speciated, lazily
+    /// otherwise.
+    fn select_parents<'a>(
+        &self,
+        evaluations: &'a [Evaluation],
+        pool: &'a GeneticPool,
+        rng: &'a mut impl Rng,
+    ) -> SelectionIter<'a, (usize, usize)> {
+        let Some(speciation) = self
+            .speciation
+            .as_ref()
+            .filter(|speciation| speciation.species().len() > 1)
+        else {
+            return select_couples_iter(
+                evaluations,
+                pool.count,
+                &pool.selection_type,
+                pool.distinct_parents,
+                rng,
+            );
+        };
+
+        // A single member cannot mate with another one of its species
+        let quotas = speciation.quotas(pool.count, |species| {
+            species.members.len() > 1 || !pool.distinct_parents
+        });
+        let mut selected = Vec::with_capacity(pool.count);
+        for (species, quota) in speciation.species().iter().zip(quotas) {
+            if quota == 0 {
+                continue;
+            }
+            let members = species_members(evaluations, species);
+            match select_couples(
+                &members,
+                quota,
+                &pool.selection_type,
+                pool.distinct_parents,
+                rng,
+            ) {
+                Ok(couples) => selected.extend(
+                    couples
+                        .into_iter()
+                        .map(|(p1, p2)| (species.members[p1], species.members[p2])),
+                ),
+                Err(e) => debug!("Unable to select parents in species {}: {e}", species.id),
+            }
+        }
+        let missing = pool.count.saturating_sub(selected.len());
+        let selected = selected.into_iter().map(Ok);
+        if missing > 0 {
+            let missing = select_couples_iter(
+                evaluations,
+                missing,
+                &pool.selection_type,
+                pool.distinct_parents,
+                rng,
+            );
+            return Box::new(selected.chain(missing));
+        }
+        Box::new(selected)
+    }
+
+    /// Returns the elites of the generation completed with random genomes, resizing the pools to
+    /// the grown population, see [`super::EvolutionEngineBuilder::restart_on_stagnation`].
+    pub(super) fn restart<T: Challenger>(
+        &mut self,
+        strategy: &T,
+        config: &EvolutionConfig,
+        policy: RestartPolicy,
+        settings: &mut ExecutionSettings,
+        rng: &mut impl Rng,
+    ) -> (Vec<Evaluation>, Vec<Origin>) {
+        let current_size = self.snapshot.evaluations.len();
+        let population_size =
+            ((current_size as f32 * policy.population_growth).round() as usize).max(1);
+        let mut resized =
+            resolve_settings(config.generation_renewal_config.as_ref(), population_size);
+        // Keep the mutation rates set through the handles
+        resized.cloning_pool.mutation_rate = settings.cloning_pool.mutation_rate;
+        resized.crossover_pool.mutation_rate = settings.crossover_pool.mutation_rate;
+        *settings = resized;
+
+        let evaluations = &self.snapshot.evaluations;
+        let mut ranked = (0..current_size)
+            .filter(|&index| evaluations[index].evaluated)
+            .collect::<Vec<_>>();
+        ranked.sort_by(|&a, &b| evaluations[b].fitness.total_cmp(&evaluations[a].fitness));
+        let elites_count = ((current_size as f32 * policy.elite_ratio).round() as usize)
+            .min(ranked.len())
+            .min(population_size);
+        let elites = ranked
+            .into_iter()
+            .take(elites_count)
+            .map(|index| (evaluations[index].survive(), Origin::Clone(index)));
+        let randoms =
+            get_random_genomes_iter(population_size - elites_count, strategy.genome_size(), rng)
+                .map(|mut genome| {
+                    strategy.repair(&mut genome);
+                    settings.reserve_tail(&mut genome);
+                    (genome.into(), Origin::Random)
+                });
+        let (evaluations, origins) = elites.chain(randoms).unzip();
+
+        if let Some(stagnation) = self.stagnation.as_mut() {
+            *stagnation = Stagnation::new(stagnation.generations(), stagnation.epsilon());
+        }
+        self.restarts += 1;
+        debug!(
+            "Restart {} with {} individual(s), {} elite(s) kept",
+            self.restarts, population_size, elites_count
+        );
+        self.notify_observers(EventType::Restarted {
+            restart: self.restarts,
+            population_size,
+        });
+        (evaluations, origins)
+    }
+
+    /// Returns the population renewed by the stages with the origin of each individual.
+    pub(super) fn get_new_generation<T: Challenger>(
+        &self,
+        strategy: &T,
+        settings: &ExecutionSettings,
+        rng: &mut impl Rng,
+    ) -> Result<(Vec<Evaluation>, Vec<Origin>), EvolutionError> {
+        let mut renewal = Renewal {
+            engine: self,
+            strategy,
+            settings,
+            deduplication: self.deduplication,
+            generation: self.snapshot.generation,
+            previous: &self.snapshot.evaluations,
+            evaluations: Vec::with_capacity(self.snapshot.evaluations.len()),
+            origins: Vec::with_capacity(self.snapshot.evaluations.len()),
+        };
+        for stage in self.renewal_stages.iter() {
+            stage.renew(&mut renewal, rng)?;
+        }
+
+        let population_size = renewal.population_size();
+        if renewal.evaluations.len() > population_size {
+            debug!(
+                "Dropping {} individual(s) beyond the population size",
+                renewal.evaluations.len() - population_size
+            );
+            renewal.evaluations.truncate(population_size);
+            renewal.origins.truncate(population_size);
+        }
+        Ok((renewal.evaluations, renewal.origins))
+    }
+}
+
+pub(super) fn get_random_genomes_iter(
+    count: usize,
+    genome_size: usize,
+    rng: &mut impl Rng,
+) -> impl Iterator<Item = Genome> + '_ {
+    (0..count).map(move |_| {
+        (&mut *rng)
+            .sample_iter(Standard)
+            .take(genome_size)
+            .collect()
+    })
+}
+
+/// Replaces the genomes found earlier in the generation, see
+/// [`super::EvolutionEngineBuilder::deduplicate`].
+pub(super) fn deduplicate<T: Repairer + ?Sized>(
+    strategy: &T,
+    settings: &ExecutionSettings,
+    deduplication: Deduplication,
+    evaluations: &mut [Evaluation],
+    origins: &mut [Origin],
+    rng: &mut impl Rng,
+) {
+    let pool = if settings.crossover_pool.count > 0 {
+        &settings.crossover_pool
+    } else {
+        &settings.cloning_pool
+    };
+    let strategy_genome_size = strategy.genome_size();
+    // At least one gene is expected to change with each attempt
+    let mutation_rate = pool
+        .mutation_rate
+        .max(1.0 / strategy_genome_size.max(1) as f32);
+
+    let mut genomes = HashSet::with_capacity(evaluations.len());
+    let mut replaced = 0;
+    for (evaluation, origin) in evaluations.iter_mut().zip(origins.iter_mut()) {
+        if genomes.contains(&evaluation.genome) {
+            let mut genome = evaluation.genome.clone();
+            if deduplication == Deduplication::Mutate {
+                for _ in 0..MUTATION_ATTEMPTS {
+                    mutate(&mut genome, mutation_rate, &pool.mutation_type, rng);
+                    repair(strategy, &mut genome, settings.reserved_size);
+                    if !genomes.contains(&genome) {
+                        break;
+                    }
+                }
+            }
+            if genomes.contains(&genome) {
+                genome = get_random_genomes_iter(1, strategy_genome_size, rng)
+                    .next()
+                    .unwrap_or_default();
+                strategy.repair(&mut genome);
+                settings.reserve_tail(&mut genome);
+                *origin = Origin::Random;
+            }
+            *evaluation = genome.into();
+            replaced += 1;
+        }
+        genomes.insert(evaluation.genome.clone());
+    }
+    if replaced > 0 {
+        debug!("Replaced {} duplicated genome(s)", replaced);
+    }
+}
+
+/// Lets the strategy repair a genome, hiding the tail reserved for the mutation operators.
+pub(super) fn repair<T: Repairer + ?Sized>(
+    strategy: &T,
+    genome: &mut Genome,
+    reserved_size: usize,
+) {
+    let tail = genome.split_off(genome.len().saturating_sub(reserved_size));
+    strategy.repair(genome);
+    genome.extend(tail);
+}
+
+pub(super) fn resolve_settings(
+    generation_renewal_config: Option<&GenerationRenewalConfig>,
+    population_size: usize,
+) -> ExecutionSettings {
+    let mut cloning_pool = GeneticPool::from_optional_params(
+        generation_renewal_config.and_then(|c| c.cloning.as_ref()),
+        population_size,
+    );
+    let mut crossover_pool = GeneticPool::from_optional_params(
+        generation_renewal_config.and_then(|c| c.crossover.as_ref()),
+        population_size,
+    );
+
+    // Pools exceeding the population share it in proportion to their ratios
+    let mut cloning_share = pool_ratio(generation_renewal_config.and_then(|c| c.cloning.as_ref()));
+    let mut crossover_share =
+        pool_ratio(generation_renewal_config.and_then(|c| c.crossover.as_ref()));
+    let pools_share = cloning_share + crossover_share;
+    if pools_share > 1.0 {
+        cloning_share /= pools_share;
+        crossover_share /= pools_share;
+    }
+    let left_share = (1.0 - cloning_share - crossover_share).max(0.0);
+    let mut randoms_share = left_share;
+    if let Some(config) = generation_renewal_config {
+        if let Some(random_ratio) = config.random_ratio {
+            let share = random_ratio.clamp(0.0, left_share);
+            if config.crossover.is_some() {
+                crossover_share += left_share - share;
+                randoms_share = share;
+            } else if config.cloning.is_some() {
+                cloning_share += left_share - share;
+                randoms_share = share;
+            }
+        }
+    }
+    let counts = largest_remainder(
+        &[cloning_share, crossover_share, randoms_share],
+        population_size,
+    );
+    cloning_pool.count = counts[0];
+    crossover_pool.count = counts[1];
+    let randoms_count = counts[2];
+    let reserved_size = cloning_pool
+        .mutation_type
+        .reserved_size()
+        .max(crossover_pool.mutation_type.reserved_size());
+    ExecutionSettings {
+        cloning_pool,
+        crossover_pool,
+        randoms_count,
+        reserved_size,
+    }
+}
+
+/// Share of the population renewed by a pool, none without.
+fn pool_ratio(params: Option<&GeneticRenewalParam>) -> f32 {
+    params.map_or(0.0, |params| params.ratio.max(0.0))
+}
+
+/// Evaluations of the members of the species, in the order of [`Species::members`].
+fn species_members(evaluations: &[Evaluation], species: &Species) -> Vec<Evaluation> {
+    species
+        .members
+        .iter()
+        .map(|&index| evaluations[index].clone())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use common_test::get_seeded_rng;
+    use rand::{distributions::Standard, Rng};
+
+    use crate::{
+        adaptation::Strategy,
+        evolution::{
+            generation_dump::Origin, genetic_pool::GeneticPool, Deduplication, EvolutionEngine,
+            GenerationRenewalConfig, GeneticRenewalParam, Snapshot,
+        },
+        selection::{FitnessScaling, SelectionType},
+        Evaluation, Genome,
+    };
+
+    use super::{deduplicate, get_random_genomes_iter, repair, resolve_settings};
+
+    /// Sorts genomes in place of penalizing unsorted ones.
+    struct Sorted;
+
+    impl Strategy for Sorted {
+        type Phenotype = Genome;
+
+        fn genome_size(&self) -> usize {
+            8
+        }
+
+        fn decode(&self, genome: &Genome) -> Genome {
+            genome.clone()
+        }
+
+        fn evaluate(&self, _: &Genome) -> f32 {
+            0.5
+        }
+
+        fn repair(&self, genome: &mut Genome) {
+            genome.sort();
+        }
+    }
+
+    /// Reads genomes as the sum of their genes.
+    struct Sum;
+
+    impl Strategy for Sum {
+        type Phenotype = u32;
+
+        fn genome_size(&self) -> usize {
+            4
+        }
+
+        fn decode(&self, genome: &Genome) -> u32 {
+            genome.iter().map(|&gene| gene as u32).sum()
+        }
+
+        fn evaluate(&self, sum: &u32) -> f32 {
+            *sum as f32 / 1020.0
+        }
+    }
+
+    #[test]
+    fn test_evolution_engine_get_clones() {
+        let mut rng = get_seeded_rng().unwrap();
+        let mut engine = EvolutionEngine::default();
+        engine.snapshot = Snapshot {
+            evaluations: vec![
+                Evaluation {
+                    fitness: 0.5,
+                    genome: vec![3],
+                    ..Default::default()
+                },
+                Evaluation {
+                    fitness: 0.2,
+                    genome: vec![5, 1],
+                    ..Default::default()
+                },
+                Evaluation {
+                    fitness: 0.8,
+                    genome: vec![6, 3],
+                    ..Default::default()
+                },
+            ],
+            generation: 0,
+            evaluation_count: 0,
+            hall_of_fame: vec![],
+            stats: None,
+            diversity: None,
+            parameters: None,
+            metadata: None,
+            lineage: None,
+        };
+
+        // Given
+        let pool = GeneticPool {
+            count: 2,
+            mutation_rate: 0.0,
+            selection_type: SelectionType::Chance,
+            ..Default::default()
+        };
+
+        // When
+        let result = engine.get_clones(&pool, &mut rng).unwrap();
+
+        // Then
+        assert_eq!(
+            pool.count,
+            result.len(),
+            "Should return the count of clone defined by the pool when mutation rate is 0"
+        );
+        assert!(
+            result.iter().all(|(_, clone)| clone.age == 1),
+            "Should age clones by one generation"
+        );
+
+        // Given
+        let pool = GeneticPool {
+            count: 2,
+            mutation_rate: 0.5,
+            selection_type: SelectionType::Chance,
+            ..Default::default()
+        };
+
+        // When
+        let result = engine.get_clones(&pool, &mut rng).unwrap();
+
+        // Then
+        assert_eq!(
+            pool.count,
+            result.len(),
+            "Should return the count of clone defined by the pool when mutation rate is greater than 0"
+        );
+    }
+
+    #[test]
+    fn test_evolution_engine_fitness_scaling() {
+        // Given
+        let mut rng = get_seeded_rng().unwrap();
+        let mut engine = EvolutionEngine {
+            snapshot: Snapshot {
+                evaluations: vec![
+                    Evaluation {
+                        fitness: 0.5,
+                        genome: vec![3],
+                        evaluated: true,
+                        ..Default::default()
+                    },
+                    Evaluation {
+                        fitness: 1.0,
+                        genome: vec![6],
+                        evaluated: true,
+                        ..Default::default()
+                    },
+                ],
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        engine.fitness_scaling = Some(FitnessScaling::Power { exponent: 50.0 });
+        let pool = GeneticPool {
+            count: 1,
+            mutation_rate: 0.0,
+            selection_type: SelectionType::Weight,
+            ..Default::default()
+        };
+
+        // When
+        let result = (0..1000)
+            .flat_map(|_| engine.get_clones(&pool, &mut rng).unwrap())
+            .collect::<Vec<_>>();
+
+        // Then
+        assert!(
+            result.iter().filter(|(index, _)| *index == 0).count() < 100,
+            "Should select on the scaled fitnesses"
+        );
+        assert!(
+            result
+                .iter()
+                .all(|(index, clone)| clone.fitness == engine.snapshot.evaluations[*index].fitness),
+            "Should keep the fitness of the clones"
+        );
+    }
+
+    #[test]
+    fn test_evolution_engine_get_offsprings() {
+        let mut rng = get_seeded_rng().unwrap();
+        let genome_size = rng.gen_range(1usize..10);
+        let mut engine = EvolutionEngine::default();
+        engine.snapshot = Snapshot {
+            evaluations: vec![
+                Evaluation {
+                    fitness: 0.5,
+                    genome: rng
+                        .clone()
+                        .sample_iter(Standard)
+                        .take(genome_size)
+                        .collect(),
+                    ..Default::default()
+                },
+                Evaluation {
+                    fitness: 0.2,
+                    genome: rng
+                        .clone()
+                        .sample_iter(Standard)
+                        .take(genome_size)
+                        .collect(),
+                    ..Default::default()
+                },
+                Evaluation {
+                    fitness: 0.8,
+                    genome: rng
+                        .clone()
+                        .sample_iter(Standard)
+                        .take(genome_size)
+                        .collect(),
+                    ..Default::default()
+                },
+            ],
+            generation: 0,
+            evaluation_count: 0,
+            hall_of_fame: vec![],
+            stats: None,
+            diversity: None,
+            parameters: None,
+            metadata: None,
+            lineage: None,
+        };
+
+        // Given
+        let pool = GeneticPool {
+            count: 2,
+            mutation_rate: 0.0,
+            selection_type: SelectionType::Chance,
+            ..Default::default()
+        };
+
+        // When
+        let result = engine.get_offsprings(genome_size, &pool, &mut rng).unwrap();
+
+        // Then
+        assert_eq!(
+            pool.count,
+            result.len(),
+            "Should return the count of clone defined by the pool when mutation rate is 0"
+        );
+
+        // Given
+        let pool = GeneticPool {
+            count: 2,
+            mutation_rate: 0.5,
+            selection_type: SelectionType::Chance,
+            ..Default::default()
+        };
+
+        // When
+        let result = engine.get_offsprings(genome_size, &pool, &mut rng).unwrap();
+
+        // Then
+        assert_eq!(
+            pool.count,
+            result.len(),
+            "Should return the count of clone defined by the pool when mutation rate is greater than 0"
+        );
+    }
+
+    #[test]
+    fn test_get_random_genomes_iter() {
+        // Given
+        let mut rng = get_seeded_rng().unwrap();
+        let count = rng.gen_range(0..64);
+        let genome_size = rng.gen_range(0..10);
+
+        // When
+        let result: Vec<Genome> = get_random_genomes_iter(count, genome_size, &mut rng).collect();
+
+        // Then
+        assert_eq!(
+            count,
+            result.len(),
+            "Should return the required count of genomes"
+        );
+        assert!(
+            result.iter().all(|g| g.len() == genome_size),
+            "Should generate genomes with the required length"
+        );
+    }
+
+    #[test]
+    fn test_generate_genomes() {
+        // Given
+        let mut rng = get_seeded_rng().unwrap();
+        let count = rng.gen_range(0..10);
+        let len = rng.gen_range(0..10);
+
+        // When
+        let result: Vec<Genome> = get_random_genomes_iter(count, len, &mut rng).collect();
+
+        // Then
+        assert_eq!(count, result.len(), "Should generate the requested count");
+    }
+
+    #[test]
+    fn test_deduplicate() {
+        // Given
+        let mut rng = get_seeded_rng().unwrap();
+        let settings = resolve_settings(None, 4);
+        let copies = || {
+            (
+                vec![Evaluation::from(vec![1, 2, 3, 4]); 4],
+                vec![Origin::Clone(0); 4],
+            )
+        };
+
+        for deduplication in [Deduplication::Randomize, Deduplication::Mutate] {
+            // Given
+            let (mut evaluations, mut origins) = copies();
+
+            // When
+            deduplicate(
+                &Sum,
+                &settings,
+                deduplication,
+                &mut evaluations,
+                &mut origins,
+                &mut rng,
+            );
+
+            // Then
+            assert_eq!(
+                vec![1, 2, 3, 4],
+                evaluations[0].genome,
+                "Should keep the first copy"
+            );
+            assert!(
+                (1..4).all(|i| (0..i).all(|j| evaluations[i].genome != evaluations[j].genome)),
+                "Should replace the copies with {deduplication:?}"
+            );
+            assert!(evaluations.iter().all(|e| e.genome.len() == 4));
+        }
+    }
+
+    #[test]
+    fn test_repair() {
+        // Given
+        let mut genome = vec![3, 1, 2, 9, 0];
+
+        // When
+        repair(&Sorted, &mut genome, 2);
+
+        // Then
+        assert_eq!(
+            vec![1, 2, 3, 9, 0],
+            genome,
+            "Should not repair the reserved tail"
+        );
+    }
+
+    #[test]
+    fn test_resolve_settings() {
+        // Given
+        let config = GenerationRenewalConfig {
+            cloning: Some(GeneticRenewalParam {
+                mutation_rate: None,
+                ratio: 0.5,
+                selection_type: SelectionType::Chance,
+                ..Default::default()
+            }),
+            crossover: None,
+            random_ratio: None,
+        };
+
+        // When
+        let result = resolve_settings(Some(&config), 64);
+
+        // Then
+        assert_eq!(32, result.randoms_count)
+    }
+
+    #[test]
+    fn test_resolve_settings_rounding() {
+        // Given
+        let param = |ratio| GeneticRenewalParam {
+            ratio,
+            ..Default::default()
+        };
+        let config = GenerationRenewalConfig {
+            cloning: Some(param(0.5)),
+            crossover: Some(param(0.5)),
+            random_ratio: None,
+        };
+
+        // When
+        let result = resolve_settings(Some(&config), 7);
+
+        // Then
+        assert_eq!(
+            (4, 3, 0),
+            (
+                result.cloning_pool.count,
+                result.crossover_pool.count,
+                result.randoms_count
+            ),
+            "Should round the counts to the population size"
+        );
+
+        // Given
+        let config = GenerationRenewalConfig {
+            cloning: Some(param(0.15)),
+            crossover: Some(param(0.55)),
+            random_ratio: None,
+        };
+
+        // When
+        let result = resolve_settings(Some(&config), 10);
+
+        // Then
+        assert_eq!(
+            (2, 5, 3),
+            (
+                result.cloning_pool.count,
+                result.crossover_pool.count,
+                result.randoms_count
+            ),
+            "Should give the remainder to the largest fractions"
+        );
+
+        // Given
+        let config = GenerationRenewalConfig {
+            cloning: Some(param(1.0)),
+            crossover: Some(param(1.0)),
+            random_ratio: None,
+        };
+
+        // When
+        let result = resolve_settings(Some(&config), 10);
+
+        // Then
+        assert_eq!(
+            (5, 5, 0),
+            (
+                result.cloning_pool.count,
+                result.crossover_pool.count,
+                result.randoms_count
+            ),
+            "Should normalize excessive ratios"
+        );
+    }
+
+    #[test]
+    fn test_resolve_settings_with_random_ratio() {
+        // Given
+        let param = GeneticRenewalParam {
+            ratio: 0.5,
+            ..Default::default()
+        };
+        let mut config = GenerationRenewalConfig {
+            cloning: Some(param.clone()),
+            crossover: Some(param.clone()),
+            random_ratio: Some(0.1),
+        };
+
+        // When
+        let result = resolve_settings(Some(&config), 10);
+
+        // Then
+        assert_eq!(
+            (5, 5, 0),
+            (
+                result.cloning_pool.count,
+                result.crossover_pool.count,
+                result.randoms_count
+            ),
+            "Should not draw randoms beyond the population"
+        );
+
+        // When
+        config.cloning = Some(GeneticRenewalParam {
+            ratio: 0.2,
+            ..param
+        });
+        config.crossover = None;
+        let result = resolve_settings(Some(&config), 10);
+
+        // Then
+        assert_eq!(
+            (9, 1),
+            (result.cloning_pool.count, result.randoms_count),
+            "Should give the cloning pool what is left without crossover"
+        );
+    }
+}
@@ -1,14 +1,18 @@
-use crate::selection::SelectionType;
+use crate::{crossover::CrossoverType, mutation::MutationType, selection::SelectionType};
 
 use super::GeneticRenewalParam;
 
-const DEFAULT_MUTATION_RATE: f32 = 0.01;
+/// Mutation rate of the pools not setting one.
+pub const DEFAULT_MUTATION_RATE: f32 = 0.01;
 
-#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[derive(Debug, Clone, PartialEq, Default)]
 pub struct GeneticPool {
     pub count: usize,
     pub mutation_rate: f32,
+    pub mutation_type: MutationType,
     pub selection_type: SelectionType,
+    pub distinct_parents: bool,
+    pub crossover_type: CrossoverType,
 }
 
 impl GeneticPool {
@@ -16,7 +20,10 @@ impl GeneticPool {
         GeneticPool {
             count: (params.ratio * total as f32) as usize,
             mutation_rate: params.mutation_rate.unwrap_or(DEFAULT_MUTATION_RATE),
-            selection_type: params.selection_type,
+            mutation_type: params.mutation_type.clone(),
+            selection_type: params.selection_type.clone(),
+            distinct_parents: params.distinct_parents.unwrap_or(true),
+            crossover_type: params.crossover_type.clone(),
         }
     }
 
@@ -28,14 +35,39 @@ impl GeneticPool {
     }
 }
 
+/// Splits `count` in proportion to the shares, the remainder going to the largest fractions.
+pub(super) fn largest_remainder(shares: &[f32], count: usize) -> Vec<usize> {
+    let total = shares.iter().sum::<f32>();
+    if total <= 0.0 {
+        return vec![0; shares.len()];
+    }
+    let exact = shares
+        .iter()
+        .map(|share| share / total * count as f32)
+        .collect::<Vec<_>>();
+    let mut quotas = exact
+        .iter()
+        .map(|quota| quota.floor() as usize)
+        .collect::<Vec<_>>();
+    let mut by_remainder = (0..shares.len()).collect::<Vec<_>>();
+    by_remainder
+        .sort_by(|&a, &b| (exact[b] - exact[b].floor()).total_cmp(&(exact[a] - exact[a].floor())));
+    let missing = count.saturating_sub(quotas.iter().sum());
+    for index in by_remainder.into_iter().take(missing) {
+        quotas[index] += 1;
+    }
+    quotas
+}
+
 #[cfg(test)]
 mod tests {
     use crate::{
         evolution::{genetic_pool::DEFAULT_MUTATION_RATE, GeneticRenewalParam},
+        mutation::MutationType,
         selection::SelectionType,
     };
 
-    use super::GeneticPool;
+    use super::{largest_remainder, GeneticPool};
 
     #[test]
     fn test_genetic_pool_from_param_should_use_default_rate_when_required() {
@@ -44,6 +76,7 @@ mod tests {
             mutation_rate: None,
             ratio: 0.1,
             selection_type: SelectionType::Chance,
+            ..Default::default()
         };
 
         // When
@@ -61,6 +94,7 @@ mod tests {
             mutation_rate: None,
             ratio: 0.1,
             selection_type: SelectionType::Chance,
+            ..Default::default()
         };
 
         // When
@@ -77,6 +111,7 @@ mod tests {
             mutation_rate: None,
             ratio: 0.1,
             selection_type: SelectionType::Chance,
+            ..Default::default()
         };
 
         // When
@@ -86,6 +121,48 @@ mod tests {
         assert_eq!(params.selection_type, result.selection_type);
     }
 
+    #[test]
+    fn test_genetic_pool_from_param_should_use_param_mutation_type() {
+        // Given
+        let params = GeneticRenewalParam {
+            ratio: 0.1,
+            mutation_type: MutationType::BitFlip,
+            ..Default::default()
+        };
+
+        // When
+        let result = GeneticPool::from_params(&params, 10);
+
+        // Then
+        assert_eq!(MutationType::BitFlip, result.mutation_type);
+    }
+
+    #[test]
+    fn test_genetic_pool_from_param_should_require_distinct_parents_by_default() {
+        // Given
+        let params = GeneticRenewalParam {
+            ratio: 0.1,
+            ..Default::default()
+        };
+
+        // When
+        let result = GeneticPool::from_params(&params, 10);
+
+        // Then
+        assert!(result.distinct_parents);
+        assert!(
+            !GeneticPool::from_params(
+                &GeneticRenewalParam {
+                    distinct_parents: Some(false),
+                    ..params
+                },
+                10
+            )
+            .distinct_parents,
+            "Should allow self-mating when requested"
+        );
+    }
+
     #[test]
     fn test_genetic_pool_from_optional_param_should_return_valid_pool_when_some() {
         // Given
@@ -94,6 +171,7 @@ mod tests {
             mutation_rate: Some(0.1),
             ratio: 5.0,
             selection_type: SelectionType::Ranking(8),
+            ..Default::default()
         };
 
         // When
@@ -111,4 +189,10 @@ mod tests {
         // Then
         assert_eq!(GeneticPool::default(), result);
     }
+
+    #[test]
+    fn test_largest_remainder() {
+        assert_eq!(vec![2, 1, 1], largest_remainder(&[5.0, 3.0, 2.0], 4));
+        assert_eq!(vec![0, 0], largest_remainder(&[0.0, 0.0], 4));
+    }
 }
@@ -0,0 +1,126 @@
+use std::{collections::VecDeque, time::Duration};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::analysis::GenerationStats;
+
+use super::Snapshot;
+
+/// Content recorded for each generation, see [`super::EvolutionEngineBuilder::record_history`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HistoryDetail {
+    Summary,
+    /// Summary along with the evaluated population.
+    Snapshot,
+}
+
+/// Outcome of an evaluated generation, injected genomes included.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct GenerationRecord {
+    pub generation: u64,
+    pub evaluation_count: u64,
+    /// Time spent since the start of the run.
+    pub elapsed: Duration,
+    pub stats: Option<GenerationStats>,
+    /// Evaluated population, only recorded with [`HistoryDetail::Snapshot`].
+    pub snapshot: Option<Snapshot>,
+}
+
+/// Last generations of a run, the oldest being dropped once full.
+#[derive(Debug, Clone)]
+pub(super) struct History {
+    capacity: usize,
+    detail: HistoryDetail,
+    records: VecDeque<GenerationRecord>,
+}
+
+impl History {
+    pub fn new(capacity: usize, detail: HistoryDetail) -> Self {
+        History {
+            capacity,
+            detail,
+            records: VecDeque::with_capacity(capacity),
+        }
+    }
+
+    pub fn record(&mut self, snapshot: &Snapshot, elapsed: Duration) {
+        if self.capacity == 0 {
+            return;
+        }
+        if self.records.len() == self.capacity {
+            self.records.pop_front();
+        }
+        self.records.push_back(GenerationRecord {
+            generation: snapshot.generation,
+            evaluation_count: snapshot.evaluation_count,
+            elapsed,
+            stats: snapshot.stats,
+            snapshot: (self.detail == HistoryDetail::Snapshot).then(|| snapshot.clone()),
+        });
+    }
+
+    pub fn records(&self) -> &VecDeque<GenerationRecord> {
+        &self.records
+    }
+
+    pub fn clear(&mut self) {
+        self.records.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::evolution::Snapshot;
+
+    use super::{History, HistoryDetail};
+
+    #[test]
+    fn test_history_record() {
+        // Given
+        let mut history = History::new(2, HistoryDetail::Summary);
+        let mut detailed = History::new(1, HistoryDetail::Snapshot);
+
+        // When
+        for generation in 0..3 {
+            let snapshot = Snapshot {
+                generation,
+                ..Default::default()
+            };
+            history.record(&snapshot, Duration::from_secs(generation));
+            detailed.record(&snapshot, Duration::ZERO);
+        }
+
+        // Then
+        assert_eq!(
+            vec![1, 2],
+            history
+                .records()
+                .iter()
+                .map(|record| record.generation)
+                .collect::<Vec<_>>(),
+            "Should keep the last generations"
+        );
+        assert!(history.records().iter().all(|r| r.snapshot.is_none()));
+        assert_eq!(
+            Some(2),
+            detailed.records()[0]
+                .snapshot
+                .as_ref()
+                .map(|snapshot| snapshot.generation)
+        );
+
+        // When
+        let mut empty = History::new(0, HistoryDetail::Summary);
+        empty.record(&Snapshot::default(), Duration::ZERO);
+
+        // Then
+        assert!(
+            empty.records().is_empty(),
+            "Should not record without capacity"
+        );
+    }
+}
@@ -0,0 +1,41 @@
+use crate::Evaluation;
+
+/// Code run by the engine around each generation with mutable access to its individuals, e.g.
+/// to transform the fitnesses before the selection, see [`super::EvolutionEngineBuilder::hook`].
+///
+/// Unlike observers, hooks may change the individuals: fitnesses are expected to stay within
+/// [0, 1] and genomes to keep their size.
+pub trait GenerationHook: Send {
+    /// Called once the generation is created, before its evaluation.
+    fn on_generation_start(&mut self, _generation: u64, _evaluations: &mut [Evaluation]) {}
+
+    /// Called once the generation is evaluated, injected genomes included, before the hall of
+    /// fame, the statistics and the selection of the next generation take its fitnesses.
+    fn on_generation_end(&mut self, _generation: u64, _evaluations: &mut [Evaluation]) {}
+}
+
+/// Hook running a closure at the start of each generation, see
+/// [`super::EvolutionEngineBuilder::on_generation_start`].
+pub(super) struct OnGenerationStart<F>(pub F);
+
+impl<F> GenerationHook for OnGenerationStart<F>
+where
+    F: FnMut(u64, &mut [Evaluation]) + Send,
+{
+    fn on_generation_start(&mut self, generation: u64, evaluations: &mut [Evaluation]) {
+        (self.0)(generation, evaluations);
+    }
+}
+
+/// Hook running a closure at the end of each generation, see
+/// [`super::EvolutionEngineBuilder::on_generation_end`].
+pub(super) struct OnGenerationEnd<F>(pub F);
+
+impl<F> GenerationHook for OnGenerationEnd<F>
+where
+    F: FnMut(u64, &mut [Evaluation]) + Send,
+{
+    fn on_generation_end(&mut self, generation: u64, evaluations: &mut [Evaluation]) {
+        (self.0)(generation, evaluations);
+    }
+}
@@ -0,0 +1,155 @@
+use rand::{seq::index::sample, Rng};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{Evaluation, Genome};
+
+/// How genomes injected into a running evolution make room in the population.
+#[derive(Copy, Clone, Debug, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ReplacementPolicy {
+    /// Injected genomes replace the least fit individuals.
+    #[default]
+    Worst,
+    /// Injected genomes replace individuals picked at random.
+    Random,
+    /// Injected genomes are added to the population until its next renewal.
+    Append,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct Injection {
+    pub genomes: Vec<Genome>,
+    pub policy: ReplacementPolicy,
+}
+
+/// Puts the injected evaluations into the population and returns the positions they took.
+///
+/// Replacing policies never inject more genomes than the population holds.
+pub(super) fn insert(
+    population: &mut Vec<Evaluation>,
+    mut injected: Vec<Evaluation>,
+    policy: ReplacementPolicy,
+    rng: &mut impl Rng,
+) -> Vec<usize> {
+    let indexes = match policy {
+        ReplacementPolicy::Append => {
+            let positions = population.len()..population.len() + injected.len();
+            population.append(&mut injected);
+            return positions.collect();
+        }
+        ReplacementPolicy::Worst => {
+            let mut indexes = (0..population.len()).collect::<Vec<_>>();
+            indexes.sort_by(|&a, &b| population[a].fitness.total_cmp(&population[b].fitness));
+            indexes.truncate(injected.len());
+            indexes
+        }
+        ReplacementPolicy::Random => {
+            let count = injected.len().min(population.len());
+            sample(rng, population.len(), count).into_vec()
+        }
+    };
+
+    for (&index, evaluation) in indexes.iter().zip(injected) {
+        population[index] = evaluation;
+    }
+    indexes
+}
+
+#[cfg(test)]
+mod tests {
+    use common_test::get_seeded_rng;
+
+    use crate::Evaluation;
+
+    use super::{insert, ReplacementPolicy};
+
+    fn get_population() -> Vec<Evaluation> {
+        [0.5, 0.1, 0.9, 0.3]
+            .into_iter()
+            .enumerate()
+            .map(|(i, fitness)| Evaluation {
+                genome: vec![i as u8],
+                fitness,
+                ..Default::default()
+            })
+            .collect()
+    }
+
+    fn get_injected(count: u8) -> Vec<Evaluation> {
+        (0..count)
+            .map(|i| Evaluation {
+                genome: vec![100 + i],
+                fitness: 0.2,
+                ..Default::default()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_insert_worst() {
+        // Given
+        let mut population = get_population();
+        let mut rng = get_seeded_rng().unwrap();
+
+        // When
+        let result = insert(
+            &mut population,
+            get_injected(2),
+            ReplacementPolicy::Worst,
+            &mut rng,
+        );
+
+        // Then
+        assert_eq!(vec![1, 3], result);
+        assert_eq!(
+            vec![vec![0], vec![100], vec![2], vec![101]],
+            population.into_iter().map(|e| e.genome).collect::<Vec<_>>(),
+            "Should replace the least fit individuals"
+        );
+    }
+
+    #[test]
+    fn test_insert_random() {
+        // Given
+        let mut population = get_population();
+        let mut rng = get_seeded_rng().unwrap();
+
+        // When
+        let result = insert(
+            &mut population,
+            get_injected(6),
+            ReplacementPolicy::Random,
+            &mut rng,
+        );
+
+        // Then
+        assert_eq!(
+            4,
+            result.len(),
+            "Should not inject more than the population"
+        );
+        assert_eq!(4, population.len());
+        assert!(population.iter().all(|e| e.genome[0] >= 100));
+    }
+
+    #[test]
+    fn test_insert_append() {
+        // Given
+        let mut population = get_population();
+        let mut rng = get_seeded_rng().unwrap();
+
+        // When
+        let result = insert(
+            &mut population,
+            get_injected(2),
+            ReplacementPolicy::Append,
+            &mut rng,
+        );
+
+        // Then
+        assert_eq!(vec![4, 5], result);
+        assert_eq!(6, population.len(), "Should grow the population");
+        assert_eq!(get_population(), population[..4]);
+    }
+}
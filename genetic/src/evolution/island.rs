@@ -0,0 +1,333 @@
+use rand::Rng;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{adaptation::Strategy, Genome};
+
+use super::{
+    EvolutionConfig, EvolutionEngine, EvolutionError, EvolutionStatus, Progress, ReplacementPolicy,
+    Snapshot, TerminationCriterion,
+};
+
+/// Connections along which the islands send their migrants.
+#[derive(Copy, Clone, Debug, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum MigrationTopology {
+    /// Each island sends to the next one, the last sending to the first.
+    #[default]
+    Ring,
+    /// Each island sends to all the others.
+    FullyConnected,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct MigrationConfig {
+    /// Generations between two migrations, none if zero.
+    pub interval: u64,
+    /// Number of the fittest individuals each island sends.
+    pub migrants: usize,
+    pub topology: MigrationTopology,
+    /// How migrants make room in the populations receiving them.
+    pub policy: ReplacementPolicy,
+}
+
+/// Runs several populations evolving independently, exchanging their fittest individuals every
+/// few generations to keep the search from converging prematurely.
+///
+/// Each island is an [`EvolutionEngine`] driven generation by generation, which observers,
+/// handles and options are set on through [`IslandEngine::islands_mut`].
+pub struct IslandEngine {
+    islands: Vec<EvolutionEngine>,
+    migration: MigrationConfig,
+}
+
+impl IslandEngine {
+    pub fn new(count: usize, migration: MigrationConfig) -> Self {
+        IslandEngine {
+            islands: (0..count).map(|_| EvolutionEngine::default()).collect(),
+            migration,
+        }
+    }
+
+    pub fn islands(&self) -> &[EvolutionEngine] {
+        &self.islands
+    }
+
+    pub fn islands_mut(&mut self) -> &mut [EvolutionEngine] {
+        &mut self.islands
+    }
+
+    /// Halts all the islands, see [`EvolutionEngine::halt`].
+    pub fn halt(&self) -> Result<bool, EvolutionError> {
+        self.islands
+            .iter()
+            .try_fold(false, |halted, island| Ok(island.halt()? || halted))
+    }
+
+    /// Evolves a population of `config.population_size` individuals on each island until the
+    /// criterion is met or all the islands are halted, returning the last generation of each.
+    ///
+    /// The criterion and the time budget are checked against the islands as a whole: the
+    /// fitnesses of all the populations, the sum of their evaluations and the time since the
+    /// start of the first island.
+    pub async fn start<T, C>(
+        &mut self,
+        strategy: &T,
+        config: &EvolutionConfig,
+        mut is_complete: C,
+        rng: &mut impl Rng,
+    ) -> Result<Vec<Snapshot>, EvolutionError>
+    where
+        T: Strategy,
+        C: TerminationCriterion,
+    {
+        let island_config = EvolutionConfig {
+            max_duration: None,
+            ..config.clone()
+        };
+        for island in self.islands.iter_mut() {
+            island.prepare(strategy, &island_config, rng, None)?;
+        }
+
+        let mut snapshots = vec![Snapshot::default(); self.islands.len()];
+        loop {
+            let mut running = false;
+            for (island, snapshot) in self.islands.iter_mut().zip(snapshots.iter_mut()) {
+                if is_running(island.status()?) {
+                    *snapshot = island.step(strategy, rng).await?;
+                    running |= is_running(island.status()?);
+                }
+            }
+            if !running {
+                return Ok(snapshots);
+            }
+
+            let fitnesses = snapshots
+                .iter()
+                .flat_map(|snapshot| snapshot.evaluations.iter())
+                .filter_map(|e| e.evaluated_fitness())
+                .collect::<Vec<_>>();
+            let progress = Progress {
+                generation: snapshots.iter().map(|s| s.generation).max().unwrap_or(0),
+                fitnesses: &fitnesses,
+                evaluation_count: snapshots.iter().map(|s| s.evaluation_count).sum(),
+                elapsed: self
+                    .islands
+                    .first()
+                    .map(|i| i.elapsed())
+                    .unwrap_or_default(),
+                diversity: None,
+            };
+            let out_of_time = config
+                .max_duration
+                .is_some_and(|max_duration| progress.elapsed >= max_duration);
+            if is_complete.try_is_met(&progress)? || out_of_time {
+                for island in self.islands.iter_mut() {
+                    island.complete()?;
+                }
+                return Ok(snapshots);
+            }
+
+            if (progress.generation + 1).checked_rem(self.migration.interval) == Some(0) {
+                self.migrate(&snapshots, strategy.genome_size())?;
+            }
+        }
+    }
+
+    /// Queues the fittest individuals of each island for injection into its neighbours.
+    fn migrate(&self, snapshots: &[Snapshot], genome_size: usize) -> Result<(), EvolutionError> {
+        let count = self.islands.len();
+        for (from, snapshot) in snapshots.iter().enumerate() {
+            let migrants = fittest_genomes(snapshot, self.migration.migrants, genome_size);
+            if migrants.is_empty() {
+                continue;
+            }
+            let destinations: Vec<usize> = match self.migration.topology {
+                MigrationTopology::Ring => vec![(from + 1) % count],
+                MigrationTopology::FullyConnected => (0..count).collect(),
+            };
+            for to in destinations.into_iter().filter(|&to| to != from) {
+                self.islands[to].inject(migrants.clone(), self.migration.policy)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn is_running(status: EvolutionStatus) -> bool {
+    matches!(
+        status,
+        EvolutionStatus::Running | EvolutionStatus::Paused | EvolutionStatus::Halting
+    )
+}
+
+/// Genomes of the fittest evaluated individuals, without the tail reserved for the mutation
+/// operators.
+fn fittest_genomes(snapshot: &Snapshot, count: usize, genome_size: usize) -> Vec<Genome> {
+    let mut evaluations = snapshot
+        .evaluations
+        .iter()
+        .filter(|e| e.evaluated)
+        .collect::<Vec<_>>();
+    evaluations.sort_by(|a, b| b.fitness.total_cmp(&a.fitness));
+    evaluations
+        .into_iter()
+        .take(count)
+        .map(|e| e.genome[..genome_size.min(e.genome.len())].to_vec())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use common::subject_observer::{Observer, Subject};
+    use common_test::get_seeded_rng;
+    use futures::executor::block_on;
+
+    use crate::{
+        adaptation::Strategy,
+        evolution::{
+            EventType, EvolutionConfig, EvolutionEngine, EvolutionError, EvolutionStatus,
+            MaxGenerations, ReplacementPolicy, Snapshot,
+        },
+        Evaluation, Genome,
+    };
+
+    use super::{fittest_genomes, IslandEngine, MigrationConfig, MigrationTopology};
+
+    struct Sum;
+
+    impl Strategy for Sum {
+        type Phenotype = u32;
+
+        fn genome_size(&self) -> usize {
+            4
+        }
+
+        fn decode(&self, genome: &Genome) -> u32 {
+            genome.iter().map(|&gene| gene as u32).sum()
+        }
+
+        fn evaluate(&self, sum: &u32) -> f32 {
+            *sum as f32 / 1020.0
+        }
+    }
+
+    /// Fails the test when genomes get injected into the island.
+    struct NoInjection;
+
+    impl Observer<EvolutionEngine, EventType> for NoInjection {
+        fn update(&self, _: &EvolutionEngine, event: EventType) {
+            assert!(!matches!(event, EventType::Injected(_)));
+        }
+    }
+
+    fn migration(interval: u64, topology: MigrationTopology) -> MigrationConfig {
+        MigrationConfig {
+            interval,
+            migrants: 2,
+            topology,
+            policy: ReplacementPolicy::Worst,
+        }
+    }
+
+    #[test]
+    fn test_island_engine_start() -> Result<(), EvolutionError> {
+        // Given
+        let mut engine = IslandEngine::new(3, migration(2, MigrationTopology::Ring));
+        let config = EvolutionConfig {
+            population_size: 10,
+            generation_renewal_config: None,
+            max_duration: None,
+        };
+
+        // When
+        let result = block_on(engine.start(
+            &Sum,
+            &config,
+            MaxGenerations(5),
+            &mut get_seeded_rng().unwrap(),
+        ))?;
+
+        // Then
+        assert_eq!(
+            3,
+            result.len(),
+            "Should return the population of each island"
+        );
+        assert!(result.iter().all(|snapshot| snapshot.generation == 5));
+        assert_eq!(
+            180 + 3 * 2 * 2,
+            result.iter().map(|s| s.evaluation_count).sum::<u64>(),
+            "Should evaluate the migrants sent after generations 1 and 3"
+        );
+        assert!(engine
+            .islands()
+            .iter()
+            .all(|island| island.status() == Ok(EvolutionStatus::Completed)));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_island_engine_start_without_migration() -> Result<(), EvolutionError> {
+        // Given
+        let mut engine = IslandEngine::new(2, migration(0, MigrationTopology::FullyConnected));
+        for island in engine.islands_mut() {
+            island.register_observer(Arc::new(NoInjection));
+        }
+        let config = EvolutionConfig {
+            population_size: 10,
+            generation_renewal_config: None,
+            max_duration: None,
+        };
+
+        // When
+        let result = block_on(engine.start(
+            &Sum,
+            &config,
+            MaxGenerations(3),
+            &mut get_seeded_rng().unwrap(),
+        ))?;
+
+        // Then
+        assert_eq!(80, result.iter().map(|s| s.evaluation_count).sum::<u64>());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_fittest_genomes() {
+        // Given
+        let snapshot = Snapshot {
+            evaluations: vec![
+                Evaluation {
+                    genome: vec![1, 0],
+                    fitness: 0.2,
+                    evaluated: true,
+                    ..Default::default()
+                },
+                Evaluation {
+                    genome: vec![2, 0],
+                    fitness: 0.9,
+                    evaluated: true,
+                    ..Default::default()
+                },
+                Evaluation::from(vec![3, 0]),
+            ],
+            ..Default::default()
+        };
+
+        // When
+        let result = fittest_genomes(&snapshot, 2, 1);
+
+        // Then
+        assert_eq!(
+            vec![vec![2], vec![1]],
+            result,
+            "Should keep the genes of the strategy of the fittest evaluated genomes"
+        );
+    }
+}
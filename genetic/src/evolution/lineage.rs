@@ -0,0 +1,244 @@
+use std::collections::{BTreeSet, VecDeque};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::Evaluation;
+
+use super::generation_dump::Origin;
+
+/// Operator which produced an individual, see [`LineageNode`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Operator {
+    /// Individual of the population the run started from, random, seeded or resumed.
+    Initial,
+    Clone,
+    Crossover,
+    Random,
+    /// Added by a custom renewal stage, see [`super::Renewal::push`].
+    Inserted,
+    /// Injected while the run was going, see [`super::EvolutionEngine::inject`].
+    Injected,
+}
+
+/// Individual of a generation in the [`Lineage`] of a run.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct LineageNode {
+    pub generation: u64,
+    /// Position of the individual in the population of its generation.
+    pub index: usize,
+    pub operator: Operator,
+    /// Nodes of the parents of the individual, in the previous generation.
+    pub parents: Vec<usize>,
+    /// Fitness of the individual once its generation is evaluated.
+    pub fitness: Option<f32>,
+}
+
+/// Genealogy of the individuals of a run, nodes referring to each other by their position in
+/// [`Lineage::nodes`], see [`super::EvolutionEngineBuilder::track_lineage`].
+///
+/// Only the ancestors of the current population are kept, so that the lineage stays bounded by
+/// the lines which survived rather than growing with every generation.
+#[derive(Clone, Debug, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Lineage {
+    pub nodes: Vec<LineageNode>,
+    /// Nodes of the individuals of the last generation, by position in its population.
+    pub population: Vec<usize>,
+}
+
+impl Lineage {
+    /// Lineage of a run starting from the population of the given generation.
+    pub(super) fn new(generation: u64, population_size: usize) -> Self {
+        let mut lineage = Lineage::default();
+        lineage.population = (0..population_size)
+            .map(|index| lineage.add(generation, index, Operator::Initial, vec![]))
+            .collect();
+        lineage
+    }
+
+    /// Nodes the given node descends from, the closest generations first.
+    pub fn ancestors(&self, node: usize) -> Vec<usize> {
+        let mut ancestors = vec![];
+        let mut visited = BTreeSet::new();
+        let mut queue = VecDeque::from([node]);
+        while let Some(node) = queue.pop_front() {
+            let Some(LineageNode { parents, .. }) = self.nodes.get(node) else {
+                continue;
+            };
+            for &parent in parents {
+                if visited.insert(parent) {
+                    ancestors.push(parent);
+                    queue.push_back(parent);
+                }
+            }
+        }
+        ancestors
+    }
+
+    /// Records the fitnesses of the evaluated population.
+    pub(super) fn evaluate(&mut self, evaluations: &[Evaluation]) {
+        for (&node, evaluation) in self.population.iter().zip(evaluations) {
+            self.nodes[node].fitness = evaluation.evaluated_fitness();
+        }
+    }
+
+    /// Records the individuals injected at the given positions of the population.
+    pub(super) fn inject(&mut self, generation: u64, positions: &[usize]) {
+        for &index in positions {
+            let node = self.add(generation, index, Operator::Injected, vec![]);
+            if index < self.population.len() {
+                self.population[index] = node;
+            } else {
+                self.population.push(node);
+            }
+        }
+    }
+
+    /// Replaces the population by the renewed one of the next generation.
+    pub(super) fn renew(&mut self, generation: u64, origins: &[Origin]) {
+        let parents = |indexes: &[usize]| {
+            indexes
+                .iter()
+                .filter_map(|&index| self.population.get(index).copied())
+                .collect::<Vec<_>>()
+        };
+        let renewed = origins
+            .iter()
+            .map(|origin| match *origin {
+                Origin::Clone(parent) => (Operator::Clone, parents(&[parent])),
+                Origin::Offspring(p1, p2) => (Operator::Crossover, parents(&[p1, p2])),
+                Origin::Random => (Operator::Random, vec![]),
+                Origin::Inserted => (Operator::Inserted, vec![]),
+            })
+            .collect::<Vec<_>>();
+        self.population = renewed
+            .into_iter()
+            .enumerate()
+            .map(|(index, (operator, parents))| self.add(generation, index, operator, parents))
+            .collect();
+        self.prune();
+    }
+
+    /// Drops the nodes no individual of the population descends from, the kept ones keeping
+    /// their order.
+    fn prune(&mut self) {
+        let mut kept = vec![false; self.nodes.len()];
+        let mut stack = self.population.clone();
+        while let Some(node) = stack.pop() {
+            if !std::mem::replace(&mut kept[node], true) {
+                stack.extend(&self.nodes[node].parents);
+            }
+        }
+        let mut positions = vec![0; kept.len()];
+        let kept_positions = positions.iter_mut().zip(&kept).filter(|(_, &kept)| kept);
+        for (next, (position, _)) in kept_positions.enumerate() {
+            *position = next;
+        }
+        self.nodes = std::mem::take(&mut self.nodes)
+            .into_iter()
+            .zip(&kept)
+            .filter(|(_, &kept)| kept)
+            .map(|(mut node, _)| {
+                for parent in node.parents.iter_mut() {
+                    *parent = positions[*parent];
+                }
+                node
+            })
+            .collect();
+        for node in self.population.iter_mut() {
+            *node = positions[*node];
+        }
+    }
+
+    fn add(
+        &mut self,
+        generation: u64,
+        index: usize,
+        operator: Operator,
+        parents: Vec<usize>,
+    ) -> usize {
+        self.nodes.push(LineageNode {
+            generation,
+            index,
+            operator,
+            parents,
+            fitness: None,
+        });
+        self.nodes.len() - 1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{evolution::generation_dump::Origin, Evaluation};
+
+    use super::{Lineage, Operator};
+
+    #[test]
+    fn test_lineage() {
+        // Given
+        let mut lineage = Lineage::new(0, 3);
+        let evaluations = [0.1, 0.5, 0.9]
+            .into_iter()
+            .map(|fitness| Evaluation {
+                fitness,
+                evaluated: true,
+                ..Default::default()
+            })
+            .collect::<Vec<_>>();
+
+        // When
+        lineage.evaluate(&evaluations);
+        lineage.renew(
+            1,
+            &[Origin::Clone(2), Origin::Offspring(1, 2), Origin::Random],
+        );
+        lineage.inject(1, &[2, 3]);
+        lineage.renew(2, &[Origin::Offspring(0, 1)]);
+
+        // Then
+        assert_eq!(
+            vec![
+                (Operator::Initial, Some(0.5)),
+                (Operator::Initial, Some(0.9)),
+                (Operator::Clone, None),
+                (Operator::Crossover, None),
+                (Operator::Crossover, None)
+            ],
+            lineage
+                .nodes
+                .iter()
+                .map(|node| (node.operator, node.fitness))
+                .collect::<Vec<_>>(),
+            "Should drop the nodes the population does not descend from"
+        );
+        assert_eq!(vec![4], lineage.population);
+        assert_eq!(
+            (2, 0),
+            (lineage.nodes[4].generation, lineage.nodes[4].index)
+        );
+        assert_eq!(
+            vec![2, 3, 1, 0],
+            lineage.ancestors(4),
+            "Should list the ancestors of the closest generations first"
+        );
+
+        // When
+        lineage.inject(2, &[1]);
+        lineage.renew(3, &[Origin::Clone(1)]);
+
+        // Then
+        assert_eq!(
+            vec![Operator::Injected, Operator::Clone],
+            lineage
+                .nodes
+                .iter()
+                .map(|node| node.operator)
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(vec![0], lineage.ancestors(1));
+    }
+}
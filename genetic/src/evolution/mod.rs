@@ -1,74 +1,303 @@
+mod bookkeeping;
+mod builder;
+mod challenger;
+#[cfg(feature = "checkpoint")]
+mod checkpoint;
+mod engine_handle;
 mod evolution_engine;
+mod generation_dump;
+mod generation_renewal;
 mod genetic_pool;
+mod history;
+mod hook;
+mod injection;
+#[cfg(feature = "island")]
+mod island;
+mod lineage;
+mod observer_timing;
+mod operator_stats;
+mod renewal;
+mod run_progress;
+mod speciation;
+mod termination;
 
-use std::sync::PoisonError;
+use std::{
+    collections::BTreeMap,
+    fmt,
+    sync::PoisonError,
+    time::{Duration, SystemTime},
+};
 
+pub use builder::{EvolutionEngineBuilder, EvolutionRun};
+#[cfg(feature = "checkpoint")]
+pub use checkpoint::{Checkpoint, CheckpointError};
+pub use engine_handle::{Command, EngineHandle};
 pub use evolution_engine::EvolutionEngine;
-use strum::{Display, EnumIter};
+pub use genetic_pool::DEFAULT_MUTATION_RATE;
+pub use history::{GenerationRecord, HistoryDetail};
+pub use hook::GenerationHook;
+pub use injection::ReplacementPolicy;
+#[cfg(feature = "island")]
+pub use island::{IslandEngine, MigrationConfig, MigrationTopology};
+pub use lineage::{Lineage, LineageNode, Operator};
+pub use observer_timing::ObserverTiming;
+pub use operator_stats::{AdaptiveSelection, OperatorStats};
+use rand::Rng;
+pub use renewal::{
+    default_renewal_stages, Cloning, Crossover, Deduplicate, Elitism, Immigrants, Renewal,
+    RenewalStage, Repair,
+};
+pub use run_progress::RunProgress;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+pub use speciation::{CompatibilityDistance, GeneDistance, Species};
+#[cfg(test)]
+use strum::EnumIter;
+pub use termination::{
+    All, Any, Fallible, MaxDuration, MaxEvaluations, MaxGenerations, MinDiversity, Progress,
+    Stagnation, TargetFitness, TerminationCriterion,
+};
 use thiserror::Error;
+use uuid::Uuid;
+#[cfg(feature = "validation")]
 use validator::{Validate, ValidationError, ValidationErrors};
 
+#[cfg(feature = "multiobjective")]
+use crate::multiobjective::pareto_front;
+
 use crate::{
-    selection::{SelectionError, SelectionType},
-    Evaluation,
+    analysis::{allele_frequencies, AlleleFrequencies, GenerationStats},
+    crossover::{CrossoverError, CrossoverType},
+    diversity::Diversity,
+    mutation::MutationType,
+    selection::{analyze, SelectionError, SelectionPressure, SelectionType},
+    Evaluation, Genome,
 };
 
-#[derive(Copy, Clone, Debug, PartialEq, Default, EnumIter, Display)]
+#[derive(Copy, Clone, Debug, PartialEq, Default)]
+#[cfg_attr(test, derive(EnumIter))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum EvolutionStatus {
     #[default]
     New,
     Initializing,
     Running,
+    Paused,
     Halting,
     Halted,
     Completed,
 }
 
+impl fmt::Display for EvolutionStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self, f)
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum EventType {
+    /// The evaluated generation holds the best fitness of the run so far, the first evaluated
+    /// generation included.
+    BestImproved {
+        fitness: f32,
+        generation: u64,
+    },
     Evaluated,
+    /// The generation went through evaluation, injections and the other checks, with the time
+    /// spent since its creation. Not notified for generations interrupted by a halt.
+    GenerationCompleted {
+        duration: Duration,
+    },
     GenerationCreated,
+    /// Genomes injected during the run entered the population, with their count.
+    Injected(usize),
+    /// Offspring of the renewal operators were evaluated, with the success of each operator,
+    /// see [`EvolutionEngineBuilder::track_operators`].
+    OperatorsAssessed(Vec<OperatorStats>),
+    /// Throughput and estimated end of the run, once a generation is completed, see
+    /// [`EvolutionEngine::progress`].
+    Progress(RunProgress),
+    /// The stagnating population was renewed with random genomes around its elites, with the
+    /// number of restarts of the run and the new population size, see
+    /// [`EvolutionEngineBuilder::restart_on_stagnation`].
+    Restarted {
+        restart: u32,
+        population_size: usize,
+    },
+    /// The best fitness stopped improving, see [`EvolutionEngineBuilder::detect_stagnation`].
+    StagnationDetected,
     StatusChanged(EvolutionStatus),
 }
 
-#[derive(Clone, Debug, Validate)]
+/// How renewed genomes already found in the new generation are replaced, see
+/// [`EvolutionEngineBuilder::deduplicate`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Deduplication {
+    /// Duplicates are replaced by random genomes.
+    Randomize,
+    /// Duplicates are mutated again with the operator of the pools, falling back to random
+    /// genomes when mutations keep producing known genomes.
+    Mutate,
+}
+
+/// Outcome of the evaluations exceeding their timeout, see
+/// [`EvolutionEngineBuilder::evaluation_timeout`].
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum TimeoutPolicy {
+    /// The genome gets the given fitness, e.g. 0 to make it the least likely to be selected.
+    Fitness(f32),
+    /// The genome is left unevaluated with a fitness of 0, out of the statistics and the hall
+    /// of fame.
+    Invalid,
+}
+
+/// How a stagnating run starts over, see [`EvolutionEngineBuilder::restart_on_stagnation`].
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RestartPolicy {
+    /// Share of the fittest individuals kept through a restart, the others being replaced by
+    /// random genomes.
+    pub elite_ratio: f32,
+    /// Factor applied to the population size at each restart, e.g. 2 to double it as IPOP does.
+    pub population_growth: f32,
+    /// Restarts after which stagnation is only notified, unlimited if none.
+    pub max_restarts: Option<u32>,
+}
+
+impl Default for RestartPolicy {
+    fn default() -> Self {
+        RestartPolicy {
+            elite_ratio: 0.1,
+            population_growth: 1.0,
+            max_restarts: None,
+        }
+    }
+}
+
+/// Genomes of the first generation of the runs not resumed from a snapshot, see
+/// [`EvolutionEngineBuilder::initial_population`].
+#[derive(Clone, Debug, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum InitialPopulation {
+    #[default]
+    Random,
+    /// Known genomes, e.g. good solutions to warm-start from, completed with random ones.
+    Seeded(Vec<Genome>),
+}
+
+/// A timestamped note attached to a run, e.g. to record a manual intervention.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Annotation {
+    pub generation: u64,
+    pub text: String,
+    pub timestamp: SystemTime,
+}
+
+impl Annotation {
+    /// Note taken at `timestamp`, e.g. from the [`crate::clock::Clock`] of the run.
+    pub fn new(generation: u64, text: impl Into<String>, timestamp: SystemTime) -> Self {
+        Self {
+            generation,
+            text: text.into(),
+            timestamp,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(default))]
+#[cfg_attr(feature = "validation", derive(Validate))]
 pub struct GeneticRenewalParam {
-    #[validate(range(min = 0f32, max = 1f32))]
+    #[cfg_attr(feature = "validation", validate(range(min = 0f32, max = 1f32)))]
     pub mutation_rate: Option<f32>,
-    #[validate(range(min = 0f32, max = 1f32))]
+    #[cfg_attr(
+        feature = "validation",
+        validate(custom(function = "validate_mutation_type"))
+    )]
+    pub mutation_type: MutationType,
+    #[cfg_attr(feature = "validation", validate(range(min = 0f32, max = 1f32)))]
     pub ratio: f32,
+    #[cfg_attr(
+        feature = "validation",
+        validate(custom(function = "validate_selection_type"))
+    )]
     pub selection_type: SelectionType,
+    /// Operator producing offsprings, only used by crossover pools.
+    #[cfg_attr(
+        feature = "validation",
+        validate(custom(function = "validate_crossover_type"))
+    )]
+    pub crossover_type: CrossoverType,
+    /// Whether crossover couples must be made of two different individuals (defaults to true).
+    pub distinct_parents: Option<bool>,
 }
 
-#[derive(Clone, Debug, Validate)]
-#[validate(schema(function = "validate_generation_renewal_config"))]
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(
+    feature = "validation",
+    derive(Validate),
+    validate(schema(function = "validate_generation_renewal_config"))
+)]
 pub struct GenerationRenewalConfig {
-    #[validate(nested)]
+    #[cfg_attr(feature = "validation", validate(nested))]
     pub cloning: Option<GeneticRenewalParam>,
-    #[validate(nested)]
+    #[cfg_attr(feature = "validation", validate(nested))]
     pub crossover: Option<GeneticRenewalParam>,
+    /// Share of random genomes brought in each generation, the individuals left once the pools
+    /// are filled going to crossover, or to cloning without crossover. Randoms only fill what is
+    /// left by the pools if none.
+    #[cfg_attr(feature = "serde", serde(default))]
+    #[cfg_attr(feature = "validation", validate(range(min = 0f32, max = 1f32)))]
+    pub random_ratio: Option<f32>,
 }
 
-#[derive(Clone, Debug, Validate)]
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "validation", derive(Validate))]
 pub struct EvolutionConfig {
-    #[validate(range(min = 1))]
+    #[cfg_attr(feature = "validation", validate(range(min = 1)))]
     pub population_size: usize,
-    #[validate(nested)]
+    #[cfg_attr(feature = "validation", validate(nested))]
     pub generation_renewal_config: Option<GenerationRenewalConfig>,
+    /// Time after which the run completes with its last evaluated generation, checked between
+    /// generations. See [`MaxDuration`] to combine it with other criteria.
+    pub max_duration: Option<Duration>,
 }
 
 #[derive(Error, Debug, PartialEq)]
 pub enum EvolutionError {
+    #[error("Unable to evaluate a batch of genomes: {0}")]
+    BatchEvaluation(String),
+    #[cfg(feature = "checkpoint")]
+    #[error("Unable to resume from checkpoint: {0}")]
+    Checkpoint(String),
+    #[error("The engine is no longer available")]
+    Disconnected,
+    #[cfg(feature = "async")]
+    #[error("An evaluation task failed: {0}")]
+    EvaluationTask(String),
+    #[error("Snapshot incompatible with the run: {0}")]
+    IncompatibleSnapshot(String),
+    #[error("Invalid crossover: {0}")]
+    InvalidCrossover(#[from] CrossoverError),
     #[error("An evaluation must be between 0 and 1, got: {0}")]
     InvalidEvaluation(f32),
     #[error("Invalid selection: {0}")]
     InvalidSelection(#[from] SelectionError),
+    #[cfg(feature = "validation")]
     #[error("Settings are not valid: {0}")]
     InvalidSettings(#[from] ValidationErrors),
     #[error("Unable to run evolution from status: {0}")]
     InvalidStatus(EvolutionStatus),
     #[error("Lock error: {0}")]
     Lock(String),
+    #[error("Unable to check the termination criterion: {0}")]
+    Termination(String),
 }
 
 impl<T> From<PoisonError<T>> for EvolutionError {
@@ -80,34 +309,219 @@ impl<T> From<PoisonError<T>> for EvolutionError {
 pub type EvolutionResult = Result<Snapshot, EvolutionError>;
 
 #[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Snapshot {
     pub generation: u64,
     pub evaluations: Vec<Evaluation>,
+    /// Number of genomes evaluated to reach the snapshot, resumed runs carrying on counting.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub evaluation_count: u64,
+    /// Fittest distinct individuals evaluated so far, fittest first, see
+    /// [`EvolutionEngineBuilder::hall_of_fame`].
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub hall_of_fame: Vec<Evaluation>,
+    /// Fitness statistics of the generation once evaluated, injected genomes included.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub stats: Option<GenerationStats>,
+    /// Diversity of the genomes of the generation, see
+    /// [`EvolutionEngineBuilder::measure_diversity`].
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub diversity: Option<Diversity>,
+    /// Parameters of the run which produced the snapshot.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub parameters: Option<RunParameters>,
+    /// Identity of the run which produced the snapshot, see [`EvolutionEngine::metadata`].
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub metadata: Option<RunMetadata>,
+    /// Genealogy of the individuals up to the snapshot, see
+    /// [`EvolutionEngineBuilder::track_lineage`].
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub lineage: Option<Lineage>,
+}
+
+/// Parameters reproducing a run, given the same strategy and generator.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RunParameters {
+    /// Seed of the random generator, only known for runs started with
+    /// [`EvolutionEngine::start_seeded`] or resumed from a checkpoint.
+    pub seed: Option<u64>,
+    pub config: EvolutionConfig,
+    /// Individuals of each generation renewed by cloning.
+    pub cloning_count: usize,
+    /// Individuals of each generation renewed by crossover.
+    pub crossover_count: usize,
+    /// Random genomes brought in each generation.
+    pub randoms_count: usize,
+    /// Bytes appended to the genomes for the needs of the mutation operators.
+    pub reserved_size: usize,
+}
+
+/// Identity of a run, telling apart concurrent runs in metrics and reports.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RunMetadata {
+    /// Random id drawn at the start of each run, resumed runs included.
+    pub id: Uuid,
+    pub started_at: SystemTime,
+    /// Time the run completed or halted, none while it runs.
+    pub ended_at: Option<SystemTime>,
+    /// Labels of the engine when the run started, see [`EvolutionEngineBuilder::label`].
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub labels: BTreeMap<String, String>,
 }
 
+impl Snapshot {
+    /// Per-locus allele distribution of the population, see [`allele_frequencies`].
+    pub fn allele_frequencies(&self) -> Vec<AlleleFrequencies> {
+        allele_frequencies(&self.evaluations)
+    }
+
+    /// Estimates how the population would be selected with the given scheme, see [`analyze`].
+    pub fn selection_pressure(
+        &self,
+        selection_type: &SelectionType,
+        samples: usize,
+        rng: &mut impl Rng,
+    ) -> Result<SelectionPressure, SelectionError> {
+        analyze(&self.evaluations, selection_type, samples, rng)
+    }
+
+    /// Individuals of the population dominated by no other one, see [`pareto_front`].
+    #[cfg(feature = "multiobjective")]
+    pub fn pareto_front(&self) -> Vec<&Evaluation> {
+        pareto_front(&self.evaluations)
+    }
+}
+
+/// Excess over 1 tolerated on the sum of the ratios of a renewal, which rounding errors may bring
+/// to ratios meant to sum to 1.
+#[cfg(feature = "validation")]
+const RATIOS_TOLERANCE: f32 = 1e-6;
+
+#[cfg(feature = "validation")]
 fn validate_generation_renewal_config(
     config: &GenerationRenewalConfig,
 ) -> Result<(), ValidationError> {
     if let Some((cloning_param, crossover_param)) =
         config.cloning.as_ref().zip(config.crossover.as_ref())
     {
-        if cloning_param.ratio + crossover_param.ratio >= 1.0 {
+        if cloning_param.ratio + crossover_param.ratio > 1.0 + RATIOS_TOLERANCE {
+            return Err(ValidationError::new("excessive_rates"));
+        }
+    }
+    if let Some(random_ratio) = config.random_ratio {
+        let pools_ratio = [config.cloning.as_ref(), config.crossover.as_ref()]
+            .into_iter()
+            .flatten()
+            .map(|param| param.ratio)
+            .sum::<f32>();
+        if pools_ratio + random_ratio > 1.0 + RATIOS_TOLERANCE {
             return Err(ValidationError::new("excessive_rates"));
         }
     }
     Ok(())
 }
 
+#[cfg(feature = "validation")]
+fn validate_crossover_type(crossover_type: &CrossoverType) -> Result<(), ValidationError> {
+    match crossover_type {
+        CrossoverType::Uniform(bias) | CrossoverType::UniformBit(bias)
+            if !(0.0..=1.0).contains(bias) =>
+        {
+            Err(ValidationError::new("invalid_bias"))
+        }
+        CrossoverType::KPoint(0) => Err(ValidationError::new("no_crossover_point")),
+        CrossoverType::Blend(alpha) if !(alpha.is_finite() && *alpha >= 0.0) => {
+            Err(ValidationError::new("invalid_alpha"))
+        }
+        _ => Ok(()),
+    }
+}
+
+#[cfg(feature = "validation")]
+fn validate_selection_type(selection_type: &SelectionType) -> Result<(), ValidationError> {
+    match selection_type {
+        SelectionType::BiasedRoulette { exponent }
+            if !(exponent.is_finite() && *exponent >= 0.0) =>
+        {
+            Err(ValidationError::new("invalid_exponent"))
+        }
+        _ => Ok(()),
+    }
+}
+
+#[cfg(feature = "validation")]
+fn validate_mutation_type(mutation_type: &MutationType) -> Result<(), ValidationError> {
+    match mutation_type {
+        MutationType::Gaussian { sigma, .. } if !(sigma.is_finite() && *sigma > 0.0) => {
+            Err(ValidationError::new("invalid_sigma"))
+        }
+        MutationType::Gaussian {
+            bounds: Some((min, max)),
+            ..
+        } if !(min.is_finite() && max.is_finite() && min <= max) => {
+            Err(ValidationError::new("invalid_bounds"))
+        }
+        MutationType::SelfAdaptive { tau, .. } if !(tau.is_finite() && *tau >= 0.0) => {
+            Err(ValidationError::new("invalid_tau"))
+        }
+        MutationType::SelfAdaptive { mutation, .. } => match **mutation {
+            MutationType::SelfAdaptive { .. } => {
+                Err(ValidationError::new("nested_self_adaptation"))
+            }
+            _ => validate_mutation_type(mutation),
+        },
+        MutationType::PerGene { rates, .. }
+            if rates.iter().any(|rate| !(0.0..=1.0).contains(rate)) =>
+        {
+            Err(ValidationError::new("invalid_gene_rate"))
+        }
+        // The tail of self-adaptation would be taken from a region instead of the genome
+        MutationType::PerGene { mutation, .. } | MutationType::Masked { mutation, .. } => {
+            match **mutation {
+                MutationType::SelfAdaptive { .. } => {
+                    Err(ValidationError::new("regional_self_adaptation"))
+                }
+                _ => validate_mutation_type(mutation),
+            }
+        }
+        _ => Ok(()),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::sync::PoisonError;
+    #[cfg(feature = "serde")]
+    use std::time::Duration;
 
-    use crate::selection::SelectionType;
-
+    #[cfg(feature = "validation")]
     use super::{
-        validate_generation_renewal_config, EvolutionError, GenerationRenewalConfig,
-        GeneticRenewalParam, Snapshot,
+        validate_crossover_type, validate_generation_renewal_config, validate_mutation_type,
+        validate_selection_type,
     };
+    #[cfg(feature = "serde")]
+    use super::{EventType, EvolutionConfig};
+    use super::{EvolutionError, EvolutionStatus, Snapshot};
+    #[cfg(any(feature = "serde", feature = "validation"))]
+    use super::{GenerationRenewalConfig, GeneticRenewalParam};
+    #[cfg(feature = "serde")]
+    use crate::Evaluation;
+    #[cfg(feature = "validation")]
+    use crate::{crossover::CrossoverType, mutation::MutationType, selection::SelectionType};
 
     #[test]
     fn test_snapshot_default() {
@@ -116,12 +530,82 @@ mod tests {
         assert_eq!(
             Snapshot {
                 evaluations: vec![],
-                generation: 0
+                generation: 0,
+                evaluation_count: 0,
+                hall_of_fame: vec![],
+                stats: None,
+                diversity: None,
+                parameters: None,
+                metadata: None,
+                lineage: None,
             },
             result
         );
     }
 
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() {
+        // Given
+        let snapshot = Snapshot {
+            generation: 3,
+            evaluations: vec![Evaluation::from(vec![1, 2])],
+            evaluation_count: 12,
+            hall_of_fame: vec![],
+            stats: None,
+            diversity: None,
+            parameters: None,
+            metadata: None,
+            lineage: None,
+        };
+        let config = EvolutionConfig {
+            population_size: 20,
+            generation_renewal_config: Some(GenerationRenewalConfig {
+                cloning: None,
+                crossover: Some(GeneticRenewalParam {
+                    ratio: 0.5,
+                    ..Default::default()
+                }),
+                random_ratio: None,
+            }),
+            max_duration: Some(Duration::from_millis(1500)),
+        };
+        let event = EventType::StatusChanged(EvolutionStatus::Halted);
+
+        // When
+        let snapshot_result = serde_json::to_string(&snapshot)
+            .and_then(|json| serde_json::from_str::<Snapshot>(&json));
+        let config_result = serde_json::to_string(&config)
+            .and_then(|json| serde_json::from_str::<EvolutionConfig>(&json));
+        let event_result =
+            serde_json::to_string(&event).and_then(|json| serde_json::from_str::<EventType>(&json));
+
+        // Then
+        assert_eq!(snapshot, snapshot_result.unwrap());
+        let config_result = config_result.unwrap();
+        assert_eq!(20, config_result.population_size);
+        assert_eq!(
+            Some(Duration::from_millis(1500)),
+            config_result.max_duration
+        );
+        assert_eq!(
+            Some(0.5),
+            config_result
+                .generation_renewal_config
+                .and_then(|config| config.crossover)
+                .map(|crossover| crossover.ratio)
+        );
+        assert_eq!(event, event_result.unwrap());
+        assert_eq!(
+            Ok(0),
+            serde_json::from_str::<Snapshot>(r#"{"generation":0,"evaluations":[]}"#)
+                .map(|snapshot| snapshot.evaluation_count)
+                .map_err(|err| err.to_string()),
+            "Should default the evaluation count of older snapshots"
+        );
+    }
+
+    #[cfg(feature = "validation")]
     #[test]
     fn test_validate_generation_renewal_config() {
         // Given
@@ -130,12 +614,15 @@ mod tests {
                 mutation_rate: None,
                 ratio: 0.51,
                 selection_type: SelectionType::Chance,
+                ..Default::default()
             }),
             crossover: Some(GeneticRenewalParam {
                 mutation_rate: None,
                 ratio: 0.51,
                 selection_type: SelectionType::Chance,
+                ..Default::default()
             }),
+            random_ratio: None,
         };
 
         // When
@@ -151,6 +638,7 @@ mod tests {
         let right_config = GenerationRenewalConfig {
             cloning: None,
             crossover: None,
+            random_ratio: None,
         };
 
         // When
@@ -161,6 +649,144 @@ mod tests {
             matches!(result, Ok(())),
             "Should return Ok for valid config"
         );
+
+        // Given
+        let random_config = GenerationRenewalConfig {
+            cloning: None,
+            crossover: Some(GeneticRenewalParam {
+                ratio: 0.8,
+                ..Default::default()
+            }),
+            random_ratio: Some(0.3),
+        };
+
+        // When
+        let result = validate_generation_renewal_config(&random_config);
+
+        // Then
+        assert!(
+            result.is_err(),
+            "Should return err when the random ratio exceeds what the pools leave"
+        );
+
+        // Given
+        let full_config = GenerationRenewalConfig {
+            cloning: Some(GeneticRenewalParam {
+                ratio: 0.3,
+                ..Default::default()
+            }),
+            crossover: Some(GeneticRenewalParam {
+                ratio: 0.7,
+                ..Default::default()
+            }),
+            random_ratio: None,
+        };
+
+        // When
+        let result = validate_generation_renewal_config(&full_config);
+
+        // Then
+        assert!(
+            matches!(result, Ok(())),
+            "Should accept pools renewing the whole population"
+        );
+    }
+
+    #[cfg(feature = "validation")]
+    #[test]
+    fn test_validate_crossover_type() {
+        assert!(validate_crossover_type(&CrossoverType::SinglePoint).is_ok());
+        assert!(validate_crossover_type(&CrossoverType::Uniform(0.5)).is_ok());
+        assert!(
+            validate_crossover_type(&CrossoverType::Uniform(1.5)).is_err(),
+            "Should return err when uniform bias is not a probability"
+        );
+        assert!(validate_crossover_type(&CrossoverType::KPoint(0)).is_err());
+        assert!(validate_crossover_type(&CrossoverType::Blend(0.5)).is_ok());
+        assert!(
+            validate_crossover_type(&CrossoverType::Blend(-0.1)).is_err(),
+            "Should return err when blend alpha is negative"
+        );
+    }
+
+    #[cfg(feature = "validation")]
+    #[test]
+    fn test_validate_selection_type() {
+        assert!(validate_selection_type(&SelectionType::Weight).is_ok());
+        assert!(validate_selection_type(&SelectionType::BiasedRoulette { exponent: 0.0 }).is_ok());
+        assert!(
+            validate_selection_type(&SelectionType::BiasedRoulette { exponent: -1.0 }).is_err(),
+            "Should return err when the exponent would invert the selection pressure"
+        );
+    }
+
+    #[cfg(feature = "validation")]
+    #[test]
+    fn test_validate_mutation_type() {
+        assert!(validate_mutation_type(&MutationType::Random).is_ok());
+        assert!(validate_mutation_type(&MutationType::Gaussian {
+            sigma: 0.1,
+            bounds: Some((-1.0, 1.0))
+        })
+        .is_ok());
+        assert!(
+            validate_mutation_type(&MutationType::Gaussian {
+                sigma: 0.0,
+                bounds: None
+            })
+            .is_err(),
+            "Should return err when gaussian sigma is not positive"
+        );
+        assert!(
+            validate_mutation_type(&MutationType::Gaussian {
+                sigma: 0.1,
+                bounds: Some((1.0, -1.0))
+            })
+            .is_err(),
+            "Should return err when gaussian bounds are reversed"
+        );
+        let self_adaptive = MutationType::SelfAdaptive {
+            tau: 0.2,
+            mutation: Box::new(MutationType::BitFlip),
+        };
+        assert!(validate_mutation_type(&self_adaptive).is_ok());
+        assert!(
+            validate_mutation_type(&MutationType::SelfAdaptive {
+                tau: 0.2,
+                mutation: Box::new(self_adaptive)
+            })
+            .is_err(),
+            "Should return err when self-adaptations are nested"
+        );
+        assert!(validate_mutation_type(&MutationType::PerGene {
+            rates: vec![0.0, 0.5],
+            mutation: Box::new(MutationType::Random)
+        })
+        .is_ok());
+        assert!(
+            validate_mutation_type(&MutationType::PerGene {
+                rates: vec![1.5],
+                mutation: Box::new(MutationType::Random)
+            })
+            .is_err(),
+            "Should return err when a gene rate is not a probability"
+        );
+        assert!(
+            validate_mutation_type(&MutationType::Masked {
+                frozen: vec![true],
+                mutation: Box::new(MutationType::SelfAdaptive {
+                    tau: 0.2,
+                    mutation: Box::new(MutationType::BitFlip),
+                })
+            })
+            .is_err(),
+            "Should return err when self-adapting a region of the genome"
+        );
+    }
+
+    #[test]
+    fn test_evolution_status_display() {
+        assert_eq!("Running", EvolutionStatus::Running.to_string());
     }
 
     #[test]
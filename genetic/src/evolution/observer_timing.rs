@@ -0,0 +1,96 @@
+use std::time::Duration;
+
+#[cfg(feature = "serde")]
+use serde::Serialize;
+
+/// Latency of the updates of an observer registered to an engine, see
+/// [`super::EvolutionEngine::observer_timings`].
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct ObserverTiming {
+    pub name: &'static str,
+    pub calls: u64,
+    pub total: Duration,
+    pub max: Duration,
+    /// Number of consecutive updates exceeding the observer budget.
+    pub slow_streak: u32,
+}
+
+impl ObserverTiming {
+    pub(super) fn new(name: &'static str) -> Self {
+        Self {
+            name,
+            calls: 0,
+            total: Duration::ZERO,
+            max: Duration::ZERO,
+            slow_streak: 0,
+        }
+    }
+
+    pub fn mean(&self) -> Duration {
+        if self.calls == 0 {
+            Duration::ZERO
+        } else {
+            self.total.div_f64(self.calls as f64)
+        }
+    }
+
+    /// Records an update and returns whether it makes the observer exceed the budget for the
+    /// tolerated count of consecutive updates.
+    pub(super) fn record(&mut self, elapsed: Duration, budget: Option<&ObserverBudget>) -> bool {
+        self.calls += 1;
+        self.total += elapsed;
+        self.max = self.max.max(elapsed);
+        match budget {
+            Some(budget) if elapsed > budget.budget => {
+                self.slow_streak += 1;
+                self.slow_streak == budget.tolerance
+            }
+            _ => {
+                self.slow_streak = 0;
+                false
+            }
+        }
+    }
+}
+
+/// Time an observer update is expected to stay within.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(super) struct ObserverBudget {
+    pub budget: Duration,
+    /// Number of consecutive updates over budget before reporting the observer as slow.
+    pub tolerance: u32,
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::{ObserverBudget, ObserverTiming};
+
+    #[test]
+    fn test_observer_timing_record() {
+        // Given
+        let mut timing = ObserverTiming::new("gateway");
+        let budget = ObserverBudget {
+            budget: Duration::from_millis(10),
+            tolerance: 2,
+        };
+
+        // Then
+        assert!(!timing.record(Duration::from_millis(20), Some(&budget)));
+        assert!(!timing.record(Duration::from_millis(5), Some(&budget)));
+        assert!(!timing.record(Duration::from_millis(20), Some(&budget)));
+        assert!(
+            timing.record(Duration::from_millis(30), Some(&budget)),
+            "Should report observers consistently over budget"
+        );
+        assert!(
+            !timing.record(Duration::from_millis(30), Some(&budget)),
+            "Should report slow observers once per streak"
+        );
+        assert_eq!(5, timing.calls);
+        assert_eq!(Duration::from_millis(30), timing.max);
+        assert_eq!(Duration::from_millis(21), timing.mean());
+    }
+}
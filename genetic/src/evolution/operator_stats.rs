@@ -0,0 +1,375 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::Evaluation;
+
+use super::{
+    generation_dump::Origin, generation_renewal::ExecutionSettings,
+    genetic_pool::largest_remainder, Operator,
+};
+
+/// Success of a renewal operator, its offspring improving on their parents when fitter than
+/// the fittest of them, see [`super::EvolutionEngineBuilder::track_operators`].
+///
+/// Offspring left unchanged, e.g. clones which were not mutated, are not counted.
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct OperatorStats {
+    /// [`Operator::Clone`] for the cloning pool, with its mutation, [`Operator::Crossover`] for
+    /// the crossover pool, with its crossover and mutation.
+    pub operator: Operator,
+    /// Offspring evaluated in the last generation.
+    pub offspring: usize,
+    /// Offspring of the last generation which improved on their parents.
+    pub improvements: usize,
+    /// Offspring evaluated since the run started.
+    pub total_offspring: u64,
+    /// Offspring which improved on their parents since the run started.
+    pub total_improvements: u64,
+    /// Share of the renewed individuals the operator produces, between 0 and 1.
+    pub share: f32,
+}
+
+impl OperatorStats {
+    fn new(operator: Operator, share: f32) -> Self {
+        OperatorStats {
+            operator,
+            offspring: 0,
+            improvements: 0,
+            total_offspring: 0,
+            total_improvements: 0,
+            share,
+        }
+    }
+
+    /// Share of the offspring of the run which improved on their parents, none before any was
+    /// evaluated.
+    pub fn success_rate(&self) -> Option<f32> {
+        (self.total_offspring > 0)
+            .then(|| self.total_improvements as f32 / self.total_offspring as f32)
+    }
+}
+
+/// Adaptation of the shares of the renewal operators to their success by probability matching,
+/// see [`super::EvolutionEngineBuilder::track_operators`].
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct AdaptiveSelection {
+    /// Weight of the last generation in the success rate expected from each operator, between
+    /// 0 and 1.
+    pub adaptation_rate: f32,
+    /// Share each operator keeps whatever its success, so that it goes on being assessed, up to
+    /// an even split.
+    pub min_share: f32,
+}
+
+impl Default for AdaptiveSelection {
+    fn default() -> Self {
+        AdaptiveSelection {
+            adaptation_rate: 0.3,
+            min_share: 0.1,
+        }
+    }
+}
+
+/// Assessment of the renewal operators of the current run.
+#[derive(Debug)]
+pub(super) struct OperatorTracker {
+    adaptive: Option<AdaptiveSelection>,
+    /// Operators whose share adapts, the ones of the pools producing individuals.
+    adapted: Vec<usize>,
+    stats: Vec<OperatorStats>,
+    /// Success rate expected from each operator, see [`AdaptiveSelection::adaptation_rate`].
+    qualities: Vec<f32>,
+    /// Operator and fitness of the fittest parent of each renewed individual, none for the ones
+    /// not to assess.
+    parents: Vec<Option<(usize, f32)>>,
+}
+
+impl OperatorTracker {
+    pub(super) fn new(adaptive: Option<AdaptiveSelection>) -> Self {
+        OperatorTracker {
+            adaptive,
+            adapted: vec![],
+            stats: vec![],
+            qualities: vec![],
+            parents: vec![],
+        }
+    }
+
+    pub(super) fn stats(&self) -> &[OperatorStats] {
+        &self.stats
+    }
+
+    /// Starts over with the pools of the run, operators not producing any individual being
+    /// left out of the adaptation.
+    pub(super) fn clear(&mut self, settings: &ExecutionSettings) {
+        let counts = [settings.cloning_pool.count, settings.crossover_pool.count];
+        let total = counts.iter().sum::<usize>().max(1) as f32;
+        self.stats = [Operator::Clone, Operator::Crossover]
+            .into_iter()
+            .zip(counts)
+            .map(|(operator, count)| OperatorStats::new(operator, count as f32 / total))
+            .collect();
+        self.adapted = (0..counts.len()).filter(|&i| counts[i] > 0).collect();
+        self.qualities = vec![0.0; self.stats.len()];
+        self.parents.clear();
+    }
+
+    /// Records the parents of the renewed individuals, the ones already evaluated being kept
+    /// unchanged.
+    pub(super) fn renew(
+        &mut self,
+        previous: &[Evaluation],
+        evaluations: &[Evaluation],
+        origins: &[Origin],
+    ) {
+        let fitness = |index: usize| previous.get(index).and_then(Evaluation::evaluated_fitness);
+        self.parents = evaluations
+            .iter()
+            .zip(origins)
+            .map(|(evaluation, origin)| {
+                if evaluation.evaluated {
+                    return None;
+                }
+                match *origin {
+                    Origin::Clone(parent) => fitness(parent).map(|fitness| (0, fitness)),
+                    Origin::Offspring(p1, p2) => match (fitness(p1), fitness(p2)) {
+                        (Some(f1), Some(f2)) => Some((1, f1.max(f2))),
+                        (f1, f2) => f1.or(f2).map(|fitness| (1, fitness)),
+                    },
+                    Origin::Random | Origin::Inserted => None,
+                }
+            })
+            .collect();
+    }
+
+    /// Counts the evaluated offspring which improved on their parents, returning whether any
+    /// was assessed.
+    pub(super) fn assess(&mut self, evaluations: &[Evaluation]) -> bool {
+        for stats in self.stats.iter_mut() {
+            stats.offspring = 0;
+            stats.improvements = 0;
+        }
+        let parents = std::mem::take(&mut self.parents);
+        for (parent, evaluation) in parents.into_iter().zip(evaluations) {
+            let (Some((operator, parent_fitness)), Some(fitness)) =
+                (parent, evaluation.evaluated_fitness())
+            else {
+                continue;
+            };
+            let stats = &mut self.stats[operator];
+            stats.offspring += 1;
+            stats.total_offspring += 1;
+            if fitness > parent_fitness {
+                stats.improvements += 1;
+                stats.total_improvements += 1;
+            }
+        }
+        if self.stats.iter().all(|stats| stats.offspring == 0) {
+            return false;
+        }
+        if let Some(adaptive) = self.adaptive {
+            self.adapt(adaptive);
+        }
+        true
+    }
+
+    /// Moves the shares of the operators towards their expected success.
+    fn adapt(&mut self, adaptive: AdaptiveSelection) {
+        let rate = adaptive.adaptation_rate;
+        for (quality, stats) in self.qualities.iter_mut().zip(&self.stats) {
+            if stats.offspring > 0 {
+                let success = stats.improvements as f32 / stats.offspring as f32;
+                *quality += rate * (success - *quality);
+            }
+        }
+        let adapted = &self.adapted;
+        let min_share = adaptive.min_share.min(1.0 / adapted.len() as f32);
+        let total_quality = adapted.iter().map(|&i| self.qualities[i]).sum::<f32>();
+        for &operator in adapted.iter() {
+            let matched = if total_quality > 0.0 {
+                self.qualities[operator] / total_quality
+            } else {
+                1.0 / adapted.len() as f32
+            };
+            self.stats[operator].share =
+                min_share + (1.0 - adapted.len() as f32 * min_share) * matched;
+        }
+    }
+
+    /// Splits the individuals renewed by the pools according to the shares of their operators.
+    pub(super) fn apply(&self, settings: &mut ExecutionSettings) {
+        if self.adaptive.is_none() {
+            return;
+        }
+        let total = settings.cloning_pool.count + settings.crossover_pool.count;
+        let shares = self
+            .stats
+            .iter()
+            .map(|stats| stats.share)
+            .collect::<Vec<_>>();
+        if let [cloning, crossover] = largest_remainder(&shares, total)[..] {
+            settings.cloning_pool.count = cloning;
+            settings.crossover_pool.count = crossover;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        evolution::{
+            generation_dump::Origin, generation_renewal::ExecutionSettings,
+            genetic_pool::GeneticPool, Operator,
+        },
+        Evaluation,
+    };
+
+    use super::{AdaptiveSelection, OperatorTracker};
+
+    fn get_settings(cloning: usize, crossover: usize) -> ExecutionSettings {
+        ExecutionSettings {
+            cloning_pool: GeneticPool {
+                count: cloning,
+                ..Default::default()
+            },
+            crossover_pool: GeneticPool {
+                count: crossover,
+                ..Default::default()
+            },
+            randoms_count: 0,
+            reserved_size: 0,
+        }
+    }
+
+    fn get_evaluations(fitnesses: &[Option<f32>]) -> Vec<Evaluation> {
+        fitnesses
+            .iter()
+            .map(|fitness| Evaluation {
+                fitness: fitness.unwrap_or_default(),
+                evaluated: fitness.is_some(),
+                ..Default::default()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_operator_tracker_assess() {
+        // Given
+        let mut tracker = OperatorTracker::new(None);
+        tracker.clear(&get_settings(2, 2));
+        let previous = get_evaluations(&[Some(0.2), Some(0.5), Some(0.8), None]);
+        let origins = [
+            Origin::Clone(0),
+            Origin::Clone(2),
+            Origin::Offspring(0, 1),
+            Origin::Offspring(3, 1),
+            Origin::Random,
+        ];
+        tracker.renew(&previous, &get_evaluations(&[None; 5]), &origins);
+
+        // When
+        let result = tracker.assess(&get_evaluations(&[
+            Some(0.3),
+            Some(0.7),
+            Some(0.4),
+            Some(0.6),
+            Some(1.0),
+        ]));
+
+        // Then
+        assert!(result);
+        let stats = tracker.stats();
+        assert_eq!(
+            (Operator::Clone, 2, 1),
+            (stats[0].operator, stats[0].offspring, stats[0].improvements)
+        );
+        assert_eq!(
+            (Operator::Crossover, 2, 1),
+            (stats[1].operator, stats[1].offspring, stats[1].improvements),
+            "Should compare the offspring to their fittest evaluated parent"
+        );
+        assert_eq!(Some(0.5), stats[1].success_rate());
+        assert_eq!(
+            0.5, stats[0].share,
+            "Should not adapt the shares by default"
+        );
+
+        // When
+        let result = tracker.assess(&get_evaluations(&[Some(1.0); 5]));
+
+        // Then
+        assert!(!result, "Should assess the renewed individuals once");
+        assert_eq!(2, tracker.stats()[0].total_offspring);
+    }
+
+    #[test]
+    fn test_operator_tracker_assess_should_skip_unchanged_offspring() {
+        // Given
+        let mut tracker = OperatorTracker::new(None);
+        tracker.clear(&get_settings(1, 0));
+        let previous = get_evaluations(&[Some(0.2)]);
+        tracker.renew(&previous, &previous, &[Origin::Clone(0)]);
+
+        // When
+        let result = tracker.assess(&previous);
+
+        // Then
+        assert!(!result);
+        assert_eq!(0, tracker.stats()[0].total_offspring);
+    }
+
+    #[test]
+    fn test_operator_tracker_apply() {
+        // Given
+        let adaptive = AdaptiveSelection {
+            adaptation_rate: 1.0,
+            min_share: 0.1,
+        };
+        let mut tracker = OperatorTracker::new(Some(adaptive));
+        let mut settings = get_settings(5, 5);
+        tracker.clear(&settings);
+        let previous = get_evaluations(&[Some(0.5), Some(0.5)]);
+        let origins = [
+            Origin::Clone(0),
+            Origin::Clone(1),
+            Origin::Offspring(0, 1),
+            Origin::Offspring(0, 1),
+        ];
+        tracker.renew(&previous, &get_evaluations(&[None; 4]), &origins);
+
+        // When
+        tracker.assess(&get_evaluations(&[
+            Some(0.1),
+            Some(0.1),
+            Some(0.9),
+            Some(0.6),
+        ]));
+        tracker.apply(&mut settings);
+
+        // Then
+        assert_eq!(
+            (1, 9),
+            (settings.cloning_pool.count, settings.crossover_pool.count),
+            "Should favor the successful operator while keeping the minimum share"
+        );
+
+        // Given
+        let mut tracker = OperatorTracker::new(Some(adaptive));
+        let mut settings = get_settings(4, 0);
+        tracker.clear(&settings);
+        tracker.renew(&previous, &get_evaluations(&[None; 2]), &origins[..2]);
+
+        // When
+        tracker.assess(&get_evaluations(&[Some(0.1), Some(0.1)]));
+        tracker.apply(&mut settings);
+
+        // Then
+        assert_eq!(
+            (4, 0),
+            (settings.cloning_pool.count, settings.crossover_pool.count),
+            "Should not bring in the operators left out by the configuration"
+        );
+    }
+}
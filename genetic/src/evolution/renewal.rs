@@ -0,0 +1,224 @@
+use std::fmt::Debug;
+
+use rand::RngCore;
+
+use crate::{Evaluation, Genome};
+
+use super::{
+    generation_dump::Origin,
+    generation_renewal::{deduplicate, get_random_genomes_iter, repair, ExecutionSettings},
+    Deduplication, EvolutionEngine, EvolutionError,
+};
+
+/// Step of the renewal of a generation, the stages of the engine running in turn on the same
+/// [`Renewal`], see [`super::EvolutionEngineBuilder::renewal_stages`].
+pub trait RenewalStage: Debug + Send + Sync {
+    fn renew(&self, renewal: &mut Renewal, rng: &mut dyn RngCore) -> Result<(), EvolutionError>;
+}
+
+/// Operations of the strategy the renewal relies on.
+pub(super) trait Repairer {
+    fn genome_size(&self) -> usize;
+
+    fn repair(&self, genome: &mut Genome);
+}
+
+/// New generation built by the renewal stages from the evaluated one.
+pub struct Renewal<'a> {
+    pub(super) engine: &'a EvolutionEngine,
+    pub(super) strategy: &'a dyn Repairer,
+    pub(super) settings: &'a ExecutionSettings,
+    pub(super) deduplication: Option<Deduplication>,
+    pub(super) generation: u64,
+    pub(super) previous: &'a [Evaluation],
+    pub(super) evaluations: Vec<Evaluation>,
+    pub(super) origins: Vec<Origin>,
+}
+
+impl Renewal<'_> {
+    /// Generation being renewed.
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    /// Evaluated individuals of the generation being renewed.
+    pub fn previous(&self) -> &[Evaluation] {
+        self.previous
+    }
+
+    /// Individuals of the new generation so far.
+    pub fn evaluations(&self) -> &[Evaluation] {
+        &self.evaluations
+    }
+
+    /// Individuals of the new generation so far, those whose genome changes being expected to
+    /// get `evaluated` reset.
+    pub fn evaluations_mut(&mut self) -> &mut [Evaluation] {
+        &mut self.evaluations
+    }
+
+    /// Size of the new generation, the individuals beyond being dropped once the stages ran.
+    pub fn population_size(&self) -> usize {
+        let settings = self.settings;
+        settings.cloning_pool.count + settings.crossover_pool.count + settings.randoms_count
+    }
+
+    /// Genes of the genomes of the strategy, without the tail reserved for the mutation
+    /// operators.
+    pub fn genome_size(&self) -> usize {
+        self.strategy.genome_size()
+    }
+
+    /// Adds an individual of the generation being renewed, one generation older.
+    pub fn keep(&mut self, index: usize) {
+        if let Some(evaluation) = self.previous.get(index) {
+            self.add(evaluation.survive(), Origin::Clone(index));
+        }
+    }
+
+    /// Adds a genome of the strategy, the reserved tail being appended.
+    pub fn push(&mut self, mut genome: Genome) {
+        self.settings.reserve_tail(&mut genome);
+        self.add(genome.into(), Origin::Inserted);
+    }
+
+    fn add(&mut self, evaluation: Evaluation, origin: Origin) {
+        self.evaluations.push(evaluation);
+        self.origins.push(origin);
+    }
+}
+
+/// Keeps the given number of fittest individuals unchanged.
+#[derive(Debug, Clone, Copy)]
+pub struct Elitism(pub usize);
+
+impl RenewalStage for Elitism {
+    fn renew(&self, renewal: &mut Renewal, _: &mut dyn RngCore) -> Result<(), EvolutionError> {
+        let previous = renewal.previous;
+        let mut ranked = (0..previous.len())
+            .filter(|&index| previous[index].evaluated)
+            .collect::<Vec<_>>();
+        ranked.sort_by(|&a, &b| previous[b].fitness.total_cmp(&previous[a].fitness));
+        for index in ranked.into_iter().take(self.0) {
+            renewal.keep(index);
+        }
+        Ok(())
+    }
+}
+
+/// Clones the individuals selected by the cloning pool, mutated by its operator.
+#[derive(Debug, Clone, Copy)]
+pub struct Cloning;
+
+impl RenewalStage for Cloning {
+    fn renew(
+        &self,
+        renewal: &mut Renewal,
+        mut rng: &mut dyn RngCore,
+    ) -> Result<(), EvolutionError> {
+        let clones = renewal
+            .engine
+            .get_clones(&renewal.settings.cloning_pool, &mut rng)?;
+        for (parent, clone) in clones {
+            renewal.add(clone, Origin::Clone(parent));
+        }
+        Ok(())
+    }
+}
+
+/// Breeds the couples selected by the crossover pool, mutated by its operator.
+#[derive(Debug, Clone, Copy)]
+pub struct Crossover;
+
+impl RenewalStage for Crossover {
+    fn renew(
+        &self,
+        renewal: &mut Renewal,
+        mut rng: &mut dyn RngCore,
+    ) -> Result<(), EvolutionError> {
+        let genome_size = renewal.genome_size() + renewal.settings.reserved_size;
+        let offsprings = renewal.engine.get_offsprings(
+            genome_size,
+            &renewal.settings.crossover_pool,
+            &mut rng,
+        )?;
+        for (offspring, origin) in offsprings {
+            renewal.add(offspring.into(), origin);
+        }
+        Ok(())
+    }
+}
+
+/// Adds random genomes for the share of the population left by the pools.
+#[derive(Debug, Clone, Copy)]
+pub struct Immigrants;
+
+impl RenewalStage for Immigrants {
+    fn renew(
+        &self,
+        renewal: &mut Renewal,
+        mut rng: &mut dyn RngCore,
+    ) -> Result<(), EvolutionError> {
+        let randoms = get_random_genomes_iter(
+            renewal.settings.randoms_count,
+            renewal.genome_size(),
+            &mut rng,
+        )
+        .collect::<Vec<_>>();
+        for mut genome in randoms {
+            renewal.settings.reserve_tail(&mut genome);
+            renewal.add(genome.into(), Origin::Random);
+        }
+        Ok(())
+    }
+}
+
+/// Lets the strategy repair the genomes not evaluated yet, e.g. mutated or new ones.
+#[derive(Debug, Clone, Copy)]
+pub struct Repair;
+
+impl RenewalStage for Repair {
+    fn renew(&self, renewal: &mut Renewal, _: &mut dyn RngCore) -> Result<(), EvolutionError> {
+        let reserved_size = renewal.settings.reserved_size;
+        for evaluation in renewal.evaluations.iter_mut().filter(|e| !e.evaluated) {
+            repair(renewal.strategy, &mut evaluation.genome, reserved_size);
+        }
+        Ok(())
+    }
+}
+
+/// Replaces the genomes found more than once, see [`super::EvolutionEngineBuilder::deduplicate`].
+#[derive(Debug, Clone, Copy)]
+pub struct Deduplicate;
+
+impl RenewalStage for Deduplicate {
+    fn renew(
+        &self,
+        renewal: &mut Renewal,
+        mut rng: &mut dyn RngCore,
+    ) -> Result<(), EvolutionError> {
+        if let Some(deduplication) = renewal.deduplication {
+            deduplicate(
+                renewal.strategy,
+                renewal.settings,
+                deduplication,
+                &mut renewal.evaluations,
+                &mut renewal.origins,
+                &mut rng,
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Stages renewing the generations by default: cloning, crossover, immigrants, repair then
+/// deduplication.
+pub fn default_renewal_stages() -> Vec<Box<dyn RenewalStage>> {
+    vec![
+        Box::new(Cloning),
+        Box::new(Crossover),
+        Box::new(Immigrants),
+        Box::new(Repair),
+        Box::new(Deduplicate),
+    ]
+}
@@ -0,0 +1,96 @@
+use std::time::Duration;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Throughput of a run along with the estimate of its end, see
+/// [`super::EvolutionEngine::progress`].
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RunProgress {
+    /// Last evaluated generation.
+    pub generation: u64,
+    /// See [`super::Snapshot::evaluation_count`].
+    pub evaluation_count: u64,
+    /// Time spent since the start of the run.
+    pub elapsed: Duration,
+    /// Generations evaluated per second since the start of the run.
+    pub generations_per_second: f32,
+    /// Genomes evaluated per second since the start of the run.
+    pub evaluations_per_second: f32,
+    /// Share of the run done within [0, 1], only known when the termination criterion or the
+    /// time budget bound the run, see [`super::TerminationCriterion::completion`].
+    pub completion: Option<f32>,
+    /// Time left estimated from the completion and the time spent so far.
+    pub eta: Option<Duration>,
+}
+
+impl RunProgress {
+    /// Progress of a run which evaluated `generations` generations and `evaluations` genomes
+    /// since it started, resumed runs not counting the work done before.
+    pub(super) fn new(
+        generation: u64,
+        evaluation_count: u64,
+        (generations, evaluations): (u64, u64),
+        elapsed: Duration,
+        completion: Option<f32>,
+    ) -> Self {
+        let seconds = elapsed.as_secs_f32();
+        let per_second = |count: u64| {
+            if seconds > 0.0 {
+                count as f32 / seconds
+            } else {
+                0.0
+            }
+        };
+        let completion = completion.map(|completion| completion.clamp(0.0, 1.0));
+        RunProgress {
+            generation,
+            evaluation_count,
+            elapsed,
+            generations_per_second: per_second(generations),
+            evaluations_per_second: per_second(evaluations),
+            completion,
+            eta: completion
+                .filter(|&completion| completion > 0.0)
+                .map(|completion| elapsed.mul_f32((1.0 - completion) / completion)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::RunProgress;
+
+    #[test]
+    fn test_run_progress_new() {
+        // When
+        let result = RunProgress::new(4, 50, (4, 40), Duration::from_secs(2), Some(0.25));
+
+        // Then
+        assert_eq!(
+            RunProgress {
+                generation: 4,
+                evaluation_count: 50,
+                elapsed: Duration::from_secs(2),
+                generations_per_second: 2.0,
+                evaluations_per_second: 20.0,
+                completion: Some(0.25),
+                eta: Some(Duration::from_secs(6)),
+            },
+            result
+        );
+
+        // When
+        let result = RunProgress::new(0, 10, (1, 10), Duration::ZERO, Some(0.0));
+
+        // Then
+        assert_eq!(0.0, result.generations_per_second);
+        assert_eq!(
+            None, result.eta,
+            "Should not estimate a run yet to progress"
+        );
+    }
+}
@@ -0,0 +1,244 @@
+use std::fmt::Debug;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{Evaluation, Genome};
+
+use super::genetic_pool::largest_remainder;
+
+/// Distance between two genomes telling whether they belong to the same species, see
+/// [`super::EvolutionEngineBuilder::speciate`].
+pub trait CompatibilityDistance: Debug + Send + Sync {
+    fn distance(&self, a: &Genome, b: &Genome) -> f32;
+}
+
+/// Share of the loci whose genes differ, genes missing from the shorter genome included.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct GeneDistance;
+
+impl CompatibilityDistance for GeneDistance {
+    fn distance(&self, a: &Genome, b: &Genome) -> f32 {
+        let length = a.len().max(b.len());
+        if length == 0 {
+            return 0.0;
+        }
+        let common = a.iter().zip(b).filter(|(a, b)| a == b).count();
+        (length - common) as f32 / length as f32
+    }
+}
+
+/// Individuals of a generation close enough to the representative of their species.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Species {
+    /// Id kept by the species from a generation to the next.
+    pub id: u64,
+    /// Genome the individuals are compared to, the one of the fittest member of the species.
+    pub representative: Genome,
+    /// Positions of the members in their generation.
+    pub members: Vec<usize>,
+    /// Sum of the fitnesses of the members shared by their number, sizing the part of the next
+    /// generation bred from the species.
+    pub shared_fitness: f32,
+}
+
+/// Species of the current run, see [`super::EvolutionEngineBuilder::speciate`].
+#[derive(Debug)]
+pub(super) struct Speciation {
+    distance: Box<dyn CompatibilityDistance>,
+    threshold: f32,
+    species: Vec<Species>,
+    next_id: u64,
+}
+
+impl Speciation {
+    pub(super) fn new(distance: Box<dyn CompatibilityDistance>, threshold: f32) -> Self {
+        Speciation {
+            distance,
+            threshold,
+            species: vec![],
+            next_id: 0,
+        }
+    }
+
+    pub(super) fn species(&self) -> &[Species] {
+        &self.species
+    }
+
+    pub(super) fn clear(&mut self) {
+        self.species.clear();
+        self.next_id = 0;
+    }
+
+    /// Assigns each individual to the first species whose representative is within the
+    /// threshold, founding a species otherwise, comparing the first `genome_size` genes.
+    pub(super) fn speciate(&mut self, evaluations: &[Evaluation], genome_size: usize) {
+        for species in self.species.iter_mut() {
+            species.members.clear();
+        }
+        for (index, evaluation) in evaluations.iter().enumerate() {
+            let genome = &evaluation.genome[..genome_size.min(evaluation.genome.len())];
+            let genome = genome.to_vec();
+            match self.species.iter_mut().find(|species| {
+                self.distance.distance(&species.representative, &genome) <= self.threshold
+            }) {
+                Some(species) => species.members.push(index),
+                None => {
+                    self.species.push(Species {
+                        id: self.next_id,
+                        representative: genome,
+                        members: vec![index],
+                        shared_fitness: 0.0,
+                    });
+                    self.next_id += 1;
+                }
+            }
+        }
+        self.species.retain(|species| !species.members.is_empty());
+
+        for species in self.species.iter_mut() {
+            let fitness = |&index: &usize| evaluations[index].fitness;
+            species.shared_fitness =
+                species.members.iter().map(fitness).sum::<f32>() / species.members.len() as f32;
+            if let Some(&fittest) = species
+                .members
+                .iter()
+                .max_by(|a, b| fitness(a).total_cmp(&fitness(b)))
+            {
+                let genome = &evaluations[fittest].genome;
+                species.representative = genome[..genome_size.min(genome.len())].to_vec();
+            }
+        }
+    }
+
+    /// Individuals bred from each species out of `count`, in proportion to their shared
+    /// fitness, or to their size when none is fit. Species not `eligible` get none.
+    pub(super) fn quotas(&self, count: usize, eligible: impl Fn(&Species) -> bool) -> Vec<usize> {
+        let mut shares = self
+            .species
+            .iter()
+            .map(|species| {
+                if eligible(species) {
+                    species.shared_fitness.max(0.0)
+                } else {
+                    0.0
+                }
+            })
+            .collect::<Vec<_>>();
+        if shares.iter().sum::<f32>() <= 0.0 {
+            shares = self
+                .species
+                .iter()
+                .map(|species| {
+                    if eligible(species) {
+                        species.members.len() as f32
+                    } else {
+                        0.0
+                    }
+                })
+                .collect();
+        }
+        largest_remainder(&shares, count)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Evaluation;
+
+    use super::{CompatibilityDistance, GeneDistance, Speciation};
+
+    fn evaluation(genome: Vec<u8>, fitness: f32) -> Evaluation {
+        Evaluation {
+            genome,
+            fitness,
+            evaluated: true,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_gene_distance() {
+        assert_eq!(0.0, GeneDistance.distance(&vec![], &vec![]));
+        assert_eq!(
+            0.25,
+            GeneDistance.distance(&vec![1, 2, 3, 4], &vec![1, 2, 3, 0])
+        );
+        assert_eq!(0.5, GeneDistance.distance(&vec![1, 2, 3, 4], &vec![1, 2]));
+    }
+
+    #[test]
+    fn test_speciation_speciate() {
+        // Given
+        let mut speciation = Speciation::new(Box::new(GeneDistance), 0.5);
+        let evaluations = vec![
+            evaluation(vec![0, 0, 0, 0, 9], 0.2),
+            evaluation(vec![9, 9, 9, 9, 9], 0.6),
+            evaluation(vec![0, 0, 0, 1, 9], 0.4),
+            evaluation(vec![9, 9, 9, 1, 9], 0.8),
+        ];
+
+        // When
+        speciation.speciate(&evaluations, 4);
+
+        // Then
+        let species = speciation.species();
+        assert_eq!(
+            vec![vec![0, 2], vec![1, 3]],
+            species
+                .iter()
+                .map(|species| species.members.clone())
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![0, 0, 0, 1],
+            species[0].representative,
+            "Should represent the species by its fittest member, without the reserved genes"
+        );
+        assert!((species[1].shared_fitness - 0.7).abs() < f32::EPSILON);
+
+        // When
+        speciation.speciate(&evaluations[1..2], 4);
+
+        // Then
+        assert_eq!(
+            vec![1],
+            speciation
+                .species()
+                .iter()
+                .map(|species| species.id)
+                .collect::<Vec<_>>(),
+            "Should keep the ids of the species and drop the empty ones"
+        );
+    }
+
+    #[test]
+    fn test_speciation_quotas() {
+        // Given
+        let mut speciation = Speciation::new(Box::new(GeneDistance), 0.0);
+        speciation.speciate(
+            &[
+                evaluation(vec![1], 0.5),
+                evaluation(vec![2], 0.25),
+                evaluation(vec![2], 0.25),
+                evaluation(vec![3], 0.0),
+            ],
+            1,
+        );
+
+        // When
+        let result = speciation.quotas(10, |_| true);
+
+        // Then
+        assert_eq!(
+            vec![7, 3, 0],
+            result,
+            "Should share the fitness within species"
+        );
+        assert_eq!(
+            vec![0, 10, 0],
+            speciation.quotas(10, |species| species.members.len() > 1)
+        );
+    }
+}
@@ -0,0 +1,481 @@
+use std::{fmt::Display, time::Duration};
+
+use log::warn;
+
+use crate::diversity::Diversity;
+
+use super::EvolutionError;
+
+/// State of a run once a generation is evaluated, checked by the [`TerminationCriterion`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Progress<'a> {
+    /// Generation just evaluated, the first one being 0.
+    pub generation: u64,
+    pub fitnesses: &'a [f32],
+    /// Number of genomes evaluated so far, injected ones included, see
+    /// [`super::Snapshot::evaluation_count`].
+    pub evaluation_count: u64,
+    /// Time spent since the start of the run.
+    pub elapsed: Duration,
+    /// Diversity of the generation, only measured on demand, see
+    /// [`super::EvolutionEngineBuilder::measure_diversity`].
+    pub diversity: Option<&'a Diversity>,
+}
+
+/// Condition completing a run, checked after the evaluation of each generation.
+///
+/// Closures of the generation and the fitnesses are criteria, which the built-in ones combine
+/// with through [`TerminationCriterion::or`] and [`TerminationCriterion::and`].
+pub trait TerminationCriterion {
+    fn is_met(&mut self, progress: &Progress) -> bool;
+
+    /// Check made by the engine, whose failure ends the run with
+    /// [`EvolutionError::Termination`]. Only [`TerminationCriterion::is_met`] by default, see
+    /// [`Fallible`] for criteria relying on external checks.
+    fn try_is_met(&mut self, progress: &Progress) -> Result<bool, EvolutionError> {
+        Ok(self.is_met(progress))
+    }
+
+    /// Share of the run done according to the criterion, within [0, 1], when it can tell, e.g.
+    /// to estimate the time left. Unknown by default.
+    fn completion(&self, _progress: &Progress) -> Option<f32> {
+        None
+    }
+
+    /// Criterion met as soon as one of both is.
+    fn or<C>(self, other: C) -> Any
+    where
+        Self: Sized + 'static,
+        C: TerminationCriterion + 'static,
+    {
+        Any(vec![Box::new(self), Box::new(other)])
+    }
+
+    /// Criterion met once both are.
+    fn and<C>(self, other: C) -> All
+    where
+        Self: Sized + 'static,
+        C: TerminationCriterion + 'static,
+    {
+        All(vec![Box::new(self), Box::new(other)])
+    }
+}
+
+impl<F> TerminationCriterion for F
+where
+    F: Fn(u64, &[f32]) -> bool,
+{
+    fn is_met(&mut self, progress: &Progress) -> bool {
+        self(progress.generation, progress.fitnesses)
+    }
+}
+
+/// Met when any of the criteria is, all of them being checked so that stateful criteria see
+/// every generation.
+pub struct Any(pub Vec<Box<dyn TerminationCriterion>>);
+
+impl TerminationCriterion for Any {
+    fn is_met(&mut self, progress: &Progress) -> bool {
+        let mut met = false;
+        for criterion in self.0.iter_mut() {
+            met |= criterion.is_met(progress);
+        }
+        met
+    }
+
+    fn try_is_met(&mut self, progress: &Progress) -> Result<bool, EvolutionError> {
+        let mut met = false;
+        for criterion in self.0.iter_mut() {
+            met |= criterion.try_is_met(progress)?;
+        }
+        Ok(met)
+    }
+
+    /// Completion of the criterion closest to be met.
+    fn completion(&self, progress: &Progress) -> Option<f32> {
+        self.0
+            .iter()
+            .filter_map(|criterion| criterion.completion(progress))
+            .reduce(f32::max)
+    }
+}
+
+/// Met when all the criteria are, all of them being checked so that stateful criteria see
+/// every generation. Always met without criteria.
+pub struct All(pub Vec<Box<dyn TerminationCriterion>>);
+
+impl TerminationCriterion for All {
+    fn is_met(&mut self, progress: &Progress) -> bool {
+        let mut met = true;
+        for criterion in self.0.iter_mut() {
+            met &= criterion.is_met(progress);
+        }
+        met
+    }
+
+    fn try_is_met(&mut self, progress: &Progress) -> Result<bool, EvolutionError> {
+        let mut met = true;
+        for criterion in self.0.iter_mut() {
+            met &= criterion.try_is_met(progress)?;
+        }
+        Ok(met)
+    }
+
+    /// Completion of the criterion furthest from being met, unknown if one of them is.
+    fn completion(&self, progress: &Progress) -> Option<f32> {
+        self.0
+            .iter()
+            .map(|criterion| criterion.completion(progress))
+            .try_fold(1.0, |completion, criterion| {
+                criterion.map(|c| f32::min(completion, c))
+            })
+    }
+}
+
+/// Criterion whose check may fail, e.g. looking up a database or a flag file, failing the run
+/// instead of panicking. Failures are logged and taken as not met when checked through
+/// [`TerminationCriterion::is_met`].
+pub struct Fallible<F>(pub F);
+
+impl<F, E> TerminationCriterion for Fallible<F>
+where
+    F: FnMut(&Progress) -> Result<bool, E>,
+    E: Display,
+{
+    fn is_met(&mut self, progress: &Progress) -> bool {
+        self.try_is_met(progress).unwrap_or_else(|err| {
+            warn!("{err}");
+            false
+        })
+    }
+
+    fn try_is_met(&mut self, progress: &Progress) -> Result<bool, EvolutionError> {
+        (self.0)(progress).map_err(|err| EvolutionError::Termination(err.to_string()))
+    }
+}
+
+/// Met once a fitness reaches the target.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TargetFitness(pub f32);
+
+impl TerminationCriterion for TargetFitness {
+    fn is_met(&mut self, progress: &Progress) -> bool {
+        progress.fitnesses.iter().any(|&fitness| fitness >= self.0)
+    }
+}
+
+/// Met once the run reaches the given generation, the first one being 0.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MaxGenerations(pub u64);
+
+impl TerminationCriterion for MaxGenerations {
+    fn is_met(&mut self, progress: &Progress) -> bool {
+        progress.generation >= self.0
+    }
+
+    fn completion(&self, progress: &Progress) -> Option<f32> {
+        Some(share(
+            progress.generation.saturating_add(1),
+            self.0.saturating_add(1),
+        ))
+    }
+}
+
+/// Met once the run evaluated at least the given number of genomes.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MaxEvaluations(pub u64);
+
+impl TerminationCriterion for MaxEvaluations {
+    fn is_met(&mut self, progress: &Progress) -> bool {
+        progress.evaluation_count >= self.0
+    }
+
+    fn completion(&self, progress: &Progress) -> Option<f32> {
+        Some(share(progress.evaluation_count, self.0))
+    }
+}
+
+/// Met once the run lasted the given duration, checked between generations only.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MaxDuration(pub Duration);
+
+impl TerminationCriterion for MaxDuration {
+    fn is_met(&mut self, progress: &Progress) -> bool {
+        progress.elapsed >= self.0
+    }
+
+    fn completion(&self, progress: &Progress) -> Option<f32> {
+        Some(duration_share(progress.elapsed, self.0))
+    }
+}
+
+/// Met once the mean pairwise Hamming distance of the generation falls below the threshold,
+/// never when the diversity is not measured, see
+/// [`super::EvolutionEngineBuilder::measure_diversity`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MinDiversity(pub f32);
+
+impl TerminationCriterion for MinDiversity {
+    fn is_met(&mut self, progress: &Progress) -> bool {
+        progress
+            .diversity
+            .is_some_and(|diversity| diversity.mean_hamming_distance < self.0)
+    }
+}
+
+/// Share of `total` reached by `count`, complete when there is nothing to reach.
+fn share(count: u64, total: u64) -> f32 {
+    if total == 0 {
+        1.0
+    } else {
+        (count as f32 / total as f32).min(1.0)
+    }
+}
+
+/// Share of `total` spent in `elapsed`, see [`share`].
+pub(super) fn duration_share(elapsed: Duration, total: Duration) -> f32 {
+    if total.is_zero() {
+        1.0
+    } else {
+        (elapsed.as_secs_f32() / total.as_secs_f32()).min(1.0)
+    }
+}
+
+/// Met once the best fitness did not improve by more than `epsilon` for `generations`
+/// generations, also notified by the engine as [`super::EventType::StagnationDetected`], see
+/// [`super::EvolutionEngineBuilder::detect_stagnation`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Stagnation {
+    generations: u64,
+    epsilon: f32,
+    /// Best fitness when it last improved.
+    best: Option<f32>,
+    stalled: u64,
+}
+
+impl Stagnation {
+    pub fn new(generations: u64, epsilon: f32) -> Self {
+        Stagnation {
+            generations,
+            epsilon,
+            best: None,
+            stalled: 0,
+        }
+    }
+
+    /// Records the fitnesses of a generation, returning the number of generations since the
+    /// best fitness last improved.
+    pub fn update(&mut self, fitnesses: &[f32]) -> u64 {
+        let best = fitnesses.iter().copied().reduce(f32::max);
+        match (self.best, best) {
+            (Some(previous), Some(best)) if best <= previous + self.epsilon => self.stalled += 1,
+            (_, Some(best)) => {
+                self.best = Some(best);
+                self.stalled = 0;
+            }
+            (_, None) => self.stalled += 1,
+        }
+        self.stalled
+    }
+
+    pub fn generations(&self) -> u64 {
+        self.generations
+    }
+
+    pub fn epsilon(&self) -> f32 {
+        self.epsilon
+    }
+
+    pub fn is_stagnating(&self) -> bool {
+        self.stalled >= self.generations
+    }
+}
+
+impl TerminationCriterion for Stagnation {
+    fn is_met(&mut self, progress: &Progress) -> bool {
+        self.update(progress.fitnesses);
+        self.is_stagnating()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::Cell, rc::Rc, time::Duration};
+
+    use crate::{diversity::Diversity, evolution::EvolutionError};
+
+    use super::{
+        All, Any, Fallible, MaxDuration, MaxEvaluations, MaxGenerations, MinDiversity, Progress,
+        Stagnation, TargetFitness, TerminationCriterion,
+    };
+
+    const PROGRESS: Progress = Progress {
+        generation: 10,
+        fitnesses: &[0.2, 0.8, 0.5],
+        evaluation_count: 30,
+        elapsed: Duration::from_secs(5),
+        diversity: None,
+    };
+
+    #[test]
+    fn test_built_in_criteria() {
+        assert!(TargetFitness(0.8).is_met(&PROGRESS));
+        assert!(!TargetFitness(0.9).is_met(&PROGRESS));
+        assert!(MaxGenerations(10).is_met(&PROGRESS));
+        assert!(!MaxGenerations(11).is_met(&PROGRESS));
+        assert!(MaxEvaluations(30).is_met(&PROGRESS));
+        assert!(!MaxEvaluations(31).is_met(&PROGRESS));
+        assert!(MaxDuration(Duration::from_secs(5)).is_met(&PROGRESS));
+        assert!(!MaxDuration(Duration::from_secs(6)).is_met(&PROGRESS));
+    }
+
+    #[test]
+    fn test_min_diversity() {
+        // Given
+        let diversity = Diversity {
+            mean_hamming_distance: 1.5,
+            ..Default::default()
+        };
+        let progress = Progress {
+            diversity: Some(&diversity),
+            ..PROGRESS
+        };
+
+        // Then
+        assert!(MinDiversity(2.0).is_met(&progress));
+        assert!(!MinDiversity(1.5).is_met(&progress));
+        assert!(
+            !MinDiversity(2.0).is_met(&PROGRESS),
+            "Should not be met without the diversity measured"
+        );
+    }
+
+    #[test]
+    fn test_closure_criterion() {
+        let mut criterion =
+            |generation: u64, fitnesses: &[f32]| generation > 5 && fitnesses.len() > 3;
+
+        assert!(!criterion.is_met(&PROGRESS));
+    }
+
+    #[test]
+    fn test_combinators() {
+        assert!(MaxGenerations(100).or(TargetFitness(0.5)).is_met(&PROGRESS));
+        assert!(!MaxGenerations(100)
+            .and(TargetFitness(0.5))
+            .is_met(&PROGRESS));
+        assert!(MaxGenerations(1)
+            .and(TargetFitness(0.5).or(MaxEvaluations(1000)))
+            .is_met(&PROGRESS));
+        assert!(!Any(vec![]).is_met(&PROGRESS));
+        assert!(All(vec![]).is_met(&PROGRESS));
+    }
+
+    /// Counts the times it is checked, never met.
+    struct Counter(Rc<Cell<usize>>);
+
+    impl TerminationCriterion for Counter {
+        fn is_met(&mut self, _progress: &Progress) -> bool {
+            self.0.set(self.0.get() + 1);
+            false
+        }
+    }
+
+    #[test]
+    fn test_combinators_should_check_all_criteria() {
+        // Given
+        let checks = Rc::new(Cell::new(0));
+        let mut any = MaxGenerations(0).or(Counter(checks.clone()));
+        let mut all = MaxGenerations(100).and(Counter(checks.clone()));
+
+        // When
+        any.is_met(&PROGRESS);
+        all.is_met(&PROGRESS);
+
+        // Then
+        assert_eq!(
+            2,
+            checks.get(),
+            "Should check the criteria after the decisive one"
+        );
+    }
+
+    #[test]
+    fn test_completion() {
+        assert_eq!(Some(0.5), MaxGenerations(21).completion(&PROGRESS));
+        assert_eq!(Some(0.5), MaxEvaluations(60).completion(&PROGRESS));
+        assert_eq!(Some(1.0), MaxEvaluations(0).completion(&PROGRESS));
+        assert_eq!(
+            Some(0.5),
+            MaxDuration(Duration::from_secs(10)).completion(&PROGRESS)
+        );
+        assert_eq!(None, TargetFitness(1.0).completion(&PROGRESS));
+        assert_eq!(
+            Some(0.75),
+            MaxGenerations(21)
+                .or(MaxEvaluations(40))
+                .completion(&PROGRESS),
+            "Should follow the criterion closest to be met"
+        );
+        assert_eq!(
+            Some(0.5),
+            MaxGenerations(21)
+                .or(TargetFitness(1.0))
+                .completion(&PROGRESS)
+        );
+        assert_eq!(
+            Some(0.5),
+            MaxGenerations(21)
+                .and(MaxEvaluations(40))
+                .completion(&PROGRESS)
+        );
+        assert_eq!(
+            None,
+            MaxGenerations(21)
+                .and(TargetFitness(1.0))
+                .completion(&PROGRESS),
+            "Should not tell when one of the criteria cannot"
+        );
+    }
+
+    #[test]
+    fn test_fallible_criterion() {
+        // Given
+        let failing = || Fallible(|_: &Progress| Err::<bool, _>("flag file unreadable"));
+        let mut criterion = MaxGenerations(100).or(Fallible(|progress: &Progress| {
+            Ok::<_, String>(progress.evaluation_count >= 30)
+        }));
+
+        // Then
+        assert_eq!(Ok(true), criterion.try_is_met(&PROGRESS));
+        assert_eq!(
+            Err(EvolutionError::Termination(
+                "flag file unreadable".to_string()
+            )),
+            MaxGenerations(0).or(failing()).try_is_met(&PROGRESS),
+            "Should surface the failures of combined criteria"
+        );
+        assert!(!failing().is_met(&PROGRESS), "Should not be met on failure");
+    }
+
+    #[test]
+    fn test_stagnation() {
+        // Given
+        let mut stagnation = Stagnation::new(2, 0.01);
+
+        // When
+        let stalled = [0.5, 0.505, 0.508, 0.52, 0.52, 0.52]
+            .iter()
+            .map(|&best| stagnation.update(&[0.1, best]))
+            .collect::<Vec<_>>();
+
+        // Then
+        assert_eq!(
+            vec![0, 1, 2, 0, 1, 2],
+            stalled,
+            "Should ignore improvements up to epsilon"
+        );
+        assert!(stagnation.is_stagnating());
+        assert!(!Stagnation::new(2, 0.0).is_met(&PROGRESS));
+    }
+}
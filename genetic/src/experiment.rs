@@ -0,0 +1,368 @@
+use std::sync::{Arc, Mutex, PoisonError};
+
+use futures::executor::block_on;
+use log::debug;
+use rand::{Rng, SeedableRng};
+#[cfg(feature = "parallel")]
+use rayon::iter::{IntoParallelIterator, ParallelIterator};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    adaptation::Strategy,
+    evolution::{EvolutionConfig, EvolutionEngineBuilder, EvolutionError, TerminationCriterion},
+};
+
+/// Outcome of one of the runs of an experiment.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RunOutcome {
+    /// Seed of the random generator of the run.
+    pub seed: u64,
+    /// Last evaluated generation.
+    pub generation: u64,
+    pub evaluation_count: u64,
+    /// Best fitness reached so far at each generation, from the first one.
+    pub curve: Vec<f32>,
+    /// First generation reaching the target of the experiment, none if missed or without target.
+    pub success_generation: Option<u64>,
+}
+
+impl RunOutcome {
+    pub fn best_fitness(&self) -> Option<f32> {
+        self.curve.last().copied()
+    }
+}
+
+/// Best fitness reached so far by the runs of an experiment at a generation, see
+/// [`ExperimentReport::curve`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CurvePoint {
+    pub generation: u64,
+    pub min: f32,
+    pub mean: f32,
+    pub max: f32,
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ExperimentReport {
+    /// Outcomes in seed order.
+    pub runs: Vec<RunOutcome>,
+}
+
+impl ExperimentReport {
+    /// Share of the runs reaching the target of the experiment, 0 without runs.
+    pub fn success_rate(&self) -> f32 {
+        if self.runs.is_empty() {
+            return 0.0;
+        }
+        let successes = self
+            .runs
+            .iter()
+            .filter(|run| run.success_generation.is_some())
+            .count();
+        successes as f32 / self.runs.len() as f32
+    }
+
+    /// Generations the successful runs took to reach the target, sorted, e.g. to plot their
+    /// distribution.
+    pub fn generations_to_success(&self) -> Vec<u64> {
+        let mut generations = self
+            .runs
+            .iter()
+            .filter_map(|run| run.success_generation)
+            .collect::<Vec<_>>();
+        generations.sort_unstable();
+        generations
+    }
+
+    /// Best fitness curves of the runs aggregated generation by generation, runs ended earlier
+    /// keeping their last best fitness.
+    pub fn curve(&self) -> Vec<CurvePoint> {
+        let length = self.runs.iter().map(|run| run.curve.len()).max();
+        (0..length.unwrap_or(0))
+            .map(|generation| {
+                let values = self
+                    .runs
+                    .iter()
+                    .filter_map(|run| run.curve.get(generation).or(run.curve.last()))
+                    .copied()
+                    .collect::<Vec<_>>();
+                CurvePoint {
+                    generation: generation as u64,
+                    min: values.iter().copied().fold(f32::INFINITY, f32::min),
+                    mean: values.iter().sum::<f32>() / values.len() as f32,
+                    max: values.iter().copied().fold(f32::NEG_INFINITY, f32::max),
+                }
+            })
+            .collect()
+    }
+}
+
+/// Runs the same configuration several times with consecutive seeds, so that configurations
+/// compared with the same runner face the same random generators.
+pub struct ExperimentRunner {
+    config: EvolutionConfig,
+    runs: usize,
+    seed: u64,
+    target: Option<f32>,
+}
+
+impl ExperimentRunner {
+    pub fn new(config: EvolutionConfig, runs: usize) -> Self {
+        ExperimentRunner {
+            config,
+            runs,
+            seed: 0,
+            target: None,
+        }
+    }
+
+    /// Seed of the first run, the next runs taking the following ones, 0 by default.
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Fitness counting a run as a success once reached.
+    pub fn target(mut self, fitness: f32) -> Self {
+        self.target = Some(fitness);
+        self
+    }
+
+    /// Executes the runs until each meets the criterion given by `is_complete`, blocking the
+    /// current thread.
+    pub fn run<R, T, C, F>(
+        &self,
+        strategy: &T,
+        is_complete: F,
+    ) -> Result<ExperimentReport, EvolutionError>
+    where
+        R: Rng + SeedableRng,
+        T: Strategy,
+        C: TerminationCriterion,
+        F: Fn() -> C,
+    {
+        debug!("Running experiment of {} run(s)", self.runs);
+        let runs = self
+            .seeds()
+            .map(|seed| self.run_once::<R, _, _>(strategy, is_complete(), seed))
+            .collect::<Result<_, _>>()?;
+        Ok(ExperimentReport { runs })
+    }
+
+    /// Executes the runs on the thread pool rather than one after the other, see
+    /// [`ExperimentRunner::run`].
+    #[cfg(feature = "parallel")]
+    pub fn run_parallel<R, T, C, F>(
+        &self,
+        strategy: &T,
+        is_complete: F,
+    ) -> Result<ExperimentReport, EvolutionError>
+    where
+        R: Rng + SeedableRng,
+        T: Strategy + Sync,
+        C: TerminationCriterion,
+        F: Fn() -> C + Sync,
+    {
+        debug!("Running experiment of {} run(s) in parallel", self.runs);
+        let runs = self
+            .seeds()
+            .collect::<Vec<_>>()
+            .into_par_iter()
+            .map(|seed| self.run_once::<R, _, _>(strategy, is_complete(), seed))
+            .collect::<Result<_, _>>()?;
+        Ok(ExperimentReport { runs })
+    }
+
+    fn seeds(&self) -> impl Iterator<Item = u64> + '_ {
+        (0..self.runs as u64).map(|run| self.seed.wrapping_add(run))
+    }
+
+    fn run_once<R, T, C>(
+        &self,
+        strategy: &T,
+        is_complete: C,
+        seed: u64,
+    ) -> Result<RunOutcome, EvolutionError>
+    where
+        R: Rng + SeedableRng,
+        T: Strategy,
+        C: TerminationCriterion,
+    {
+        let maxima = Arc::new(Mutex::new(vec![]));
+        let recorder = maxima.clone();
+        let mut run = EvolutionEngineBuilder::new(R::seed_from_u64(seed))
+            .config(self.config.clone())
+            .on_generation_end(move |_, evaluations| {
+                let max = evaluations
+                    .iter()
+                    .filter(|e| e.evaluated)
+                    .map(|e| e.fitness)
+                    .fold(0.0, f32::max);
+                recorder
+                    .lock()
+                    .unwrap_or_else(PoisonError::into_inner)
+                    .push(max);
+            })
+            .build()?;
+        let snapshot = block_on(run.start(strategy, is_complete))?;
+
+        let curve = maxima
+            .lock()?
+            .iter()
+            .scan(0.0, |best: &mut f32, &max| {
+                *best = best.max(max);
+                Some(*best)
+            })
+            .collect::<Vec<_>>();
+        let success_generation = self.target.and_then(|target| {
+            curve
+                .iter()
+                .position(|&best| best >= target)
+                .map(|generation| generation as u64)
+        });
+        Ok(RunOutcome {
+            seed,
+            generation: snapshot.generation,
+            evaluation_count: snapshot.evaluation_count,
+            curve,
+            success_generation,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::rngs::StdRng;
+
+    use crate::{
+        adaptation::Strategy,
+        evolution::{
+            EvolutionConfig, EvolutionError, MaxGenerations, TargetFitness, TerminationCriterion,
+        },
+        Genome,
+    };
+
+    use super::{CurvePoint, ExperimentReport, ExperimentRunner, RunOutcome};
+
+    struct Sum;
+
+    impl Strategy for Sum {
+        type Phenotype = u32;
+
+        fn genome_size(&self) -> usize {
+            4
+        }
+
+        fn decode(&self, genome: &Genome) -> u32 {
+            genome.iter().map(|&gene| gene as u32).sum()
+        }
+
+        fn evaluate(&self, sum: &u32) -> f32 {
+            *sum as f32 / 1020.0
+        }
+    }
+
+    fn runner() -> ExperimentRunner {
+        let config = EvolutionConfig {
+            population_size: 10,
+            generation_renewal_config: None,
+            max_duration: None,
+        };
+        ExperimentRunner::new(config, 4).seed(7).target(0.7)
+    }
+
+    fn outcome(curve: Vec<f32>, success_generation: Option<u64>) -> RunOutcome {
+        RunOutcome {
+            seed: 0,
+            generation: curve.len() as u64 - 1,
+            evaluation_count: 0,
+            curve,
+            success_generation,
+        }
+    }
+
+    #[test]
+    fn test_experiment_runner_run() -> Result<(), EvolutionError> {
+        // Given
+        let runner = runner();
+
+        // When
+        let result =
+            runner.run::<StdRng, _, _, _>(&Sum, || TargetFitness(0.7).or(MaxGenerations(5)))?;
+
+        // Then
+        assert_eq!(
+            vec![7, 8, 9, 10],
+            result.runs.iter().map(|run| run.seed).collect::<Vec<_>>()
+        );
+        for run in result.runs.iter() {
+            assert_eq!(run.generation as usize + 1, run.curve.len());
+            assert!(
+                run.curve.windows(2).all(|pair| pair[0] <= pair[1]),
+                "Should keep the best fitness so far"
+            );
+            assert_eq!(
+                run.best_fitness().is_some_and(|best| best >= 0.7),
+                run.success_generation == Some(run.generation),
+                "Should stop the runs at their success"
+            );
+        }
+        assert_eq!(
+            result,
+            runner.run::<StdRng, _, _, _>(&Sum, || TargetFitness(0.7).or(MaxGenerations(5)))?,
+            "Should reproduce the experiment from its seeds"
+        );
+
+        Ok(())
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn test_experiment_runner_run_parallel() -> Result<(), EvolutionError> {
+        // When
+        let result = runner().run_parallel::<StdRng, _, _, _>(&Sum, || MaxGenerations(3))?;
+
+        // Then
+        assert_eq!(
+            runner().run::<StdRng, _, _, _>(&Sum, || MaxGenerations(3))?,
+            result,
+            "Should not depend on the threads running the experiment"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_experiment_report() {
+        // Given
+        let report = ExperimentReport {
+            runs: vec![
+                outcome(vec![0.25, 0.5, 1.0], Some(2)),
+                outcome(vec![0.75], Some(0)),
+                outcome(vec![0.0, 0.25, 0.5, 0.5], None),
+            ],
+        };
+
+        // When
+        let result = report.curve();
+
+        // Then
+        assert_eq!(
+            CurvePoint {
+                generation: 2,
+                min: 0.5,
+                mean: 0.75,
+                max: 1.0,
+            },
+            result[2],
+            "Should carry the best fitness of the ended runs over"
+        );
+        assert_eq!(4, result.len());
+        assert_eq!(2.0 / 3.0, report.success_rate());
+        assert_eq!(vec![0, 2], report.generations_to_success());
+    }
+}
@@ -0,0 +1,581 @@
+//! Tree genomes for genetic programming, e.g. symbolic regression or program evolution.
+//!
+//! A [`Tree`] lists its nodes in prefix order, each node being a [`Primitive`] of a
+//! [`PrimitiveSet`]: functions take as many children as their arity while terminals are leaves.
+//! Trees are stored in byte genomes, one gene per node followed by unused genes, and any genome
+//! decodes to a tree fitting in it, so that the random genomes of the engine are valid programs.
+//!
+//! The engine evolves trees with [`SubtreeCrossover`], [`PointMutation`] and [`SubtreeMutation`],
+//! through [`crate::crossover::CrossoverType::Custom`] and
+//! [`crate::mutation::MutationType::Custom`].
+
+use rand::{seq::SliceRandom, Rng, RngCore};
+use thiserror::Error;
+
+use crate::{crossover::CrossoverOperator, mutation::MutationOperator, Genome};
+
+#[derive(Error, Debug, PartialEq)]
+pub enum GpError {
+    #[error("A primitive set needs at least one terminal")]
+    NoTerminal,
+    #[error("A primitive set holds up to 256 primitives, got: {0}")]
+    TooManyPrimitives(usize),
+}
+
+/// Function of the given arity, or terminal when the arity is 0.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Primitive {
+    pub name: String,
+    pub arity: usize,
+}
+
+impl Primitive {
+    pub fn function(name: &str, arity: usize) -> Self {
+        Primitive {
+            name: name.to_owned(),
+            arity,
+        }
+    }
+
+    pub fn terminal(name: &str) -> Self {
+        Self::function(name, 0)
+    }
+}
+
+/// Node of a [`Tree`], `primitive` being its index in the [`PrimitiveSet`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Node {
+    pub primitive: u8,
+    pub arity: usize,
+}
+
+/// Primitives the trees are made of, identified by their index.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PrimitiveSet {
+    primitives: Vec<Primitive>,
+    terminals: Vec<u8>,
+}
+
+impl PrimitiveSet {
+    pub fn new(primitives: Vec<Primitive>) -> Result<Self, GpError> {
+        if primitives.len() > u8::MAX as usize + 1 {
+            return Err(GpError::TooManyPrimitives(primitives.len()));
+        }
+        let terminals = (0..primitives.len())
+            .filter(|&index| primitives[index].arity == 0)
+            .map(|index| index as u8)
+            .collect::<Vec<_>>();
+        if terminals.is_empty() {
+            return Err(GpError::NoTerminal);
+        }
+        Ok(PrimitiveSet {
+            primitives,
+            terminals,
+        })
+    }
+
+    pub fn get(&self, primitive: u8) -> &Primitive {
+        &self.primitives[primitive as usize]
+    }
+
+    fn node(&self, primitive: u8) -> Node {
+        Node {
+            primitive,
+            arity: self.get(primitive).arity,
+        }
+    }
+
+    /// Reads the tree of the genome, one node per gene until the tree is complete.
+    ///
+    /// Each gene selects a primitive modulo their count, functions being replaced by terminals
+    /// when their children would not fit in the rest of the genome. Empty genomes hold the first
+    /// terminal.
+    pub fn decode(&self, genome: &Genome) -> Tree {
+        let mut nodes = Vec::new();
+        // Children still expected by the nodes read so far
+        let mut open = 1;
+        for (locus, &gene) in genome.iter().enumerate() {
+            if open == 0 {
+                break;
+            }
+            let mut node = self.node((gene as usize % self.primitives.len()) as u8);
+            if open + node.arity > genome.len() - locus {
+                node = self.node(self.terminals[gene as usize % self.terminals.len()]);
+            }
+            open = open - 1 + node.arity;
+            nodes.push(node);
+        }
+        if nodes.is_empty() {
+            nodes.push(self.node(self.terminals[0]));
+        }
+        Tree { nodes }
+    }
+
+    /// Writes the tree at the head of a genome of `genome_size` genes, the other genes being set
+    /// to 0. Trees longer than the genome are truncated.
+    pub fn encode(&self, tree: &Tree, genome_size: usize) -> Genome {
+        let mut genome = tree
+            .nodes
+            .iter()
+            .map(|node| node.primitive)
+            .collect::<Genome>();
+        genome.resize(genome_size, 0);
+        genome
+    }
+
+    /// Random tree of at most `max_depth` levels below its root and `max_size` nodes, drawing
+    /// each node uniformly among the primitives that fit ("grow" initialization).
+    pub fn grow(&self, max_depth: usize, max_size: usize, rng: &mut impl Rng) -> Tree {
+        let mut nodes = Vec::new();
+        // Depths of the children still expected, the last one being the next node
+        let mut pending = vec![0];
+        while let Some(depth) = pending.pop() {
+            let mut node = self.node(rng.gen_range(0..self.primitives.len()) as u8);
+            let remaining = max_size.saturating_sub(nodes.len());
+            if node.arity > 0 && (depth >= max_depth || pending.len() + 1 + node.arity > remaining)
+            {
+                node = self.node(*self.terminals.choose(rng).expect("Terminals are not empty"));
+            }
+            pending.resize(pending.len() + node.arity, depth + 1);
+            nodes.push(node);
+        }
+        Tree { nodes }
+    }
+
+    /// Replaces, at the mutation rate, the primitive of each node with a random one of the same
+    /// arity.
+    pub fn point_mutation(&self, tree: &mut Tree, mutation_rate: f32, rng: &mut impl Rng) {
+        for node in tree.nodes.iter_mut() {
+            if rng.gen::<f32>() < mutation_rate {
+                let same_arity = (0..self.primitives.len())
+                    .filter(|&index| self.primitives[index].arity == node.arity)
+                    .collect::<Vec<_>>();
+                if let Some(&primitive) = same_arity.choose(rng) {
+                    node.primitive = primitive as u8;
+                }
+            }
+        }
+    }
+
+    /// Replaces, with probability `mutation_rate`, a random subtree with a tree grown within
+    /// `max_depth` levels, keeping the tree within `max_size` nodes.
+    pub fn subtree_mutation(
+        &self,
+        tree: &mut Tree,
+        mutation_rate: f32,
+        max_depth: usize,
+        max_size: usize,
+        rng: &mut impl Rng,
+    ) {
+        if rng.gen::<f32>() < mutation_rate {
+            let start = rng.gen_range(0..tree.len());
+            let end = tree.subtree_end(start);
+            let budget = max_size.saturating_sub(tree.len() - (end - start)).max(1);
+            let grown = self.grow(max_depth, budget, rng);
+            tree.nodes.splice(start..end, grown.nodes);
+        }
+    }
+}
+
+/// Tree of primitives, its nodes listed in prefix order.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Tree {
+    nodes: Vec<Node>,
+}
+
+impl Tree {
+    pub fn nodes(&self) -> &[Node] {
+        &self.nodes
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// End, exclusive, of the subtree rooted at `start`.
+    pub fn subtree_end(&self, start: usize) -> usize {
+        let mut open = 1;
+        let mut end = start;
+        while open > 0 && end < self.nodes.len() {
+            open = open - 1 + self.nodes[end].arity;
+            end += 1;
+        }
+        end
+    }
+
+    /// Number of levels below the root.
+    pub fn depth(&self) -> usize {
+        let mut depth = 0;
+        // Depths of the children still expected
+        let mut pending = vec![0];
+        for node in &self.nodes {
+            let node_depth = pending.pop().unwrap_or_default();
+            depth = depth.max(node_depth);
+            pending.resize(pending.len() + node.arity, node_depth + 1);
+        }
+        depth
+    }
+
+    /// Evaluates the tree bottom-up, `apply` computing the value of a primitive from the values
+    /// of its children.
+    pub fn evaluate<T>(&self, mut apply: impl FnMut(u8, Vec<T>) -> T) -> T {
+        let mut values = Vec::with_capacity(self.nodes.len());
+        for node in self.nodes.iter().rev() {
+            // Children were pushed last to first
+            let arguments = values.split_off(values.len() - node.arity);
+            values.push(apply(node.primitive, arguments.into_iter().rev().collect()));
+        }
+        values.pop().expect("Trees are not empty")
+    }
+
+    /// Replaces a random subtree with a random subtree of `donor`, keeping the offspring within
+    /// `max_size` nodes.
+    pub fn subtree_crossover(&self, donor: &Tree, max_size: usize, rng: &mut impl Rng) -> Tree {
+        let start = rng.gen_range(0..self.len());
+        let end = self.subtree_end(start);
+        let budget = max_size.saturating_sub(self.len() - (end - start)).max(1);
+        // Leaves always fit
+        let donor_starts = (0..donor.len())
+            .filter(|&donor_start| donor.subtree_end(donor_start) - donor_start <= budget)
+            .collect::<Vec<_>>();
+        let donor_start = *donor_starts.choose(rng).expect("Donor has leaves");
+        let donor_end = donor.subtree_end(donor_start);
+
+        let mut nodes = self.nodes.clone();
+        nodes.splice(
+            start..end,
+            donor.nodes[donor_start..donor_end].iter().copied(),
+        );
+        Tree { nodes }
+    }
+}
+
+/// [`Tree::subtree_crossover`] applied by the engine, through
+/// [`crate::crossover::CrossoverType::Custom`].
+#[derive(Debug, Clone)]
+pub struct SubtreeCrossover {
+    pub primitives: PrimitiveSet,
+}
+
+impl CrossoverOperator for SubtreeCrossover {
+    fn crossover(&self, a: &Genome, b: &Genome, mut rng: &mut dyn RngCore) -> Genome {
+        let offspring = self.primitives.decode(a).subtree_crossover(
+            &self.primitives.decode(b),
+            a.len(),
+            &mut rng,
+        );
+        self.primitives.encode(&offspring, a.len())
+    }
+}
+
+/// [`PrimitiveSet::point_mutation`] applied by the engine, through
+/// [`crate::mutation::MutationType::Custom`].
+#[derive(Debug, Clone)]
+pub struct PointMutation {
+    pub primitives: PrimitiveSet,
+}
+
+impl MutationOperator for PointMutation {
+    fn mutate(&self, genome: &mut Genome, mutation_rate: f32, mut rng: &mut dyn RngCore) {
+        let mut tree = self.primitives.decode(genome);
+        self.primitives
+            .point_mutation(&mut tree, mutation_rate, &mut rng);
+        *genome = self.primitives.encode(&tree, genome.len());
+    }
+}
+
+/// [`PrimitiveSet::subtree_mutation`] applied by the engine, through
+/// [`crate::mutation::MutationType::Custom`].
+#[derive(Debug, Clone)]
+pub struct SubtreeMutation {
+    pub primitives: PrimitiveSet,
+    /// Maximum depth of the grown subtrees.
+    pub max_depth: usize,
+}
+
+impl MutationOperator for SubtreeMutation {
+    fn mutate(&self, genome: &mut Genome, mutation_rate: f32, mut rng: &mut dyn RngCore) {
+        let mut tree = self.primitives.decode(genome);
+        self.primitives.subtree_mutation(
+            &mut tree,
+            mutation_rate,
+            self.max_depth,
+            genome.len(),
+            &mut rng,
+        );
+        *genome = self.primitives.encode(&tree, genome.len());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use common_test::get_seeded_rng;
+    use futures::executor::block_on;
+    use rand::{distributions::Standard, Rng};
+
+    use crate::{
+        adaptation::Strategy,
+        crossover::{CrossoverOperator, CrossoverType},
+        evolution::{
+            EvolutionConfig, EvolutionEngine, GenerationRenewalConfig, GeneticRenewalParam,
+            MaxGenerations, TargetFitness, TerminationCriterion,
+        },
+        mutation::{MutationOperator, MutationType},
+        selection::SelectionType,
+        Genome,
+    };
+
+    use super::{GpError, Primitive, PrimitiveSet, SubtreeCrossover, SubtreeMutation, Tree};
+
+    const ADD: u8 = 0;
+    const MUL: u8 = 1;
+    const X: u8 = 2;
+    const ONE: u8 = 3;
+
+    fn arithmetic() -> PrimitiveSet {
+        PrimitiveSet::new(vec![
+            Primitive::function("add", 2),
+            Primitive::function("mul", 2),
+            Primitive::terminal("x"),
+            Primitive::terminal("1"),
+        ])
+        .unwrap()
+    }
+
+    fn compute(tree: &Tree, x: f32) -> f32 {
+        tree.evaluate(|primitive, arguments: Vec<f32>| match primitive {
+            ADD => arguments[0] + arguments[1],
+            MUL => arguments[0] * arguments[1],
+            X => x,
+            _ => 1.0,
+        })
+    }
+
+    fn is_complete(tree: &Tree) -> bool {
+        tree.subtree_end(0) == tree.len()
+    }
+
+    #[test]
+    fn test_primitive_set_new() {
+        assert_eq!(
+            Err(GpError::NoTerminal),
+            PrimitiveSet::new(vec![Primitive::function("add", 2)])
+        );
+        assert_eq!(
+            Err(GpError::TooManyPrimitives(257)),
+            PrimitiveSet::new(vec![Primitive::terminal("x"); 257])
+        );
+    }
+
+    #[test]
+    fn test_decode() {
+        // Given
+        let primitives = arithmetic();
+
+        // When
+        let tree = primitives.decode(&vec![ADD, MUL, X, X, ONE, 7, 7]);
+
+        // Then
+        assert_eq!(5, tree.len());
+        assert_eq!(2, tree.depth());
+        assert_eq!(5.0, compute(&tree, 2.0), "Should compute x * x + 1");
+        assert_eq!(
+            vec![ADD, MUL, X, X, ONE, 0, 0],
+            primitives.encode(&tree, 7),
+            "Should pad the genome"
+        );
+        assert_eq!(
+            3,
+            primitives.decode(&vec![ADD, ADD, ADD]).len(),
+            "Should close trees with terminals at the end of the genome"
+        );
+    }
+
+    #[test]
+    fn test_decode_random_genomes() {
+        // Given
+        let mut rng = get_seeded_rng().unwrap();
+        let primitives = arithmetic();
+
+        for _ in 0..32 {
+            let genome: Genome = (&mut rng).sample_iter(Standard).take(16).collect();
+
+            // When
+            let tree = primitives.decode(&genome);
+
+            // Then
+            assert!(tree.len() <= 16 && is_complete(&tree));
+            assert_eq!(tree, primitives.decode(&primitives.encode(&tree, 16)));
+        }
+    }
+
+    #[test]
+    fn test_grow() {
+        // Given
+        let mut rng = get_seeded_rng().unwrap();
+        let primitives = arithmetic();
+
+        for _ in 0..32 {
+            // When
+            let tree = primitives.grow(3, 9, &mut rng);
+
+            // Then
+            assert!(tree.depth() <= 3, "Should keep within the maximum depth");
+            assert!(tree.len() <= 9, "Should keep within the maximum size");
+            assert!(is_complete(&tree));
+        }
+    }
+
+    #[test]
+    fn test_subtree_crossover() {
+        // Given
+        let mut rng = get_seeded_rng().unwrap();
+        let primitives = arithmetic();
+        let operator = SubtreeCrossover {
+            primitives: primitives.clone(),
+        };
+
+        for _ in 0..32 {
+            let a = primitives.encode(&primitives.grow(4, 12, &mut rng), 12);
+            let b = primitives.encode(&primitives.grow(4, 12, &mut rng), 12);
+
+            // When
+            let offspring = operator.crossover(&a, &b, &mut rng);
+
+            // Then
+            assert_eq!(12, offspring.len());
+            let tree = primitives.decode(&offspring);
+            assert!(
+                is_complete(&tree) && offspring == primitives.encode(&tree, 12),
+                "Should fit the offspring in the genome"
+            );
+        }
+    }
+
+    #[test]
+    fn test_point_mutation() {
+        // Given
+        let mut rng = get_seeded_rng().unwrap();
+        let primitives = arithmetic();
+        let mut tree = primitives.decode(&vec![ADD, MUL, X, X, ONE]);
+
+        // When
+        primitives.point_mutation(&mut tree, 1.0, &mut rng);
+
+        // Then
+        assert_eq!(
+            vec![2, 2, 0, 0, 0],
+            tree.nodes()
+                .iter()
+                .map(|node| node.arity)
+                .collect::<Vec<_>>(),
+            "Should keep the shape of the tree"
+        );
+    }
+
+    #[test]
+    fn test_subtree_mutation() {
+        // Given
+        let mut rng = get_seeded_rng().unwrap();
+        let primitives = arithmetic();
+        let operator = SubtreeMutation {
+            primitives: primitives.clone(),
+            max_depth: 2,
+        };
+
+        for _ in 0..32 {
+            let mut genome = primitives.encode(&primitives.grow(4, 10, &mut rng), 10);
+
+            // When
+            operator.mutate(&mut genome, 1.0, &mut rng);
+
+            // Then
+            let tree = primitives.decode(&genome);
+            assert!(is_complete(&tree) && genome == primitives.encode(&tree, 10));
+        }
+    }
+
+    /// Fits x² + x + 1 over a few points.
+    struct SymbolicRegression(PrimitiveSet);
+
+    impl Strategy for SymbolicRegression {
+        type Phenotype = Tree;
+
+        fn genome_size(&self) -> usize {
+            16
+        }
+
+        fn decode(&self, genome: &Genome) -> Tree {
+            self.0.decode(genome)
+        }
+
+        fn evaluate(&self, tree: &Tree) -> f32 {
+            let error = (-4..=4)
+                .map(|x| x as f32)
+                .map(|x| (compute(tree, x) - (x * x + x + 1.0)).abs())
+                .sum::<f32>();
+            1.0 / (1.0 + error)
+        }
+    }
+
+    #[test]
+    fn test_symbolic_regression() {
+        // Given
+        let mut rng = get_seeded_rng().unwrap();
+        let primitives = arithmetic();
+        let strategy = SymbolicRegression(primitives.clone());
+        let config = EvolutionConfig {
+            population_size: 64,
+            generation_renewal_config: Some(GenerationRenewalConfig {
+                cloning: Some(GeneticRenewalParam {
+                    mutation_rate: Some(0.0),
+                    ratio: 0.2,
+                    selection_type: SelectionType::Ranking(16),
+                    ..Default::default()
+                }),
+                crossover: Some(GeneticRenewalParam {
+                    mutation_rate: Some(0.3),
+                    mutation_type: MutationType::Custom(Arc::new(SubtreeMutation {
+                        primitives: primitives.clone(),
+                        max_depth: 2,
+                    })),
+                    ratio: 0.7,
+                    selection_type: SelectionType::BiasedRoulette { exponent: 4.0 },
+                    crossover_type: CrossoverType::Custom(Arc::new(SubtreeCrossover {
+                        primitives,
+                    })),
+                    ..Default::default()
+                }),
+                random_ratio: None,
+            }),
+            max_duration: None,
+        };
+        let mut engine = EvolutionEngine::default();
+
+        // When
+        let result = block_on(engine.start(
+            &strategy,
+            &config,
+            MaxGenerations(200).or(TargetFitness(1.0)),
+            &mut rng,
+        ))
+        .unwrap();
+
+        // Then
+        assert!(
+            result.evaluations.iter().any(|e| e.fitness == 1.0),
+            "Should fit the expression, reached {:?}",
+            result
+                .evaluations
+                .iter()
+                .map(|e| e.fitness)
+                .reduce(f32::max)
+        );
+    }
+}
@@ -0,0 +1,253 @@
+//! Grammatical evolution: byte genomes select the productions of a BNF grammar, so that
+//! strategies evolve programs or expressions of any language they can describe.
+//!
+//! Grammars are written one rule per line, alternatives being separated by `|`, e.g.
+//!
+//! ```text
+//! <expr> ::= <expr> <op> <expr> | <var>
+//! <op>   ::= + | *
+//! <var>  ::= x | 1
+//! ```
+//!
+//! Lines starting with `|` continue the alternatives of the previous rule, the first rule giving
+//! the start symbol. Text outside of `<...>` is kept as is, spaces within alternatives included.
+//!
+//! Strategies derive sentences in [`crate::adaptation::Strategy::decode`], the sentence or the
+//! derivation error being their phenotype.
+
+use thiserror::Error;
+
+use crate::Genome;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum GrammarError {
+    #[error("The grammar has no rule")]
+    Empty,
+    #[error("Invalid rule at line {0}")]
+    InvalidRule(usize),
+    #[error("No rule defines <{0}>")]
+    UndefinedSymbol(String),
+    #[error("<{0}> never derives a sentence")]
+    Unproductive(String),
+    #[error("The genome ran out of codons after {0} wraps")]
+    Incomplete(usize),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Symbol {
+    Terminal(String),
+    NonTerminal(usize),
+}
+
+/// Context-free grammar mapping genomes to sentences, see [`Grammar::derive`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Grammar {
+    names: Vec<String>,
+    /// Alternatives of each non terminal, by index of its name.
+    rules: Vec<Vec<Vec<Symbol>>>,
+}
+
+impl Grammar {
+    pub fn parse(bnf: &str) -> Result<Self, GrammarError> {
+        let mut names = Vec::<String>::new();
+        let mut definitions = Vec::<Vec<&str>>::new();
+        for (line_index, line) in bnf.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+            if let Some(continuation) = line.strip_prefix('|') {
+                definitions
+                    .last_mut()
+                    .ok_or(GrammarError::InvalidRule(line_index + 1))?
+                    .extend(continuation.split('|'));
+                continue;
+            }
+            let (name, alternatives) = line
+                .split_once("::=")
+                .and_then(|(name, alternatives)| {
+                    let name = name.trim().strip_prefix('<')?.strip_suffix('>')?;
+                    Some((name, alternatives))
+                })
+                .ok_or(GrammarError::InvalidRule(line_index + 1))?;
+            names.push(name.to_owned());
+            definitions.push(alternatives.split('|').collect());
+        }
+
+        let rules = definitions
+            .into_iter()
+            .map(|alternatives| {
+                alternatives
+                    .into_iter()
+                    .map(|alternative| parse_symbols(alternative.trim(), &names))
+                    .collect()
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        if rules.is_empty() {
+            return Err(GrammarError::Empty);
+        }
+        let grammar = Grammar { names, rules };
+        grammar.check_productive()?;
+        Ok(grammar)
+    }
+
+    /// Rejects non terminals without finite derivations, which also bounds the derivations not
+    /// consuming codons.
+    fn check_productive(&self) -> Result<(), GrammarError> {
+        let mut productive = vec![false; self.rules.len()];
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for (index, alternatives) in self.rules.iter().enumerate() {
+                if !productive[index]
+                    && alternatives.iter().any(|symbols| {
+                        symbols.iter().all(|symbol| match symbol {
+                            Symbol::Terminal(_) => true,
+                            Symbol::NonTerminal(other) => productive[*other],
+                        })
+                    })
+                {
+                    productive[index] = true;
+                    changed = true;
+                }
+            }
+        }
+        match productive.iter().position(|&productive| !productive) {
+            Some(index) => Err(GrammarError::Unproductive(self.names[index].clone())),
+            None => Ok(()),
+        }
+    }
+
+    /// Derives the sentence encoded by the genome, expanding the leftmost non terminal with the
+    /// alternative given by the next codon modulo the number of alternatives.
+    ///
+    /// Rules with a single alternative do not consume codons. The genome is read again from its
+    /// start up to `max_wraps` times, the derivation failing when codons run out.
+    pub fn derive(&self, genome: &Genome, max_wraps: usize) -> Result<String, GrammarError> {
+        let mut sentence = String::new();
+        let mut codons = genome.iter().cycle().take(genome.len() * (max_wraps + 1));
+        // Symbols left to derive, the next one last
+        let mut pending = vec![Symbol::NonTerminal(0)];
+        while let Some(symbol) = pending.pop() {
+            match symbol {
+                Symbol::Terminal(text) => sentence.push_str(&text),
+                Symbol::NonTerminal(index) => {
+                    let alternatives = &self.rules[index];
+                    let choice = if alternatives.len() > 1 {
+                        let codon = codons.next().ok_or(GrammarError::Incomplete(max_wraps))?;
+                        *codon as usize % alternatives.len()
+                    } else {
+                        0
+                    };
+                    pending.extend(alternatives[choice].iter().rev().cloned());
+                }
+            }
+        }
+        Ok(sentence)
+    }
+}
+
+/// Splits an alternative into terminals and non terminals.
+fn parse_symbols(alternative: &str, names: &[String]) -> Result<Vec<Symbol>, GrammarError> {
+    let mut symbols = Vec::new();
+    let mut rest = alternative;
+    while let Some(start) = rest.find('<') {
+        let Some(length) = rest[start..].find('>') else {
+            break;
+        };
+        let name = &rest[start + 1..start + length];
+        let index = names
+            .iter()
+            .position(|other| other == name)
+            .ok_or_else(|| GrammarError::UndefinedSymbol(name.to_owned()))?;
+        if start > 0 {
+            symbols.push(Symbol::Terminal(rest[..start].to_owned()));
+        }
+        symbols.push(Symbol::NonTerminal(index));
+        rest = &rest[start + length + 1..];
+    }
+    if !rest.is_empty() {
+        symbols.push(Symbol::Terminal(rest.to_owned()));
+    }
+    Ok(symbols)
+}
+
+#[cfg(test)]
+mod tests {
+    use common_test::get_seeded_rng;
+    use rand::{distributions::Standard, Rng};
+
+    use super::{Grammar, GrammarError};
+
+    const EXPRESSIONS: &str = "
+        <expr> ::= <expr><op><expr> | <var>
+        <op>   ::= + | *
+        <var>  ::= x
+                 | 1
+    ";
+
+    #[test]
+    fn test_parse() {
+        assert!(Grammar::parse(EXPRESSIONS).is_ok());
+        assert_eq!(Err(GrammarError::Empty), Grammar::parse(""));
+        assert_eq!(
+            Err(GrammarError::InvalidRule(2)),
+            Grammar::parse("<a> ::= x\na ::= y")
+        );
+        assert_eq!(
+            Err(GrammarError::UndefinedSymbol("b".to_owned())),
+            Grammar::parse("<a> ::= <b> | x")
+        );
+        assert_eq!(
+            Err(GrammarError::Unproductive("a".to_owned())),
+            Grammar::parse("<a> ::= <a>x"),
+            "Should reject rules deriving forever"
+        );
+    }
+
+    #[test]
+    fn test_derive() {
+        // Given
+        let grammar = Grammar::parse(EXPRESSIONS).unwrap();
+
+        // When
+        // <expr> -> <expr><op><expr> -> <var><op><expr> -> x<op><expr> -> x*<expr> -> x*<var>
+        // -> x*1
+        let result = grammar.derive(&vec![0, 1, 0, 1, 3, 1], 0);
+
+        // Then
+        assert_eq!(Ok("x*1".to_owned()), result);
+        assert_eq!(
+            Ok("1".to_owned()),
+            grammar.derive(&vec![1], 1),
+            "Should wrap the genome"
+        );
+        assert_eq!(
+            Err(GrammarError::Incomplete(1)),
+            grammar.derive(&vec![0], 1),
+            "Should fail when codons run out"
+        );
+    }
+
+    #[test]
+    fn test_derive_random_genomes() {
+        // Given
+        let mut rng = get_seeded_rng().unwrap();
+        let grammar = Grammar::parse(EXPRESSIONS).unwrap();
+
+        for _ in 0..32 {
+            let genome = (&mut rng).sample_iter(Standard).take(32).collect();
+
+            // When
+            let result = grammar.derive(&genome, 2);
+
+            // Then
+            if let Ok(sentence) = result {
+                assert!(
+                    sentence.chars().all(|c| "x1+*".contains(c)),
+                    "Should only produce terminals, got {sentence}"
+                );
+            }
+        }
+    }
+}
@@ -1,15 +1,80 @@
-use std::fmt::Debug;
+use std::{collections::BTreeMap, fmt::Debug};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 
 pub mod adaptation;
+pub mod analysis;
+pub mod bits;
+pub mod clock;
+pub mod codec;
+pub mod crossover;
+pub mod diversity;
 pub mod evolution;
+#[cfg(feature = "experiment")]
+pub mod experiment;
+pub mod gp;
+pub mod grammar;
+pub mod local_search;
+#[cfg(feature = "multiobjective")]
+pub mod multiobjective;
+pub mod mutation;
+pub mod permutation;
+pub mod real;
+pub mod schema;
 pub mod selection;
+#[cfg(feature = "soak")]
+pub mod soak;
+#[cfg(feature = "tuning")]
+pub mod tuning;
 
 pub type Genome = Vec<u8>;
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Evaluation {
     pub genome: Genome,
+    /// Score given by the strategy, only meaningful once `evaluated`.
     pub fitness: f32,
+    /// Variance of the fitness over the samples of the genome, only computed when resampling,
+    /// see [`evolution::EvolutionEngineBuilder::resampling`].
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub fitness_variance: Option<f32>,
+    /// Values of the objectives of multi-objective strategies, none otherwise, see
+    /// [`adaptation::Strategy::evaluate_objectives`].
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub objectives: Vec<f32>,
+    /// Number of generations the genome survived through cloning.
+    pub age: u32,
+    /// Whether `fitness` was computed for the current genome, new and mutated genomes waiting
+    /// for the evaluation of their generation.
+    #[cfg_attr(feature = "serde", serde(default = "evaluated_by_default"))]
+    pub evaluated: bool,
+    /// Readable phenotype of the genome, see [`adaptation::Strategy::describe`].
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub phenotype: Option<String>,
+    /// Auxiliary data of the evaluation, e.g. statistics of a simulation, see
+    /// [`adaptation::Strategy::evaluate_with_metadata`].
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "BTreeMap::is_empty")
+    )]
+    pub metadata: BTreeMap<String, String>,
+}
+
+/// Evaluations serialized before the marker only held evaluated genomes.
+#[cfg(feature = "serde")]
+fn evaluated_by_default() -> bool {
+    true
 }
 
 impl From<Genome> for Evaluation {
@@ -17,6 +82,27 @@ impl From<Genome> for Evaluation {
         Self {
             genome,
             fitness: 0f32,
+            fitness_variance: None,
+            objectives: vec![],
+            age: 0,
+            evaluated: false,
+            phenotype: None,
+            metadata: BTreeMap::new(),
+        }
+    }
+}
+
+impl Evaluation {
+    /// Fitness of the genome, `None` until evaluated.
+    pub fn evaluated_fitness(&self) -> Option<f32> {
+        self.evaluated.then_some(self.fitness)
+    }
+
+    /// Returns the evaluation carried over to the next generation, one generation older.
+    pub fn survive(&self) -> Self {
+        Self {
+            age: self.age + 1,
+            ..self.clone()
         }
     }
 }
@@ -38,6 +124,8 @@ where
 
 #[cfg(test)]
 mod tests {
+    use std::collections::BTreeMap;
+
     use common_test::get_seeded_rng;
     use rand::Rng;
 
@@ -51,7 +139,54 @@ mod tests {
         assert_eq!(
             Evaluation {
                 genome,
-                fitness: 0f32
+                fitness: 0f32,
+                fitness_variance: None,
+                objectives: vec![],
+                age: 0,
+                evaluated: false,
+                phenotype: None,
+                metadata: BTreeMap::new(),
+            },
+            result,
+            "Should wait for the evaluation of the genome"
+        );
+    }
+
+    #[test]
+    fn test_evaluated_fitness() {
+        let evaluation = Evaluation {
+            fitness: 0.0,
+            evaluated: true,
+            ..Default::default()
+        };
+
+        assert_eq!(Some(0.0), evaluation.evaluated_fitness());
+        assert_eq!(
+            None,
+            Evaluation::from(vec![3]).evaluated_fitness(),
+            "Should tell a zero fitness from a missing one"
+        );
+    }
+
+    #[test]
+    fn test_survive() {
+        let evaluation = Evaluation {
+            genome: vec![3],
+            fitness: 0.5,
+            fitness_variance: None,
+            objectives: vec![],
+            age: 2,
+            evaluated: true,
+            phenotype: None,
+            metadata: BTreeMap::new(),
+        };
+
+        let result = evaluation.survive();
+
+        assert_eq!(
+            Evaluation {
+                age: 3,
+                ..evaluation
             },
             result
         );
@@ -72,5 +207,6 @@ mod tests {
         let result_states = result.iter().map(|e| e.genome.clone()).collect::<Vec<_>>();
         assert_eq!(result_states, genomes);
         assert!(result.iter().map(|e| e.fitness).all(|x| x == 0f32));
+        assert!(result.iter().map(|e| e.age).all(|x| x == 0));
     }
 }
@@ -0,0 +1,68 @@
+//! Local search refining the fittest individuals of each generation, turning the evolution into
+//! a memetic algorithm, see [`crate::evolution::EvolutionEngineBuilder::local_search`].
+
+use std::fmt::Debug;
+
+use rand::{Rng, RngCore};
+
+use crate::Genome;
+
+/// Moves of the local search around a genome, each neighbour being evaluated and kept in place of
+/// the genome when fitter, i.e. hill climbing over the neighbourhood of the search.
+pub trait LocalSearch: Debug + Send + Sync {
+    /// Changes the genome into one of its neighbours, e.g. the genome with a gene altered.
+    fn neighbour(&self, genome: &mut Genome, rng: &mut dyn RngCore);
+}
+
+/// Hill climbing over the genomes differing by one gene, given a random value.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct HillClimbing;
+
+impl LocalSearch for HillClimbing {
+    fn neighbour(&self, genome: &mut Genome, rng: &mut dyn RngCore) {
+        if genome.is_empty() {
+            return;
+        }
+        let locus = rng.gen_range(0..genome.len());
+        genome[locus] = rng.gen();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use common_test::get_seeded_rng;
+
+    use super::{HillClimbing, LocalSearch};
+
+    #[test]
+    fn test_hill_climbing_neighbour() {
+        // Given
+        let mut rng = get_seeded_rng().unwrap();
+        let genome = vec![0; 8];
+
+        // When
+        let mut result = genome.clone();
+        HillClimbing.neighbour(&mut result, &mut rng);
+
+        // Then
+        assert_eq!(genome.len(), result.len());
+        assert!(
+            genome
+                .iter()
+                .zip(result.iter())
+                .filter(|(a, b)| a != b)
+                .count()
+                <= 1,
+            "Should alter a single gene"
+        );
+
+        // Given
+        let mut empty = vec![];
+
+        // When
+        HillClimbing.neighbour(&mut empty, &mut rng);
+
+        // Then
+        assert!(empty.is_empty());
+    }
+}
@@ -0,0 +1,264 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{adaptation::Strategy, Evaluation, Genome};
+
+/// Problem optimizing several objectives at once, evolved through a [`Scalarized`] strategy.
+pub trait MultiObjectiveStrategy {
+    /// See [`Strategy::Phenotype`].
+    type Phenotype;
+
+    fn genome_size(&self) -> usize;
+
+    fn decode(&self, genome: &Genome) -> Self::Phenotype;
+
+    /// Values of the objectives of a phenotype, each within [0, 1] and maximized.
+    fn evaluate(&self, phenotype: &Self::Phenotype) -> Vec<f32>;
+
+    /// See [`Strategy::describe`].
+    fn describe(&self, _phenotype: &Self::Phenotype) -> Option<String> {
+        None
+    }
+
+    /// See [`Strategy::repair`].
+    fn repair(&self, _genome: &mut Genome) {}
+}
+
+/// Reduction of the objectives of a phenotype to the fitness driving the selection.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Scalarization {
+    /// Sum of the objectives weighted by their importance, weights summing to 1.
+    WeightedSum(Vec<f32>),
+    /// Complement of the largest weighted shortfall of the objectives, reaching the solutions
+    /// of non-convex fronts that weighted sums miss.
+    Chebyshev(Vec<f32>),
+}
+
+impl Scalarization {
+    /// Fitness of the objectives within [0, 1], objectives without weight being ignored.
+    pub fn scalarize(&self, objectives: &[f32]) -> f32 {
+        let fitness = match self {
+            Scalarization::WeightedSum(weights) => weights
+                .iter()
+                .zip(objectives)
+                .map(|(weight, objective)| weight * objective)
+                .sum(),
+            Scalarization::Chebyshev(weights) => {
+                1.0 - weights
+                    .iter()
+                    .zip(objectives)
+                    .map(|(weight, objective)| weight * (1.0 - objective))
+                    .fold(0.0, f32::max)
+            }
+        };
+        fitness.clamp(0.0, 1.0)
+    }
+}
+
+/// Strategy evolving a [`MultiObjectiveStrategy`] on the scalarization of its objectives, which
+/// values are kept in [`Evaluation::objectives`].
+pub struct Scalarized<S> {
+    strategy: S,
+    scalarization: Scalarization,
+}
+
+impl<S: MultiObjectiveStrategy> Scalarized<S> {
+    pub fn new(strategy: S, scalarization: Scalarization) -> Self {
+        Scalarized {
+            strategy,
+            scalarization,
+        }
+    }
+}
+
+impl<S: MultiObjectiveStrategy> Strategy for Scalarized<S> {
+    type Phenotype = S::Phenotype;
+
+    fn genome_size(&self) -> usize {
+        self.strategy.genome_size()
+    }
+
+    fn decode(&self, genome: &Genome) -> S::Phenotype {
+        self.strategy.decode(genome)
+    }
+
+    fn evaluate(&self, phenotype: &S::Phenotype) -> f32 {
+        self.scalarization
+            .scalarize(&self.strategy.evaluate(phenotype))
+    }
+
+    fn evaluate_objectives(&self, phenotype: &S::Phenotype) -> (f32, Vec<f32>) {
+        let objectives = self.strategy.evaluate(phenotype);
+        (self.scalarization.scalarize(&objectives), objectives)
+    }
+
+    fn describe(&self, phenotype: &S::Phenotype) -> Option<String> {
+        self.strategy.describe(phenotype)
+    }
+
+    fn repair(&self, genome: &mut Genome) {
+        self.strategy.repair(genome);
+    }
+}
+
+/// Whether `a` is at least as good as `b` on all the objectives and better on one of them.
+pub fn dominates(a: &[f32], b: &[f32]) -> bool {
+    a.len() == b.len()
+        && a.iter().zip(b).all(|(a, b)| a >= b)
+        && a.iter().zip(b).any(|(a, b)| a > b)
+}
+
+/// Evaluated individuals with objectives dominated by no other one, in population order.
+pub fn pareto_front(evaluations: &[Evaluation]) -> Vec<&Evaluation> {
+    let candidates = evaluations
+        .iter()
+        .filter(|e| e.evaluated && !e.objectives.is_empty())
+        .collect::<Vec<_>>();
+    candidates
+        .iter()
+        .filter(|e| {
+            !candidates
+                .iter()
+                .any(|other| dominates(&other.objectives, &e.objectives))
+        })
+        .copied()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use common_test::get_seeded_rng;
+    use futures::executor::block_on;
+
+    use crate::{
+        adaptation::Strategy,
+        evolution::{EvolutionConfig, EvolutionEngine, EvolutionError, MaxGenerations},
+        Evaluation, Genome,
+    };
+
+    use super::{dominates, pareto_front, MultiObjectiveStrategy, Scalarization, Scalarized};
+
+    struct Genes;
+
+    impl MultiObjectiveStrategy for Genes {
+        type Phenotype = Vec<f32>;
+
+        fn genome_size(&self) -> usize {
+            2
+        }
+
+        fn decode(&self, genome: &Genome) -> Vec<f32> {
+            genome.iter().map(|&gene| gene as f32 / 255.0).collect()
+        }
+
+        fn evaluate(&self, objectives: &Vec<f32>) -> Vec<f32> {
+            objectives.clone()
+        }
+    }
+
+    fn evaluation(objectives: Vec<f32>) -> Evaluation {
+        Evaluation {
+            objectives,
+            evaluated: true,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_scalarize() {
+        let objectives = [0.2, 0.8];
+
+        assert!(
+            (Scalarization::WeightedSum(vec![0.5, 0.5]).scalarize(&objectives) - 0.5).abs() < 1e-6
+        );
+        assert!(
+            (Scalarization::Chebyshev(vec![0.5, 0.5]).scalarize(&objectives) - 0.6).abs() < 1e-6,
+            "Should penalize the largest shortfall"
+        );
+        assert_eq!(
+            1.0,
+            Scalarization::WeightedSum(vec![2.0, 2.0]).scalarize(&objectives),
+            "Should keep the fitness standardized"
+        );
+    }
+
+    #[test]
+    fn test_scalarized_evaluate_objectives() {
+        // Given
+        let strategy = Scalarized::new(Genes, Scalarization::WeightedSum(vec![1.0, 0.0]));
+
+        // When
+        let (fitness, objectives) = strategy.evaluate_objectives(&strategy.decode(&vec![255, 0]));
+
+        // Then
+        assert_eq!(1.0, fitness);
+        assert_eq!(vec![1.0, 0.0], objectives);
+    }
+
+    #[test]
+    fn test_scalarized_start() -> Result<(), EvolutionError> {
+        // Given
+        let strategy = Scalarized::new(Genes, Scalarization::Chebyshev(vec![0.5, 0.5]));
+        let config = EvolutionConfig {
+            population_size: 10,
+            generation_renewal_config: None,
+            max_duration: None,
+        };
+
+        // When
+        let result = block_on(EvolutionEngine::default().start(
+            &strategy,
+            &config,
+            MaxGenerations(2),
+            &mut get_seeded_rng().unwrap(),
+        ))?;
+
+        // Then
+        assert!(result
+            .evaluations
+            .iter()
+            .all(|e| e.objectives == Genes.decode(&e.genome)));
+        let front = result.pareto_front();
+        assert!(!front.is_empty());
+        assert!(front.iter().all(|e| result
+            .evaluations
+            .iter()
+            .all(|other| !dominates(&other.objectives, &e.objectives))));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_dominates() {
+        assert!(dominates(&[0.5, 0.5], &[0.5, 0.4]));
+        assert!(!dominates(&[0.5, 0.5], &[0.5, 0.5]), "Should be strict");
+        assert!(!dominates(&[0.9, 0.1], &[0.1, 0.9]));
+    }
+
+    #[test]
+    fn test_pareto_front() {
+        // Given
+        let evaluations = vec![
+            evaluation(vec![0.9, 0.1]),
+            evaluation(vec![0.5, 0.5]),
+            evaluation(vec![0.4, 0.4]),
+            evaluation(vec![0.1, 0.9]),
+            Evaluation {
+                evaluated: false,
+                ..evaluation(vec![1.0, 1.0])
+            },
+            Evaluation::default(),
+        ];
+
+        // When
+        let result = pareto_front(&evaluations);
+
+        // Then
+        assert_eq!(
+            vec![&evaluations[0], &evaluations[1], &evaluations[3]],
+            result,
+            "Should only keep non-dominated evaluated individuals"
+        );
+    }
+}
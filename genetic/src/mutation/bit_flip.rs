@@ -0,0 +1,51 @@
+use rand::Rng;
+
+use crate::Genome;
+
+/// Flips each bit of the genome with probability `mutation_rate`.
+pub fn bit_flip_mutation(genome: &mut Genome, mutation_rate: f32, rng: &mut impl Rng) {
+    for part in genome.iter_mut() {
+        for bit in 0..u8::BITS {
+            if rng.gen::<f32>() < mutation_rate {
+                *part ^= 1 << bit;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use common_test::get_seeded_rng;
+
+    use super::bit_flip_mutation;
+
+    #[test]
+    fn test_bit_flip_mutation() {
+        // Given
+        let mut rng = get_seeded_rng().unwrap();
+        let mut genome = vec![0; 1000];
+
+        // When
+        bit_flip_mutation(&mut genome, 0.1, &mut rng);
+
+        // Then
+        let flipped_bits = genome.iter().map(|g| g.count_ones()).sum::<u32>();
+        assert!(
+            (650..950).contains(&flipped_bits),
+            "Should flip bits at the mutation rate"
+        );
+    }
+
+    #[test]
+    fn test_bit_flip_mutation_with_full_rate() {
+        // Given
+        let mut rng = get_seeded_rng().unwrap();
+        let mut genome = vec![0b1010_0101, 0];
+
+        // When
+        bit_flip_mutation(&mut genome, 1.0, &mut rng);
+
+        // Then
+        assert_eq!(vec![0b0101_1010, 0xff], genome, "Should flip every bit");
+    }
+}
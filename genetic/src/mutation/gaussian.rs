@@ -0,0 +1,108 @@
+use std::f32::consts::TAU;
+
+use rand::Rng;
+
+use crate::{real::REAL_GENE_SIZE, Genome};
+
+/// Adds, at the mutation rate, a normally distributed noise of deviation `sigma` to each gene of
+/// a real-valued genome, see [`crate::real`]. Mutated genes are clamped within `bounds` when set.
+///
+/// Trailing bytes not forming a whole gene are left untouched.
+pub fn gaussian_mutation(
+    genome: &mut Genome,
+    mutation_rate: f32,
+    sigma: f32,
+    bounds: Option<(f32, f32)>,
+    rng: &mut impl Rng,
+) {
+    for bytes in genome.chunks_exact_mut(REAL_GENE_SIZE) {
+        if rng.gen::<f32>() < mutation_rate {
+            let gene =
+                f32::from_le_bytes((&*bytes).try_into().expect("Chunks should be gene sized"));
+            let mut mutated = gene + sigma * standard_normal(rng);
+            if let Some((min, max)) = bounds {
+                mutated = mutated.max(min).min(max);
+            }
+            bytes.copy_from_slice(&mutated.to_le_bytes());
+        }
+    }
+}
+
+/// Draws from the standard normal distribution with the Box-Muller transform.
+pub(crate) fn standard_normal(rng: &mut impl Rng) -> f32 {
+    // Excludes 0 from the first draw to keep the logarithm finite
+    let first = 1.0 - rng.gen::<f32>();
+    let second = rng.gen::<f32>();
+    (-2.0 * first.ln()).sqrt() * (TAU * second).cos()
+}
+
+#[cfg(test)]
+mod tests {
+    use common_test::get_seeded_rng;
+
+    use crate::real::{decode_reals, encode_reals};
+
+    use super::{gaussian_mutation, standard_normal};
+
+    #[test]
+    fn test_gaussian_mutation() {
+        // Given
+        let mut rng = get_seeded_rng().unwrap();
+        let mut genome = encode_reals(&[0.0; 1000]);
+        genome.push(7);
+
+        // When
+        gaussian_mutation(&mut genome, 1.0, 2.0, None, &mut rng);
+
+        // Then
+        assert_eq!(7, genome[4000], "Should keep trailing bytes");
+        let reals = decode_reals(&genome);
+        let mean = reals.iter().sum::<f32>() / 1000.0;
+        let deviation = (reals.iter().map(|r| (r - mean).powi(2)).sum::<f32>() / 1000.0).sqrt();
+        assert!(mean.abs() < 0.2, "Should center the noise on the gene");
+        assert!(
+            (deviation - 2.0).abs() < 0.2,
+            "Should draw noise of deviation sigma"
+        );
+    }
+
+    #[test]
+    fn test_gaussian_mutation_with_bounds() {
+        // Given
+        let mut rng = get_seeded_rng().unwrap();
+        let mut genome = encode_reals(&[0.0; 100]);
+
+        // When
+        gaussian_mutation(&mut genome, 1.0, 10.0, Some((-1.0, 1.0)), &mut rng);
+
+        // Then
+        assert!(
+            decode_reals(&genome)
+                .iter()
+                .all(|real| (-1.0..=1.0).contains(real)),
+            "Should clamp mutated genes within bounds"
+        );
+    }
+
+    #[test]
+    fn test_gaussian_mutation_with_zero_rate() {
+        // Given
+        let mut rng = get_seeded_rng().unwrap();
+        let mut genome = encode_reals(&[1.5, -2.5]);
+
+        // When
+        gaussian_mutation(&mut genome, 0.0, 1.0, None, &mut rng);
+
+        // Then
+        assert_eq!(vec![1.5, -2.5], decode_reals(&genome));
+    }
+
+    #[test]
+    fn test_standard_normal() {
+        // Given
+        let mut rng = get_seeded_rng().unwrap();
+
+        // Then
+        assert!((0..1000).all(|_| standard_normal(&mut rng).is_finite()));
+    }
+}
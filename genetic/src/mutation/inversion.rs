@@ -0,0 +1,63 @@
+use rand::Rng;
+
+use crate::Genome;
+
+/// Reverses, with probability `mutation_rate`, the genes between two random loci, which keeps
+/// the adjacency of most genes of ordering encodings.
+pub fn inversion_mutation(genome: &mut Genome, mutation_rate: f32, rng: &mut impl Rng) {
+    if rng.gen::<f32>() < mutation_rate {
+        let first_point = rng.gen_range(0..=genome.len());
+        let second_point = rng.gen_range(0..=genome.len());
+        let (start, end) = if first_point <= second_point {
+            (first_point, second_point)
+        } else {
+            (second_point, first_point)
+        };
+        genome[start..end].reverse();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use common_test::get_seeded_rng;
+
+    use super::inversion_mutation;
+
+    #[test]
+    fn test_inversion_mutation() {
+        // Given
+        let mut rng = get_seeded_rng().unwrap();
+        let original = (0..32).collect::<Vec<u8>>();
+        let mut genome = original.clone();
+
+        // When
+        inversion_mutation(&mut genome, 1.0, &mut rng);
+
+        // Then
+        let start = genome.iter().zip(&original).position(|(a, b)| a != b);
+        let end = genome.iter().zip(&original).rposition(|(a, b)| a != b);
+        if let (Some(start), Some(end)) = (start, end) {
+            assert!(
+                genome[start..=end]
+                    .iter()
+                    .eq(original[start..=end].iter().rev()),
+                "Should reverse a single segment"
+            );
+        }
+        genome.sort_unstable();
+        assert_eq!(original, genome, "Should keep the genes of the genome");
+    }
+
+    #[test]
+    fn test_inversion_mutation_with_zero_rate() {
+        // Given
+        let mut rng = get_seeded_rng().unwrap();
+        let mut genome = (0..32).collect::<Vec<u8>>();
+
+        // When
+        inversion_mutation(&mut genome, 0.0, &mut rng);
+
+        // Then
+        assert_eq!((0..32).collect::<Vec<u8>>(), genome);
+    }
+}
@@ -0,0 +1,392 @@
+mod bit_flip;
+mod gaussian;
+mod inversion;
+mod per_gene;
+mod random;
+mod scramble;
+mod self_adaptive;
+mod swap;
+
+use std::{fmt::Debug, sync::Arc};
+
+use bit_flip::bit_flip_mutation;
+use gaussian::gaussian_mutation;
+pub(crate) use gaussian::standard_normal;
+use inversion::inversion_mutation;
+use per_gene::{masked_mutation, per_gene_mutation};
+use rand::{Rng, RngCore};
+use random::random_mutation;
+use scramble::scramble_mutation;
+use self_adaptive::self_adaptive_mutation;
+pub use self_adaptive::{
+    append_mutation_rate, decode_mutation_rate, MAX_MUTATION_RATE, MIN_MUTATION_RATE,
+    MUTATION_RATE_GENE_SIZE,
+};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use swap::swap_mutation;
+
+use crate::Genome;
+
+/// A mutation scheme that can be plugged into [`MutationType::Custom`], e.g. to keep genes within
+/// the values allowed by the domain.
+pub trait MutationOperator: Debug + Send + Sync {
+    fn mutate(&self, genome: &mut Genome, mutation_rate: f32, rng: &mut dyn RngCore);
+}
+
+/// Operator altering genomes of the renewed population.
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum MutationType {
+    /// Each gene replaced with a random value at the mutation rate.
+    #[default]
+    Random,
+    /// Each bit flipped at the mutation rate, for a finer-grained search of bitstring-encoded
+    /// problems.
+    BitFlip,
+    /// Each gene swapped at the mutation rate with another one, keeping the genes of permutation
+    /// and fixed-multiset encodings.
+    Swap,
+    /// Genes between two random loci reversed, applied to a genome at the mutation rate: the
+    /// classic operator of ordering problems.
+    Inversion,
+    /// Genes between two random loci shuffled, applied to a genome at the mutation rate.
+    Scramble,
+    /// Normally distributed noise of deviation `sigma` added to each real-valued gene at the
+    /// mutation rate, see [`crate::real`]. Mutated genes are clamped within `bounds` when set.
+    Gaussian {
+        sigma: f32,
+        bounds: Option<(f32, f32)>,
+    },
+    /// Evolution strategies style self-adaptation: each genome carries its own mutation rate in
+    /// [`MUTATION_RATE_GENE_SIZE`] tail bytes reserved by the engine, perturbed by a log-normal
+    /// factor of deviation `tau` before mutating the rest of the genome with `mutation`.
+    ///
+    /// The pool mutation rate only seeds the rates of new genomes.
+    SelfAdaptive {
+        tau: f32,
+        mutation: Box<MutationType>,
+    },
+    /// Each gene mutated at its own rate of `rates`, e.g. to mutate some regions of the genome
+    /// more aggressively than others, genes beyond `rates` using the pool mutation rate.
+    ///
+    /// Genes sharing a rate are mutated together with `mutation`, so that positional operators
+    /// only move genes within their region. Genes of real-valued genomes should share the rate
+    /// of their [`crate::real::REAL_GENE_SIZE`] bytes.
+    PerGene {
+        rates: Vec<f32>,
+        mutation: Box<MutationType>,
+    },
+    /// Genes flagged in `frozen` kept as is, e.g. when they encode a structure that must stay
+    /// stable, the others being mutated together with `mutation` at the pool mutation rate.
+    Masked {
+        frozen: Vec<bool>,
+        mutation: Box<MutationType>,
+    },
+    #[cfg_attr(feature = "serde", serde(skip))]
+    Custom(Arc<dyn MutationOperator>),
+}
+
+impl PartialEq for MutationType {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Random, Self::Random)
+            | (Self::BitFlip, Self::BitFlip)
+            | (Self::Swap, Self::Swap)
+            | (Self::Inversion, Self::Inversion)
+            | (Self::Scramble, Self::Scramble) => true,
+            (
+                Self::Gaussian { sigma, bounds },
+                Self::Gaussian {
+                    sigma: other_sigma,
+                    bounds: other_bounds,
+                },
+            ) => sigma == other_sigma && bounds == other_bounds,
+            (
+                Self::SelfAdaptive { tau, mutation },
+                Self::SelfAdaptive {
+                    tau: other_tau,
+                    mutation: other_mutation,
+                },
+            ) => tau == other_tau && mutation == other_mutation,
+            (
+                Self::PerGene { rates, mutation },
+                Self::PerGene {
+                    rates: other_rates,
+                    mutation: other_mutation,
+                },
+            ) => rates == other_rates && mutation == other_mutation,
+            (
+                Self::Masked { frozen, mutation },
+                Self::Masked {
+                    frozen: other_frozen,
+                    mutation: other_mutation,
+                },
+            ) => frozen == other_frozen && mutation == other_mutation,
+            (Self::Custom(a), Self::Custom(b)) => Arc::ptr_eq(a, b),
+            _ => false,
+        }
+    }
+}
+
+impl MutationType {
+    /// Number of bytes the operator needs at the tail of genomes, not seen by strategies.
+    pub fn reserved_size(&self) -> usize {
+        match self {
+            MutationType::SelfAdaptive { .. } => MUTATION_RATE_GENE_SIZE,
+            _ => 0,
+        }
+    }
+}
+
+pub fn mutate(
+    genome: &mut Genome,
+    mutation_rate: f32,
+    mutation_type: &MutationType,
+    rng: &mut impl Rng,
+) {
+    match mutation_type {
+        MutationType::Random => random_mutation(genome, mutation_rate, rng),
+        MutationType::BitFlip => bit_flip_mutation(genome, mutation_rate, rng),
+        MutationType::Swap => swap_mutation(genome, mutation_rate, rng),
+        MutationType::Inversion => inversion_mutation(genome, mutation_rate, rng),
+        MutationType::Scramble => scramble_mutation(genome, mutation_rate, rng),
+        MutationType::Gaussian { sigma, bounds } => {
+            gaussian_mutation(genome, mutation_rate, *sigma, *bounds, rng)
+        }
+        MutationType::SelfAdaptive { tau, mutation } => {
+            self_adaptive_mutation(genome, mutation_rate, *tau, mutation, rng)
+        }
+        MutationType::PerGene { rates, mutation } => {
+            per_gene_mutation(genome, mutation_rate, rates, mutation, rng)
+        }
+        MutationType::Masked { frozen, mutation } => {
+            masked_mutation(genome, mutation_rate, frozen, mutation, rng)
+        }
+        MutationType::Custom(operator) => operator.mutate(genome, mutation_rate, rng),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use common_test::get_seeded_rng;
+    use rand::{Rng, RngCore};
+
+    use crate::Genome;
+
+    use super::{
+        bit_flip::bit_flip_mutation, gaussian::gaussian_mutation, inversion::inversion_mutation,
+        mutate, random::random_mutation, scramble::scramble_mutation,
+        self_adaptive::self_adaptive_mutation, swap::swap_mutation, MutationOperator, MutationType,
+    };
+
+    /// Replaces genes at the mutation rate with values of a 4 letters alphabet.
+    #[derive(Debug)]
+    struct Nucleotide;
+
+    impl MutationOperator for Nucleotide {
+        fn mutate(&self, genome: &mut Genome, mutation_rate: f32, rng: &mut dyn RngCore) {
+            for gene in genome.iter_mut() {
+                if rng.gen::<f32>() < mutation_rate {
+                    *gene = b"ACGT"[rng.gen_range(0..4)];
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_mutate_custom() {
+        // Given
+        let mutation_type = MutationType::Custom(Arc::new(Nucleotide));
+        let mut genome = b"AAAAAAAAAAAAAAAA".to_vec();
+
+        // When
+        mutate(
+            &mut genome,
+            1.0,
+            &mutation_type,
+            &mut get_seeded_rng().unwrap(),
+        );
+
+        // Then
+        assert!(
+            genome.iter().all(|gene| b"ACGT".contains(gene)),
+            "Should use the custom operator to mutate"
+        );
+        assert_ne!(b"AAAAAAAAAAAAAAAA".to_vec(), genome);
+    }
+
+    #[test]
+    fn test_mutation_type_eq() {
+        let operator: Arc<dyn MutationOperator> = Arc::new(Nucleotide);
+
+        assert_eq!(MutationType::BitFlip, MutationType::BitFlip);
+        assert_ne!(
+            MutationType::Gaussian {
+                sigma: 0.1,
+                bounds: None
+            },
+            MutationType::Gaussian {
+                sigma: 0.2,
+                bounds: None
+            }
+        );
+        assert_eq!(
+            MutationType::Custom(operator.clone()),
+            MutationType::Custom(operator),
+            "Should compare custom operators by identity"
+        );
+        assert_ne!(
+            MutationType::Custom(Arc::new(Nucleotide)),
+            MutationType::Custom(Arc::new(Nucleotide))
+        );
+    }
+
+    #[test]
+    fn test_mutate() {
+        // Given
+        let genome = (0..16).collect::<Vec<u8>>();
+
+        // When
+        let mut result = genome.clone();
+        mutate(
+            &mut result,
+            0.5,
+            &MutationType::Random,
+            &mut get_seeded_rng().unwrap(),
+        );
+
+        // Then
+        let mut expected = genome.clone();
+        random_mutation(&mut expected, 0.5, &mut get_seeded_rng().unwrap());
+        assert_eq!(
+            expected, result,
+            "Should use random_mutation to match mutation_type"
+        );
+
+        // When
+        let mut result = genome.clone();
+        mutate(
+            &mut result,
+            0.5,
+            &MutationType::BitFlip,
+            &mut get_seeded_rng().unwrap(),
+        );
+
+        // Then
+        let mut expected = genome.clone();
+        bit_flip_mutation(&mut expected, 0.5, &mut get_seeded_rng().unwrap());
+        assert_eq!(
+            expected, result,
+            "Should use bit_flip_mutation to match mutation_type"
+        );
+
+        // When
+        let mut result = genome.clone();
+        mutate(
+            &mut result,
+            0.5,
+            &MutationType::Swap,
+            &mut get_seeded_rng().unwrap(),
+        );
+
+        // Then
+        let mut expected = genome.clone();
+        swap_mutation(&mut expected, 0.5, &mut get_seeded_rng().unwrap());
+        assert_eq!(
+            expected, result,
+            "Should use swap_mutation to match mutation_type"
+        );
+
+        // When
+        let mut result = genome.clone();
+        mutate(
+            &mut result,
+            0.5,
+            &MutationType::Inversion,
+            &mut get_seeded_rng().unwrap(),
+        );
+
+        // Then
+        let mut expected = genome.clone();
+        inversion_mutation(&mut expected, 0.5, &mut get_seeded_rng().unwrap());
+        assert_eq!(
+            expected, result,
+            "Should use inversion_mutation to match mutation_type"
+        );
+
+        // When
+        let mut result = genome.clone();
+        mutate(
+            &mut result,
+            0.5,
+            &MutationType::Scramble,
+            &mut get_seeded_rng().unwrap(),
+        );
+
+        // Then
+        let mut expected = genome.clone();
+        scramble_mutation(&mut expected, 0.5, &mut get_seeded_rng().unwrap());
+        assert_eq!(
+            expected, result,
+            "Should use scramble_mutation to match mutation_type"
+        );
+
+        // When
+        let mut result = genome.clone();
+        mutate(
+            &mut result,
+            0.5,
+            &MutationType::Gaussian {
+                sigma: 0.1,
+                bounds: Some((0.0, 1.0)),
+            },
+            &mut get_seeded_rng().unwrap(),
+        );
+
+        // Then
+        let mut expected = genome.clone();
+        gaussian_mutation(
+            &mut expected,
+            0.5,
+            0.1,
+            Some((0.0, 1.0)),
+            &mut get_seeded_rng().unwrap(),
+        );
+        assert_eq!(
+            expected, result,
+            "Should use gaussian_mutation to match mutation_type"
+        );
+
+        // When
+        let mutation_type = MutationType::SelfAdaptive {
+            tau: 0.2,
+            mutation: Box::new(MutationType::Swap),
+        };
+        let mut result = genome.clone();
+        mutate(
+            &mut result,
+            0.5,
+            &mutation_type,
+            &mut get_seeded_rng().unwrap(),
+        );
+
+        // Then
+        let mut expected = genome;
+        self_adaptive_mutation(
+            &mut expected,
+            0.5,
+            0.2,
+            &MutationType::Swap,
+            &mut get_seeded_rng().unwrap(),
+        );
+        assert_eq!(
+            expected, result,
+            "Should use self_adaptive_mutation to match mutation_type"
+        );
+        assert_eq!(4, mutation_type.reserved_size());
+        assert_eq!(0, MutationType::Swap.reserved_size());
+    }
+}
@@ -0,0 +1,121 @@
+use rand::Rng;
+
+use crate::Genome;
+
+use super::{mutate, MutationType};
+
+/// Mutates the genes of each rate of `rates` together with `mutation_type` at that rate, genes
+/// beyond `rates` using `default_rate`.
+pub fn per_gene_mutation(
+    genome: &mut Genome,
+    default_rate: f32,
+    rates: &[f32],
+    mutation_type: &MutationType,
+    rng: &mut impl Rng,
+) {
+    let mut regions: Vec<(f32, Vec<usize>)> = vec![];
+    for locus in 0..genome.len() {
+        let rate = rates.get(locus).copied().unwrap_or(default_rate);
+        match regions
+            .iter_mut()
+            .find(|(r, _)| r.to_bits() == rate.to_bits())
+        {
+            Some((_, loci)) => loci.push(locus),
+            None => regions.push((rate, vec![locus])),
+        }
+    }
+
+    for (rate, loci) in regions {
+        if rate > 0.0 {
+            mutate_loci(genome, &loci, rate, mutation_type, rng);
+        }
+    }
+}
+
+/// Mutates the genes not flagged in `frozen` together with `mutation_type`, genes beyond
+/// `frozen` being mutable.
+pub fn masked_mutation(
+    genome: &mut Genome,
+    mutation_rate: f32,
+    frozen: &[bool],
+    mutation_type: &MutationType,
+    rng: &mut impl Rng,
+) {
+    let loci = (0..genome.len())
+        .filter(|&locus| !frozen.get(locus).copied().unwrap_or(false))
+        .collect::<Vec<_>>();
+    mutate_loci(genome, &loci, mutation_rate, mutation_type, rng);
+}
+
+/// Gathers the genes at `loci` into a genome of their own so that positional operators, e.g.
+/// [`MutationType::Swap`], only move genes between these loci.
+fn mutate_loci(
+    genome: &mut Genome,
+    loci: &[usize],
+    mutation_rate: f32,
+    mutation_type: &MutationType,
+    rng: &mut impl Rng,
+) {
+    let mut region = loci.iter().map(|&locus| genome[locus]).collect();
+    mutate(&mut region, mutation_rate, mutation_type, rng);
+    for (&locus, gene) in loci.iter().zip(region) {
+        genome[locus] = gene;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use common_test::get_seeded_rng;
+
+    use crate::mutation::MutationType;
+
+    use super::{masked_mutation, per_gene_mutation};
+
+    #[test]
+    fn test_per_gene_mutation() {
+        // Given
+        let mut rng = get_seeded_rng().unwrap();
+        let mut genome = vec![0; 64];
+        let rates = [vec![0.0; 16], vec![1.0; 16]].concat();
+
+        // When
+        per_gene_mutation(&mut genome, 1.0, &rates, &MutationType::BitFlip, &mut rng);
+
+        // Then
+        assert_eq!(
+            vec![0; 16],
+            genome[..16],
+            "Should not mutate at a zero rate"
+        );
+        assert!(
+            genome[16..].iter().all(|&gene| gene == 0xff),
+            "Should mutate genes at their own rate, or the default one beyond the rates"
+        );
+    }
+
+    #[test]
+    fn test_masked_mutation() {
+        // Given
+        let mut rng = get_seeded_rng().unwrap();
+        let mut genome = (0..32).collect::<Vec<u8>>();
+        let frozen = (0..32).map(|locus| locus % 2 == 0).collect::<Vec<_>>();
+
+        // When
+        masked_mutation(&mut genome, 1.0, &frozen, &MutationType::Swap, &mut rng);
+
+        // Then
+        assert!(
+            genome
+                .iter()
+                .step_by(2)
+                .zip((0..32).step_by(2))
+                .all(|(&gene, locus)| gene == locus),
+            "Should not mutate frozen genes"
+        );
+        assert!(
+            genome.iter().skip(1).step_by(2).all(|gene| gene % 2 == 1),
+            "Should only swap genes between mutable loci"
+        );
+        assert_ne!((0..32).collect::<Vec<u8>>(), genome);
+    }
+}
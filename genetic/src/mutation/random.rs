@@ -0,0 +1,41 @@
+use rand::Rng;
+
+use crate::Genome;
+
+/// Replaces each gene with a random value with probability `mutation_rate`.
+pub fn random_mutation(genome: &mut Genome, mutation_rate: f32, rng: &mut impl Rng) {
+    for part in genome.iter_mut() {
+        if rng.gen::<f32>() < mutation_rate {
+            *part = rng.gen();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use common_test::get_seeded_rng;
+
+    use super::random_mutation;
+
+    #[test]
+    fn test_random_mutation() {
+        // Given
+        let mut rng = get_seeded_rng().unwrap();
+        let mut genome = vec![0; 1000];
+
+        // When
+        random_mutation(&mut genome, 0.0, &mut rng);
+
+        // Then
+        assert_eq!(vec![0; 1000], genome, "Should not mutate at a zero rate");
+
+        // When
+        random_mutation(&mut genome, 1.0, &mut rng);
+
+        // Then
+        assert!(
+            genome.iter().filter(|&&g| g != 0).count() > 900,
+            "Should replace every gene at a full rate"
+        );
+    }
+}
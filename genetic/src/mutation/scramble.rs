@@ -0,0 +1,52 @@
+use rand::{seq::SliceRandom, Rng};
+
+use crate::Genome;
+
+/// Shuffles, with probability `mutation_rate`, the genes between two random loci.
+pub fn scramble_mutation(genome: &mut Genome, mutation_rate: f32, rng: &mut impl Rng) {
+    if rng.gen::<f32>() < mutation_rate {
+        let first_point = rng.gen_range(0..=genome.len());
+        let second_point = rng.gen_range(0..=genome.len());
+        let (start, end) = if first_point <= second_point {
+            (first_point, second_point)
+        } else {
+            (second_point, first_point)
+        };
+        genome[start..end].shuffle(rng);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use common_test::get_seeded_rng;
+
+    use super::scramble_mutation;
+
+    #[test]
+    fn test_scramble_mutation() {
+        // Given
+        let mut rng = get_seeded_rng().unwrap();
+        let original = (0..32).collect::<Vec<u8>>();
+        let mut genome = original.clone();
+
+        // When
+        scramble_mutation(&mut genome, 1.0, &mut rng);
+
+        // Then
+        genome.sort_unstable();
+        assert_eq!(original, genome, "Should keep the genes of the genome");
+    }
+
+    #[test]
+    fn test_scramble_mutation_with_zero_rate() {
+        // Given
+        let mut rng = get_seeded_rng().unwrap();
+        let mut genome = (0..32).collect::<Vec<u8>>();
+
+        // When
+        scramble_mutation(&mut genome, 0.0, &mut rng);
+
+        // Then
+        assert_eq!((0..32).collect::<Vec<u8>>(), genome);
+    }
+}
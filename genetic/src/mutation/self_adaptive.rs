@@ -0,0 +1,128 @@
+use rand::Rng;
+
+use crate::{real::REAL_GENE_SIZE, Genome};
+
+use super::{gaussian::standard_normal, mutate, MutationType};
+
+/// Number of bytes reserved at the tail of genomes for their own mutation rate, see
+/// [`MutationType::SelfAdaptive`].
+pub const MUTATION_RATE_GENE_SIZE: usize = REAL_GENE_SIZE;
+/// Lowest self-adapted mutation rate, keeping genomes from freezing.
+pub const MIN_MUTATION_RATE: f32 = 1e-4;
+/// Highest self-adapted mutation rate, keeping offsprings related to their parents.
+pub const MAX_MUTATION_RATE: f32 = 0.5;
+
+/// Reads the mutation rate encoded at the tail of the genome, `None` when missing or not a
+/// rate, e.g. after a crossover cut through the tail.
+pub fn decode_mutation_rate(genome: &Genome) -> Option<f32> {
+    let start = genome.len().checked_sub(MUTATION_RATE_GENE_SIZE)?;
+    let rate = f32::from_le_bytes(genome[start..].try_into().ok()?);
+    (MIN_MUTATION_RATE..=MAX_MUTATION_RATE)
+        .contains(&rate)
+        .then_some(rate)
+}
+
+/// Appends the tail holding the mutation rate of the genome.
+pub fn append_mutation_rate(genome: &mut Genome, mutation_rate: f32) {
+    genome.extend(
+        mutation_rate
+            .clamp(MIN_MUTATION_RATE, MAX_MUTATION_RATE)
+            .to_le_bytes(),
+    );
+}
+
+/// Perturbs the mutation rate encoded at the tail of the genome by a log-normal factor of
+/// deviation `tau`, then mutates the rest of the genome with `mutation_type` at the new rate.
+///
+/// Genomes without a valid rate start from `default_rate`.
+pub fn self_adaptive_mutation(
+    genome: &mut Genome,
+    default_rate: f32,
+    tau: f32,
+    mutation_type: &MutationType,
+    rng: &mut impl Rng,
+) {
+    let Some(body_size) = genome.len().checked_sub(MUTATION_RATE_GENE_SIZE) else {
+        return;
+    };
+    let rate = decode_mutation_rate(genome).unwrap_or(default_rate);
+    let rate = rate * (tau * standard_normal(rng)).exp();
+
+    genome.truncate(body_size);
+    let rate = rate.clamp(MIN_MUTATION_RATE, MAX_MUTATION_RATE);
+    mutate(genome, rate, mutation_type, rng);
+    append_mutation_rate(genome, rate);
+}
+
+#[cfg(test)]
+mod tests {
+    use common_test::get_seeded_rng;
+
+    use crate::mutation::MutationType;
+
+    use super::{
+        append_mutation_rate, decode_mutation_rate, self_adaptive_mutation, MAX_MUTATION_RATE,
+    };
+
+    #[test]
+    fn test_decode_mutation_rate() {
+        // Given
+        let mut genome = vec![1, 2];
+        append_mutation_rate(&mut genome, 0.05);
+
+        // Then
+        assert_eq!(6, genome.len());
+        assert_eq!(Some(0.05), decode_mutation_rate(&genome));
+        assert_eq!(None, decode_mutation_rate(&vec![1, 2]));
+        assert_eq!(
+            None,
+            decode_mutation_rate(&2.0f32.to_le_bytes().to_vec()),
+            "Should reject values out of the rate bounds"
+        );
+    }
+
+    #[test]
+    fn test_self_adaptive_mutation() {
+        // Given
+        let mut rng = get_seeded_rng().unwrap();
+        let mut genome = vec![0; 64];
+        append_mutation_rate(&mut genome, 0.1);
+
+        // When
+        self_adaptive_mutation(&mut genome, 0.01, 0.5, &MutationType::BitFlip, &mut rng);
+
+        // Then
+        assert_eq!(68, genome.len(), "Should keep the rate tail");
+        let rate = decode_mutation_rate(&genome).unwrap();
+        assert_ne!(0.1, rate, "Should evolve the mutation rate");
+        assert!(
+            genome[..64].iter().any(|&gene| gene != 0),
+            "Should mutate the rest of the genome"
+        );
+    }
+
+    #[test]
+    fn test_self_adaptive_mutation_should_fall_back_to_default_rate() {
+        // Given
+        let mut rng = get_seeded_rng().unwrap();
+        let mut genome = vec![0; 8];
+        genome.extend(f32::NAN.to_le_bytes());
+
+        // When
+        self_adaptive_mutation(&mut genome, 0.01, 0.0, &MutationType::Random, &mut rng);
+
+        // Then
+        assert_eq!(Some(0.01), decode_mutation_rate(&genome));
+
+        // When
+        let mut genome = vec![0; 8];
+        append_mutation_rate(&mut genome, 1.0);
+
+        // Then
+        assert_eq!(
+            Some(MAX_MUTATION_RATE),
+            decode_mutation_rate(&genome),
+            "Should bound encoded rates"
+        );
+    }
+}
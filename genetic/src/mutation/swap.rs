@@ -0,0 +1,57 @@
+use rand::Rng;
+
+use crate::Genome;
+
+/// Swaps each gene with probability `mutation_rate` with the gene of another random locus, which
+/// keeps the genes of the genome, e.g. permutations stay permutations.
+pub fn swap_mutation(genome: &mut Genome, mutation_rate: f32, rng: &mut impl Rng) {
+    let genome_size = genome.len();
+    if genome_size < 2 {
+        return;
+    }
+    for locus in 0..genome_size {
+        if rng.gen::<f32>() < mutation_rate {
+            let other = (locus + rng.gen_range(1..genome_size)).rem_euclid(genome_size);
+            genome.swap(locus, other);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use common_test::get_seeded_rng;
+
+    use super::swap_mutation;
+
+    #[test]
+    fn test_swap_mutation() {
+        // Given
+        let mut rng = get_seeded_rng().unwrap();
+        let mut genome = (0..32).collect::<Vec<u8>>();
+
+        // When
+        swap_mutation(&mut genome, 0.2, &mut rng);
+
+        // Then
+        assert_ne!((0..32).collect::<Vec<u8>>(), genome, "Should move genes");
+        genome.sort_unstable();
+        assert_eq!(
+            (0..32).collect::<Vec<u8>>(),
+            genome,
+            "Should keep the genes of the genome"
+        );
+    }
+
+    #[test]
+    fn test_swap_mutation_with_single_gene() {
+        // Given
+        let mut rng = get_seeded_rng().unwrap();
+        let mut genome = vec![7];
+
+        // When
+        swap_mutation(&mut genome, 1.0, &mut rng);
+
+        // Then
+        assert_eq!(vec![7], genome);
+    }
+}
@@ -0,0 +1,158 @@
+//! Permutation genomes for ordering problems, each gene being an element of `0..size` appearing
+//! exactly once.
+//!
+//! [`crate::crossover::CrossoverType::Order`], [`crate::crossover::CrossoverType::PartiallyMapped`],
+//! [`crate::mutation::MutationType::Swap`], [`crate::mutation::MutationType::Inversion`] and
+//! [`crate::mutation::MutationType::Scramble`] keep permutations valid. Strategies turn the random
+//! genomes of the engine into permutations by delegating [`crate::adaptation::Strategy::repair`]
+//! to [`Permutation::repair`].
+
+use rand::{seq::SliceRandom, Rng};
+use thiserror::Error;
+
+use crate::Genome;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum PermutationError {
+    #[error("A permutation genome holds up to 256 elements, got: {0}")]
+    TooLarge(usize),
+}
+
+/// Permutations of `0..size`, one byte gene per element.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Permutation {
+    size: usize,
+}
+
+impl Permutation {
+    pub fn new(size: usize) -> Result<Self, PermutationError> {
+        if size > u8::MAX as usize + 1 {
+            return Err(PermutationError::TooLarge(size));
+        }
+        Ok(Permutation { size })
+    }
+
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Uniformly drawn permutation.
+    pub fn random(&self, rng: &mut impl Rng) -> Genome {
+        let mut genome = self.identity();
+        genome.shuffle(rng);
+        genome
+    }
+
+    pub fn identity(&self) -> Genome {
+        (0..self.size).map(|element| element as u8).collect()
+    }
+
+    /// Whether the genome lists each element exactly once.
+    pub fn is_valid(&self, genome: &Genome) -> bool {
+        if genome.len() != self.size {
+            return false;
+        }
+        let mut seen = [false; u8::MAX as usize + 1];
+        genome.iter().all(|&gene| {
+            let is_new = (gene as usize) < self.size && !seen[gene as usize];
+            seen[gene as usize] = true;
+            is_new
+        })
+    }
+
+    /// Turns the genome into a permutation, valid permutations being kept as is.
+    ///
+    /// Other genomes are read as random keys, each gene being replaced by its rank, ties broken by
+    /// locus: random genomes thus become random permutations while genomes with a few mutated genes
+    /// mostly keep their order.
+    pub fn repair(&self, genome: &mut Genome) {
+        if self.is_valid(genome) {
+            return;
+        }
+        genome.resize(self.size, u8::MAX);
+        let mut loci = (0..self.size).collect::<Vec<_>>();
+        loci.sort_by_key(|&locus| genome[locus]);
+        for (rank, locus) in loci.into_iter().enumerate() {
+            genome[locus] = rank as u8;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use common_test::get_seeded_rng;
+    use rand::{distributions::Standard, Rng};
+
+    use super::{Permutation, PermutationError};
+
+    #[test]
+    fn test_new() {
+        assert!(Permutation::new(256).is_ok());
+        assert_eq!(Err(PermutationError::TooLarge(257)), Permutation::new(257));
+    }
+
+    #[test]
+    fn test_is_valid() {
+        let permutation = Permutation::new(4).unwrap();
+
+        assert!(permutation.is_valid(&vec![2, 0, 3, 1]));
+        assert!(
+            !permutation.is_valid(&vec![2, 0, 2, 1]),
+            "Should reject duplicates"
+        );
+        assert!(
+            !permutation.is_valid(&vec![2, 0, 4, 1]),
+            "Should reject unknown elements"
+        );
+        assert!(
+            !permutation.is_valid(&vec![2, 0, 1]),
+            "Should reject missing elements"
+        );
+    }
+
+    #[test]
+    fn test_random() {
+        // Given
+        let mut rng = get_seeded_rng().unwrap();
+        let permutation = Permutation::new(32).unwrap();
+
+        // When
+        let result = permutation.random(&mut rng);
+
+        // Then
+        assert!(permutation.is_valid(&result));
+        assert_ne!(
+            permutation.identity(),
+            result,
+            "Should shuffle the elements"
+        );
+    }
+
+    #[test]
+    fn test_repair() {
+        // Given
+        let mut rng = get_seeded_rng().unwrap();
+        let permutation = Permutation::new(6).unwrap();
+        let mut valid = vec![3, 0, 5, 1, 4, 2];
+        let mut mutated = vec![3, 0, 5, 1, 200, 2];
+        let mut random = (&mut rng).sample_iter(Standard).take(6).collect();
+
+        // When
+        permutation.repair(&mut valid);
+        permutation.repair(&mut mutated);
+        permutation.repair(&mut random);
+
+        // Then
+        assert_eq!(
+            vec![3, 0, 5, 1, 4, 2],
+            valid,
+            "Should keep valid permutations"
+        );
+        assert_eq!(
+            vec![3, 0, 4, 1, 5, 2],
+            mutated,
+            "Should rank the genes of invalid genomes"
+        );
+        assert!(permutation.is_valid(&random));
+    }
+}
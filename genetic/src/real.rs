@@ -0,0 +1,324 @@
+//! Real-valued genomes, encoding each gene as a little-endian `f32` over [`REAL_GENE_SIZE`] bytes.
+//!
+//! Double precision genes within bounds are described by [`RealBounds`], encoding each gene as a
+//! little-endian `f64` over [`REAL64_GENE_SIZE`] bytes. The engine evolves them with
+//! [`BoundedGaussian`] and [`BoundedBlend`], and strategies read them through
+//! [`crate::codec::Decoded`].
+
+use rand::{Rng, RngCore};
+use thiserror::Error;
+
+use crate::{
+    codec::GenomeCodec,
+    crossover::CrossoverOperator,
+    mutation::{standard_normal, MutationOperator},
+    Genome,
+};
+
+/// Number of bytes of a real-valued gene.
+pub const REAL_GENE_SIZE: usize = std::mem::size_of::<f32>();
+
+/// Reads the genes of a real-valued genome, ignoring trailing bytes not forming a whole gene.
+pub fn decode_reals(genome: &Genome) -> Vec<f32> {
+    genome
+        .chunks_exact(REAL_GENE_SIZE)
+        .map(|bytes| f32::from_le_bytes(bytes.try_into().expect("Chunks should be gene sized")))
+        .collect()
+}
+
+pub fn encode_reals(reals: &[f32]) -> Genome {
+    reals.iter().flat_map(|real| real.to_le_bytes()).collect()
+}
+
+/// Number of bytes of a double precision gene, see [`RealBounds`].
+pub const REAL64_GENE_SIZE: usize = std::mem::size_of::<f64>();
+
+#[derive(Error, Debug, PartialEq)]
+pub enum RealError {
+    #[error("Invalid bounds of gene {0}: [{1}, {2}]")]
+    InvalidBounds(usize, f64, f64),
+}
+
+/// Bounds of each gene of a double precision real-valued genome.
+///
+/// Genes are clamped within their bounds on both encoding and decoding, so that any genome,
+/// including the random genomes of the initial population, maps to a valid vector.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RealBounds {
+    bounds: Vec<(f64, f64)>,
+}
+
+impl RealBounds {
+    pub fn new(bounds: Vec<(f64, f64)>) -> Result<Self, RealError> {
+        match bounds
+            .iter()
+            .enumerate()
+            .find(|(_, (min, max))| !(min.is_finite() && max.is_finite() && min <= max))
+        {
+            Some((index, &(min, max))) => Err(RealError::InvalidBounds(index, min, max)),
+            None => Ok(RealBounds { bounds }),
+        }
+    }
+
+    /// Same bounds for `count` genes.
+    pub fn uniform(count: usize, min: f64, max: f64) -> Result<Self, RealError> {
+        Self::new(vec![(min, max); count])
+    }
+
+    pub fn len(&self) -> usize {
+        self.bounds.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bounds.is_empty()
+    }
+
+    /// Clamps each gene within its bounds, genes that are not a number being set to their lower
+    /// bound.
+    pub fn clamp(&self, values: &mut [f64]) {
+        for (value, &(min, max)) in values.iter_mut().zip(&self.bounds) {
+            *value = if value.is_nan() {
+                min
+            } else {
+                value.clamp(min, max)
+            };
+        }
+    }
+
+    /// Adds, at the mutation rate, a normally distributed noise of deviation `sigma` times the
+    /// width of its bounds to each gene.
+    pub fn gaussian_mutation(
+        &self,
+        values: &mut [f64],
+        mutation_rate: f32,
+        sigma: f64,
+        rng: &mut impl Rng,
+    ) {
+        for (value, &(min, max)) in values.iter_mut().zip(&self.bounds) {
+            if rng.gen::<f32>() < mutation_rate {
+                *value += sigma * (max - min) * standard_normal(rng) as f64;
+            }
+        }
+        self.clamp(values);
+    }
+
+    /// Blend crossover (BLX-α), see [`crate::crossover::CrossoverType::Blend`], offspring genes
+    /// being clamped within their bounds.
+    pub fn blend_crossover(
+        &self,
+        first: &[f64],
+        second: &[f64],
+        alpha: f64,
+        rng: &mut impl Rng,
+    ) -> Vec<f64> {
+        let mut values = first
+            .iter()
+            .zip(second)
+            .map(|(&first, &second)| {
+                let extent = alpha * (first - second).abs();
+                let low = first.min(second) - extent;
+                let high = first.max(second) + extent;
+                if low < high {
+                    rng.gen_range(low..=high)
+                } else {
+                    first
+                }
+            })
+            .collect::<Vec<_>>();
+        self.clamp(&mut values);
+        values
+    }
+}
+
+impl GenomeCodec for RealBounds {
+    type Gene = f64;
+
+    fn genome_size(&self) -> usize {
+        self.bounds.len() * REAL64_GENE_SIZE
+    }
+
+    fn encode(&self, genes: &[f64]) -> Genome {
+        let mut values = genes.to_vec();
+        self.clamp(&mut values);
+        values
+            .iter()
+            .flat_map(|value| value.to_le_bytes())
+            .collect()
+    }
+
+    fn decode(&self, genome: &Genome) -> Vec<f64> {
+        let mut values = genome
+            .chunks_exact(REAL64_GENE_SIZE)
+            .map(|bytes| f64::from_le_bytes(bytes.try_into().expect("Chunks should be gene sized")))
+            .collect::<Vec<_>>();
+        self.clamp(&mut values);
+        values
+    }
+}
+
+/// [`RealBounds::gaussian_mutation`] applied by the engine, through
+/// [`crate::mutation::MutationType::Custom`].
+#[derive(Debug, Clone)]
+pub struct BoundedGaussian {
+    pub bounds: RealBounds,
+    /// Deviation of the noise, relative to the width of the bounds.
+    pub sigma: f64,
+}
+
+impl MutationOperator for BoundedGaussian {
+    fn mutate(&self, genome: &mut Genome, mutation_rate: f32, mut rng: &mut dyn RngCore) {
+        let mut values = self.bounds.decode(genome);
+        self.bounds
+            .gaussian_mutation(&mut values, mutation_rate, self.sigma, &mut rng);
+        *genome = self.bounds.encode(&values);
+    }
+}
+
+/// [`RealBounds::blend_crossover`] applied by the engine, through
+/// [`crate::crossover::CrossoverType::Custom`].
+#[derive(Debug, Clone)]
+pub struct BoundedBlend {
+    pub bounds: RealBounds,
+    pub alpha: f64,
+}
+
+impl CrossoverOperator for BoundedBlend {
+    fn crossover(&self, a: &Genome, b: &Genome, mut rng: &mut dyn RngCore) -> Genome {
+        let values = self.bounds.blend_crossover(
+            &self.bounds.decode(a),
+            &self.bounds.decode(b),
+            self.alpha,
+            &mut rng,
+        );
+        self.bounds.encode(&values)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use common_test::get_seeded_rng;
+    use rand::{distributions::Standard, Rng};
+
+    use crate::{codec::GenomeCodec, crossover::CrossoverOperator, mutation::MutationOperator};
+
+    use super::{decode_reals, encode_reals, BoundedBlend, BoundedGaussian, RealBounds, RealError};
+
+    #[test]
+    fn test_encode_reals() {
+        // Given
+        let reals = vec![0.5, -1.25, 1e6];
+
+        // When
+        let result = encode_reals(&reals);
+
+        // Then
+        assert_eq!(12, result.len());
+        assert_eq!(reals, decode_reals(&result), "Should decode encoded reals");
+    }
+
+    #[test]
+    fn test_decode_reals_should_ignore_trailing_bytes() {
+        // Given
+        let mut genome = encode_reals(&[2.0]);
+        genome.push(1);
+
+        // Then
+        assert_eq!(vec![2.0], decode_reals(&genome));
+    }
+
+    #[test]
+    fn test_real_bounds_new() {
+        assert_eq!(
+            Err(RealError::InvalidBounds(1, 2.0, 1.0)),
+            RealBounds::new(vec![(0.0, 1.0), (2.0, 1.0)])
+        );
+        assert!(RealBounds::uniform(2, f64::NEG_INFINITY, 0.0).is_err());
+        assert_eq!(3, RealBounds::uniform(3, -1.0, 1.0).unwrap().len());
+    }
+
+    #[test]
+    fn test_real_bounds_encode() {
+        // Given
+        let bounds = RealBounds::new(vec![(-1.0, 1.0), (0.0, 10.0)]).unwrap();
+
+        // When
+        let result = bounds.encode(&[0.123456789, 12.0]);
+
+        // Then
+        assert_eq!(16, bounds.genome_size());
+        assert_eq!(
+            vec![0.123456789, 10.0],
+            bounds.decode(&result),
+            "Should keep double precision genes within bounds"
+        );
+        assert_eq!(
+            vec![-1.0, 0.0],
+            bounds.decode(
+                &[f64::NAN, -3.0]
+                    .iter()
+                    .flat_map(|v| v.to_le_bytes())
+                    .collect()
+            ),
+            "Should decode any genome within bounds"
+        );
+    }
+
+    #[test]
+    fn test_bounded_gaussian_mutate() {
+        // Given
+        let mut rng = get_seeded_rng().unwrap();
+        let bounds = RealBounds::uniform(1000, -1.0, 1.0).unwrap();
+        let operator = BoundedGaussian {
+            bounds: bounds.clone(),
+            sigma: 0.05,
+        };
+        let mut genome = bounds.encode(&[0.0; 1000]);
+
+        // When
+        operator.mutate(&mut genome, 1.0, &mut rng);
+
+        // Then
+        let values = bounds.decode(&genome);
+        let deviation = (values.iter().map(|v| v.powi(2)).sum::<f64>() / 1000.0).sqrt();
+        assert!(
+            (deviation - 0.1).abs() < 0.01,
+            "Should draw noise relative to the width of the bounds, got {deviation}"
+        );
+    }
+
+    #[test]
+    fn test_bounded_blend_crossover() {
+        // Given
+        let mut rng = get_seeded_rng().unwrap();
+        let bounds = RealBounds::uniform(100, 0.0, 1.0).unwrap();
+        let operator = BoundedBlend {
+            bounds: bounds.clone(),
+            alpha: 0.5,
+        };
+        let random_genome =
+            |rng: &mut _| -> Vec<u8> { Rng::sample_iter(rng, Standard).take(800).collect() };
+
+        // When
+        let result = operator.crossover(
+            &bounds.encode(&[0.2; 100]),
+            &bounds.encode(&[0.9; 100]),
+            &mut rng,
+        );
+
+        // Then
+        assert!(
+            bounds
+                .decode(&result)
+                .iter()
+                .all(|value| (0.0..=1.0).contains(value)),
+            "Should clamp offspring genes within bounds"
+        );
+        assert_eq!(
+            800,
+            operator
+                .crossover(&random_genome(&mut rng), &random_genome(&mut rng), &mut rng)
+                .len(),
+            "Should recombine random genomes"
+        );
+    }
+}
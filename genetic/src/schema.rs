@@ -0,0 +1,384 @@
+//! Declarative layout of genomes made of named integer and real fields within ranges, e.g.
+//!
+//! ```
+//! # use genetic::{codec::GenomeCodec, schema::GeneSchema};
+//! let schema = GeneSchema::new().int("x", 0..100).float("y", -1.0..1.0);
+//! assert_eq!(5, schema.genome_size());
+//! ```
+//!
+//! Each field spreads a little-endian unsigned integer over its range, so that any genome of
+//! [`GeneSchema::genome_size`] bytes, e.g. a random one, decodes to values within the ranges.
+//!
+//! With the `derive` feature, structs describe their own schema, one field per struct field:
+//!
+//! ```ignore
+//! #[derive(GenomeDecode)]
+//! struct Params {
+//!     #[gene(range = 1..10)]
+//!     depth: u8,
+//!     #[gene(range = 0.0..1.0)]
+//!     rate: f32,
+//! }
+//!
+//! let params: Params = genome.decode()?;
+//! ```
+
+use std::ops::Range;
+
+use rand::{distributions::Standard, Rng};
+use thiserror::Error;
+
+use crate::{codec::GenomeCodec, Genome};
+
+#[cfg(feature = "derive")]
+pub use genetic_derive::GenomeDecode;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum SchemaError {
+    #[error("Invalid genome size: {actual}; The schema expects {expected} bytes")]
+    GenomeSize { expected: usize, actual: usize },
+}
+
+/// Number of bytes of a real field.
+const FLOAT_FIELD_SIZE: usize = std::mem::size_of::<u32>();
+
+#[derive(Debug, Clone, PartialEq)]
+enum FieldKind {
+    Int(Range<i64>),
+    Float(Range<f64>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Field {
+    name: String,
+    kind: FieldKind,
+}
+
+impl Field {
+    fn size(&self) -> usize {
+        match &self.kind {
+            FieldKind::Int(range) => int_field_size(span(range)),
+            FieldKind::Float(_) => FLOAT_FIELD_SIZE,
+        }
+    }
+}
+
+/// Value of a field of a [`GeneSchema`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GeneValue {
+    Int(i64),
+    Float(f64),
+}
+
+impl GeneValue {
+    pub fn as_int(&self) -> Option<i64> {
+        match self {
+            GeneValue::Int(value) => Some(*value),
+            GeneValue::Float(_) => None,
+        }
+    }
+
+    pub fn as_float(&self) -> Option<f64> {
+        match self {
+            GeneValue::Float(value) => Some(*value),
+            GeneValue::Int(_) => None,
+        }
+    }
+}
+
+/// Fields of a genome, in order. Empty ranges always decode to their start.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct GeneSchema {
+    fields: Vec<Field>,
+}
+
+impl GeneSchema {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds an integer field, taking as few bytes as its range needs.
+    pub fn int(mut self, name: &str, range: Range<i64>) -> Self {
+        self.fields.push(Field {
+            name: name.to_owned(),
+            kind: FieldKind::Int(range),
+        });
+        self
+    }
+
+    /// Adds a real field of [`FLOAT_FIELD_SIZE`] bytes.
+    pub fn float(mut self, name: &str, range: Range<f64>) -> Self {
+        self.fields.push(Field {
+            name: name.to_owned(),
+            kind: FieldKind::Float(range),
+        });
+        self
+    }
+
+    /// Genome made of random fields.
+    pub fn random(&self, rng: &mut impl Rng) -> Genome {
+        rng.sample_iter(Standard).take(self.genome_size()).collect()
+    }
+
+    /// Reads the fields of the genome, accessible by name.
+    pub fn values(&self, genome: &Genome) -> SchemaValues<'_> {
+        SchemaValues {
+            schema: self,
+            values: self.decode(genome),
+        }
+    }
+}
+
+impl GenomeCodec for GeneSchema {
+    type Gene = GeneValue;
+
+    fn genome_size(&self) -> usize {
+        self.fields.iter().map(Field::size).sum()
+    }
+
+    /// Values are clamped within the range of their field, values of the wrong type being
+    /// encoded as the start of the range.
+    fn encode(&self, genes: &[GeneValue]) -> Genome {
+        let mut genome = Vec::with_capacity(self.genome_size());
+        for (field, gene) in self.fields.iter().zip(genes) {
+            let size = field.size();
+            let raw = match (&field.kind, gene) {
+                (FieldKind::Int(range), GeneValue::Int(value)) if span(range) > 1 => {
+                    let offset = (*value.clamp(&range.start, &(range.end - 1)) as i128
+                        - range.start as i128) as u128;
+                    (offset << (8 * size)).div_ceil(span(range))
+                }
+                (FieldKind::Float(range), GeneValue::Float(value)) if range.start < range.end => {
+                    let ratio = ((value - range.start) / (range.end - range.start)).clamp(0.0, 1.0);
+                    ((ratio * (1u64 << 32) as f64) as u128).min(u32::MAX as u128)
+                }
+                _ => 0,
+            };
+            genome.extend_from_slice(&raw.to_le_bytes()[..size]);
+        }
+        genome.resize(self.genome_size(), 0);
+        genome
+    }
+
+    fn decode(&self, genome: &Genome) -> Vec<GeneValue> {
+        let mut start = 0;
+        self.fields
+            .iter()
+            .map(|field| {
+                let size = field.size();
+                let mut bytes = [0; 16];
+                bytes[..size].copy_from_slice(&genome[start..start + size]);
+                start += size;
+                let raw = u128::from_le_bytes(bytes);
+                match &field.kind {
+                    FieldKind::Int(range) => {
+                        let offset = (raw * span(range)) >> (8 * size);
+                        GeneValue::Int((range.start as i128 + offset as i128) as i64)
+                    }
+                    FieldKind::Float(range) if range.start < range.end => GeneValue::Float(
+                        range.start + (range.end - range.start) * raw as f64 / (1u64 << 32) as f64,
+                    ),
+                    FieldKind::Float(range) => GeneValue::Float(range.start),
+                }
+            })
+            .collect()
+    }
+}
+
+/// Types built from the fields of a [`GeneSchema`], usually through `#[derive(GenomeDecode)]`.
+pub trait GenomeDecode: Sized {
+    fn schema() -> GeneSchema;
+
+    /// Builds the value from the decoded fields of its schema, in order.
+    fn from_genes(genes: &[GeneValue]) -> Self;
+}
+
+/// Decoding of genomes into [`GenomeDecode`] types.
+pub trait DecodeGenome {
+    fn decode<T: GenomeDecode>(&self) -> Result<T, SchemaError>;
+}
+
+impl DecodeGenome for Genome {
+    fn decode<T: GenomeDecode>(&self) -> Result<T, SchemaError> {
+        let schema = T::schema();
+        if self.len() != schema.genome_size() {
+            return Err(SchemaError::GenomeSize {
+                expected: schema.genome_size(),
+                actual: self.len(),
+            });
+        }
+        Ok(T::from_genes(&GenomeCodec::decode(&schema, self)))
+    }
+}
+
+/// Decoded fields of a genome, see [`GeneSchema::values`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct SchemaValues<'a> {
+    schema: &'a GeneSchema,
+    values: Vec<GeneValue>,
+}
+
+impl SchemaValues<'_> {
+    pub fn get(&self, name: &str) -> Option<GeneValue> {
+        self.schema
+            .fields
+            .iter()
+            .position(|field| field.name == name)
+            .map(|index| self.values[index])
+    }
+
+    pub fn int(&self, name: &str) -> Option<i64> {
+        self.get(name)?.as_int()
+    }
+
+    pub fn float(&self, name: &str) -> Option<f64> {
+        self.get(name)?.as_float()
+    }
+
+    pub fn values(&self) -> &[GeneValue] {
+        &self.values
+    }
+}
+
+/// Number of values of the range, 0 when empty.
+fn span(range: &Range<i64>) -> u128 {
+    (range.end as i128 - range.start as i128).max(0) as u128
+}
+
+/// Smallest number of bytes holding `span` distinct values.
+fn int_field_size(span: u128) -> usize {
+    let mut size = 0;
+    while size < 8 && (1u128 << (8 * size)) < span {
+        size += 1;
+    }
+    size
+}
+
+#[cfg(test)]
+mod tests {
+    use common_test::get_seeded_rng;
+
+    use crate::codec::GenomeCodec;
+
+    use super::{DecodeGenome, GeneSchema, GeneValue, GenomeDecode, SchemaError};
+
+    #[derive(Debug, PartialEq)]
+    struct Params {
+        depth: u8,
+        rate: f32,
+    }
+
+    impl GenomeDecode for Params {
+        fn schema() -> GeneSchema {
+            GeneSchema::new()
+                .int("depth", 1..10)
+                .float("rate", 0.0..1.0)
+        }
+
+        fn from_genes(genes: &[GeneValue]) -> Self {
+            Params {
+                depth: genes[0].as_int().unwrap() as u8,
+                rate: genes[1].as_float().unwrap() as f32,
+            }
+        }
+    }
+
+    #[test]
+    fn test_genome_size() {
+        assert_eq!(0, GeneSchema::new().genome_size());
+        assert_eq!(
+            7,
+            GeneSchema::new()
+                .int("a", 0..256)
+                .int("b", -1..256)
+                .float("c", 0.0..1.0)
+                .int("d", 5..6)
+                .genome_size(),
+            "Should take as few bytes as the ranges need"
+        );
+        assert_eq!(
+            8,
+            GeneSchema::new().int("e", i64::MIN..i64::MAX).genome_size()
+        );
+    }
+
+    #[test]
+    fn test_encode() {
+        // Given
+        let schema = GeneSchema::new()
+            .int("x", 0..100)
+            .float("y", -1.0..1.0)
+            .int("z", -1000..1000);
+        let values = [
+            GeneValue::Int(42),
+            GeneValue::Float(0.25),
+            GeneValue::Int(-999),
+        ];
+
+        // When
+        let result = schema.decode(&schema.encode(&values));
+
+        // Then
+        assert_eq!(values[0], result[0]);
+        assert!((0.25 - result[1].as_float().unwrap()).abs() < 1e-6);
+        assert_eq!(values[2], result[2]);
+        assert_eq!(
+            vec![
+                GeneValue::Int(99),
+                GeneValue::Float(-1.0),
+                GeneValue::Int(-1000)
+            ],
+            schema.decode(&schema.encode(&[
+                GeneValue::Int(500),
+                GeneValue::Int(0),
+                GeneValue::Int(-5000)
+            ])),
+            "Should clamp values within their field"
+        );
+    }
+
+    #[test]
+    fn test_random() {
+        // Given
+        let mut rng = get_seeded_rng().unwrap();
+        let schema = GeneSchema::new().int("x", 1..4).float("y", -1.0..1.0);
+
+        for _ in 0..100 {
+            // When
+            let genome = schema.random(&mut rng);
+
+            // Then
+            let values = schema.values(&genome);
+            assert!((1..4).contains(&values.int("x").unwrap()));
+            assert!((-1.0..1.0).contains(&values.float("y").unwrap()));
+            assert_eq!(None, values.float("x"));
+            assert_eq!(None, values.get("unknown"));
+        }
+    }
+
+    #[test]
+    fn test_decode_genome() {
+        // Given
+        let genome = Params::schema().encode(&[GeneValue::Int(7), GeneValue::Float(0.5)]);
+
+        // When
+        let result = genome.decode::<Params>();
+
+        // Then
+        assert_eq!(
+            Ok(Params {
+                depth: 7,
+                rate: 0.5
+            }),
+            result
+        );
+        assert_eq!(
+            Err(SchemaError::GenomeSize {
+                expected: 5,
+                actual: 4
+            }),
+            vec![0; 4].decode::<Params>(),
+            "Should reject genomes not matching the schema"
+        );
+    }
+}
@@ -1,15 +1,27 @@
+mod pressure;
 mod rng_wrapper;
+mod scaling;
+mod select_by_age;
 mod select_by_chance;
 mod select_by_rank;
 mod select_by_tournament;
 mod select_by_weight;
 
+use std::{collections::HashMap, fmt::Debug, iter, sync::Arc};
+
+pub use pressure::{analyze, PressureSummary, SelectionPressure};
 use rand::Rng;
 use rng_wrapper::Random;
-use select_by_chance::select_by_chance;
-use select_by_rank::select_by_rank;
-use select_by_tournament::select_by_tournament;
-use select_by_weight::select_by_weight;
+pub use rng_wrapper::RngWrapper;
+pub use scaling::FitnessScaling;
+use select_by_age::{select_by_age, select_by_age_iter};
+use select_by_chance::{select_by_chance, select_by_chance_iter};
+use select_by_rank::{select_by_rank, select_by_rank_iter};
+use select_by_tournament::{select_by_tournament, select_by_tournament_iter};
+use select_by_weight::{
+    select_by_biased_weight, select_by_biased_weight_iter, select_by_weight, select_by_weight_iter,
+};
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
@@ -23,75 +35,531 @@ pub enum SelectionError {
     OutOfRange(usize, usize),
     #[error("Unable to select by rank {0} genome(s) whereas the max rank is {1}")]
     OutOfRank(usize, usize),
+    #[error("Unable to select distinct parents after {0} draws")]
+    IdenticalParents(usize),
 }
 
 pub type SelectionResult = Result<Vec<usize>, SelectionError>;
 
-#[derive(Copy, Clone, Debug, Serialize, Deserialize, PartialEq, Default)]
+/// Selections yielded lazily, see [`select_iter`] and [`select_couples_iter`].
+pub type SelectionIter<'a, T> = Box<dyn Iterator<Item = Result<T, SelectionError>> + 'a>;
+
+/// Max number of draws of a couple before giving up on getting distinct parents.
+const MAX_COUPLE_DRAWS: usize = 100;
+
+/// Permutation of `0..len` shuffled in place, only the moved positions being stored so that
+/// drawing a few indexes out of many does not allocate them all.
+#[derive(Default)]
+struct SparsePermutation(HashMap<usize, usize>);
+
+impl SparsePermutation {
+    fn get(&self, position: usize) -> usize {
+        self.0.get(&position).copied().unwrap_or(position)
+    }
+
+    fn swap(&mut self, a: usize, b: usize) {
+        let (value_a, value_b) = (self.get(a), self.get(b));
+        self.0.insert(a, value_b);
+        self.0.insert(b, value_a);
+    }
+}
+
+/// A selection scheme that can be plugged into [`SelectionType::Custom`].
+///
+/// Implementations return the indexes of the selected evaluations.
+pub trait SelectionOperator: Debug + Send + Sync {
+    fn select(
+        &self,
+        evaluations: &[Evaluation],
+        expected_count: usize,
+        rng: &mut dyn RngWrapper,
+    ) -> SelectionResult;
+}
+
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum SelectionType {
+    /// Weight selection penalizing genomes by the number of generations they survived.
+    Age,
     Chance,
     Ranking(usize),
     Tournament(usize),
     #[default]
     Weight,
+    /// Weight selection on the fitness normalized by the best one and raised to `exponent`, from
+    /// uniform (0) to aggressive exploitation of the best genomes.
+    BiasedRoulette {
+        exponent: f32,
+    },
+    #[cfg_attr(feature = "serde", serde(skip))]
+    Custom(Arc<dyn SelectionOperator>),
+}
+
+impl PartialEq for SelectionType {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Age, Self::Age)
+            | (Self::Chance, Self::Chance)
+            | (Self::Weight, Self::Weight) => true,
+            (Self::Ranking(a), Self::Ranking(b)) | (Self::Tournament(a), Self::Tournament(b)) => {
+                a == b
+            }
+            (
+                Self::BiasedRoulette { exponent },
+                Self::BiasedRoulette {
+                    exponent: other_exponent,
+                },
+            ) => exponent == other_exponent,
+            (Self::Custom(a), Self::Custom(b)) => Arc::ptr_eq(a, b),
+            _ => false,
+        }
+    }
 }
 
 pub fn select(
     evaluations: &[Evaluation],
     selection_count: usize,
-    selection_type: SelectionType,
+    selection_type: &SelectionType,
     rng: &mut impl Rng,
 ) -> SelectionResult {
-    let mut random = Random::new(rng);
-    match selection_type {
-        SelectionType::Chance => select_by_chance(evaluations, selection_count, &mut random),
+    select_iter(evaluations, selection_count, selection_type, rng).collect()
+}
+
+/// Lazy variant of [`select`], yielding the selected indexes one at a time.
+///
+/// Errors detected before any draw are yielded as the only item.
+pub fn select_iter<'a>(
+    evaluations: &'a [Evaluation],
+    selection_count: usize,
+    selection_type: &'a SelectionType,
+    rng: &'a mut impl Rng,
+) -> SelectionIter<'a, usize> {
+    let random = Random::new(rng);
+    let selections: Result<SelectionIter<'a, usize>, SelectionError> = match selection_type {
+        SelectionType::Age => select_by_age_iter(evaluations, selection_count, random)
+            .map(|selections| Box::new(selections) as SelectionIter<'a, usize>),
+        SelectionType::Chance => select_by_chance_iter(evaluations, selection_count, random)
+            .map(|selections| Box::new(selections.map(Ok)) as SelectionIter<'a, usize>),
         SelectionType::Ranking(max_rank) => {
-            select_by_rank(evaluations, selection_count, max_rank, &mut random)
+            select_by_rank_iter(evaluations, selection_count, *max_rank, random)
+                .map(|selections| Box::new(selections.map(Ok)) as SelectionIter<'a, usize>)
         }
         SelectionType::Tournament(pool_size) => {
-            select_by_tournament(evaluations, selection_count, pool_size, &mut random)
+            select_by_tournament_iter(evaluations, selection_count, *pool_size, random)
+                .map(|selections| Box::new(selections.map(Ok)) as SelectionIter<'a, usize>)
+        }
+        SelectionType::Weight => select_by_weight_iter(evaluations, selection_count, random)
+            .map(|selections| Box::new(selections) as SelectionIter<'a, usize>),
+        SelectionType::BiasedRoulette { exponent } => {
+            select_by_biased_weight_iter(evaluations, selection_count, *exponent, random)
+                .map(|selections| Box::new(selections) as SelectionIter<'a, usize>)
         }
-        SelectionType::Weight => select_by_weight(evaluations, selection_count, &mut random),
+        SelectionType::Custom(operator) => {
+            let mut random = random;
+            select_by_operator(operator.as_ref(), evaluations, selection_count, &mut random).map(
+                |selections| Box::new(selections.into_iter().map(Ok)) as SelectionIter<'a, usize>,
+            )
+        }
+    };
+    selections.unwrap_or_else(|err| Box::new(iter::once(Err(err))))
+}
+
+/// Runs a custom operator, checking that it selected the expected count of existing indexes.
+fn select_by_operator(
+    operator: &dyn SelectionOperator,
+    evaluations: &[Evaluation],
+    expected_count: usize,
+    rng: &mut dyn RngWrapper,
+) -> SelectionResult {
+    let selections = operator.select(evaluations, expected_count, rng)?;
+    if selections.len() != expected_count {
+        return Err(SelectionError::OutOfRange(expected_count, selections.len()));
+    }
+    match selections.iter().find(|&&index| index >= evaluations.len()) {
+        Some(index) => Err(SelectionError::OutOfRange(index + 1, evaluations.len())),
+        None => Ok(selections),
     }
 }
 
+/// Selects `couples_count` pairs of parents.
+///
+/// When `distinct_parents` is set, couples made of the same individual are redrawn
+/// so that crossover never degenerates into cloning.
 pub fn select_couples(
     evaluations: &[Evaluation],
     couples_count: usize,
-    selection_type: SelectionType,
+    selection_type: &SelectionType,
+    distinct_parents: bool,
     rng: &mut impl Rng,
 ) -> Result<Vec<(usize, usize)>, SelectionError> {
+    select_couples_iter(
+        evaluations,
+        couples_count,
+        selection_type,
+        distinct_parents,
+        rng,
+    )
+    .collect()
+}
+
+/// Lazy variant of [`select_couples`], drawing each couple when requested so that huge counts
+/// can be streamed.
+pub fn select_couples_iter<'a>(
+    evaluations: &'a [Evaluation],
+    couples_count: usize,
+    selection_type: &'a SelectionType,
+    distinct_parents: bool,
+    rng: &'a mut impl Rng,
+) -> SelectionIter<'a, (usize, usize)> {
+    if distinct_parents && couples_count > 0 && evaluations.len() < 2 {
+        return Box::new(iter::once(Err(SelectionError::OutOfRange(
+            2,
+            evaluations.len(),
+        ))));
+    }
     let mut random = Random::new(rng);
-    let mut selector: Box<dyn FnMut() -> Result<Vec<_>, SelectionError>> = match selection_type {
-        SelectionType::Chance => Box::new(|| select_by_chance(evaluations, 2, &mut random)),
+    let mut selector: Box<dyn FnMut() -> SelectionResult + 'a> = match selection_type {
+        SelectionType::Age => Box::new(move || select_by_age(evaluations, 2, &mut random)),
+        SelectionType::Chance => Box::new(move || select_by_chance(evaluations, 2, &mut random)),
         SelectionType::Ranking(max_rank) => {
+            let max_rank = *max_rank;
             Box::new(move || select_by_rank(evaluations, 2, max_rank, &mut random))
         }
         SelectionType::Tournament(pool_size) => {
+            let pool_size = *pool_size;
             Box::new(move || select_by_tournament(evaluations, 2, pool_size, &mut random))
         }
-        SelectionType::Weight => Box::new(|| select_by_weight(evaluations, 2, &mut random)),
+        SelectionType::Weight => Box::new(move || select_by_weight(evaluations, 2, &mut random)),
+        SelectionType::BiasedRoulette { exponent } => {
+            let exponent = *exponent;
+            Box::new(move || select_by_biased_weight(evaluations, 2, exponent, &mut random))
+        }
+        SelectionType::Custom(operator) => {
+            Box::new(move || select_by_operator(operator.as_ref(), evaluations, 2, &mut random))
+        }
     };
 
-    (0..couples_count)
-        .map(|_| selector().map(|arr| (arr[0], arr[1])))
-        .collect()
+    Box::new((0..couples_count).map(move |_| {
+        for _ in 0..MAX_COUPLE_DRAWS {
+            let couple = selector().map(|arr| (arr[0], arr[1]))?;
+            if !distinct_parents || couple.0 != couple.1 {
+                return Ok(couple);
+            }
+        }
+        Err(SelectionError::IdenticalParents(MAX_COUPLE_DRAWS))
+    }))
 }
 
 #[cfg(test)]
 mod tests {
+    use std::sync::Arc;
+
     use common_test::get_seeded_rng;
 
     use crate::{
         selection::{
             rng_wrapper::Random, select_by_chance::select_by_chance,
             select_by_rank::select_by_rank, select_by_tournament::select_by_tournament,
-            select_by_weight::select_by_weight, SelectionType,
+            select_by_weight::select_by_weight, RngWrapper, SelectionError, SelectionOperator,
+            SelectionResult, SelectionType, MAX_COUPLE_DRAWS,
         },
         Evaluation,
     };
 
-    use super::{select, select_couples};
+    use super::{select, select_couples, select_couples_iter, select_iter};
+
+    #[derive(Debug)]
+    struct SelectLast;
+
+    impl SelectionOperator for SelectLast {
+        fn select(
+            &self,
+            evaluations: &[Evaluation],
+            expected_count: usize,
+            _: &mut dyn RngWrapper,
+        ) -> SelectionResult {
+            Ok((0..expected_count)
+                .map(|i| evaluations.len() - 1 - i)
+                .collect())
+        }
+    }
+
+    #[test]
+    fn test_select_custom() {
+        // Given
+        let evaluations = vec![
+            Evaluation {
+                genome: vec![3],
+                fitness: 0.1,
+                ..Default::default()
+            },
+            Evaluation {
+                genome: vec![5],
+                fitness: 0.4,
+                ..Default::default()
+            },
+            Evaluation {
+                genome: vec![4],
+                fitness: 0.5,
+                ..Default::default()
+            },
+        ];
+        let selection_type = SelectionType::Custom(Arc::new(SelectLast));
+        let mut rng = get_seeded_rng().unwrap();
+
+        // When
+        let result = select(&evaluations, 2, &selection_type, &mut rng).unwrap();
+
+        // Then
+        assert_eq!(
+            vec![2, 1],
+            result,
+            "Should use the custom operator to select"
+        );
+
+        // When
+        let result = select_couples(&evaluations, 2, &selection_type, true, &mut rng).unwrap();
+
+        // Then
+        assert_eq!(
+            vec![(2, 1), (2, 1)],
+            result,
+            "Should use the custom operator to select couples"
+        );
+    }
+
+    #[derive(Debug)]
+    struct SelectFixed(Vec<usize>);
+
+    impl SelectionOperator for SelectFixed {
+        fn select(&self, _: &[Evaluation], _: usize, _: &mut dyn RngWrapper) -> SelectionResult {
+            Ok(self.0.clone())
+        }
+    }
+
+    #[test]
+    fn test_select_custom_should_check_selections() {
+        // Given
+        let evaluations = vec![Evaluation::default(), Evaluation::default()];
+        let mut rng = get_seeded_rng().unwrap();
+
+        // When
+        let selection_type = SelectionType::Custom(Arc::new(SelectFixed(vec![1])));
+        let result = select(&evaluations, 2, &selection_type, &mut rng);
+        let couples_result = select_couples(&evaluations, 1, &selection_type, false, &mut rng);
+
+        // Then
+        assert_eq!(
+            Err(SelectionError::OutOfRange(2, 1)),
+            result,
+            "Should fail when the operator does not select the expected count"
+        );
+        assert_eq!(Err(SelectionError::OutOfRange(2, 1)), couples_result);
+
+        // When
+        let selection_type = SelectionType::Custom(Arc::new(SelectFixed(vec![0, 2])));
+        let result = select(&evaluations, 2, &selection_type, &mut rng);
+        let couples_result = select_couples(&evaluations, 1, &selection_type, false, &mut rng);
+
+        // Then
+        assert_eq!(
+            Err(SelectionError::OutOfRange(3, 2)),
+            result,
+            "Should fail when the operator selects a missing evaluation"
+        );
+        assert_eq!(Err(SelectionError::OutOfRange(3, 2)), couples_result);
+    }
+
+    #[derive(Debug)]
+    struct SelectFirst;
+
+    impl SelectionOperator for SelectFirst {
+        fn select(
+            &self,
+            _: &[Evaluation],
+            expected_count: usize,
+            _: &mut dyn RngWrapper,
+        ) -> SelectionResult {
+            Ok(vec![0; expected_count])
+        }
+    }
+
+    #[test]
+    fn test_select_couples_distinct_parents() {
+        // Given
+        let evaluations = vec![
+            Evaluation {
+                genome: vec![3],
+                ..Default::default()
+            },
+            Evaluation {
+                genome: vec![5],
+                ..Default::default()
+            },
+        ];
+        let selection_type = SelectionType::Custom(Arc::new(SelectFirst));
+        let mut rng = get_seeded_rng().unwrap();
+
+        // When
+        let result = select_couples(&evaluations, 2, &selection_type, false, &mut rng);
+
+        // Then
+        assert_eq!(
+            Ok(vec![(0, 0), (0, 0)]),
+            result,
+            "Should allow self-mating when parents may be identical"
+        );
+
+        // When
+        let result = select_couples(&evaluations, 2, &selection_type, true, &mut rng);
+
+        // Then
+        assert_eq!(
+            Err(SelectionError::IdenticalParents(MAX_COUPLE_DRAWS)),
+            result,
+            "Should give up when distinct parents cannot be drawn"
+        );
+    }
+
+    #[test]
+    fn test_select_couples_distinct_parents_should_fail_with_single_member() {
+        // Given
+        let evaluations = vec![Evaluation {
+            genome: vec![3],
+            ..Default::default()
+        }];
+        let mut rng = get_seeded_rng().unwrap();
+
+        // When
+        let result = select_couples(&evaluations, 1, &SelectionType::Chance, true, &mut rng);
+
+        // Then
+        assert_eq!(Err(SelectionError::OutOfRange(2, 1)), result);
+    }
+
+    #[test]
+    fn test_select_iter() {
+        // Given
+        let evaluations = (0..16)
+            .map(|i| Evaluation {
+                genome: vec![i],
+                fitness: i as f32 / 16.0,
+                ..Default::default()
+            })
+            .collect::<Vec<_>>();
+
+        for selection_type in [
+            SelectionType::Age,
+            SelectionType::Chance,
+            SelectionType::Ranking(12),
+            SelectionType::Tournament(3),
+            SelectionType::Weight,
+            SelectionType::BiasedRoulette { exponent: 2.0 },
+        ] {
+            // When
+            let mut rng = get_seeded_rng().unwrap();
+            let result = select_iter(&evaluations, 10, &selection_type, &mut rng)
+                .collect::<Result<Vec<_>, _>>();
+
+            // Then
+            assert_eq!(
+                select(
+                    &evaluations,
+                    10,
+                    &selection_type,
+                    &mut get_seeded_rng().unwrap()
+                ),
+                result,
+                "Should yield the selection of {selection_type:?}"
+            );
+        }
+
+        // When
+        let mut rng = get_seeded_rng().unwrap();
+        let result =
+            select_iter(&evaluations, 17, &SelectionType::Chance, &mut rng).collect::<Vec<_>>();
+
+        // Then
+        assert_eq!(
+            vec![Err(SelectionError::OutOfRange(17, 16))],
+            result,
+            "Should yield the error as only item"
+        );
+    }
+
+    #[test]
+    fn test_select_couples_iter() {
+        // Given
+        let evaluations = (0..4)
+            .map(|i| Evaluation {
+                genome: vec![i],
+                fitness: 0.5,
+                ..Default::default()
+            })
+            .collect::<Vec<_>>();
+        let mut rng = get_seeded_rng().unwrap();
+
+        // When
+        let result = select_couples_iter(
+            &evaluations,
+            usize::MAX,
+            &SelectionType::Weight,
+            true,
+            &mut rng,
+        )
+        .take(5)
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+
+        // Then
+        assert_eq!(5, result.len(), "Should draw couples on demand");
+        assert!(result.iter().all(|(a, b)| a != b));
+    }
+
+    #[test]
+    fn test_select_couples_distinct_parents_should_redraw() {
+        // Given
+        let evaluations = vec![
+            Evaluation {
+                genome: vec![3],
+                fitness: 0.1,
+                ..Default::default()
+            },
+            Evaluation {
+                genome: vec![5],
+                fitness: 0.9,
+                ..Default::default()
+            },
+        ];
+        let mut rng = get_seeded_rng().unwrap();
+
+        // When
+        let result =
+            select_couples(&evaluations, 20, &SelectionType::Chance, true, &mut rng).unwrap();
+
+        // Then
+        assert!(
+            result.iter().all(|(a, b)| a != b),
+            "Should never pair an individual with itself"
+        );
+    }
+
+    #[test]
+    fn test_selection_type_eq() {
+        let operator: Arc<dyn SelectionOperator> = Arc::new(SelectLast);
+
+        assert_eq!(SelectionType::Ranking(3), SelectionType::Ranking(3));
+        assert_ne!(SelectionType::Ranking(3), SelectionType::Tournament(3));
+        assert_eq!(
+            SelectionType::Custom(operator.clone()),
+            SelectionType::Custom(operator),
+            "Should compare custom operators by identity"
+        );
+        assert_ne!(
+            SelectionType::Custom(Arc::new(SelectLast)),
+            SelectionType::Custom(Arc::new(SelectLast))
+        );
+    }
 
     #[test]
     fn test_select() {
@@ -99,18 +567,22 @@ mod tests {
             Evaluation {
                 genome: vec![3],
                 fitness: 0.1,
+                ..Default::default()
             },
             Evaluation {
                 genome: vec![5],
                 fitness: 0.4,
+                ..Default::default()
             },
             Evaluation {
                 genome: vec![4],
                 fitness: 0.5,
+                ..Default::default()
             },
             Evaluation {
                 genome: vec![8],
                 fitness: 0.9,
+                ..Default::default()
             },
         ];
         let max_rank = 3;
@@ -124,7 +596,7 @@ mod tests {
         let result = select(
             &evaluations,
             selection_count,
-            SelectionType::Chance,
+            &SelectionType::Chance,
             &mut rng,
         )
         .unwrap();
@@ -153,7 +625,7 @@ mod tests {
         let result = select(
             &evaluations,
             selection_count,
-            SelectionType::Ranking(max_rank),
+            &SelectionType::Ranking(max_rank),
             &mut rng,
         )
         .unwrap();
@@ -183,7 +655,7 @@ mod tests {
         let result = select(
             &evaluations,
             selection_count,
-            SelectionType::Tournament(pool_size),
+            &SelectionType::Tournament(pool_size),
             &mut rng,
         )
         .unwrap();
@@ -213,7 +685,7 @@ mod tests {
         let result = select(
             &evaluations,
             selection_count,
-            SelectionType::Weight,
+            &SelectionType::Weight,
             &mut rng,
         )
         .unwrap();
@@ -242,18 +714,22 @@ mod tests {
             Evaluation {
                 genome: vec![3],
                 fitness: 0.1,
+                ..Default::default()
             },
             Evaluation {
                 genome: vec![5],
                 fitness: 0.4,
+                ..Default::default()
             },
             Evaluation {
                 genome: vec![4],
                 fitness: 0.5,
+                ..Default::default()
             },
             Evaluation {
                 genome: vec![8],
                 fitness: 0.9,
+                ..Default::default()
             },
         ];
         let max_rank = 3;
@@ -264,8 +740,14 @@ mod tests {
         let mut rng = get_seeded_rng().unwrap();
 
         // When
-        let result =
-            select_couples(&evaluations, couples_count, SelectionType::Chance, &mut rng).unwrap();
+        let result = select_couples(
+            &evaluations,
+            couples_count,
+            &SelectionType::Chance,
+            true,
+            &mut rng,
+        )
+        .unwrap();
 
         // Then
         assert_eq!(
@@ -296,7 +778,8 @@ mod tests {
         let result = select_couples(
             &evaluations,
             couples_count,
-            SelectionType::Ranking(max_rank),
+            &SelectionType::Ranking(max_rank),
+            true,
             &mut rng,
         )
         .unwrap();
@@ -330,7 +813,8 @@ mod tests {
         let result = select_couples(
             &evaluations,
             couples_count,
-            SelectionType::Tournament(pool_size),
+            &SelectionType::Tournament(pool_size),
+            true,
             &mut rng,
         )
         .unwrap();
@@ -361,8 +845,14 @@ mod tests {
         let mut rng = get_seeded_rng().unwrap();
 
         // When
-        let result =
-            select_couples(&evaluations, couples_count, SelectionType::Weight, &mut rng).unwrap();
+        let result = select_couples(
+            &evaluations,
+            couples_count,
+            &SelectionType::Weight,
+            true,
+            &mut rng,
+        )
+        .unwrap();
 
         // Then
         assert_eq!(
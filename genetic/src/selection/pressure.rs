@@ -0,0 +1,221 @@
+use rand::Rng;
+
+use crate::Evaluation;
+
+use super::{select, SelectionError, SelectionType};
+
+/// Empirical distribution of the picks made by a selection scheme over a population.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SelectionPressure {
+    pub samples: usize,
+    pub counts: Vec<usize>,
+}
+
+impl SelectionPressure {
+    pub fn probability(&self, index: usize) -> f32 {
+        if self.samples > 0 {
+            self.counts[index] as f32 / self.samples as f32
+        } else {
+            0.0
+        }
+    }
+
+    pub fn probabilities(&self) -> Vec<f32> {
+        (0..self.counts.len())
+            .map(|i| self.probability(i))
+            .collect()
+    }
+
+    /// Expected count of copies of each evaluation when selecting a whole population.
+    pub fn expected_offspring(&self) -> Vec<f32> {
+        let population_size = self.counts.len() as f32;
+        self.probabilities()
+            .into_iter()
+            .map(|p| p * population_size)
+            .collect()
+    }
+
+    pub fn summary(&self) -> PressureSummary {
+        let population_size = self.counts.len() as f32;
+        let mut probabilities = self.probabilities();
+        probabilities.sort_by(f32::total_cmp);
+
+        let max_expected_offspring =
+            probabilities.last().copied().unwrap_or_default() * population_size;
+        let loss_of_diversity = if population_size > 0.0 {
+            probabilities
+                .iter()
+                .map(|p| (1.0 - p).powf(population_size))
+                .sum::<f32>()
+                / population_size
+        } else {
+            0.0
+        };
+        // Gini coefficient from the probabilities sorted in increasing order
+        let gini = if population_size > 1.0 {
+            let weighted_sum = probabilities
+                .iter()
+                .enumerate()
+                .map(|(i, p)| (2.0 * (i + 1) as f32 - population_size - 1.0) * p)
+                .sum::<f32>();
+            weighted_sum / (population_size - 1.0)
+        } else {
+            0.0
+        };
+
+        PressureSummary {
+            max_expected_offspring,
+            loss_of_diversity,
+            gini,
+        }
+    }
+}
+
+/// Summary statistics of a [`SelectionPressure`], computed for the selection of a whole
+/// population.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PressureSummary {
+    /// Expected copies of the most favoured evaluation, 1 meaning no pressure at all.
+    pub max_expected_offspring: f32,
+    /// Expected share of the population that is never selected.
+    pub loss_of_diversity: f32,
+    /// Inequality of the selection probabilities, from 0 (uniform) to 1 (single winner).
+    pub gini: f32,
+}
+
+/// Runs `samples` single selections and reports how often each evaluation was picked.
+pub fn analyze(
+    evaluations: &[Evaluation],
+    selection_type: &SelectionType,
+    samples: usize,
+    rng: &mut impl Rng,
+) -> Result<SelectionPressure, SelectionError> {
+    let mut counts = vec![0; evaluations.len()];
+    for _ in 0..samples {
+        for index in select(evaluations, 1, selection_type, rng)? {
+            counts[index] += 1;
+        }
+    }
+    Ok(SelectionPressure { samples, counts })
+}
+
+#[cfg(test)]
+mod tests {
+    use common_test::get_seeded_rng;
+
+    use crate::{
+        selection::{SelectionError, SelectionType},
+        Evaluation,
+    };
+
+    use super::{analyze, PressureSummary, SelectionPressure};
+
+    fn get_evaluations() -> Vec<Evaluation> {
+        [0.1, 0.2, 0.9]
+            .into_iter()
+            .map(|fitness| Evaluation {
+                fitness,
+                ..Default::default()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_analyze() {
+        // Given
+        let mut rng = get_seeded_rng().unwrap();
+        let evaluations = get_evaluations();
+        let samples = 3000;
+
+        // When
+        let result = analyze(&evaluations, &SelectionType::Weight, samples, &mut rng).unwrap();
+
+        // Then
+        assert_eq!(samples, result.samples);
+        assert_eq!(
+            samples,
+            result.counts.iter().sum::<usize>(),
+            "Should pick one evaluation per sample"
+        );
+        assert!(
+            result.probability(2) > result.probability(0),
+            "Should reflect the pressure toward fitter evaluations"
+        );
+    }
+
+    #[test]
+    fn test_analyze_should_fail_when_selection_fails() {
+        // Given
+        let mut rng = get_seeded_rng().unwrap();
+
+        // When
+        let result = analyze(&[], &SelectionType::Chance, 1, &mut rng);
+
+        // Then
+        assert_eq!(Err(SelectionError::OutOfRange(1, 0)), result);
+    }
+
+    #[test]
+    fn test_selection_pressure_probabilities() {
+        // Given
+        let pressure = SelectionPressure {
+            samples: 4,
+            counts: vec![1, 0, 3],
+        };
+
+        // Then
+        assert_eq!(vec![0.25, 0.0, 0.75], pressure.probabilities());
+        assert_eq!(
+            vec![0.0, 0.0],
+            SelectionPressure {
+                samples: 0,
+                counts: vec![0, 0]
+            }
+            .probabilities(),
+            "Should not divide by zero without samples"
+        );
+    }
+
+    #[test]
+    fn test_selection_pressure_expected_offspring() {
+        // Given
+        let pressure = SelectionPressure {
+            samples: 4,
+            counts: vec![1, 0, 3, 0],
+        };
+
+        // Then
+        assert_eq!(vec![1.0, 0.0, 3.0, 0.0], pressure.expected_offspring());
+    }
+
+    #[test]
+    fn test_selection_pressure_summary() {
+        // Given
+        let uniform = SelectionPressure {
+            samples: 4,
+            counts: vec![1, 1, 1, 1],
+        };
+        let single_winner = SelectionPressure {
+            samples: 4,
+            counts: vec![0, 0, 4, 0],
+        };
+
+        // When
+        let uniform_summary = uniform.summary();
+        let single_winner_summary = single_winner.summary();
+
+        // Then
+        assert_eq!(1.0, uniform_summary.max_expected_offspring);
+        assert_eq!(0.0, uniform_summary.gini, "Should be even without pressure");
+        assert_eq!(
+            PressureSummary {
+                max_expected_offspring: 4.0,
+                loss_of_diversity: 0.75,
+                gini: 1.0,
+            },
+            single_winner_summary,
+            "Should be maximal when a single evaluation is selected"
+        );
+        assert!(uniform_summary.loss_of_diversity < single_winner_summary.loss_of_diversity);
+    }
+}
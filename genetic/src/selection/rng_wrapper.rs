@@ -1,16 +1,26 @@
+use std::ops::Range;
+
 use rand::{
-    distributions::{uniform::SampleRange, Distribution, WeightedIndex},
+    distributions::{Distribution, WeightedIndex},
     Rng,
 };
 
 pub trait RngWrapper {
-    fn gen_range<R>(&mut self, range: R) -> usize
-    where
-        R: SampleRange<usize>;
+    fn gen_range(&mut self, range: Range<usize>) -> usize;
 
     fn sample_from_distribution(&mut self, distribution: &WeightedIndex<f64>) -> usize;
 }
 
+impl<W: RngWrapper + ?Sized> RngWrapper for &mut W {
+    fn gen_range(&mut self, range: Range<usize>) -> usize {
+        (**self).gen_range(range)
+    }
+
+    fn sample_from_distribution(&mut self, distribution: &WeightedIndex<f64>) -> usize {
+        (**self).sample_from_distribution(distribution)
+    }
+}
+
 pub struct Random<'a, T>
 where
     T: Rng,
@@ -31,10 +41,7 @@ impl<'a, T> RngWrapper for Random<'a, T>
 where
     T: Rng,
 {
-    fn gen_range<R>(&mut self, range: R) -> usize
-    where
-        R: SampleRange<usize>,
-    {
+    fn gen_range(&mut self, range: Range<usize>) -> usize {
         self.rng.gen_range(range)
     }
 
@@ -97,6 +104,8 @@ mod tests {
 
 #[cfg(test)]
 pub mod test_utils {
+    use std::ops::Range;
+
     use super::RngWrapper;
 
     pub struct RngTest {
@@ -127,10 +136,7 @@ pub mod test_utils {
     }
 
     impl RngWrapper for RngTest {
-        fn gen_range<R>(&mut self, _: R) -> usize
-        where
-            R: rand::distributions::uniform::SampleRange<usize>,
-        {
+        fn gen_range(&mut self, _: Range<usize>) -> usize {
             self.next()
         }
 
@@ -0,0 +1,183 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::Evaluation;
+
+/// Transformation of the fitnesses the individuals are selected on, adjusting the selection
+/// pressure otherwise dictated by the raw fitness magnitudes, see
+/// [`crate::evolution::EvolutionEngineBuilder::fitness_scaling`].
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum FitnessScaling {
+    /// Affine scaling keeping the mean fitness and giving the best one `ratio` times the mean,
+    /// e.g. 2 for the best individual to expect two selections, the slope being reduced when
+    /// the worst fitness would get negative.
+    Linear { ratio: f32 },
+    /// Fitness minus the mean lowered by `c` standard deviations, negative values being
+    /// truncated to 0.
+    SigmaTruncation { c: f32 },
+    /// Fitness raised to `exponent`, above 1 to favor the best individuals.
+    Power { exponent: f32 },
+}
+
+impl FitnessScaling {
+    /// Scales the fitnesses in place.
+    pub fn scale(&self, fitnesses: &mut [f32]) {
+        if fitnesses.is_empty() {
+            return;
+        }
+        let count = fitnesses.len() as f32;
+        let mean = fitnesses.iter().sum::<f32>() / count;
+        match *self {
+            FitnessScaling::Linear { ratio } => {
+                let max = fitnesses.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+                let min = fitnesses.iter().copied().fold(f32::INFINITY, f32::min);
+                if max <= mean {
+                    return;
+                }
+                let mut slope = (ratio - 1.0) * mean / (max - mean);
+                let mut intercept = mean * (1.0 - slope);
+                if slope * min + intercept < 0.0 {
+                    slope = mean / (mean - min);
+                    intercept = -min * slope;
+                }
+                for fitness in fitnesses.iter_mut() {
+                    *fitness = (slope * *fitness + intercept).max(0.0);
+                }
+            }
+            FitnessScaling::SigmaTruncation { c } => {
+                let variance = fitnesses
+                    .iter()
+                    .map(|fitness| (fitness - mean).powi(2))
+                    .sum::<f32>()
+                    / count;
+                let floor = mean - c * variance.sqrt();
+                for fitness in fitnesses.iter_mut() {
+                    *fitness = (*fitness - floor).max(0.0);
+                }
+            }
+            FitnessScaling::Power { exponent } => {
+                for fitness in fitnesses.iter_mut() {
+                    *fitness = fitness.max(0.0).powf(exponent);
+                }
+            }
+        }
+    }
+
+    /// Copies of the evaluations with the fitnesses of the evaluated ones scaled.
+    pub fn scale_evaluations(&self, evaluations: &[Evaluation]) -> Vec<Evaluation> {
+        let mut scaled = evaluations.to_vec();
+        let mut fitnesses = scaled
+            .iter()
+            .filter(|e| e.evaluated)
+            .map(|e| e.fitness)
+            .collect::<Vec<_>>();
+        self.scale(&mut fitnesses);
+        for (evaluation, fitness) in scaled.iter_mut().filter(|e| e.evaluated).zip(fitnesses) {
+            evaluation.fitness = fitness;
+        }
+        scaled
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Evaluation;
+
+    use super::FitnessScaling;
+
+    fn assert_close(expected: &[f32], actual: &[f32]) {
+        assert_eq!(expected.len(), actual.len());
+        for (expected, actual) in expected.iter().zip(actual) {
+            assert!(
+                (expected - actual).abs() < 1e-5,
+                "Expected {expected} but got {actual}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_fitness_scaling_linear() {
+        // Given
+        let mut fitnesses = [0.4, 0.5, 0.6];
+
+        // When
+        FitnessScaling::Linear { ratio: 2.0 }.scale(&mut fitnesses);
+
+        // Then
+        assert_close(&[0.0, 0.5, 1.0], &fitnesses);
+
+        // Given
+        let mut fitnesses = [0.1, 0.5, 0.5, 0.5, 0.9];
+
+        // When
+        FitnessScaling::Linear { ratio: 3.0 }.scale(&mut fitnesses);
+
+        // Then
+        assert_close(&[0.0, 0.5, 0.5, 0.5, 1.0], &fitnesses);
+
+        // Given
+        let mut fitnesses = [0.5, 0.5];
+
+        // When
+        FitnessScaling::Linear { ratio: 2.0 }.scale(&mut fitnesses);
+
+        // Then
+        assert_eq!([0.5, 0.5], fitnesses, "Should not scale equal fitnesses");
+    }
+
+    #[test]
+    fn test_fitness_scaling_sigma_truncation() {
+        // Given
+        let mut fitnesses = [0.2, 0.4, 0.6];
+
+        // When
+        FitnessScaling::SigmaTruncation { c: 1.0 }.scale(&mut fitnesses);
+
+        // Then
+        let sigma = (0.08f32 / 3.0).sqrt();
+        assert_close(&[0.0, sigma, 0.2 + sigma], &fitnesses);
+    }
+
+    #[test]
+    fn test_fitness_scaling_power() {
+        // Given
+        let mut fitnesses = [0.5, -0.5, 1.0];
+
+        // When
+        FitnessScaling::Power { exponent: 2.0 }.scale(&mut fitnesses);
+
+        // Then
+        assert_close(&[0.25, 0.0, 1.0], &fitnesses);
+    }
+
+    #[test]
+    fn test_fitness_scaling_scale_evaluations() {
+        // Given
+        let evaluations = vec![
+            Evaluation {
+                genome: vec![1],
+                fitness: 0.5,
+                evaluated: true,
+                ..Default::default()
+            },
+            Evaluation {
+                genome: vec![2],
+                fitness: 0.8,
+                evaluated: false,
+                ..Default::default()
+            },
+        ];
+
+        // When
+        let result = FitnessScaling::Power { exponent: 2.0 }.scale_evaluations(&evaluations);
+
+        // Then
+        assert_eq!(0.25, result[0].fitness);
+        assert_eq!(
+            0.8, result[1].fitness,
+            "Should leave unevaluated genomes as is"
+        );
+        assert_eq!(evaluations[1].genome, result[1].genome);
+    }
+}
@@ -0,0 +1,144 @@
+use crate::{
+    selection::{SelectionError, SelectionResult},
+    Evaluation,
+};
+
+use super::{
+    rng_wrapper::RngWrapper,
+    select_by_weight::{select_by_weights, select_by_weights_iter, MIN_WEIGHT},
+};
+
+/// Selects by weight, the weight of each genome being divided by its age layer so that
+/// genomes having survived many generations are less likely to be picked.
+pub fn select_by_age(
+    evaluations: &[Evaluation],
+    expected_count: usize,
+    rng: &mut impl RngWrapper,
+) -> SelectionResult {
+    let weights = evaluations
+        .iter()
+        .map(|e| (MIN_WEIGHT + e.fitness) as f64 / (1 + e.age) as f64);
+    select_by_weights(weights, expected_count, rng)
+}
+
+pub(super) fn select_by_age_iter<'a>(
+    evaluations: &'a [Evaluation],
+    expected_count: usize,
+    rng: impl RngWrapper + 'a,
+) -> Result<impl Iterator<Item = Result<usize, SelectionError>> + 'a, SelectionError> {
+    let weights = evaluations
+        .iter()
+        .map(|e| (MIN_WEIGHT + e.fitness) as f64 / (1 + e.age) as f64);
+    select_by_weights_iter(weights, expected_count, rng)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use common_test::get_seeded_rng;
+
+    use super::select_by_age;
+
+    use crate::{
+        selection::{
+            rng_wrapper::{test_utils::RngTest, Random},
+            SelectionError,
+        },
+        Evaluation,
+    };
+
+    #[test]
+    fn select_by_age_should_return_result() {
+        let evaluations = vec![
+            Evaluation {
+                genome: vec![1],
+                fitness: 1.0,
+                fitness_variance: None,
+                objectives: vec![],
+                age: 3,
+                evaluated: true,
+                phenotype: None,
+                metadata: BTreeMap::new(),
+            },
+            Evaluation {
+                genome: vec![2],
+                fitness: 2.0,
+                fitness_variance: None,
+                objectives: vec![],
+                age: 0,
+                evaluated: true,
+                phenotype: None,
+                metadata: BTreeMap::new(),
+            },
+            Evaluation {
+                genome: vec![3],
+                fitness: 1.0,
+                fitness_variance: None,
+                objectives: vec![],
+                age: 1,
+                evaluated: true,
+                phenotype: None,
+                metadata: BTreeMap::new(),
+            },
+        ];
+
+        let mut rng_mock = RngTest::with_samples(vec![2, 0, 0, 1]);
+        let result = select_by_age(&evaluations, 3, &mut rng_mock);
+        assert_eq!(result, Ok(vec![2, 1, 0]));
+    }
+
+    #[test]
+    fn select_by_age_should_favor_young_genomes() {
+        // Given
+        let mut rng = get_seeded_rng().unwrap();
+        let mut random = Random::new(&mut rng);
+        let evaluations = vec![
+            Evaluation {
+                genome: vec![1],
+                fitness: 1.0,
+                fitness_variance: None,
+                objectives: vec![],
+                age: 99,
+                evaluated: true,
+                phenotype: None,
+                metadata: BTreeMap::new(),
+            },
+            Evaluation {
+                genome: vec![2],
+                fitness: 1.0,
+                fitness_variance: None,
+                objectives: vec![],
+                age: 0,
+                evaluated: true,
+                phenotype: None,
+                metadata: BTreeMap::new(),
+            },
+        ];
+
+        // When
+        let young_count = (0..1000)
+            .map(|_| select_by_age(&evaluations, 1, &mut random).unwrap()[0])
+            .filter(|&index| index == 1)
+            .count();
+
+        // Then
+        assert!(
+            young_count > 900,
+            "Should mostly select the youngest genome when fitnesses are equal"
+        );
+    }
+
+    #[test]
+    fn select_by_age_should_return_error_when_not_valid_expected_count() {
+        let evaluations = vec![Evaluation {
+            genome: vec![1],
+            fitness: 1.0,
+            ..Default::default()
+        }];
+
+        let mut rng_mock = RngTest::new();
+        let result = select_by_age(&evaluations, 2, &mut rng_mock);
+        assert_eq!(result, Err(SelectionError::OutOfRange(2, 1)));
+    }
+}
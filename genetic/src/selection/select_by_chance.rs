@@ -1,17 +1,24 @@
-use std::cmp::min;
-
 use crate::{
     selection::{SelectionError, SelectionResult},
     Evaluation,
 };
 
-use super::rng_wrapper::RngWrapper;
+use super::{rng_wrapper::RngWrapper, SparsePermutation};
 
 pub fn select_by_chance(
     evaluations: &[Evaluation],
     expected_count: usize,
     rng: &mut impl RngWrapper,
 ) -> SelectionResult {
+    Ok(select_by_chance_iter(evaluations, expected_count, rng)?.collect())
+}
+
+/// Lazily draws `expected_count` distinct indexes uniformly.
+pub(super) fn select_by_chance_iter<'a>(
+    evaluations: &'a [Evaluation],
+    expected_count: usize,
+    mut rng: impl RngWrapper + 'a,
+) -> Result<impl Iterator<Item = usize> + 'a, SelectionError> {
     let len = evaluations.len();
 
     // Cannot select above evaluations count
@@ -19,18 +26,15 @@ pub fn select_by_chance(
         return Err(SelectionError::OutOfRange(expected_count, len));
     }
 
-    let selected_indexes = if expected_count > 0 {
-        let mut indexes: Vec<usize> = (0..len).collect();
-        let selection_count = min(expected_count, len - 1);
-        for i in 0..selection_count {
+    let mut indexes = SparsePermutation::default();
+    Ok((0..expected_count).map(move |i| {
+        // The last remaining index needs no draw
+        if i + 1 < len {
             let selected_index = rng.gen_range(i..len);
             indexes.swap(i, selected_index);
         }
-        indexes[0..expected_count].to_vec()
-    } else {
-        vec![]
-    };
-    Ok(selected_indexes)
+        indexes.get(i)
+    }))
 }
 
 #[cfg(test)]
@@ -49,14 +53,17 @@ mod tests {
             Evaluation {
                 genome: vec![1],
                 fitness: 1.0,
+                ..Default::default()
             },
             Evaluation {
                 genome: vec![2],
                 fitness: 2.0,
+                ..Default::default()
             },
             Evaluation {
                 genome: vec![3],
                 fitness: 1.0,
+                ..Default::default()
             },
         ];
 
@@ -72,6 +79,7 @@ mod tests {
         let evaluations = vec![Evaluation {
             genome: vec![1],
             fitness: 1.0,
+            ..Default::default()
         }];
 
         let mut rng_mock = RngTest::new();
@@ -84,6 +92,7 @@ mod tests {
         let evaluations = vec![Evaluation {
             genome: vec![1],
             fitness: 1.0,
+            ..Default::default()
         }];
 
         let mut rng_mock = RngTest::new();
@@ -1,4 +1,7 @@
-use std::cmp::{min, Ordering};
+use std::{
+    cmp::{min, Ordering, Reverse},
+    collections::BinaryHeap,
+};
 
 use crate::{
     selection::{SelectionError, SelectionResult},
@@ -13,6 +16,16 @@ pub fn select_by_rank(
     max_rank: usize,
     rng: &mut impl RngWrapper,
 ) -> SelectionResult {
+    Ok(select_by_rank_iter(evaluations, expected_count, max_rank, rng)?.collect())
+}
+
+/// Lazily draws `expected_count` distinct indexes uniformly among the `max_rank` fittest.
+pub(super) fn select_by_rank_iter<'a>(
+    evaluations: &'a [Evaluation],
+    expected_count: usize,
+    max_rank: usize,
+    mut rng: impl RngWrapper + 'a,
+) -> Result<impl Iterator<Item = usize> + 'a, SelectionError> {
     // Cannot select above max_rank
     if expected_count > max_rank {
         return Err(SelectionError::OutOfRank(expected_count, max_rank));
@@ -25,26 +38,69 @@ pub fn select_by_rank(
         return Err(SelectionError::OutOfRange(expected_count, len));
     }
 
-    let selected_indexes = if expected_count > 0 {
-        let mut indexes: Vec<usize> = (0..len).collect();
-        indexes.sort_by(|&a, &b| {
-            evaluations[b]
-                .fitness
-                .partial_cmp(&evaluations[a].fitness)
-                .unwrap_or(Ordering::Equal)
-        });
-        let max_rank = min(max_rank, len);
-        let selection_count = min(expected_count, max_rank - 1);
-        for i in 0..selection_count {
-            let selected_index = rng.gen_range(i..max_rank);
-            indexes.swap(i, selected_index);
-        }
-        indexes[0..expected_count].to_vec()
+    let max_rank = min(max_rank, len);
+    let mut indexes = if expected_count > 0 {
+        fittest(evaluations, max_rank)
     } else {
         vec![]
     };
+    Ok((0..expected_count).map(move |i| {
+        // The last remaining index needs no draw
+        if i + 1 < max_rank {
+            let selected_index = rng.gen_range(i..max_rank);
+            indexes.swap(i, selected_index);
+        }
+        indexes[i]
+    }))
+}
 
-    Ok(selected_indexes)
+/// Evaluation ordered by fitness then by reversed position, so that the fittest and first
+/// ones are the greatest.
+struct Ranked {
+    fitness: f32,
+    index: usize,
+}
+
+impl Ord for Ranked {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.fitness
+            .partial_cmp(&other.fitness)
+            .unwrap_or(Ordering::Equal)
+            .then(other.index.cmp(&self.index))
+    }
+}
+
+impl PartialOrd for Ranked {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for Ranked {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Ranked {}
+
+/// Indexes of the `count` fittest evaluations from the fittest, ties in their order, keeping
+/// only them in memory.
+fn fittest(evaluations: &[Evaluation], count: usize) -> Vec<usize> {
+    let mut heap = BinaryHeap::with_capacity(count + 1);
+    for (index, evaluation) in evaluations.iter().enumerate() {
+        heap.push(Reverse(Ranked {
+            fitness: evaluation.fitness,
+            index,
+        }));
+        if heap.len() > count {
+            heap.pop();
+        }
+    }
+    heap.into_sorted_vec()
+        .into_iter()
+        .map(|Reverse(ranked)| ranked.index)
+        .collect()
 }
 
 #[cfg(test)]
@@ -62,18 +118,22 @@ mod tests {
             Evaluation {
                 genome: vec![1],
                 fitness: 2.0,
+                ..Default::default()
             },
             Evaluation {
                 genome: vec![2],
                 fitness: 5.0,
+                ..Default::default()
             },
             Evaluation {
                 genome: vec![3],
                 fitness: 1.0,
+                ..Default::default()
             },
             Evaluation {
                 genome: vec![4],
                 fitness: 1.0,
+                ..Default::default()
             },
         ];
 
@@ -90,10 +150,12 @@ mod tests {
             Evaluation {
                 genome: vec![1],
                 fitness: 1.0,
+                ..Default::default()
             },
             Evaluation {
                 genome: vec![2],
                 fitness: 1.0,
+                ..Default::default()
             },
         ];
 
@@ -118,6 +180,7 @@ mod tests {
         let evaluations = vec![Evaluation {
             genome: vec![1],
             fitness: 1.0,
+            ..Default::default()
         }];
 
         let mut rng_mock = RngTest::new();
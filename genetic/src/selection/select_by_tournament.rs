@@ -51,6 +51,18 @@ pub fn select_by_tournament(
     Ok(selected_indexes)
 }
 
+/// Iterator over [`select_by_tournament`]: tournaments may reorder the slots already selected,
+/// so the selection is drawn at once.
+pub(super) fn select_by_tournament_iter(
+    evaluations: &[Evaluation],
+    expected_count: usize,
+    pool_size: usize,
+    mut rng: impl RngWrapper,
+) -> Result<impl Iterator<Item = usize>, SelectionError> {
+    select_by_tournament(evaluations, expected_count, pool_size, &mut rng)
+        .map(|selected_indexes| selected_indexes.into_iter())
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -98,10 +110,12 @@ mod tests {
             Evaluation {
                 genome: vec![1],
                 fitness: 1.0,
+                ..Default::default()
             },
             Evaluation {
                 genome: vec![2],
                 fitness: 1.0,
+                ..Default::default()
             },
         ];
         let mut rng_mock = RngTest::with_samples(vec![2, 0, 0, 1]);
@@ -123,6 +137,7 @@ mod tests {
         let evaluations = vec![Evaluation {
             genome: vec![1],
             fitness: 1.0,
+            ..Default::default()
         }];
         let mut rng_mock = RngTest::new();
 
@@ -1,4 +1,4 @@
-use std::{cmp::min, collections::VecDeque};
+use std::collections::VecDeque;
 
 use rand::distributions::WeightedIndex;
 
@@ -7,73 +7,211 @@ use crate::{
     Evaluation,
 };
 
-use super::rng_wrapper::RngWrapper;
+use super::{rng_wrapper::RngWrapper, SparsePermutation};
 
-const MIN_WEIGHT: f32 = 0.01;
+pub(super) const MIN_WEIGHT: f32 = 0.01;
 
 pub fn select_by_weight(
     evaluations: &[Evaluation],
     expected_count: usize,
     rng: &mut impl RngWrapper,
 ) -> SelectionResult {
-    let len = evaluations.len();
+    let weights = evaluations.iter().map(|e| (MIN_WEIGHT + e.fitness) as f64);
+    select_by_weights(weights, expected_count, rng)
+}
+
+pub(super) fn select_by_weight_iter<'a>(
+    evaluations: &'a [Evaluation],
+    expected_count: usize,
+    rng: impl RngWrapper + 'a,
+) -> Result<impl Iterator<Item = Result<usize, SelectionError>> + 'a, SelectionError> {
+    let weights = evaluations.iter().map(|e| (MIN_WEIGHT + e.fitness) as f64);
+    select_by_weights_iter(weights, expected_count, rng)
+}
+
+/// Selects by weight, the fitness of each genome being normalized by the best one then raised to
+/// `exponent`: 0 selects uniformly, 1 proportionally to the fitness, higher values increasingly
+/// favor the best genomes.
+pub fn select_by_biased_weight(
+    evaluations: &[Evaluation],
+    expected_count: usize,
+    exponent: f32,
+    rng: &mut impl RngWrapper,
+) -> SelectionResult {
+    select_by_weights(biased_weights(evaluations, exponent), expected_count, rng)
+}
+
+pub(super) fn select_by_biased_weight_iter<'a>(
+    evaluations: &'a [Evaluation],
+    expected_count: usize,
+    exponent: f32,
+    rng: impl RngWrapper + 'a,
+) -> Result<impl Iterator<Item = Result<usize, SelectionError>> + 'a, SelectionError> {
+    select_by_weights_iter(biased_weights(evaluations, exponent), expected_count, rng)
+}
+
+fn biased_weights(evaluations: &[Evaluation], exponent: f32) -> impl Iterator<Item = f64> + '_ {
+    let best = evaluations.iter().map(|e| e.fitness).fold(0.0, f32::max);
+    evaluations.iter().map(move |e| {
+        let normalized = if best > 0.0 { e.fitness / best } else { 1.0 };
+        ((MIN_WEIGHT + normalized) as f64).powf(exponent as f64)
+    })
+}
+
+/// Draws `expected_count` distinct indexes, each with a probability proportional to its weight
+/// among the remaining ones.
+pub(super) fn select_by_weights(
+    weights: impl Iterator<Item = f64>,
+    expected_count: usize,
+    rng: &mut impl RngWrapper,
+) -> SelectionResult {
+    select_by_weights_iter(weights, expected_count, rng)?.collect()
+}
+
+/// Lazy variant of [`select_by_weights`], the drawn indexes being tracked sparsely.
+pub(super) fn select_by_weights_iter<'a>(
+    weights: impl Iterator<Item = f64> + 'a,
+    expected_count: usize,
+    mut rng: impl RngWrapper + 'a,
+) -> Result<impl Iterator<Item = Result<usize, SelectionError>> + 'a, SelectionError> {
+    let mut values = weights.collect::<VecDeque<_>>();
+    let len = values.len();
 
     // Cannot select above evaluations count
     if expected_count > len {
         return Err(SelectionError::OutOfRange(expected_count, len));
     }
 
-    let selected_indexes = if expected_count > 1 {
-        let mut indexes = (0..len).collect::<Vec<_>>();
-        let selection_count = min(expected_count, len - 1);
-        let mut values = evaluations
-            .iter()
-            .map(|e| e.fitness)
-            .collect::<VecDeque<_>>();
-        for i in 0..selection_count {
-            let total = values.iter().map(|&v| (MIN_WEIGHT + v) as f64).sum::<f64>();
-            let weights = values
-                .iter()
-                .map(|&v| (MIN_WEIGHT + v) as f64 / total)
-                .collect::<Vec<_>>();
-            let distribution = WeightedIndex::new(weights)
+    let mut indexes = SparsePermutation::default();
+    Ok((0..expected_count).map(move |i| {
+        // The last remaining index needs no draw
+        if i + 1 < len {
+            let total = values.iter().sum::<f64>();
+            let distribution = WeightedIndex::new(values.iter().map(|&v| v / total))
                 .map_err(|e| SelectionError::InvalidWeights(e.to_string()))?;
             let index = rng.sample_from_distribution(&distribution);
             indexes.swap(i, i + index);
             values.swap(0, index);
             values.pop_front();
         }
-        indexes[0..expected_count].to_vec()
-    } else {
-        vec![]
-    };
-
-    Ok(selected_indexes)
+        Ok(indexes.get(i))
+    }))
 }
 
 #[cfg(test)]
 mod tests {
-    use super::select_by_weight;
+    use common_test::get_seeded_rng;
+
+    use super::{select_by_biased_weight, select_by_weight, select_by_weights, MIN_WEIGHT};
 
     use crate::{
-        selection::{rng_wrapper::test_utils::RngTest, SelectionError},
+        selection::{
+            rng_wrapper::{test_utils::RngTest, Random},
+            SelectionError,
+        },
         Evaluation,
     };
 
+    /// Frequencies at which each evaluation is selected alone over many draws.
+    fn biased_frequencies(evaluations: &[Evaluation], exponent: f32) -> Vec<f32> {
+        let mut rng = get_seeded_rng().unwrap();
+        let mut random = Random::new(&mut rng);
+        let draws = 20_000;
+        let mut counts = vec![0; evaluations.len()];
+        for _ in 0..draws {
+            let selected = select_by_biased_weight(evaluations, 1, exponent, &mut random).unwrap();
+            counts[selected[0]] += 1;
+        }
+        counts
+            .into_iter()
+            .map(|count| count as f32 / draws as f32)
+            .collect()
+    }
+
+    fn fitness_evaluations(fitnesses: &[f32]) -> Vec<Evaluation> {
+        fitnesses
+            .iter()
+            .map(|&fitness| Evaluation {
+                fitness,
+                evaluated: true,
+                ..Default::default()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn select_by_biased_weight_should_follow_the_biased_distribution() {
+        // Given
+        let fitnesses = [0.2, 0.4, 0.8];
+        let evaluations = fitness_evaluations(&fitnesses);
+
+        for exponent in [0.0, 1.0, 3.0] {
+            // When
+            let result = biased_frequencies(&evaluations, exponent);
+
+            // Then
+            let weights = fitnesses.map(|fitness| (MIN_WEIGHT + fitness / 0.8).powf(exponent));
+            let total = weights.iter().sum::<f32>();
+            assert!(
+                result
+                    .iter()
+                    .zip(weights)
+                    .all(|(frequency, weight)| (frequency - weight / total).abs() < 0.015),
+                "Should select proportionally to the normalized fitness raised to {exponent}, got {result:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn select_by_biased_weight_should_increase_pressure_with_exponent() {
+        // Given
+        let evaluations = fitness_evaluations(&[0.1, 0.3, 0.5, 0.7, 0.9]);
+
+        // When
+        let result =
+            [0.0, 0.5, 1.0, 2.0, 4.0].map(|exponent| biased_frequencies(&evaluations, exponent)[4]);
+
+        // Then
+        assert!(
+            result.windows(2).all(|pair| pair[0] < pair[1]),
+            "Should select the best genome more often as the exponent grows, got {result:?}"
+        );
+        assert!(
+            (result[0] - 0.2).abs() < 0.015,
+            "Should select uniformly with a zero exponent"
+        );
+    }
+
+    #[test]
+    fn select_by_biased_weight_should_handle_null_fitnesses() {
+        // Given
+        let evaluations = fitness_evaluations(&[0.0, 0.0]);
+        let mut rng = get_seeded_rng().unwrap();
+
+        // When
+        let result = select_by_biased_weight(&evaluations, 2, 2.0, &mut Random::new(&mut rng));
+
+        // Then
+        assert_eq!(2, result.unwrap().len());
+    }
+
     #[test]
     fn select_by_weight_should_return_result() {
         let evaluations = vec![
             Evaluation {
                 genome: vec![1],
                 fitness: 1.0,
+                ..Default::default()
             },
             Evaluation {
                 genome: vec![2],
                 fitness: 2.0,
+                ..Default::default()
             },
             Evaluation {
                 genome: vec![3],
                 fitness: 1.0,
+                ..Default::default()
             },
         ];
 
@@ -89,6 +227,7 @@ mod tests {
         let evaluations = vec![Evaluation {
             genome: vec![1],
             fitness: 1.0,
+            ..Default::default()
         }];
 
         let mut rng_mock = RngTest::new();
@@ -101,10 +240,38 @@ mod tests {
         let evaluations = vec![Evaluation {
             genome: vec![1],
             fitness: 1.0,
+            ..Default::default()
         }];
 
         let mut rng_mock = RngTest::new();
         let result = select_by_weight(&evaluations, 0, &mut rng_mock);
         assert_eq!(result, Ok(vec![]));
     }
+
+    #[test]
+    fn select_by_weight_should_select_single_genome() {
+        let evaluations = vec![
+            Evaluation {
+                genome: vec![1],
+                fitness: 1.0,
+                ..Default::default()
+            },
+            Evaluation {
+                genome: vec![2],
+                fitness: 2.0,
+                ..Default::default()
+            },
+        ];
+
+        let mut rng_mock = RngTest::with_samples(vec![1]);
+        let result = select_by_weight(&evaluations, 1, &mut rng_mock);
+        assert_eq!(result, Ok(vec![1]));
+    }
+
+    #[test]
+    fn select_by_weights_should_return_error_when_weights_are_not_valid() {
+        let mut rng_mock = RngTest::new();
+        let result = select_by_weights([0.0, 0.0].into_iter(), 1, &mut rng_mock);
+        assert!(matches!(result, Err(SelectionError::InvalidWeights(_))));
+    }
 }
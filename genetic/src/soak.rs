@@ -0,0 +1,328 @@
+//! Soak testing of the engine: runs many short evolutions in a loop, as a server creating an
+//! engine per request would, and fails on leak-like growth or invalid status transitions.
+//!
+//! Memory is only tracked when [`CountingAllocator`] is the global allocator of the binary.
+
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex, PoisonError,
+    },
+};
+
+use common::subject_observer::{Observer, Subject};
+use futures::executor::block_on;
+use log::debug;
+use rand::Rng;
+use thiserror::Error;
+
+use crate::{
+    adaptation::Strategy,
+    evolution::{
+        EventType, EvolutionConfig, EvolutionEngine, EvolutionError, EvolutionStatus,
+        MaxGenerations,
+    },
+};
+
+static ALLOCATED_BYTES: AtomicUsize = AtomicUsize::new(0);
+static PEAK_BYTES: AtomicUsize = AtomicUsize::new(0);
+
+fn record_allocation(size: usize) {
+    let allocated = ALLOCATED_BYTES.fetch_add(size, Ordering::Relaxed) + size;
+    PEAK_BYTES.fetch_max(allocated, Ordering::Relaxed);
+}
+
+/// Allocator of the system keeping count of the allocated bytes, see [`allocated_bytes`].
+pub struct CountingAllocator;
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            record_allocation(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        ALLOCATED_BYTES.fetch_sub(layout.size(), Ordering::Relaxed);
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            ALLOCATED_BYTES.fetch_sub(layout.size(), Ordering::Relaxed);
+            record_allocation(new_size);
+        }
+        new_ptr
+    }
+}
+
+/// Bytes currently allocated through the [`CountingAllocator`].
+pub fn allocated_bytes() -> usize {
+    ALLOCATED_BYTES.load(Ordering::Relaxed)
+}
+
+/// Most bytes allocated at once through the [`CountingAllocator`] since the last reset.
+pub fn peak_allocated_bytes() -> usize {
+    PEAK_BYTES.load(Ordering::Relaxed)
+}
+
+fn reset_peak_allocated_bytes() {
+    PEAK_BYTES.store(allocated_bytes(), Ordering::Relaxed);
+}
+
+#[derive(Error, Debug)]
+pub enum SoakError {
+    #[error(transparent)]
+    Evolution(#[from] EvolutionError),
+    #[error("Run {run} changed status from {from} to {to}")]
+    InvalidTransition {
+        run: usize,
+        from: EvolutionStatus,
+        to: EvolutionStatus,
+    },
+    #[error("Run {run} kept {count} observer(s) registered or referenced")]
+    ObserverLeak { run: usize, count: usize },
+    #[error("Allocated memory grew by {growth} bytes, more than the allowed {allowed}")]
+    MemoryGrowth { growth: usize, allowed: usize },
+}
+
+#[derive(Debug, Clone)]
+pub struct SoakConfig {
+    pub evolution: EvolutionConfig,
+    /// Number of evolutions run after the warmup.
+    pub runs: usize,
+    /// Number of evolutions run before measuring the baseline, so that lazily allocated state
+    /// (loggers, thread pools...) is not mistaken for a leak.
+    pub warmup_runs: usize,
+    pub generations: u64,
+    /// Growth of the allocated bytes over the runs above which they are considered leaking.
+    pub allowed_growth: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SoakReport {
+    pub runs: usize,
+    pub baseline_bytes: usize,
+    pub peak_bytes: usize,
+    pub final_bytes: usize,
+}
+
+/// Whether the engine may go from status `from` to `to`.
+///
+/// A halt requested while the last generation completes ends the run as completed.
+pub fn is_valid_transition(from: EvolutionStatus, to: EvolutionStatus) -> bool {
+    use EvolutionStatus::*;
+    matches!(
+        (from, to),
+        (New, Initializing)
+            | (Initializing, Running)
+            | (Running, Paused)
+            | (Paused, Running)
+            | (Running | Paused, Halting)
+            | (Halting, Halted)
+            | (Running | Halting, Completed)
+    )
+}
+
+/// Records the statuses of a run, requesting a halt after the first evaluation when told to.
+struct StatusRecorder {
+    halt: bool,
+    statuses: Mutex<Vec<EvolutionStatus>>,
+}
+
+impl Observer<EvolutionEngine, EventType> for StatusRecorder {
+    fn update(&self, source: &EvolutionEngine, event: EventType) {
+        match event {
+            EventType::StatusChanged(status) => self
+                .statuses
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .push(status),
+            EventType::Evaluated if self.halt => {
+                // Fails only when the run already stopped
+                let _ = source.halt();
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Runs `config.runs` evolutions of `strategy` one after the other, alternating runs going to
+/// completion and runs halted early.
+pub fn soak<T: Strategy>(
+    strategy: &T,
+    config: &SoakConfig,
+    rng: &mut impl Rng,
+) -> Result<SoakReport, SoakError> {
+    let shared_observer = Arc::new(StatusRecorder {
+        halt: false,
+        statuses: Default::default(),
+    });
+
+    let mut baseline_bytes = allocated_bytes();
+    for run in 0..config.warmup_runs + config.runs {
+        if run == config.warmup_runs {
+            baseline_bytes = allocated_bytes();
+            reset_peak_allocated_bytes();
+        }
+        soak_run(strategy, config, run, &shared_observer, rng)?;
+        shared_observer
+            .statuses
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clear();
+    }
+
+    let final_bytes = allocated_bytes();
+    let peak_bytes = peak_allocated_bytes();
+    let growth = final_bytes.saturating_sub(baseline_bytes);
+    debug!("Soak run allocated {growth} more bytes, peaking at {peak_bytes}");
+    if growth > config.allowed_growth {
+        return Err(SoakError::MemoryGrowth {
+            growth,
+            allowed: config.allowed_growth,
+        });
+    }
+    Ok(SoakReport {
+        runs: config.runs,
+        baseline_bytes,
+        peak_bytes,
+        final_bytes,
+    })
+}
+
+fn soak_run<T: Strategy>(
+    strategy: &T,
+    config: &SoakConfig,
+    run: usize,
+    shared_observer: &Arc<StatusRecorder>,
+    rng: &mut impl Rng,
+) -> Result<(), SoakError> {
+    let recorder = Arc::new(StatusRecorder {
+        halt: run % 2 == 1,
+        statuses: Default::default(),
+    });
+    {
+        let mut engine = EvolutionEngine::default();
+        engine.register_observer(recorder.clone());
+        engine.register_observer(shared_observer.clone());
+        let generations = config.generations;
+        block_on(engine.start(
+            strategy,
+            &config.evolution,
+            MaxGenerations(generations.saturating_sub(1)),
+            rng,
+        ))?;
+
+        engine.unregister_observer(recorder.clone());
+        if engine.observer_count() != 1 {
+            return Err(SoakError::ObserverLeak {
+                run,
+                count: engine.observer_count(),
+            });
+        }
+    }
+
+    let references = Arc::strong_count(&recorder) + Arc::strong_count(shared_observer) - 2;
+    if references > 0 {
+        return Err(SoakError::ObserverLeak {
+            run,
+            count: references,
+        });
+    }
+
+    let statuses = recorder
+        .statuses
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner);
+    statuses
+        .iter()
+        .try_fold(EvolutionStatus::New, |from, &to| {
+            is_valid_transition(from, to)
+                .then_some(to)
+                .ok_or(SoakError::InvalidTransition { run, from, to })
+        })
+        .map(|_| ())
+}
+
+#[cfg(test)]
+mod tests {
+    use common_test::get_seeded_rng;
+
+    use crate::{
+        adaptation::Strategy,
+        evolution::{EvolutionConfig, EvolutionStatus},
+        Genome,
+    };
+
+    use super::{is_valid_transition, soak, SoakConfig};
+
+    struct Sum;
+
+    impl Strategy for Sum {
+        type Phenotype = Genome;
+
+        fn genome_size(&self) -> usize {
+            4
+        }
+
+        fn decode(&self, genome: &Genome) -> Genome {
+            genome.clone()
+        }
+
+        fn evaluate(&self, genome: &Genome) -> f32 {
+            genome.iter().map(|&g| g as f32).sum::<f32>() / 1020.0
+        }
+    }
+
+    #[test]
+    fn test_is_valid_transition() {
+        // Then
+        assert!(is_valid_transition(
+            EvolutionStatus::New,
+            EvolutionStatus::Initializing
+        ));
+        assert!(is_valid_transition(
+            EvolutionStatus::Halting,
+            EvolutionStatus::Halted
+        ));
+        assert!(
+            !is_valid_transition(EvolutionStatus::New, EvolutionStatus::Running),
+            "Should reject skipped initialization"
+        );
+        assert!(!is_valid_transition(
+            EvolutionStatus::Completed,
+            EvolutionStatus::Running
+        ));
+    }
+
+    #[test]
+    fn test_soak() {
+        // Given
+        let mut rng = get_seeded_rng().unwrap();
+        let config = SoakConfig {
+            evolution: EvolutionConfig {
+                population_size: 8,
+                generation_renewal_config: None,
+                max_duration: None,
+            },
+            runs: 50,
+            warmup_runs: 5,
+            generations: 3,
+            allowed_growth: 0,
+        };
+
+        // When
+        let result = soak(&Sum, &config, &mut rng);
+
+        // Then
+        assert!(
+            result.is_ok(),
+            "Should run evolutions without leaking: {result:?}"
+        );
+    }
+}
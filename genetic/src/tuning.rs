@@ -0,0 +1,491 @@
+use log::debug;
+#[cfg(feature = "validation")]
+use log::warn;
+use rand::{seq::index::sample, Rng};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "validation")]
+use validator::Validate;
+
+use crate::{
+    adaptation::Strategy,
+    evolution::{
+        EvolutionConfig, EvolutionEngineBuilder, EvolutionError, GenerationRenewalConfig,
+        GeneticRenewalParam, MaxGenerations,
+    },
+    selection::SelectionType,
+};
+
+/// Value given to a setting of the evolution by a candidate of the search.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Hyperparameter {
+    PopulationSize(usize),
+    /// Ratio of the cloning pool, created with default settings if missing.
+    CloningRatio(f32),
+    /// Ratio of the crossover pool, created with default settings if missing.
+    CrossoverRatio(f32),
+    /// See [`GenerationRenewalConfig::random_ratio`].
+    RandomRatio(f32),
+    /// Mutation rate of the pools of the configuration.
+    MutationRate(f32),
+    /// Selection of the pools of the configuration.
+    SelectionType(SelectionType),
+}
+
+impl Hyperparameter {
+    pub fn apply(&self, config: &mut EvolutionConfig) {
+        match self {
+            Hyperparameter::PopulationSize(size) => config.population_size = *size,
+            Hyperparameter::CloningRatio(ratio) => {
+                renewal(config)
+                    .cloning
+                    .get_or_insert_with(Default::default)
+                    .ratio = *ratio;
+            }
+            Hyperparameter::CrossoverRatio(ratio) => {
+                renewal(config)
+                    .crossover
+                    .get_or_insert_with(Default::default)
+                    .ratio = *ratio;
+            }
+            Hyperparameter::RandomRatio(ratio) => renewal(config).random_ratio = Some(*ratio),
+            Hyperparameter::MutationRate(rate) => {
+                for param in pools(config) {
+                    param.mutation_rate = Some(*rate);
+                }
+            }
+            Hyperparameter::SelectionType(selection_type) => {
+                for param in pools(config) {
+                    param.selection_type = selection_type.clone();
+                }
+            }
+        }
+    }
+}
+
+fn renewal(config: &mut EvolutionConfig) -> &mut GenerationRenewalConfig {
+    config
+        .generation_renewal_config
+        .get_or_insert(GenerationRenewalConfig {
+            cloning: None,
+            crossover: None,
+            random_ratio: None,
+        })
+}
+
+fn pools(config: &mut EvolutionConfig) -> impl Iterator<Item = &mut GeneticRenewalParam> {
+    config
+        .generation_renewal_config
+        .iter_mut()
+        .flat_map(|renewal| [renewal.cloning.as_mut(), renewal.crossover.as_mut()])
+        .flatten()
+}
+
+/// How the candidates of the search space are picked and compared.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum SearchMethod {
+    /// Every candidate runs with the budget of the tuner.
+    Grid,
+    /// The given number of distinct candidates drawn at random runs with the budget of the
+    /// tuner.
+    Random(usize),
+    /// Every candidate runs with the budget of the tuner, the fittest half running again with
+    /// twice the budget until one is left, so that most of the time goes to the promising ones.
+    SuccessiveHalving,
+}
+
+/// Outcome of the runs of a candidate.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Trial {
+    /// Values of the candidate, one for each dimension of the search space.
+    pub hyperparameters: Vec<Hyperparameter>,
+    /// Configuration of the runs, the base one with the values applied.
+    pub config: EvolutionConfig,
+    /// Generations each run went through.
+    pub generations: u64,
+    /// Best fitness reached by the runs, averaged over the repeats.
+    pub fitness: f32,
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TuningReport {
+    /// Trials in run order, candidates of successive halving having one per round.
+    pub trials: Vec<Trial>,
+}
+
+impl TuningReport {
+    /// Fittest trial among the ones given the largest budget.
+    pub fn best(&self) -> Option<&Trial> {
+        self.trials.iter().max_by(|a, b| {
+            a.generations
+                .cmp(&b.generations)
+                .then(a.fitness.total_cmp(&b.fitness))
+        })
+    }
+}
+
+/// Searches the settings of the evolution suiting a strategy best, running inner evolutions of
+/// the candidates of a search space and comparing the best fitness they reach.
+///
+/// The search space is the cartesian product of its dimensions, each holding the values tried
+/// for a setting, applied to the base configuration in the order of the dimensions.
+pub struct Tuner {
+    config: EvolutionConfig,
+    dimensions: Vec<Vec<Hyperparameter>>,
+    generations: u64,
+    method: SearchMethod,
+    repeats: usize,
+}
+
+impl Tuner {
+    /// Tuner of the base configuration running each candidate for `generations` generations
+    /// after the first one.
+    pub fn new(config: EvolutionConfig, method: SearchMethod, generations: u64) -> Self {
+        Tuner {
+            config,
+            dimensions: vec![],
+            generations,
+            method,
+            repeats: 1,
+        }
+    }
+
+    /// Adds the values tried for a setting, dimensions without values being ignored.
+    pub fn dimension(mut self, values: Vec<Hyperparameter>) -> Self {
+        if !values.is_empty() {
+            self.dimensions.push(values);
+        }
+        self
+    }
+
+    /// Runs each candidate `repeats` times, e.g. for strategies whose runs vary a lot with the
+    /// random generator.
+    pub fn repeats(mut self, repeats: usize) -> Self {
+        self.repeats = repeats.max(1);
+        self
+    }
+
+    /// Number of candidates of the search space, the base configuration alone without
+    /// dimensions.
+    pub fn candidate_count(&self) -> usize {
+        self.dimensions
+            .iter()
+            .fold(1, |count, values| count.saturating_mul(values.len()))
+    }
+
+    /// Values of the candidate at the given position of the search space, the first dimension
+    /// varying the fastest.
+    fn candidate(&self, mut index: usize) -> Vec<Hyperparameter> {
+        self.dimensions
+            .iter()
+            .map(|values| {
+                let value = values[index % values.len()].clone();
+                index /= values.len();
+                value
+            })
+            .collect()
+    }
+
+    /// Runs the candidates picked by the search method, skipping the ones whose configuration
+    /// is not valid.
+    pub async fn run<T: Strategy>(
+        &self,
+        strategy: &T,
+        rng: &mut impl Rng,
+    ) -> Result<TuningReport, EvolutionError> {
+        let count = self.candidate_count();
+        let mut candidates = match self.method {
+            SearchMethod::Grid | SearchMethod::SuccessiveHalving => {
+                (0..count).map(|index| self.candidate(index)).collect()
+            }
+            SearchMethod::Random(draws) => sample(rng, count, draws.min(count))
+                .into_iter()
+                .map(|index| self.candidate(index))
+                .collect::<Vec<_>>(),
+        };
+
+        let mut report = TuningReport::default();
+        let mut generations = self.generations;
+        loop {
+            let mut trials = Vec::with_capacity(candidates.len());
+            for hyperparameters in candidates {
+                if let Some(trial) = self
+                    .trial(strategy, hyperparameters, generations, rng)
+                    .await?
+                {
+                    trials.push(trial);
+                }
+            }
+            report.trials.extend(trials.iter().cloned());
+
+            if self.method != SearchMethod::SuccessiveHalving || trials.len() <= 1 {
+                return Ok(report);
+            }
+            trials.sort_by(|a, b| b.fitness.total_cmp(&a.fitness));
+            let survivors = trials.len().div_ceil(2);
+            candidates = trials
+                .into_iter()
+                .take(survivors)
+                .map(|trial| trial.hyperparameters)
+                .collect();
+            generations = generations.saturating_mul(2);
+            debug!(
+                "Running {} candidate(s) over {} generations",
+                candidates.len(),
+                generations
+            );
+        }
+    }
+
+    async fn trial<T: Strategy>(
+        &self,
+        strategy: &T,
+        hyperparameters: Vec<Hyperparameter>,
+        generations: u64,
+        rng: &mut impl Rng,
+    ) -> Result<Option<Trial>, EvolutionError> {
+        let mut config = self.config.clone();
+        for hyperparameter in hyperparameters.iter() {
+            hyperparameter.apply(&mut config);
+        }
+        #[cfg(feature = "validation")]
+        if let Err(err) = config.validate() {
+            warn!("Skipping candidate {:?}: {}", hyperparameters, err);
+            return Ok(None);
+        }
+
+        let mut fitness = 0.0;
+        for _ in 0..self.repeats {
+            let snapshot = EvolutionEngineBuilder::new(&mut *rng)
+                .config(config.clone())
+                .hall_of_fame(1)
+                .build()?
+                .start(strategy, MaxGenerations(generations))
+                .await?;
+            fitness += snapshot
+                .hall_of_fame
+                .first()
+                .map_or(0.0, |best| best.fitness);
+        }
+        Ok(Some(Trial {
+            hyperparameters,
+            config,
+            generations,
+            fitness: fitness / self.repeats as f32,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use common_test::get_seeded_rng;
+    use futures::executor::block_on;
+
+    use crate::{
+        adaptation::Strategy,
+        evolution::{
+            EvolutionConfig, EvolutionError, GenerationRenewalConfig, GeneticRenewalParam,
+        },
+        selection::SelectionType,
+        Genome,
+    };
+
+    use super::{Hyperparameter, SearchMethod, Tuner};
+
+    struct Sum;
+
+    impl Strategy for Sum {
+        type Phenotype = u32;
+
+        fn genome_size(&self) -> usize {
+            4
+        }
+
+        fn decode(&self, genome: &Genome) -> u32 {
+            genome.iter().map(|&gene| gene as u32).sum()
+        }
+
+        fn evaluate(&self, sum: &u32) -> f32 {
+            *sum as f32 / 1020.0
+        }
+    }
+
+    fn config() -> EvolutionConfig {
+        EvolutionConfig {
+            population_size: 10,
+            generation_renewal_config: Some(GenerationRenewalConfig {
+                cloning: None,
+                crossover: Some(GeneticRenewalParam {
+                    ratio: 0.5,
+                    ..Default::default()
+                }),
+                random_ratio: None,
+            }),
+            max_duration: None,
+        }
+    }
+
+    fn tuner(method: SearchMethod) -> Tuner {
+        Tuner::new(config(), method, 2)
+            .dimension(vec![
+                Hyperparameter::CrossoverRatio(0.4),
+                Hyperparameter::CrossoverRatio(0.8),
+            ])
+            .dimension(vec![
+                Hyperparameter::MutationRate(0.01),
+                Hyperparameter::MutationRate(0.1),
+            ])
+    }
+
+    #[test]
+    fn test_hyperparameter_apply() {
+        // Given
+        let mut config = config();
+
+        // When
+        Hyperparameter::CloningRatio(0.2).apply(&mut config);
+        Hyperparameter::SelectionType(SelectionType::Tournament(3)).apply(&mut config);
+        Hyperparameter::PopulationSize(40).apply(&mut config);
+
+        // Then
+        assert_eq!(40, config.population_size);
+        let renewal = config.generation_renewal_config.unwrap();
+        assert_eq!(Some(0.2), renewal.cloning.as_ref().map(|param| param.ratio));
+        assert!(
+            [renewal.cloning, renewal.crossover]
+                .into_iter()
+                .flatten()
+                .all(|param| param.selection_type == SelectionType::Tournament(3)),
+            "Should set the selection of all the pools"
+        );
+    }
+
+    #[test]
+    fn test_tuner_grid() -> Result<(), EvolutionError> {
+        // Given
+        let tuner = tuner(SearchMethod::Grid).repeats(2);
+
+        // When
+        let result = block_on(tuner.run(&Sum, &mut get_seeded_rng().unwrap()))?;
+
+        // Then
+        assert_eq!(4, tuner.candidate_count());
+        assert_eq!(
+            vec![
+                vec![
+                    Hyperparameter::CrossoverRatio(0.4),
+                    Hyperparameter::MutationRate(0.01)
+                ],
+                vec![
+                    Hyperparameter::CrossoverRatio(0.8),
+                    Hyperparameter::MutationRate(0.01)
+                ],
+                vec![
+                    Hyperparameter::CrossoverRatio(0.4),
+                    Hyperparameter::MutationRate(0.1)
+                ],
+                vec![
+                    Hyperparameter::CrossoverRatio(0.8),
+                    Hyperparameter::MutationRate(0.1)
+                ],
+            ],
+            result
+                .trials
+                .iter()
+                .map(|trial| trial.hyperparameters.clone())
+                .collect::<Vec<_>>(),
+            "Should run every candidate"
+        );
+        let best = result.best().unwrap();
+        assert!(result
+            .trials
+            .iter()
+            .all(|trial| trial.fitness <= best.fitness && trial.generations == 2));
+        for trial in result.trials.iter() {
+            let mut config = config();
+            for hyperparameter in trial.hyperparameters.iter() {
+                hyperparameter.apply(&mut config);
+            }
+            assert_eq!(
+                config, trial.config,
+                "Should run the base configuration with the values of the candidate"
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_tuner_random() -> Result<(), EvolutionError> {
+        // Given
+        let tuner = tuner(SearchMethod::Random(3));
+
+        // When
+        let result = block_on(tuner.run(&Sum, &mut get_seeded_rng().unwrap()))?;
+
+        // Then
+        assert_eq!(3, result.trials.len());
+        for (index, trial) in result.trials.iter().enumerate() {
+            assert!(
+                result.trials[index + 1..]
+                    .iter()
+                    .all(|other| other.hyperparameters != trial.hyperparameters),
+                "Should draw distinct candidates"
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_tuner_successive_halving() -> Result<(), EvolutionError> {
+        // Given
+        let tuner = tuner(SearchMethod::SuccessiveHalving);
+
+        // When
+        let result = block_on(tuner.run(&Sum, &mut get_seeded_rng().unwrap()))?;
+
+        // Then
+        assert_eq!(
+            vec![2, 2, 2, 2, 4, 4, 8],
+            result
+                .trials
+                .iter()
+                .map(|trial| trial.generations)
+                .collect::<Vec<_>>(),
+            "Should double the budget of the fittest half at each round"
+        );
+        assert_eq!(result.trials.last(), result.best());
+
+        Ok(())
+    }
+
+    #[cfg(feature = "validation")]
+    #[test]
+    fn test_tuner_skip_invalid_candidates() -> Result<(), EvolutionError> {
+        // Given
+        let tuner = Tuner::new(config(), SearchMethod::Grid, 1).dimension(vec![
+            Hyperparameter::RandomRatio(0.2),
+            Hyperparameter::RandomRatio(0.9),
+        ]);
+
+        // When
+        let result = block_on(tuner.run(&Sum, &mut get_seeded_rng().unwrap()))?;
+
+        // Then
+        assert_eq!(
+            vec![vec![Hyperparameter::RandomRatio(0.2)]],
+            result
+                .trials
+                .into_iter()
+                .map(|trial| trial.hyperparameters)
+                .collect::<Vec<_>>()
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,71 @@
+use common_test::get_seeded_rng;
+use genetic::{
+    adaptation::Strategy,
+    crossover::CrossoverType,
+    evolution::{EvolutionConfig, GenerationRenewalConfig, GeneticRenewalParam},
+    selection::SelectionType,
+    soak::{soak, CountingAllocator, SoakConfig},
+    Genome,
+};
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+struct Sum;
+
+impl Strategy for Sum {
+    type Phenotype = Genome;
+
+    fn genome_size(&self) -> usize {
+        8
+    }
+
+    fn decode(&self, genome: &Genome) -> Genome {
+        genome.clone()
+    }
+
+    fn evaluate(&self, genome: &Genome) -> f32 {
+        genome.iter().map(|&g| g as f32).sum::<f32>() / 2040.0
+    }
+}
+
+#[test]
+fn test_soak_engine_per_request() {
+    // Given
+    let mut rng = get_seeded_rng().unwrap();
+    let config = SoakConfig {
+        evolution: EvolutionConfig {
+            population_size: 16,
+            generation_renewal_config: Some(GenerationRenewalConfig {
+                cloning: Some(GeneticRenewalParam {
+                    mutation_rate: Some(0.1),
+                    ratio: 0.25,
+                    selection_type: SelectionType::Tournament(2),
+                    ..Default::default()
+                }),
+                crossover: Some(GeneticRenewalParam {
+                    ratio: 0.5,
+                    selection_type: SelectionType::Tournament(2),
+                    crossover_type: CrossoverType::Uniform(0.5),
+                    ..Default::default()
+                }),
+                random_ratio: None,
+            }),
+            max_duration: None,
+        },
+        runs: 2000,
+        warmup_runs: 50,
+        generations: 5,
+        allowed_growth: 64 * 1024,
+    };
+
+    // When
+    let result = soak(&Sum, &config, &mut rng);
+
+    // Then
+    let report = result.unwrap();
+    assert!(
+        report.peak_bytes > report.baseline_bytes,
+        "Should track allocations of the runs"
+    );
+}
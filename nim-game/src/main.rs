@@ -0,0 +1,83 @@
+use std::{
+    env,
+    io::{self, BufRead, BufReader, Write},
+};
+
+use anyhow::{bail, Context};
+use serde_json::{json, Value};
+
+const DEFAULT_API_URL: &str = "http://localhost:80";
+const STICK_COUNT: u8 = 21;
+
+/// Evolves a Nim agent on a running evolution API, `API_URL` or `http://localhost:80`, printing
+/// the progress of the run, then plays against the agent on the standard input: players take 1
+/// to 3 sticks in turn, whoever takes the last one loses.
+fn main() -> anyhow::Result<()> {
+    let api_url = env::var("API_URL").unwrap_or_else(|_| DEFAULT_API_URL.to_string());
+
+    let response = ureq::post(&format!("{api_url}/runs")).send_json(json!({
+        "strategy": "nim",
+        "strategy_parameters": { "stick_count": STICK_COUNT },
+        "population_size": 64
+    }))?;
+    let location = response
+        .header("location")
+        .context("Started run without location")?
+        .to_string();
+    println!("Evolving a Nim agent in {location}");
+
+    let events = ureq::get(&format!("{api_url}{location}/events")).call()?;
+    let mut event = String::new();
+    for line in BufReader::new(events.into_reader()).lines() {
+        let line = line?;
+        if let Some(name) = line.strip_prefix("event: ") {
+            event = name.to_string();
+        } else if let Some(data) = line.strip_prefix("data: ") {
+            let data: Value = serde_json::from_str(data)?;
+            match event.as_str() {
+                "progress" => println!(
+                    "Generation {}, {:.1} generations/s",
+                    data["generation"], data["generations_per_second"]
+                ),
+                "status" => println!("Run {}", data.as_str().unwrap_or_default()),
+                "done" => {
+                    println!("Agent evolved in {} generations", data["generation"]);
+                    break;
+                }
+                _ => {}
+            }
+        }
+    }
+
+    let mut remaining = STICK_COUNT;
+    let mut lines = io::stdin().lock().lines();
+    while remaining > 0 {
+        print!("{remaining} sticks left, take 1 to 3: ");
+        io::stdout().flush()?;
+        let Some(line) = lines.next() else {
+            return Ok(());
+        };
+        let taken = match line?.trim().parse::<u8>() {
+            Ok(taken @ 1..=3) if taken <= remaining => taken,
+            _ => continue,
+        };
+        remaining -= taken;
+        if remaining == 0 {
+            println!("You took the last stick, the agent wins");
+            return Ok(());
+        }
+
+        let agent_move: Value = ureq::post(&format!("{api_url}{location}/nim/moves"))
+            .send_json(json!({ "remaining": remaining }))?
+            .into_json()?;
+        let Some(taken) = agent_move["taken"].as_u64() else {
+            bail!("Invalid move of the agent: {agent_move}");
+        };
+        remaining -= taken as u8;
+        println!("The agent takes {taken}");
+        if remaining == 0 {
+            println!("The agent took the last stick, you win");
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,22 @@
+use std::env;
+
+use strategies::info::shipped_strategies;
+
+/// Lists the shipped strategies, as JSON with `--json`.
+fn main() {
+    let strategies = shipped_strategies();
+    if env::args().any(|arg| arg == "--json") {
+        println!("{}", serde_json::to_string_pretty(&strategies).unwrap());
+        return;
+    }
+
+    for strategy in strategies {
+        println!("{} {}", strategy.name, strategy.version);
+        println!("    {}", strategy.description);
+        println!(
+            "    genome: {:?}, {}",
+            strategy.genome.encoding, strategy.genome.size
+        );
+        println!("    parameters: {}", strategy.parameters);
+    }
+}
@@ -1,10 +1,8 @@
-use std::{env, rc::Rc};
+use std::{env, sync::Arc};
 
 use dipstick::{Input, InputScope, Log, LogScope};
 use genetic::{
-    evolution::{
-        EventType, EvolutionConfig, EvolutionEngine, GenerationRenewalConfig, GeneticRenewalParam,
-    },
+    evolution::{EventType, EvolutionEngine, GeneticRenewalParam, TargetFitness},
     selection::SelectionType,
 };
 use log::{error, info};
@@ -12,7 +10,7 @@ use rand::thread_rng;
 
 use futures::executor::block_on;
 
-use common::subject_observer::{Observer, Subject};
+use common::subject_observer::Observer;
 use simple_logger::SimpleLogger;
 use strategies::my_strategy::MyStrategy;
 
@@ -52,30 +50,22 @@ fn main() {
     let bytes = target.as_bytes();
     let threshold = bytes.len() as f32;
 
-    let settings = EvolutionConfig {
-        population_size: 128,
-        generation_renewal_config: Some(GenerationRenewalConfig {
-            cloning: None,
-            crossover: Some(GeneticRenewalParam {
-                mutation_rate: None,
-                ratio: 1.0,
-                selection_type: SelectionType::Weight,
-            }),
-        }),
-    };
-
-    let mut runner = EvolutionEngine::default();
-    let observer = Rc::new(MyObserver::new());
-    runner.register_observer(observer.clone());
+    let run = EvolutionEngine::builder()
+        .population_size(128)
+        .crossover(GeneticRenewalParam {
+            mutation_rate: None,
+            ratio: 1.0,
+            selection_type: SelectionType::Weight,
+            ..Default::default()
+        })
+        .observer(Arc::new(MyObserver::new()))
+        .rng(thread_rng())
+        .build();
 
-    let result = block_on(runner.start(
-        &MyStrategy::new(bytes),
-        &settings,
-        |_, fitnesses| fitnesses.iter().any(|&fitness| fitness >= threshold),
-        &mut thread_rng(),
-    ));
-
-    runner.unregister_observer(observer);
+    let result = match run {
+        Ok(mut run) => block_on(run.start(&MyStrategy::new(bytes), TargetFitness(threshold))),
+        Err(err) => Err(err),
+    };
 
     match result {
         Ok(infos) => info!(
@@ -86,9 +76,7 @@ fn main() {
                 .iter()
                 .enumerate()
                 .filter(|e| e.1.fitness >= threshold)
-                .map(|e| (e.0, unsafe {
-                    String::from_utf8_unchecked(e.1.genome.clone())
-                }))
+                .map(|e| (e.0, e.1.phenotype.clone().unwrap_or_default()))
                 .collect::<Vec<_>>()
         ),
         Err(err) => error!("{}", err),
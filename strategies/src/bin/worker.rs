@@ -0,0 +1,37 @@
+use std::{env, net::TcpListener};
+
+use anyhow::{bail, Context};
+use genetic::adaptation::{DynStrategy, Erased};
+use genetic_ext::distributed::serve;
+use strategies::{
+    info::StrategyInfo, my_strategy::MyStrategy, nim_strategy::NimStrategy, one_max::OneMax,
+};
+
+const USAGE: &str = "Usage: worker <address> <strategy> <parameter>";
+
+/// Evaluates the genomes of a shipped strategy for the masters dispatching their generations
+/// to the address, e.g. `worker 0.0.0.0:7878 one_max 64`.
+fn main() -> anyhow::Result<()> {
+    let args = env::args().skip(1).collect::<Vec<_>>();
+    let [address, name, parameter] = args.as_slice() else {
+        bail!(USAGE);
+    };
+    let strategy = strategy(name, parameter)?;
+    let listener =
+        TcpListener::bind(address).with_context(|| format!("Unable to listen to {address}"))?;
+    println!("Serving {name} evaluations on {}", listener.local_addr()?);
+    serve(listener, &strategy)?;
+    Ok(())
+}
+
+/// Builds the strategy from its name and single parameter.
+fn strategy(name: &str, parameter: &str) -> anyhow::Result<Box<DynStrategy>> {
+    Ok(match name {
+        MyStrategy::NAME => Erased::boxed(MyStrategy::new(parameter.as_bytes())),
+        NimStrategy::NAME => Erased::boxed(NimStrategy::new(
+            parameter.parse().context("Invalid stick count")?,
+        )?),
+        OneMax::NAME => Erased::boxed(OneMax::new(parameter.parse().context("Invalid bit count")?)),
+        _ => bail!("Unknown or unsupported strategy: {name}"),
+    })
+}
@@ -0,0 +1,101 @@
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::{my_strategy::MyStrategy, nim_strategy::NimStrategy, one_max::OneMax, tsp::Tsp};
+
+/// How the genes of a strategy are read.
+#[derive(Serialize, Clone, Copy, Debug, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum GenomeEncoding {
+    /// Each byte is a gene.
+    Bytes,
+    /// Each bit is a gene, see [`genetic::bits`].
+    Bits,
+    /// Each byte is a distinct element of `0..size`, see [`genetic::permutation`].
+    Permutation,
+}
+
+#[derive(Serialize, Clone, Debug, PartialEq)]
+pub struct GenomeSchema {
+    pub encoding: GenomeEncoding,
+    /// How the genome size follows from the parameters of the strategy.
+    pub size: &'static str,
+}
+
+/// Machine-readable documentation of a strategy, see [`StrategyInfo`].
+#[derive(Serialize, Clone, Debug, PartialEq)]
+pub struct StrategyDescription {
+    pub name: &'static str,
+    pub version: &'static str,
+    pub description: &'static str,
+    pub genome: GenomeSchema,
+    /// JSON schema of the parameters building the strategy.
+    pub parameters: Value,
+}
+
+/// Metadata of a strategy, the single source of its documentation for the API, its OpenAPI
+/// schemas and the command line.
+pub trait StrategyInfo {
+    /// Unique identifier of the strategy.
+    const NAME: &'static str;
+    /// Version of the fitness definition, fitnesses of different versions not being comparable.
+    const VERSION: &'static str = env!("CARGO_PKG_VERSION");
+    const DESCRIPTION: &'static str;
+
+    fn genome_schema() -> GenomeSchema;
+
+    fn parameters_schema() -> Value;
+
+    fn describe() -> StrategyDescription {
+        StrategyDescription {
+            name: Self::NAME,
+            version: Self::VERSION,
+            description: Self::DESCRIPTION,
+            genome: Self::genome_schema(),
+            parameters: Self::parameters_schema(),
+        }
+    }
+}
+
+/// Descriptions of the strategies shipped with this crate, by name.
+pub fn shipped_strategies() -> Vec<StrategyDescription> {
+    vec![
+        MyStrategy::describe(),
+        NimStrategy::describe(),
+        OneMax::describe(),
+        Tsp::describe(),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use serde_json::Value;
+
+    use super::shipped_strategies;
+
+    #[test]
+    fn test_shipped_strategies() {
+        // When
+        let result = shipped_strategies();
+
+        // Then
+        assert_eq!(
+            result.len(),
+            result
+                .iter()
+                .map(|strategy| strategy.name)
+                .collect::<HashSet<_>>()
+                .len(),
+            "Should name strategies uniquely"
+        );
+        assert!(
+            result.iter().all(|strategy| {
+                strategy.parameters["type"] == "object"
+                    && matches!(strategy.parameters["required"], Value::Array(_))
+            }),
+            "Should describe the parameters with an object schema"
+        );
+    }
+}
@@ -1,2 +1,5 @@
+pub mod info;
 pub mod my_strategy;
 pub mod nim_strategy;
+pub mod one_max;
+pub mod tsp;
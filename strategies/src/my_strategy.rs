@@ -1,4 +1,7 @@
 use genetic::{adaptation::Strategy, Genome};
+use serde_json::{json, Value};
+
+use crate::info::{GenomeEncoding, GenomeSchema, StrategyInfo};
 
 pub struct MyStrategy {
     target: Genome,
@@ -12,11 +15,43 @@ impl MyStrategy {
     }
 }
 
+impl StrategyInfo for MyStrategy {
+    const NAME: &'static str = "my_strategy";
+    const DESCRIPTION: &'static str = "Evolves a word, scoring the ratio of letters in place.";
+
+    fn genome_schema() -> GenomeSchema {
+        GenomeSchema {
+            encoding: GenomeEncoding::Bytes,
+            size: "length of the target",
+        }
+    }
+
+    fn parameters_schema() -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "target": {
+                    "type": "string",
+                    "description": "Word to evolve",
+                    "minLength": 1
+                }
+            },
+            "required": ["target"]
+        })
+    }
+}
+
 impl Strategy for MyStrategy {
+    type Phenotype = Genome;
+
     fn genome_size(&self) -> usize {
         self.target.len()
     }
 
+    fn decode(&self, genome: &Genome) -> Genome {
+        genome.clone()
+    }
+
     fn evaluate(&self, genome: &Genome) -> f32 {
         genome
             .iter()
@@ -25,4 +60,9 @@ impl Strategy for MyStrategy {
             .count() as f32
             / self.target.len() as f32
     }
+
+    /// The evolved word, invalid UTF-8 sequences being replaced.
+    fn describe(&self, genome: &Genome) -> Option<String> {
+        Some(String::from_utf8_lossy(genome).into_owned())
+    }
 }
@@ -1,5 +1,8 @@
 use anyhow::{bail, Result};
-use genetic::adaptation::Strategy;
+use genetic::{adaptation::Strategy, Genome};
+use serde_json::{json, Value};
+
+use crate::info::{GenomeEncoding, GenomeSchema, StrategyInfo};
 
 const MAX_STICK_CHOICE: u8 = 3;
 const MIN_STICK_CHOICE: u8 = 1;
@@ -27,27 +30,74 @@ impl NimStrategy {
             normalization_factor,
         })
     }
+
+    /// Sticks an agent of `genome` takes out of `remaining_stick_count`, always a legal move.
+    pub fn play(&self, genome: &Genome, remaining_stick_count: u8) -> u8 {
+        let legal_max = MAX_STICK_CHOICE
+            .min(remaining_stick_count)
+            .max(MIN_STICK_CHOICE);
+        (self.best_actions.len() + 1)
+            .checked_sub(remaining_stick_count as usize)
+            .and_then(|locus| genome.get(locus))
+            .map(|&gene| express(gene))
+            .unwrap_or(MIN_STICK_CHOICE)
+            .clamp(MIN_STICK_CHOICE, legal_max)
+    }
+}
+
+impl StrategyInfo for NimStrategy {
+    const NAME: &'static str = "nim";
+    const DESCRIPTION: &'static str =
+        "Learns the winning moves of the game of Nim, one gene per count of remaining sticks.";
+
+    fn genome_schema() -> GenomeSchema {
+        GenomeSchema {
+            encoding: GenomeEncoding::Bytes,
+            size: "initial stick count minus one",
+        }
+    }
+
+    fn parameters_schema() -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "stick_count": {
+                    "type": "integer",
+                    "description": "Initial number of sticks",
+                    "minimum": MIN_STICK_CHOICE + 1,
+                    "maximum": u8::MAX
+                }
+            },
+            "required": ["stick_count"]
+        })
+    }
 }
 
 impl Strategy for NimStrategy {
+    type Phenotype = Genome;
+
     fn genome_size(&self) -> usize {
         self.best_actions.len()
     }
 
-    fn evaluate(&self, genome: &genetic::Genome) -> f32 {
+    fn decode(&self, genome: &Genome) -> Genome {
+        genome.clone()
+    }
+
+    fn evaluate(&self, genome: &Genome) -> f32 {
         genome
             .iter()
             .zip(self.best_actions.iter())
-            .map(|(&gene, &best)| {
-                let expression =
-                    ((gene as u16 * ACTIONS_COUNT_U16 + CODES_COUNT - 1) / CODES_COUNT) as u8;
-                (ACTIONS_COUNT - best.abs_diff(expression)) as f32
-            })
+            .map(|(&gene, &best)| ACTIONS_COUNT.saturating_sub(best.abs_diff(express(gene))) as f32)
             .sum::<f32>()
             / self.normalization_factor
     }
 }
 
+fn express(gene: u8) -> u8 {
+    (gene as u16 * ACTIONS_COUNT_U16).div_ceil(CODES_COUNT) as u8
+}
+
 fn get_best_actions(remaining_stick_count: u8) -> Vec<u8> {
     (MIN_STICK_CHOICE + 1..=remaining_stick_count)
         .rev()
@@ -121,6 +171,41 @@ mod tests {
         let result = strategy.evaluate(&vec![50, 50, 50]);
         // Then
         assert_eq!(2.0 / 3.0, result, "Should return right fitness");
+
+        // When
+        let result = NimStrategy::new(8).unwrap().evaluate(&vec![0; 7]);
+        // Then
+        assert!(
+            (0.0..1.0).contains(&result),
+            "Should not score below zero the actions far from the best ones"
+        );
+    }
+
+    #[test]
+    fn test_play() {
+        // Given
+        let strategy = NimStrategy::new(6).unwrap();
+        let genome = vec![255, 170, 85, 0, 255];
+
+        // Then
+        assert_eq!(3, strategy.play(&genome, 6));
+        assert_eq!(2, strategy.play(&genome, 5));
+        assert_eq!(
+            1,
+            strategy.play(&genome, 3),
+            "Should take at least one stick"
+        );
+        assert_eq!(
+            2,
+            strategy.play(&genome, 2),
+            "Should not take more sticks than remaining"
+        );
+        assert_eq!(1, strategy.play(&genome, 1));
+        assert_eq!(
+            1,
+            strategy.play(&genome, 9),
+            "Should take one stick beyond the genome"
+        );
     }
 
     #[test]
@@ -0,0 +1,149 @@
+use genetic::{
+    adaptation::Strategy,
+    bits::{bits, genome_size_for_bits},
+    crossover::CrossoverType,
+    evolution::{EvolutionConfig, GenerationRenewalConfig, GeneticRenewalParam},
+    mutation::MutationType,
+    selection::SelectionType,
+    Genome,
+};
+use serde_json::{json, Value};
+
+use crate::info::{GenomeEncoding, GenomeSchema, StrategyInfo};
+
+/// OneMax benchmark: maximizes the number of set bits of a bitstring.
+pub struct OneMax {
+    bits: usize,
+}
+
+impl OneMax {
+    pub fn new(bits: usize) -> Self {
+        OneMax { bits }
+    }
+
+    /// Settings searching at bit granularity: the fittest quarter is cloned unchanged while
+    /// offsprings mix fit parents with uniform bit crossover, then flip one bit on average.
+    pub fn evolution_config(&self, population_size: usize) -> EvolutionConfig {
+        let mutation_rate = 1.0 / self.bits.max(1) as f32;
+        EvolutionConfig {
+            population_size,
+            generation_renewal_config: Some(GenerationRenewalConfig {
+                cloning: Some(GeneticRenewalParam {
+                    mutation_rate: Some(0.0),
+                    ratio: 0.2,
+                    selection_type: SelectionType::Ranking((population_size / 4).max(1)),
+                    ..Default::default()
+                }),
+                crossover: Some(GeneticRenewalParam {
+                    mutation_rate: Some(mutation_rate),
+                    mutation_type: MutationType::BitFlip,
+                    ratio: 0.7,
+                    selection_type: SelectionType::Ranking((population_size / 4).max(2)),
+                    crossover_type: CrossoverType::UniformBit(0.5),
+                    ..Default::default()
+                }),
+                random_ratio: None,
+            }),
+            max_duration: None,
+        }
+    }
+}
+
+impl StrategyInfo for OneMax {
+    const NAME: &'static str = "one_max";
+    const DESCRIPTION: &'static str = "Maximizes the number of set bits of a bitstring.";
+
+    fn genome_schema() -> GenomeSchema {
+        GenomeSchema {
+            encoding: GenomeEncoding::Bits,
+            size: "number of bits, rounded up to whole bytes",
+        }
+    }
+
+    fn parameters_schema() -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "bits": {
+                    "type": "integer",
+                    "description": "Length of the bitstring",
+                    "minimum": 1
+                }
+            },
+            "required": ["bits"]
+        })
+    }
+}
+
+impl Strategy for OneMax {
+    type Phenotype = Genome;
+
+    fn genome_size(&self) -> usize {
+        genome_size_for_bits(self.bits)
+    }
+
+    fn decode(&self, genome: &Genome) -> Genome {
+        genome.clone()
+    }
+
+    /// Ratio of set bits, ignoring the padding bits of the last gene.
+    fn evaluate(&self, genome: &Genome) -> f32 {
+        bits(genome).take(self.bits).filter(|&bit| bit).count() as f32 / self.bits as f32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use common_test::get_seeded_rng;
+    use futures::executor::block_on;
+    use genetic::{
+        adaptation::Strategy,
+        evolution::{EvolutionEngine, MaxGenerations, TargetFitness, TerminationCriterion},
+    };
+
+    use super::OneMax;
+
+    #[test]
+    fn test_one_max_evaluate() {
+        // Given
+        let strategy = OneMax::new(10);
+
+        // Then
+        assert_eq!(2, strategy.genome_size());
+        assert_eq!(0.3, strategy.evaluate(&vec![0b111, 0]));
+        assert_eq!(
+            1.0,
+            strategy.evaluate(&vec![0xff, 0xff]),
+            "Should ignore padding bits"
+        );
+    }
+
+    #[test]
+    fn test_one_max_evolution() {
+        // Given
+        let mut rng = get_seeded_rng().unwrap();
+        let strategy = OneMax::new(64);
+        let config = strategy.evolution_config(64);
+        let mut engine = EvolutionEngine::default();
+
+        // When
+        let result = block_on(engine.start(
+            &strategy,
+            &config,
+            MaxGenerations(500).or(TargetFitness(1.0)),
+            &mut rng,
+        ))
+        .unwrap();
+
+        // Then
+        assert!(
+            result.evaluations.iter().any(|e| e.fitness == 1.0),
+            "Should solve OneMax, reached {:?}",
+            result
+                .evaluations
+                .iter()
+                .map(|e| e.fitness)
+                .reduce(f32::max)
+        );
+    }
+}
@@ -0,0 +1,222 @@
+use anyhow::{bail, Result};
+use genetic::{
+    adaptation::Strategy,
+    crossover::CrossoverType,
+    evolution::{EvolutionConfig, GenerationRenewalConfig, GeneticRenewalParam},
+    mutation::MutationType,
+    permutation::Permutation,
+    selection::SelectionType,
+    Genome,
+};
+use serde_json::{json, Value};
+
+use crate::info::{GenomeEncoding, GenomeSchema, StrategyInfo};
+
+/// Traveling salesman problem: finds the shortest closed tour visiting each city once, genomes
+/// being the permutations of the cities in visiting order.
+pub struct Tsp {
+    cities: Vec<(f32, f32)>,
+    permutation: Permutation,
+}
+
+impl Tsp {
+    pub fn new(cities: Vec<(f32, f32)>) -> Result<Self> {
+        if cities.len() < 2 {
+            bail!(
+                "Invalid city count: {}; It must be at least 2",
+                cities.len()
+            );
+        }
+        let permutation = Permutation::new(cities.len())?;
+        Ok(Tsp {
+            cities,
+            permutation,
+        })
+    }
+
+    /// Length of the closed tour visiting the cities in the order of the genome.
+    pub fn tour_length(&self, genome: &Genome) -> f32 {
+        let city = |gene: &u8| self.cities[*gene as usize];
+        genome
+            .iter()
+            .zip(genome.iter().cycle().skip(1))
+            .map(|(from, to)| {
+                let ((from_x, from_y), (to_x, to_y)) = (city(from), city(to));
+                (to_x - from_x).hypot(to_y - from_y)
+            })
+            .sum()
+    }
+
+    /// Settings keeping tours valid: the fittest quarter is cloned unchanged while offsprings
+    /// inherit sub-tours of their parents through order crossover, then reverse a sub-tour.
+    pub fn evolution_config(&self, population_size: usize) -> EvolutionConfig {
+        EvolutionConfig {
+            population_size,
+            generation_renewal_config: Some(GenerationRenewalConfig {
+                cloning: Some(GeneticRenewalParam {
+                    mutation_rate: Some(0.0),
+                    ratio: 0.2,
+                    selection_type: SelectionType::Ranking((population_size / 4).max(1)),
+                    ..Default::default()
+                }),
+                crossover: Some(GeneticRenewalParam {
+                    mutation_rate: Some(0.5),
+                    mutation_type: MutationType::Inversion,
+                    ratio: 0.7,
+                    selection_type: SelectionType::BiasedRoulette { exponent: 8.0 },
+                    crossover_type: CrossoverType::Order,
+                    ..Default::default()
+                }),
+                random_ratio: None,
+            }),
+            max_duration: None,
+        }
+    }
+}
+
+impl StrategyInfo for Tsp {
+    const NAME: &'static str = "tsp";
+    const DESCRIPTION: &'static str =
+        "Finds the shortest closed tour visiting each city once, scoring the inverse of its length.";
+
+    fn genome_schema() -> GenomeSchema {
+        GenomeSchema {
+            encoding: GenomeEncoding::Permutation,
+            size: "number of cities",
+        }
+    }
+
+    fn parameters_schema() -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "cities": {
+                    "type": "array",
+                    "description": "Coordinates of the cities",
+                    "items": {
+                        "type": "array",
+                        "items": { "type": "number" },
+                        "minItems": 2,
+                        "maxItems": 2
+                    },
+                    "minItems": 2,
+                    "maxItems": u8::MAX as usize + 1
+                }
+            },
+            "required": ["cities"]
+        })
+    }
+}
+
+impl Strategy for Tsp {
+    type Phenotype = Genome;
+
+    fn genome_size(&self) -> usize {
+        self.permutation.size()
+    }
+
+    fn decode(&self, genome: &Genome) -> Genome {
+        genome.clone()
+    }
+
+    fn evaluate(&self, genome: &Genome) -> f32 {
+        1.0 / (1.0 + self.tour_length(genome))
+    }
+
+    fn repair(&self, genome: &mut Genome) {
+        self.permutation.repair(genome);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f32::consts::TAU;
+
+    use common_test::get_seeded_rng;
+    use futures::executor::block_on;
+    use genetic::{
+        adaptation::Strategy,
+        evolution::{EvolutionEngine, MaxGenerations},
+        permutation::Permutation,
+    };
+
+    use super::Tsp;
+
+    /// Cities evenly spread on the unit circle, the shortest tour following the circle.
+    fn circle(count: usize) -> Vec<(f32, f32)> {
+        (0..count)
+            .map(|i| {
+                let angle = TAU * i as f32 / count as f32;
+                (angle.cos(), angle.sin())
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_new() {
+        assert!(Tsp::new(vec![(0.0, 0.0)]).is_err());
+        assert!(Tsp::new(vec![(0.0, 0.0); 257]).is_err());
+        assert_eq!(3, Tsp::new(vec![(0.0, 0.0); 3]).unwrap().genome_size());
+    }
+
+    #[test]
+    fn test_tour_length() {
+        // Given
+        let strategy = Tsp::new(vec![(0.0, 0.0), (0.0, 1.0), (1.0, 1.0), (1.0, 0.0)]).unwrap();
+
+        // Then
+        assert_eq!(4.0, strategy.tour_length(&vec![0, 1, 2, 3]));
+        assert!(
+            (2.0 + 2.0 * 2f32.sqrt() - strategy.tour_length(&vec![0, 2, 1, 3])).abs() < 1e-6,
+            "Should close the tour"
+        );
+        assert_eq!(0.2, strategy.evaluate(&vec![3, 2, 1, 0]));
+    }
+
+    #[test]
+    fn test_repair() {
+        // Given
+        let strategy = Tsp::new(circle(5)).unwrap();
+        let mut genome = vec![9, 9, 9, 9, 9];
+
+        // When
+        strategy.repair(&mut genome);
+
+        // Then
+        assert!(Permutation::new(5).unwrap().is_valid(&genome));
+    }
+
+    #[test]
+    fn test_tsp_evolution() {
+        // Given
+        let mut rng = get_seeded_rng().unwrap();
+        let strategy = Tsp::new(circle(12)).unwrap();
+        let shortest = strategy.tour_length(&(0..12).collect());
+        let config = strategy.evolution_config(64);
+        let mut engine = EvolutionEngine::default();
+
+        // When
+        let result =
+            block_on(engine.start(&strategy, &config, MaxGenerations(200), &mut rng)).unwrap();
+
+        // Then
+        let permutation = Permutation::new(12).unwrap();
+        assert!(
+            result
+                .evaluations
+                .iter()
+                .all(|e| permutation.is_valid(&e.genome)),
+            "Should only evolve valid tours"
+        );
+        let best = result
+            .evaluations
+            .iter()
+            .map(|e| strategy.tour_length(&e.genome))
+            .reduce(f32::min)
+            .unwrap();
+        assert!(
+            best < shortest * 1.01,
+            "Should find the shortest tour {shortest}, reached {best}"
+        );
+    }
+}